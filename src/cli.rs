@@ -0,0 +1,715 @@
+// Command-line interface: scriptable subcommands that reuse the scanner without the GUI
+
+// Disk scanning and drive actions
+use crate::gui::disk_scanner::scan_disks;
+use crate::gui::drive_actions::DriveAction;
+// JSON reports
+use crate::gui::report::Report;
+// Timestamps in the text report
+use crate::gui::settings::format_timestamp;
+// Alert rules from the settings file
+use crate::gui::alerts::{drive_rules, local_minute_of_day, notify, AlertEngine, AlertLog, NotificationThrottle};
+use crate::gui::settings::Settings;
+// Metric sinks for the monitor loop
+use crate::metrics::{MetricsTarget, Sink};
+// Import data models for disk information
+use crate::models::{humanize_hours, DiskInfo};
+// Thousands separators in the text report
+use crate::locale::{group_digits, group_raw_digits};
+// Monitor interval and timestamps
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Usage text printed by `help` and after argument errors
+pub const USAGE: &str = "\
+Usage: ssd_info_cli [COMMAND] [OPTIONS]
+
+Without a command the graphical interface starts.
+
+Commands:
+  scan                      List drives with health and temperature
+  report [--device DEV]     Detailed report of one drive (all drives without --device)
+  test [--device DEV]       Start a SMART self-test (all drives without --device)
+       [--short | --long | --conveyance]
+  monitor [--interval SECS] Print a status line per drive every SECS seconds (default 60)
+          [--count N]       Stop after N rounds
+          [--influx TARGET] Also write InfluxDB line protocol to a file or http:// write URL
+          [--influx-token TOKEN]  API token for the InfluxDB endpoint
+          [--zabbix SERVER[:PORT]]  Also push values to a Zabbix server/proxy (sender protocol)
+          [--zabbix-host NAME]      Host name in Zabbix (default: this machine's host name)
+          [--csv FILE]      Also append a CSV row per drive to FILE
+  check                     Nagios/Icinga plugin: one status line with perfdata,
+                            exit 0 OK, 1 WARNING, 2 CRITICAL, 3 UNKNOWN
+        [--temp-warn C] [--temp-crit C]       Temperature thresholds (default 55/65)
+        [--health-warn P] [--health-crit P]   Remaining health thresholds (default 20/10)
+        [--device DEV]                        Check one drive only
+  serve [--listen ADDR]     Serve the latest scan as JSON on http://ADDR/drives and /drives/{serial}
+        [--interval SECS]   (default 127.0.0.1:8190, rescanning every 60 seconds)
+  zabbix-template           Print a Zabbix 6.0 template matching the values sent by monitor --zabbix
+  tui, --tui                Drive list and reports in the terminal (for SSH sessions)
+  gui [--minimized]         Start the graphical interface (minimized, as on login)
+  help                      Show this text
+
+Options:
+  --json                    Print scan/report output as a JSON report
+  --version                 Print the version";
+
+/// Options that take a value ("--name value" or "--name=value"); all others are flags
+const VALUE_OPTIONS: &[&str] = &[
+    "device",
+    "interval",
+    "count",
+    "listen",
+    "influx",
+    "influx-token",
+    "zabbix",
+    "zabbix-host",
+    "csv",
+];
+
+/// A parsed command line.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    /// Start the GUI (no command given)
+    Gui {
+        /// Start with the window minimized (used by the autostart entry)
+        minimized: bool,
+    },
+    /// List drives
+    Scan {
+        /// Print a JSON report instead of a table
+        json: bool,
+    },
+    /// Detailed report
+    Report {
+        /// Device to report on, None for all drives
+        device: Option<String>,
+        /// Print a JSON report instead of text
+        json: bool,
+    },
+    /// Start a SMART self-test
+    Test {
+        /// Device to test, None for all drives
+        device: Option<String>,
+        /// Test type: "short", "long" or "conveyance"
+        kind: String,
+    },
+    /// Print status lines periodically
+    Monitor {
+        /// Seconds between scans
+        interval_secs: u64,
+        /// Number of rounds, None to run until interrupted
+        count: Option<u64>,
+        /// Metric sinks written after every scan
+        sinks: Vec<Sink>,
+    },
+    /// Nagios/Icinga plugin check
+    Check {
+        /// Device to check, None for all drives
+        device: Option<String>,
+        /// Alert thresholds
+        thresholds: CheckThresholds,
+    },
+    /// Local REST API server
+    Serve {
+        /// Address to listen on
+        listen: String,
+        /// Seconds between scans
+        interval_secs: u64,
+    },
+    /// Print the Zabbix template
+    ZabbixTemplate,
+    /// Interactive terminal interface
+    Tui,
+    /// Print usage
+    Help,
+    /// Print the version
+    Version,
+}
+
+/// Thresholds for the `check` command.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CheckThresholds {
+    /// Temperature in °C at or above which a drive is WARNING
+    pub temp_warn: i32,
+    /// Temperature in °C at or above which a drive is CRITICAL
+    pub temp_crit: i32,
+    /// Remaining health in % at or below which a drive is WARNING
+    pub health_warn: u8,
+    /// Remaining health in % at or below which a drive is CRITICAL
+    pub health_crit: u8,
+}
+
+impl Default for CheckThresholds {
+    fn default() -> Self {
+        Self { temp_warn: 55, temp_crit: 65, health_warn: 20, health_crit: 10 }
+    }
+}
+
+/// Parses command-line arguments (without the program name).
+/// Hand-written rather than clap: every option is a flat `--name[=value]`, and the build must work
+/// offline from the crates already vendored for the GUI.
+///
+/// # Arguments
+/// * `args` - Arguments as passed to the program
+///
+/// # Returns
+/// The command, or an error message for unknown commands and options.
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+    let Some((command, rest)) = args.split_first() else {
+        return Ok(Command::Gui { minimized: false });
+    };
+
+    // Split "--name=value" and "--name value" forms into (name, value) pairs
+    let mut options: Vec<(&str, Option<String>)> = Vec::new();
+    let mut iter = rest.iter();
+    while let Some(arg) = iter.next() {
+        let Some(name) = arg.strip_prefix("--") else {
+            return Err(format!("unexpected argument '{}'", arg));
+        };
+        let (name, value) = match name.split_once('=') {
+            Some((n, v)) => (n, Some(v.to_string())),
+            None if VALUE_OPTIONS.contains(&name) || name.starts_with("temp-") || name.starts_with("health-") => {
+                (name, Some(iter.next().ok_or(format!("--{} needs a value", name))?.clone()))
+            }
+            None => (name, None),
+        };
+        options.push((name, value));
+    }
+
+    let mut json = false;
+    let mut minimized = false;
+    let mut device = None;
+    let mut kind = None;
+    let mut interval_secs = 60;
+    let mut count = None;
+    let mut thresholds = CheckThresholds::default();
+    let mut influx = None;
+    let mut influx_token = None;
+    let mut zabbix = None;
+    let mut zabbix_host = None;
+    let mut csv = None;
+    let mut listen = crate::server::DEFAULT_LISTEN.to_string();
+    let number = |name: &str, value: &str| value.parse::<u64>().map_err(|_| format!("--{} expects a number, got '{}'", name, value));
+
+    for (name, value) in options {
+        let allowed = match command.as_str() {
+            "scan" => matches!(name, "json"),
+            "report" => matches!(name, "json" | "device"),
+            "test" => matches!(name, "device" | "short" | "long" | "conveyance"),
+            "monitor" => matches!(name, "interval" | "count" | "influx" | "influx-token" | "zabbix" | "zabbix-host" | "csv"),
+            "serve" => matches!(name, "listen" | "interval"),
+            "check" => matches!(name, "device" | "temp-warn" | "temp-crit" | "health-warn" | "health-crit"),
+            "gui" => matches!(name, "minimized"),
+            _ => false,
+        };
+        if !allowed {
+            return Err(format!("unknown option '--{}' for '{}'", name, command));
+        }
+        match name {
+            "json" => json = true,
+            "minimized" => minimized = true,
+            "device" => device = value,
+            "short" | "long" | "conveyance" => kind = Some(name.to_string()),
+            "interval" => interval_secs = number(name, value.as_deref().unwrap_or_default())?.max(1),
+            "count" => count = Some(number(name, value.as_deref().unwrap_or_default())?),
+            "influx" => influx = Some(MetricsTarget::parse(value.as_deref().unwrap_or_default())?),
+            "influx-token" => influx_token = value,
+            "zabbix" => zabbix = value,
+            "zabbix-host" => zabbix_host = value,
+            "listen" => listen = value.unwrap_or_default(),
+            "csv" => csv = value.map(std::path::PathBuf::from),
+            "temp-warn" => thresholds.temp_warn = number(name, value.as_deref().unwrap_or_default())? as i32,
+            "temp-crit" => thresholds.temp_crit = number(name, value.as_deref().unwrap_or_default())? as i32,
+            "health-warn" => thresholds.health_warn = number(name, value.as_deref().unwrap_or_default())?.min(100) as u8,
+            "health-crit" => thresholds.health_crit = number(name, value.as_deref().unwrap_or_default())?.min(100) as u8,
+            _ => {}
+        }
+    }
+
+    match command.as_str() {
+        "scan" => Ok(Command::Scan { json }),
+        "report" => Ok(Command::Report { device, json }),
+        "test" => Ok(Command::Test {
+            device,
+            kind: kind.ok_or("test needs --short, --long or --conveyance")?,
+        }),
+        "monitor" => {
+            if influx.is_none() && influx_token.is_some() {
+                return Err("--influx-token needs --influx".to_string());
+            }
+            if zabbix.is_none() && zabbix_host.is_some() {
+                return Err("--zabbix-host needs --zabbix".to_string());
+            }
+            let mut sinks: Vec<Sink> = influx.map(|target| Sink::Influx { target, token: influx_token }).into_iter().collect();
+            if let Some(server) = zabbix {
+                sinks.push(Sink::Zabbix { server, host: zabbix_host });
+            }
+            sinks.extend(csv.map(Sink::Csv));
+            Ok(Command::Monitor { interval_secs, count, sinks })
+        }
+        "check" => {
+            if thresholds.temp_warn > thresholds.temp_crit {
+                return Err(format!("--temp-warn ({}) is above --temp-crit ({})", thresholds.temp_warn, thresholds.temp_crit));
+            }
+            // Lower health is worse, so the warning level must be the higher percentage
+            if thresholds.health_warn < thresholds.health_crit {
+                return Err(format!(
+                    "--health-warn ({}) is below --health-crit ({})",
+                    thresholds.health_warn, thresholds.health_crit
+                ));
+            }
+            Ok(Command::Check { device, thresholds })
+        }
+        "serve" => Ok(Command::Serve { listen, interval_secs }),
+        "zabbix-template" => Ok(Command::ZabbixTemplate),
+        "tui" | "--tui" => Ok(Command::Tui),
+        "gui" => Ok(Command::Gui { minimized }),
+        "help" | "--help" | "-h" => Ok(Command::Help),
+        "--version" | "-V" => Ok(Command::Version),
+        other => Err(format!("unknown command '{}'", other)),
+    }
+}
+
+/// Exit code for arguments `parse_args` rejected.
+/// `check` runs as a Nagios/Icinga plugin, where 2 means CRITICAL, so its usage errors are 3 (UNKNOWN).
+///
+/// # Arguments
+/// * `args` - Arguments as passed to the program
+pub fn usage_exit_code(args: &[String]) -> i32 {
+    if args.first().is_some_and(|command| command == "check") {
+        3
+    } else {
+        2
+    }
+}
+
+/// Runs a non-GUI command.
+///
+/// # Arguments
+/// * `command` - Parsed command (Gui is handled by main)
+///
+/// # Returns
+/// The process exit code: 0 on success, 1 on failure.
+pub fn run(command: Command) -> i32 {
+    match command {
+        Command::Gui { .. } => 0,
+        Command::Tui => crate::tui::run(),
+        Command::Help => {
+            println!("{}", USAGE);
+            0
+        }
+        Command::Serve { listen, interval_secs } => {
+            let listener = match std::net::TcpListener::bind(&listen) {
+                Ok(listener) => listener,
+                Err(e) => {
+                    eprintln!("Error: cannot listen on {}: {}", listen, e);
+                    return 1;
+                }
+            };
+            println!("Serving drive data on http://{}/drives", listen);
+            let latest = crate::server::spawn_scanner(host_name(), Duration::from_secs(interval_secs));
+            crate::server::serve(listener, latest);
+            0
+        }
+        Command::ZabbixTemplate => {
+            print!("{}", crate::metrics::zabbix_template());
+            0
+        }
+        Command::Version => {
+            println!("ssd_info_cli {}", env!("CARGO_PKG_VERSION"));
+            0
+        }
+        Command::Scan { json } => with_drives(None, |drives| {
+            if json {
+                println!("{}", report(drives).to_json().to_pretty());
+            } else {
+                print!("{}", format_scan_table(drives));
+            }
+        }),
+        Command::Report { device, json } => with_drives(device.as_deref(), |drives| {
+            if json {
+                println!("{}", report(drives).to_json().to_pretty());
+            } else {
+                let text: Vec<String> = drives.iter().map(format_drive_report).collect();
+                print!("{}", text.join("\n"));
+            }
+        }),
+        Command::Test { device, kind } => {
+            let mut code = 0;
+            let status = with_drives(device.as_deref(), |drives| {
+                for di in drives {
+                    match DriveAction::StartSelfTest(di.dev.clone(), kind.clone()).run() {
+                        Ok(msg) => println!("{}", msg),
+                        Err(e) => {
+                            eprintln!("{}: {}", di.dev, e);
+                            code = 1;
+                        }
+                    }
+                }
+            });
+            status.max(code)
+        }
+        Command::Check { device, thresholds } => {
+            // Plugins must always print one line; a failed scan is UNKNOWN rather than an error exit
+            let drives = scan_disks().map(|list| match &device {
+                Some(dev) => list.into_iter().filter(|d| &d.dev == dev).collect(),
+                None => list,
+            });
+            let (code, line) = match drives {
+                Ok(drives) if !drives.is_empty() => check_status(&drives, &thresholds),
+                Ok(_) => (3, format!("SSD UNKNOWN - no drive {}", device.unwrap_or_default())),
+                Err(e) => (3, format!("SSD UNKNOWN - {}", e)),
+            };
+            println!("{}", line);
+            code
+        }
+        Command::Monitor { interval_secs, count, sinks } => {
+            let host = host_name();
+            // Alert rules from the [alerts] section and the notification schedule; invalid entries are reported once and skipped
+            let settings = Settings::load_default();
+            let (rules, channels) = (settings.alert_rules(), settings.channels());
+            for e in rules.iter().filter_map(|r| r.as_ref().err()) {
+                eprintln!("Ignoring alert rule {}", e);
+            }
+            for e in &settings.schedule_errors {
+                eprintln!("Ignoring notification setting {}", e);
+            }
+            let rules: Vec<_> = rules.into_iter().flatten().collect();
+            let mut alerts = AlertEngine::default();
+            let mut alert_log = AlertLog::open_default();
+            let mut throttle = NotificationThrottle::default();
+            let mut round = 0;
+            loop {
+                let now = unix_now();
+                match scan_disks() {
+                    Ok(drives) => {
+                        for di in &drives {
+                            println!("{} {}", format_timestamp(now), format_status_line(di));
+                        }
+                        // A drive's own temperature alarm replaces the global temp_c rules for it
+                        let watched = drives
+                            .iter()
+                            .filter(|d| !settings.is_hidden(d))
+                            .map(|d| (d, drive_rules(&rules, settings.temp_alarm(d))));
+                        for alert in alerts.check_each(watched, now) {
+                            println!("{} ALERT {}: {}", format_timestamp(now), alert.severity.name(), alert.message);
+                            // Snoozed alerts, quiet hours and the rate limit hold back notifications, not the log
+                            match alert_log.record(alert.clone()) {
+                                Ok(false) => continue,
+                                Ok(true) => {}
+                                Err(e) => eprintln!("{} alert log: {}", format_timestamp(now), e),
+                            }
+                            let minute = settings.schedule.quiet_hours.map_or(0, |_| local_minute_of_day(now));
+                            if !throttle.allow(&settings.schedule, &alert, minute) {
+                                continue;
+                            }
+                            for e in notify(&channels, &alert) {
+                                eprintln!("{} notification failed: {}", format_timestamp(now), e);
+                            }
+                        }
+                        // A failing sink is reported but does not stop monitoring
+                        for sink in &sinks {
+                            if let Err(e) = sink.send(&drives, &host, now) {
+                                eprintln!("{} metrics: {}", format_timestamp(now), e);
+                            }
+                        }
+                    }
+                    Err(e) => eprintln!("{} scan failed: {}", format_timestamp(now), e),
+                }
+                round += 1;
+                if count.is_some_and(|c| round >= c) {
+                    return 0;
+                }
+                std::thread::sleep(Duration::from_secs(interval_secs));
+            }
+        }
+    }
+}
+
+/// Scans drives, optionally keeps one device, and hands them to `f`.
+///
+/// # Returns
+/// 0 on success, 1 if the scan failed or the device was not found.
+fn with_drives(device: Option<&str>, f: impl FnOnce(&[DiskInfo])) -> i32 {
+    let drives = match scan_disks() {
+        Ok(drives) => drives,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 1;
+        }
+    };
+    let drives: Vec<DiskInfo> = match device {
+        Some(dev) => drives.into_iter().filter(|d| d.dev == dev).collect(),
+        None => drives,
+    };
+    if drives.is_empty() {
+        eprintln!("Error: no drive {}", device.unwrap_or_default());
+        return 1;
+    }
+    f(&drives);
+    0
+}
+
+/// Wraps scanned drives in a report stamped with this host and the current time.
+fn report(drives: &[DiskInfo]) -> Report {
+    Report {
+        host: host_name(),
+        generated_at: unix_now(),
+        drives: drives.to_vec(),
+    }
+}
+
+/// Host name used in reports and metrics.
+fn host_name() -> String {
+    sysinfo::System::host_name().unwrap_or_else(|| "localhost".to_string())
+}
+
+/// Current Unix time in seconds.
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Formats the drive list as an aligned table.
+///
+/// # Arguments
+/// * `drives` - Scanned drives
+pub fn format_scan_table(drives: &[DiskInfo]) -> String {
+    let mut out = format!(
+        "{:<14} {:<5} {:<32} {:>9} {:>7} {:>6}  {}\n",
+        "DEVICE", "TYPE", "MODEL", "CAPACITY", "HEALTH", "TEMP", "STATUS"
+    );
+    for di in drives {
+        out.push_str(&format!(
+            "{:<14} {:<5} {:<32} {:>9} {:>7} {:>6}  {}\n",
+            di.dev,
+            di.device_type.as_deref().unwrap_or(&di.kind),
+            di.model.as_deref().unwrap_or("--"),
+            di.capacity_str.as_deref().unwrap_or("--"),
+            di.health_percent.map(|h| format!("{}%", h)).unwrap_or("--".into()),
+            di.temp_c.map(|t| format!("{}°C", t)).unwrap_or("--".into()),
+            status_word(di),
+        ));
+    }
+    out
+}
+
+/// Formats one monitor line: device, health, temperature, data written and status.
+///
+/// # Arguments
+/// * `di` - Drive to summarize
+pub fn format_status_line(di: &DiskInfo) -> String {
+    format!(
+        "{} health={} temp={} written={} status={}",
+        di.dev,
+        di.health_percent.map(|h| format!("{}%", h)).unwrap_or("--".into()),
+        di.temp_c.map(|t| format!("{}C", t)).unwrap_or("--".into()),
+        di.data_written_tb.map(|t| format!("{:.2}TB", t)).unwrap_or("--".into()),
+        status_word(di),
+    )
+}
+
+/// Formats the detailed text report of one drive.
+///
+/// # Arguments
+/// * `di` - Drive to describe
+pub fn format_drive_report(di: &DiskInfo) -> String {
+    let mut out = format!("== {} ==\n", di.dev);
+    let mut field = |label: &str, value: Option<String>| {
+        if let Some(value) = value {
+            out.push_str(&format!("{:<20} {}\n", format!("{}:", label), value));
+        }
+    };
+    field("Model", di.model.clone());
+    field("Serial", di.serial.clone());
+    field("Firmware", di.firmware.clone());
+    field("WWN", di.wwn.clone());
+    field("Type", di.device_type.clone().or(Some(di.kind.clone())));
+    field("Network", di.network_transport.as_ref().map(|t| format!("{} (SMART data stays with the remote target)", t)));
+    field("Removable", di.removable.then(|| "card reader or flash stick (SMART not reliable)".to_string()));
+    field("Capacity", di.capacity_str.clone());
+    field("Health", di.health_percent.map(|h| format!("{}%", h)));
+    field("SMART", (di.smart_enabled == Some(false)).then(|| format!("disabled (enable with: smartctl -s on {})", di.dev)));
+    field("SMART self-assessment", di.smart_passed.map(|p| if p { "PASSED" } else { "FAILED" }.to_string()));
+    field(
+        "Temperature",
+        di.temp_c.map(|t| match di.temperature_range() {
+            Some(range) => format!("{}°C ({})", t, range.describe()),
+            None => format!("{}°C", t),
+        }),
+    );
+    field("Data written", di.data_written_tb.map(|t| format!("{:.2} TB", t)));
+    field("Data read", di.data_read_tb.map(|t| format!("{:.2} TB", t)));
+    field("Power on hours", di.power_on_hours.map(|h| format!("{} ({})", group_digits(h), humanize_hours(h))));
+    field(
+        "Power cycles",
+        di.power_cycles.map(|c| match di.hours_per_power_cycle() {
+            Some(h) => format!("{} (average uptime {} per cycle)", group_digits(c), humanize_hours(h.round() as u64)),
+            None => group_digits(c),
+        }),
+    );
+    field("Unsafe shutdowns", di.unsafe_shutdowns.map(group_digits));
+    field("Media errors", di.media_errors.map(group_digits));
+    field(
+        "Controller busy",
+        di.controller_busy_minutes.map(|m| match di.busy_fraction() {
+            Some(f) => format!("{} min ({:.1}% of power-on time)", group_digits(m), f * 100.0),
+            None => format!("{} min", group_digits(m)),
+        }),
+    );
+    field("Rotation rate", di.rotation_rpm.map(|r| format!("{} rpm", r)));
+    field("Link power", di.link_power.as_ref().map(|lp| format!("{} ({}): {}", lp.policy, lp.host, lp.description())));
+    field("Write amplification", di.write_amplification.map(|w| format!("{:.2}", w)));
+    field("Last self-test", di.last_self_test.clone());
+    if let Some(controller) = &di.nvme_controller {
+        field("NVMe version", controller.nvme_version.clone());
+        field("Controller ID", controller.controller_id.map(|id| id.to_string()));
+        field("IEEE OUI", controller.ieee_oui.clone());
+        field("Namespaces", controller.namespaces.map(|n| n.to_string()));
+        field("Subsystem NQN", controller.subsystem_nqn.clone());
+        field("APST", controller.apst.as_ref().map(|apst| apst.summary()));
+        field(
+            "Firmware slots",
+            controller.firmware_slots.as_ref().map(|fw| {
+                let slots: Vec<String> = fw
+                    .slots
+                    .iter()
+                    .map(|(slot, revision)| {
+                        let mark = if *slot == fw.active { " (active)" } else if fw.next_reset == Some(*slot) { " (next reset)" } else { "" };
+                        format!("{}: {}{}", slot, revision, mark)
+                    })
+                    .collect();
+                slots.join(", ")
+            }),
+        );
+        field(
+            "Host memory buffer",
+            controller.hmb_preferred_bytes.filter(|&b| b > 0).map(|preferred| match controller.hmb_allocated_bytes {
+                Some(allocated) => format!("{} of {} MiB allocated", allocated >> 20, preferred >> 20),
+                None => format!("{} MiB requested", preferred >> 20),
+            }),
+        );
+        for group in &controller.endurance_groups {
+            let mut parts = vec![group.percent_used.map(|p| format!("{}% used", p)).unwrap_or("wear unknown".into())];
+            parts.extend(group.available_spare.map(|s| format!("{}% spare", s)));
+            parts.extend(group.media_bytes_written.map(|b| format!("{:.2} TB media written", b as f64 / 1e12)));
+            let namespaces: Vec<String> = controller.group_namespaces(group.id).iter().map(|ns| ns.nsid.to_string()).collect();
+            if !namespaces.is_empty() {
+                parts.push(format!("namespaces {}", namespaces.join(", ")));
+            }
+            field(&format!("Endurance group {}", group.id), Some(parts.join(", ")));
+        }
+    }
+
+    for problem in di.problems() {
+        out.push_str(&format!("! {}\n", problem));
+    }
+    for advisory in &di.advisories {
+        out.push_str(&format!("! Advisory: {}\n", advisory.title));
+    }
+
+    if !di.smart_attributes.is_empty() {
+        out.push_str(&format!(
+            "\n{:>3} {:<26} {:<8} {:>5} {:>5} {:>6} {:<8} {}\n",
+            "ID", "ATTRIBUTE", "TYPE", "VALUE", "WORST", "THRESH", "STATUS", "RAW"
+        ));
+        for a in &di.smart_attributes {
+            out.push_str(&format!(
+                "{:>3} {:<26} {:<8} {:>5} {:>5} {:>6} {:<8} {}\n",
+                a.id,
+                a.name,
+                if a.pre_fail { "Pre-fail" } else { "Old_age" },
+                a.current,
+                a.worst,
+                a.threshold,
+                format!("{:?}", a.status),
+                group_raw_digits(&a.raw_value)
+            ));
+        }
+    }
+    out
+}
+
+/// Evaluates drives for the `check` command.
+/// CRITICAL: any problem from `DiskInfo::problems` or a threshold crossed at the critical level.
+/// WARNING: a warning-level threshold or a SMART attribute near its threshold.
+///
+/// # Arguments
+/// * `drives` - Drives to evaluate
+/// * `thresholds` - Alert thresholds
+///
+/// # Returns
+/// The plugin exit code (0, 1 or 2) and the status line, with perfdata after " | " when there is any.
+pub fn check_status(drives: &[DiskInfo], thresholds: &CheckThresholds) -> (i32, String) {
+    let mut worst = 0;
+    let mut messages = Vec::new();
+    let mut perfdata = Vec::new();
+
+    for di in drives {
+        let name = di.dev.trim_start_matches("/dev/");
+        let mut reasons: Vec<(i32, String)> = di.problems().into_iter().map(|p| (2, p)).collect();
+
+        if let Some(t) = di.temp_c {
+            if t >= thresholds.temp_crit {
+                reasons.push((2, format!("temperature {}°C", t)));
+            } else if t >= thresholds.temp_warn {
+                reasons.push((1, format!("temperature {}°C", t)));
+            }
+            perfdata.push(format!("'{}_temp'={};{};{}", name, t, thresholds.temp_warn, thresholds.temp_crit));
+        }
+        if let Some(h) = di.health_percent {
+            if h <= thresholds.health_crit {
+                reasons.push((2, format!("health {}%", h)));
+            } else if h <= thresholds.health_warn {
+                reasons.push((1, format!("health {}%", h)));
+            }
+            perfdata.push(format!(
+                "'{}_health'={}%;{}:;{}:;0;100",
+                name, h, thresholds.health_warn, thresholds.health_crit
+            ));
+        }
+        let warning_attrs = di
+            .smart_attributes
+            .iter()
+            .filter(|a| a.status == crate::models::AttributeStatus::Warning)
+            .count();
+        if warning_attrs > 0 {
+            reasons.push((1, format!("{} SMART attribute(s) near threshold", warning_attrs)));
+        }
+        if let Some(tb) = di.data_written_tb {
+            perfdata.push(format!("'{}_written'={:.3}TB", name, tb));
+        }
+
+        let level = reasons.iter().map(|r| r.0).max().unwrap_or(0);
+        worst = worst.max(level);
+        if level > 0 {
+            let text: Vec<String> = reasons.into_iter().filter(|r| r.0 == level).map(|r| r.1).collect();
+            messages.push(format!("{}: {}", di.dev, text.join(", ")));
+        }
+    }
+
+    let state = ["OK", "WARNING", "CRITICAL"][worst as usize];
+    let summary = if messages.is_empty() {
+        format!("{} drive(s) healthy", drives.len())
+    } else {
+        messages.join("; ")
+    };
+    let line = format!("SSD {} - {}", state, summary);
+    if perfdata.is_empty() {
+        (worst, line)
+    } else {
+        (worst, format!("{} | {}", line, perfdata.join(" ")))
+    }
+}
+
+/// One-word overall status used in tables and monitor lines.
+pub(crate) fn status_word(di: &DiskInfo) -> &'static str {
+    if di.cached {
+        "SLEEPING"
+    } else if !di.problems().is_empty() {
+        "FAILING"
+    } else if di.smart_attributes.iter().any(|a| a.status == crate::models::AttributeStatus::Warning) {
+        "WARNING"
+    } else {
+        "OK"
+    }
+}
@@ -0,0 +1,804 @@
+// User-defined alert rules ("temp_c > 65") evaluated on every scan, and the channels alerts are sent to
+
+// Drive keys identify a drive across device renames; the alert log lives in the data directory
+use crate::gui::history::{data_dir, drive_key};
+// HTTP client for webhooks
+use crate::http;
+// Webhook payloads
+use crate::json::Json;
+// Import data models for disk information
+use crate::models::{AttributeStatus, DiskInfo};
+// Alert commands run on the host and desktop notifications go through the portal inside Flatpak
+use crate::sandbox::{host_command, in_flatpak};
+// Rule/drive pairs currently in alert, and when each was last notified
+use std::collections::{HashMap, HashSet};
+// Alert log location
+use std::path::PathBuf;
+// Notification commands
+use std::process::Command;
+// Local time zone offset for quiet hours
+use nix::libc;
+// Notification portal calls inside Flatpak
+use zbus::blocking::Connection;
+use zbus::zvariant::Value;
+
+/// Oldest alerts are dropped from the log beyond this many entries
+const MAX_LOGGED_ALERTS: usize = 1000;
+
+/// Name of the rule raised by a drive's own temperature alarm (`temp_alarm` in its settings section)
+pub const TEMP_ALARM_RULE: &str = "temp_alarm";
+
+/// Drive fields usable in rules, with a short description
+pub const RULE_FIELDS: &[(&str, &str)] = &[
+    ("temp_c", "temperature in °C"),
+    ("health_percent", "remaining health in %"),
+    ("data_written_tb", "total data written in TB"),
+    ("data_read_tb", "total data read in TB"),
+    ("power_on_hours", "power-on hours"),
+    ("power_cycles", "power cycles"),
+    ("unsafe_shutdowns", "unsafe shutdowns"),
+    ("media_errors", "media and data integrity errors"),
+    ("write_amplification", "write amplification factor"),
+    ("smart_passed", "1 if the SMART self-assessment passed, 0 if it failed"),
+    ("critical_attributes", "number of critical SMART attributes"),
+];
+
+/// How urgent an alert is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    /// Worth a look soon
+    Warning,
+    /// Needs attention now
+    Critical,
+}
+
+impl Severity {
+    /// Lower-case name as used in the config file.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+        }
+    }
+
+    /// Parses a name from `name`; anything but "critical" is a warning.
+    fn from_name(name: &str) -> Self {
+        if name == "critical" {
+            Severity::Critical
+        } else {
+            Severity::Warning
+        }
+    }
+}
+
+/// The value a rule looks at.
+#[derive(Clone, Debug, PartialEq)]
+enum Metric {
+    /// One of `RULE_FIELDS`
+    Field(String),
+    /// A column of a SMART attribute: `attribute(ID).raw|value|worst|threshold`
+    Attribute {
+        /// Attribute ID, e.g. "197"
+        id: String,
+        /// "raw", "value", "worst" or "threshold"
+        column: String,
+    },
+}
+
+/// A parsed alert rule: `[severity:] metric operator number`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AlertRule {
+    /// Rule name from the config file
+    pub name: String,
+    /// Severity, "warning" unless the rule starts with "critical:"
+    pub severity: Severity,
+    /// Condition as written, without the severity prefix (e.g., "temp_c > 65")
+    pub condition: String,
+    /// Value compared
+    metric: Metric,
+    /// Comparison operator: ">", ">=", "<", "<=", "==" or "!="
+    op: String,
+    /// Number the value is compared against
+    threshold: f64,
+}
+
+impl AlertRule {
+    /// Parses and validates a rule.
+    ///
+    /// # Arguments
+    /// * `name` - Rule name
+    /// * `text` - Rule text, e.g. "critical: attribute(197).raw > 0"
+    ///
+    /// # Returns
+    /// The rule, or an error message explaining what is wrong with it.
+    pub fn parse(name: &str, text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        let (severity, condition) = match text.split_once(':') {
+            Some((prefix, rest)) if prefix.trim() == "critical" => (Severity::Critical, rest.trim()),
+            Some((prefix, rest)) if prefix.trim() == "warning" => (Severity::Warning, rest.trim()),
+            _ => (Severity::Warning, text),
+        };
+
+        // Two-character operators first so ">=" is not read as ">"
+        let (index, op) = [">=", "<=", "==", "!=", ">", "<"]
+            .iter()
+            .find_map(|op| condition.find(op).map(|i| (i, *op)))
+            .ok_or("expected a comparison such as 'temp_c > 65'")?;
+        let (left, right) = (condition[..index].trim(), condition[index + op.len()..].trim());
+        let threshold: f64 = right.parse().map_err(|_| format!("'{}' is not a number", right))?;
+
+        let metric = if let Some(rest) = left.strip_prefix("attribute(") {
+            let (id, column) = rest.split_once(").").ok_or("expected attribute(ID).raw, .value, .worst or .threshold")?;
+            let id = id.trim();
+            if id.is_empty() || !id.chars().all(|c| c.is_ascii_digit()) {
+                return Err(format!("attribute ID '{}' is not a number", id));
+            }
+            if !matches!(column, "raw" | "value" | "worst" | "threshold") {
+                return Err(format!("unknown attribute column '{}', use raw, value, worst or threshold", column));
+            }
+            Metric::Attribute { id: id.to_string(), column: column.to_string() }
+        } else if RULE_FIELDS.iter().any(|(field, _)| *field == left) {
+            Metric::Field(left.to_string())
+        } else {
+            let fields: Vec<&str> = RULE_FIELDS.iter().map(|(field, _)| *field).collect();
+            return Err(format!("unknown field '{}', use attribute(ID).raw or one of {}", left, fields.join(", ")));
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            severity,
+            condition: condition.to_string(),
+            metric,
+            op: op.to_string(),
+            threshold,
+        })
+    }
+
+    /// Builds the rule of a drive's own temperature alarm.
+    ///
+    /// # Arguments
+    /// * `limit` - Temperature in °C at which the drive alerts
+    pub fn temp_alarm(limit: i32) -> Self {
+        Self {
+            name: TEMP_ALARM_RULE.to_string(),
+            severity: Severity::Warning,
+            condition: format!("temp_c >= {}", limit),
+            metric: Metric::Field("temp_c".to_string()),
+            op: ">=".to_string(),
+            threshold: f64::from(limit),
+        }
+    }
+
+    /// Whether the rule looks at the drive temperature.
+    pub fn is_temperature(&self) -> bool {
+        self.metric == Metric::Field("temp_c".to_string())
+    }
+
+    /// Reads the value the rule looks at.
+    ///
+    /// # Returns
+    /// None if the drive does not report it.
+    pub fn value(&self, di: &DiskInfo) -> Option<f64> {
+        match &self.metric {
+            Metric::Field(field) => match field.as_str() {
+                "temp_c" => di.temp_c.map(f64::from),
+                "health_percent" => di.health_percent.map(f64::from),
+                "data_written_tb" => di.data_written_tb,
+                "data_read_tb" => di.data_read_tb,
+                "power_on_hours" => di.power_on_hours.map(|v| v as f64),
+                "power_cycles" => di.power_cycles.map(|v| v as f64),
+                "unsafe_shutdowns" => di.unsafe_shutdowns.map(|v| v as f64),
+                "media_errors" => di.media_errors.map(|v| v as f64),
+                "write_amplification" => di.write_amplification,
+                "smart_passed" => di.smart_passed.map(|p| if p { 1.0 } else { 0.0 }),
+                "critical_attributes" => (!di.smart_attributes.is_empty()).then(|| {
+                    di.smart_attributes.iter().filter(|a| a.status == AttributeStatus::Critical).count() as f64
+                }),
+                _ => None,
+            },
+            Metric::Attribute { id, column } => {
+                let attr = di.attribute(id)?;
+                match column.as_str() {
+                    "raw" => attr.raw_numeric().map(|v| v as f64),
+                    "value" => attr.current.trim().parse().ok(),
+                    "worst" => attr.worst.trim().parse().ok(),
+                    _ => attr.threshold.trim().parse().ok(),
+                }
+            }
+        }
+    }
+
+    /// Evaluates the rule against a drive.
+    ///
+    /// # Returns
+    /// The value if the condition holds, None if it does not or the value is unknown.
+    pub fn matches(&self, di: &DiskInfo) -> Option<f64> {
+        let value = self.value(di)?;
+        let hit = match self.op.as_str() {
+            ">" => value > self.threshold,
+            ">=" => value >= self.threshold,
+            "<" => value < self.threshold,
+            "<=" => value <= self.threshold,
+            "==" => value == self.threshold,
+            _ => value != self.threshold,
+        };
+        hit.then_some(value)
+    }
+
+    /// Builds the alert this rule raises for a drive.
+    ///
+    /// # Arguments
+    /// * `di` - Drive the rule fired on
+    /// * `value` - Value that matched
+    /// * `timestamp` - Unix time in seconds
+    pub fn alert(&self, di: &DiskInfo, value: f64, timestamp: u64) -> Alert {
+        let drive = di.model.clone().unwrap_or_else(|| di.dev.clone());
+        Alert {
+            timestamp,
+            rule: self.name.clone(),
+            severity: self.severity,
+            device: di.dev.clone(),
+            drive_key: drive_key(di).unwrap_or_else(|| di.dev.clone()),
+            value,
+            message: format!("{} ({}): {} — {} (now {})", drive, di.dev, self.name, self.condition, value),
+        }
+    }
+}
+
+/// A rule that fired on a drive.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Alert {
+    /// Unix time the alert fired, in seconds
+    pub timestamp: u64,
+    /// Name of the rule
+    pub rule: String,
+    /// Severity of the rule
+    pub severity: Severity,
+    /// Device path at the time, e.g. /dev/sda
+    pub device: String,
+    /// Drive key (model + serial), or the device path for drives without a serial
+    pub drive_key: String,
+    /// Value that matched
+    pub value: f64,
+    /// Human-readable summary
+    pub message: String,
+}
+
+/// Remembers which rule/drive pairs are in alert so each condition notifies once when it starts,
+/// not on every scan while it lasts.
+#[derive(Default)]
+pub struct AlertEngine {
+    /// (rule name, drive key) pairs whose condition held at the last scan
+    active: HashSet<(String, String)>,
+}
+
+impl AlertEngine {
+    /// Evaluates all rules against freshly scanned drives.
+    /// Sleeping drives keep their previous state since their data was not re-read.
+    ///
+    /// # Arguments
+    /// * `rules` - Valid rules
+    /// * `drives` - Scanned drives
+    /// * `now` - Unix time in seconds
+    ///
+    /// # Returns
+    /// Alerts whose condition started holding since the previous scan.
+    pub fn check<'a>(&mut self, rules: &[AlertRule], drives: impl IntoIterator<Item = &'a DiskInfo>, now: u64) -> Vec<Alert> {
+        self.check_each(drives.into_iter().map(|di| (di, rules.to_vec())), now)
+    }
+
+    /// Evaluates each drive against its own rules, e.g. the global rules adjusted by `drive_rules`.
+    /// Rules a drive no longer has are forgotten for it, so they notify again if they come back.
+    ///
+    /// # Arguments
+    /// * `drives` - Scanned drives with the rules that apply to each
+    /// * `now` - Unix time in seconds
+    ///
+    /// # Returns
+    /// Alerts whose condition started holding since the previous scan.
+    pub fn check_each<'a>(&mut self, drives: impl IntoIterator<Item = (&'a DiskInfo, Vec<AlertRule>)>, now: u64) -> Vec<Alert> {
+        let mut fired = Vec::new();
+        for (di, rules) in drives.into_iter().filter(|(d, _)| !d.cached) {
+            let key = drive_key(di).unwrap_or_else(|| di.dev.clone());
+            self.active.retain(|(rule, k)| *k != key || rules.iter().any(|r| r.name == *rule));
+            for rule in &rules {
+                let pair = (rule.name.clone(), key.clone());
+                match rule.matches(di) {
+                    Some(value) => {
+                        if self.active.insert(pair) {
+                            fired.push(rule.alert(di, value, now));
+                        }
+                    }
+                    None => {
+                        self.active.remove(&pair);
+                    }
+                }
+            }
+        }
+        fired
+    }
+}
+
+/// Rules that apply to one drive: a drive with its own temperature alarm drops the global `temp_c` rules
+/// in favour of that limit, so a hot-running NVMe and a cool HDD can each alert at their own temperature.
+///
+/// # Arguments
+/// * `rules` - Global rules from the `[alerts]` section
+/// * `temp_alarm` - The drive's temperature alarm in °C, if set
+pub fn drive_rules(rules: &[AlertRule], temp_alarm: Option<i32>) -> Vec<AlertRule> {
+    match temp_alarm {
+        Some(limit) => rules
+            .iter()
+            .filter(|rule| !rule.is_temperature())
+            .cloned()
+            .chain(std::iter::once(AlertRule::temp_alarm(limit)))
+            .collect(),
+        None => rules.to_vec(),
+    }
+}
+
+/// An alert in the persistent log.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoggedAlert {
+    /// The alert as fired
+    pub alert: Alert,
+    /// Whether the user has seen and acknowledged it
+    pub acknowledged: bool,
+    /// Unix time until which further alerts of the same rule on the same drive are silenced
+    pub snoozed_until: Option<u64>,
+}
+
+/// Persistent log of fired alerts, with acknowledgement and snoozing.
+#[derive(Default)]
+pub struct AlertLog {
+    /// Logged alerts, oldest first
+    pub entries: Vec<LoggedAlert>,
+    /// Log file, None to keep the log in memory only
+    path: Option<PathBuf>,
+}
+
+impl AlertLog {
+    /// Loads `alerts.log` from the data directory; a missing file yields an empty log.
+    pub fn open_default() -> Self {
+        Self::open(data_dir().map(|dir| dir.join("alerts.log")))
+    }
+
+    /// Loads a log file.
+    ///
+    /// # Arguments
+    /// * `path` - Log file, None for an in-memory log
+    pub fn open(path: Option<PathBuf>) -> Self {
+        let entries = path
+            .as_ref()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .map(|text| parse_log(&text))
+            .unwrap_or_default();
+        Self { entries, path }
+    }
+
+    /// Adds a fired alert to the log.
+    /// Alerts of a snoozed rule/drive pair are logged already acknowledged.
+    ///
+    /// # Returns
+    /// True if the alert should be notified, false if it is snoozed.
+    pub fn record(&mut self, alert: Alert) -> Result<bool, String> {
+        let snoozed = self.is_snoozed(&alert.rule, &alert.drive_key, alert.timestamp);
+        self.entries.push(LoggedAlert { alert, acknowledged: snoozed, snoozed_until: None });
+        if self.entries.len() > MAX_LOGGED_ALERTS {
+            self.entries.drain(..self.entries.len() - MAX_LOGGED_ALERTS);
+        }
+        self.save()?;
+        Ok(!snoozed)
+    }
+
+    /// Marks an entry as acknowledged.
+    ///
+    /// # Arguments
+    /// * `index` - Position in `entries`
+    pub fn acknowledge(&mut self, index: usize) -> Result<(), String> {
+        if let Some(entry) = self.entries.get_mut(index) {
+            entry.acknowledged = true;
+        }
+        self.save()
+    }
+
+    /// Acknowledges an entry and silences its rule on its drive until `until`.
+    ///
+    /// # Arguments
+    /// * `index` - Position in `entries`
+    /// * `until` - Unix time the snooze ends
+    pub fn snooze(&mut self, index: usize, until: u64) -> Result<(), String> {
+        if let Some(entry) = self.entries.get_mut(index) {
+            entry.acknowledged = true;
+            entry.snoozed_until = Some(until);
+        }
+        self.save()
+    }
+
+    /// Whether a rule is snoozed on a drive at time `now`.
+    pub fn is_snoozed(&self, rule: &str, drive_key: &str, now: u64) -> bool {
+        self.entries.iter().any(|e| {
+            e.alert.rule == rule && e.alert.drive_key == drive_key && e.snoozed_until.is_some_and(|until| until > now)
+        })
+    }
+
+    /// Number of alerts not acknowledged yet.
+    pub fn unacknowledged(&self) -> usize {
+        self.entries.iter().filter(|e| !e.acknowledged).count()
+    }
+
+    /// Rewrites the log file.
+    fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        let text: String = self.entries.iter().map(|e| format_log_entry(e) + "\n").collect();
+        std::fs::write(path, text).map_err(|e| format!("Failed to save {}: {}", path.display(), e))
+    }
+}
+
+/// Parses alert log contents.
+/// Format per line, tab-separated: timestamp, severity, rule, device, drive key, value,
+/// acknowledged (0/1), snoozed-until (empty if not snoozed), message. Malformed lines are skipped.
+///
+/// # Arguments
+/// * `text` - Contents of an alert log
+pub fn parse_log(text: &str) -> Vec<LoggedAlert> {
+    text.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.splitn(9, '\t').collect();
+            if fields.len() != 9 {
+                return None;
+            }
+            Some(LoggedAlert {
+                alert: Alert {
+                    timestamp: fields[0].parse().ok()?,
+                    severity: Severity::from_name(fields[1]),
+                    rule: fields[2].to_string(),
+                    device: fields[3].to_string(),
+                    drive_key: fields[4].to_string(),
+                    value: fields[5].parse().ok()?,
+                    message: fields[8].to_string(),
+                },
+                acknowledged: fields[6] == "1",
+                snoozed_until: fields[7].parse().ok(),
+            })
+        })
+        .collect()
+}
+
+/// Formats a log entry as one line (see `parse_log`). Tabs and line breaks in text fields become spaces.
+fn format_log_entry(entry: &LoggedAlert) -> String {
+    let clean = |s: &str| s.replace(['\t', '\n', '\r'], " ");
+    let a = &entry.alert;
+    format!(
+        "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+        a.timestamp,
+        a.severity.name(),
+        clean(&a.rule),
+        clean(&a.device),
+        clean(&a.drive_key),
+        a.value,
+        u8::from(entry.acknowledged),
+        entry.snoozed_until.map(|t| t.to_string()).unwrap_or_default(),
+        clean(&a.message)
+    )
+}
+
+/// Where alerts are delivered.
+#[derive(Clone, Debug, PartialEq)]
+pub enum NotificationChannel {
+    /// Desktop notification through `notify-send`
+    Desktop,
+    /// Shell command run with SSD_ALERT_* environment variables
+    Command(String),
+    /// JSON POST to a plain-HTTP URL
+    Webhook(String),
+}
+
+impl NotificationChannel {
+    /// Parses a `[notifications]` entry.
+    ///
+    /// # Arguments
+    /// * `name` - "desktop", "command" or "webhook"
+    /// * `value` - "true"/"false" for desktop, the command line or URL otherwise
+    ///
+    /// # Returns
+    /// The channel, or None for unknown names, disabled desktop notifications and empty values.
+    pub fn parse(name: &str, value: &str) -> Option<Self> {
+        let value = value.trim();
+        match name.trim() {
+            "desktop" => (value == "true").then_some(NotificationChannel::Desktop),
+            "command" if !value.is_empty() => Some(NotificationChannel::Command(value.to_string())),
+            "webhook" if value.starts_with("http://") => Some(NotificationChannel::Webhook(value.to_string())),
+            _ => None,
+        }
+    }
+
+    /// Formats the channel as a `[notifications]` line (see `parse`).
+    pub fn to_line(&self) -> String {
+        match self {
+            NotificationChannel::Desktop => "desktop = true".to_string(),
+            NotificationChannel::Command(cmd) => format!("command = {}", cmd),
+            NotificationChannel::Webhook(url) => format!("webhook = {}", url),
+        }
+    }
+
+    /// Delivers an alert.
+    ///
+    /// # Returns
+    /// Ok(()) on success, Err with a message otherwise.
+    pub fn send(&self, alert: &Alert) -> Result<(), String> {
+        match self {
+            NotificationChannel::Desktop if in_flatpak() => {
+                let connection = Connection::session().map_err(|e| format!("No D-Bus session for notifications: {}", e))?;
+                connection
+                    .call_method(
+                        Some("org.freedesktop.portal.Desktop"),
+                        "/org/freedesktop/portal/desktop",
+                        Some("org.freedesktop.portal.Notification"),
+                        "AddNotification",
+                        &portal_notification(alert),
+                    )
+                    .map_err(|e| format!("Notification portal: {}", e))?;
+                Ok(())
+            }
+            NotificationChannel::Desktop => {
+                let urgency = if alert.severity == Severity::Critical { "critical" } else { "normal" };
+                let status = Command::new("notify-send")
+                    .args(["-u", urgency, "-a", "SSD Health Checker"])
+                    .arg(format!("Drive alert: {}", alert.rule))
+                    .arg(&alert.message)
+                    .status()
+                    .map_err(|e| format!("notify-send: {}", e))?;
+                status.success().then_some(()).ok_or(format!("notify-send exited with {}", status))
+            }
+            NotificationChannel::Command(cmd) => {
+                let value = alert.value.to_string();
+                let env = [
+                    ("SSD_ALERT_RULE", alert.rule.as_str()),
+                    ("SSD_ALERT_SEVERITY", alert.severity.name()),
+                    ("SSD_ALERT_DEVICE", alert.device.as_str()),
+                    ("SSD_ALERT_DRIVE", alert.drive_key.as_str()),
+                    ("SSD_ALERT_VALUE", value.as_str()),
+                    ("SSD_ALERT_MESSAGE", alert.message.as_str()),
+                ];
+                let status = host_command("sh", &env)
+                    .args(["-c", cmd])
+                    .status()
+                    .map_err(|e| format!("{}: {}", cmd, e))?;
+                status.success().then_some(()).ok_or(format!("{} exited with {}", cmd, status))
+            }
+            NotificationChannel::Webhook(url) => {
+                let body = Json::Object(vec![
+                    ("rule".into(), alert.rule.as_str().into()),
+                    ("severity".into(), alert.severity.name().into()),
+                    ("device".into(), alert.device.as_str().into()),
+                    ("drive".into(), alert.drive_key.as_str().into()),
+                    ("value".into(), alert.value.into()),
+                    ("message".into(), alert.message.as_str().into()),
+                    ("timestamp".into(), alert.timestamp.into()),
+                ]);
+                match http::request("POST", url, &[("Content-Type", "application/json")], &body.to_string())? {
+                    (200..=299, _) => Ok(()),
+                    (status, _) => Err(format!("{} answered {}", url, status)),
+                }
+            }
+        }
+    }
+}
+
+/// Arguments of the portal's `AddNotification` call that shows an alert, which is how a Flatpak app reaches
+/// the notification daemon (notify-send is not in the sandbox).
+/// Each rule/drive pair has its own notification ID, so a repeat replaces the previous notification.
+///
+/// # Arguments
+/// * `alert` - Alert to show
+///
+/// # Returns
+/// The notification ID and the notification's title, body and priority.
+pub fn portal_notification(alert: &Alert) -> (String, HashMap<&'static str, Value<'static>>) {
+    let priority = if alert.severity == Severity::Critical { "urgent" } else { "normal" };
+    let notification = HashMap::from([
+        ("title", Value::from(format!("Drive alert: {}", alert.rule))),
+        ("body", Value::from(alert.message.clone())),
+        ("priority", Value::from(priority)),
+    ]);
+    (format!("ssd-alert-{}-{}", alert.rule, alert.drive_key), notification)
+}
+
+/// Sound the audible alarm plays when no file is configured (freedesktop sound theme)
+pub const DEFAULT_ALARM_SOUND: &str = "/usr/share/sounds/freedesktop/stereo/alarm-clock-elapsed.oga";
+
+/// Conditions that sound the audible alarm: a failed SMART self-assessment or a critical attribute.
+pub fn alarm_rules() -> Vec<AlertRule> {
+    [("smart_failed", "critical: smart_passed == 0"), ("critical_attribute", "critical: critical_attributes > 0")]
+        .iter()
+        .filter_map(|(name, text)| AlertRule::parse(name, text).ok())
+        .collect()
+}
+
+/// Plays the alarm sound with `paplay` (PulseAudio/PipeWire), falling back to the sound theme's
+/// alarm through `canberra-gtk-play`. Both run on the host inside a Flatpak. Blocks until the sound has played.
+///
+/// # Arguments
+/// * `sound` - Sound file to play
+///
+/// # Returns
+/// Ok(()) if either player succeeded, Err with a message otherwise.
+pub fn play_alarm(sound: &str) -> Result<(), String> {
+    let played = |cmd: &mut Command| cmd.status().is_ok_and(|s| s.success());
+    if played(host_command("paplay", &[]).arg(sound))
+        || played(host_command("canberra-gtk-play", &[]).args(["--id", "alarm-clock-elapsed"]))
+    {
+        Ok(())
+    } else {
+        Err(format!("Could not play {} (paplay and canberra-gtk-play failed)", sound))
+    }
+}
+
+/// Sends an alert to every channel.
+///
+/// # Returns
+/// One error message per channel that failed.
+pub fn notify(channels: &[NotificationChannel], alert: &Alert) -> Vec<String> {
+    channels.iter().filter_map(|c| c.send(alert).err()).collect()
+}
+
+/// When notifications may be sent: the `quiet_hours` and `rate_limit` entries of `[notifications]`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NotificationSchedule {
+    /// Start and end of quiet hours in minutes after local midnight; the end may be past midnight
+    pub quiet_hours: Option<(u32, u32)>,
+    /// Minimum seconds between notifications of the same rule on the same drive, 0 for no limit
+    pub rate_limit_secs: u64,
+}
+
+impl NotificationSchedule {
+    /// Applies a `[notifications]` entry. A malformed value leaves the setting unchanged.
+    ///
+    /// # Arguments
+    /// * `name` - "quiet_hours" or "rate_limit"
+    /// * `value` - "22:00-07:00" for quiet hours, a duration such as "30m", "6h" or "1d" for the rate limit
+    ///
+    /// # Returns
+    /// Ok(true) if the entry is a schedule setting, Ok(false) for other names, or an error message naming
+    /// the entry if its value is malformed.
+    pub fn parse_entry(&mut self, name: &str, value: &str) -> Result<bool, String> {
+        let value = value.trim();
+        match name.trim() {
+            "quiet_hours" => {
+                let range = parse_time_range(value)
+                    .ok_or_else(|| format!("quiet_hours: expected HH:MM-HH:MM, got \"{}\"", value))?;
+                self.quiet_hours = Some(range);
+            }
+            "rate_limit" => {
+                self.rate_limit_secs = parse_duration(value)
+                    .ok_or_else(|| format!("rate_limit: expected a duration such as 30m, 6h or 1d, got \"{}\"", value))?;
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Formats the schedule as `[notifications]` lines (see `parse_entry`); empty for the default.
+    pub fn to_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some((start, end)) = self.quiet_hours {
+            lines.push(format!(
+                "quiet_hours = {:02}:{:02}-{:02}:{:02}",
+                start / 60,
+                start % 60,
+                end / 60,
+                end % 60
+            ));
+        }
+        if self.rate_limit_secs > 0 {
+            lines.push(format!("rate_limit = {}", format_duration(self.rate_limit_secs)));
+        }
+        lines
+    }
+
+    /// Whether a local time of day falls in quiet hours.
+    ///
+    /// # Arguments
+    /// * `minute` - Minutes after local midnight
+    pub fn is_quiet(&self, minute: u32) -> bool {
+        match self.quiet_hours {
+            Some((start, end)) if start <= end => (start..end).contains(&minute),
+            // Wraps past midnight, e.g., 22:00-07:00
+            Some((start, end)) => minute >= start || minute < end,
+            None => false,
+        }
+    }
+}
+
+/// Applies a `NotificationSchedule`, remembering when each rule/drive pair was last notified.
+#[derive(Default)]
+pub struct NotificationThrottle {
+    /// Unix time of the last notification per (rule name, drive key)
+    last_sent: HashMap<(String, String), u64>,
+}
+
+impl NotificationThrottle {
+    /// Decides whether an alert is notified, and if so records it as sent.
+    /// Critical alerts ignore quiet hours; all alerts are rate limited.
+    ///
+    /// # Arguments
+    /// * `schedule` - Quiet hours and rate limit
+    /// * `alert` - The alert about to be notified
+    /// * `minute` - Local time of day of the alert, in minutes after midnight
+    pub fn allow(&mut self, schedule: &NotificationSchedule, alert: &Alert, minute: u32) -> bool {
+        if alert.severity != Severity::Critical && schedule.is_quiet(minute) {
+            return false;
+        }
+        let key = (alert.rule.clone(), alert.drive_key.clone());
+        if let Some(last) = self.last_sent.get(&key) {
+            if alert.timestamp < last + schedule.rate_limit_secs {
+                return false;
+            }
+        }
+        self.last_sent.insert(key, alert.timestamp);
+        true
+    }
+}
+
+/// Minutes after local midnight at a Unix time.
+///
+/// # Arguments
+/// * `now` - Unix time in seconds
+pub fn local_minute_of_day(now: u64) -> u32 {
+    ((now as i64 + utc_offset(now)).rem_euclid(86_400) / 60) as u32
+}
+
+/// The local time zone's UTC offset in seconds at a Unix time, as the C library's `localtime_r` computes it
+/// from `TZ` or /etc/localtime (0, i.e. UTC, if it fails).
+///
+/// # Arguments
+/// * `now` - Unix time in seconds
+fn utc_offset(now: u64) -> i64 {
+    let time = now as libc::time_t;
+    // SAFETY: an all-zero `tm` is a valid value, and localtime_r only writes to it through the pointers given
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    let converted = unsafe { libc::localtime_r(&time, &mut tm) };
+    if converted.is_null() {
+        0
+    } else {
+        tm.tm_gmtoff
+    }
+}
+
+/// Parses "HH:MM-HH:MM" into minutes after midnight.
+fn parse_time_range(s: &str) -> Option<(u32, u32)> {
+    let time = |t: &str| -> Option<u32> {
+        let (h, m) = t.trim().split_once(':')?;
+        let (h, m): (u32, u32) = (h.parse().ok()?, m.parse().ok()?);
+        (h < 24 && m < 60).then_some(h * 60 + m)
+    };
+    let (start, end) = s.split_once('-')?;
+    Some((time(start)?, time(end)?))
+}
+
+/// Parses a duration with an s, m, h or d suffix ("90s", "30m", "6h", "1d") into seconds.
+fn parse_duration(s: &str) -> Option<u64> {
+    let unit = match s.chars().last()? {
+        's' => 1,
+        'm' => 60,
+        'h' => 3600,
+        'd' => 86_400,
+        _ => return None,
+    };
+    s[..s.len() - 1].trim().parse::<u64>().ok().map(|n| n * unit)
+}
+
+/// Formats seconds with the largest suffix that divides them evenly (see `parse_duration`).
+fn format_duration(secs: u64) -> String {
+    [(86_400, 'd'), (3600, 'h'), (60, 'm')]
+        .iter()
+        .find(|(unit, _)| secs.is_multiple_of(*unit))
+        .map(|(unit, suffix)| format!("{}{}", secs / unit, suffix))
+        .unwrap_or_else(|| format!("{}s", secs))
+}
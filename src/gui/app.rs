@@ -1,19 +1,209 @@
 // Main application state and UI rendering logic for the SSD Health Checker
 
 // Import disk scanning functionality
-use crate::gui::{disk_scanner::scan_disks, stat_card};
+use crate::gui::disk_scanner::{
+    forget_static_probes, is_sleep_state, partition_type_name, probe_persistent_event_log, probe_sct_temp_history, scan_disk, scan_disks_from, SystemRoot,
+};
+use crate::gui::drive_actions::{default_telemetry_path, DriveAction};
+use crate::gui::usage_analyzer::UsageAnalysis;
+// Background read-only surface scan of the selected drive
+use crate::gui::surface_scan::SurfaceScan;
+// Read-only benchmark runs and their stored history
+use crate::gui::benchmark::{change_since_first, BenchmarkResult, BenchmarkRun, BenchmarkStore};
+// Per-drive settings entered by the user
+use crate::gui::settings::{
+    format_date, format_timestamp, parse_date, today, DriveSettings, EnduranceRating, PowerSaver, Settings,
+};
+// Exported JSON reports and the snapshot viewer
+use crate::gui::report::{default_report_path, Report};
+// Automatic report exports on a schedule
+use crate::gui::scheduled_export::{ExportFormat, ExportInterval, ExportSchedule};
+// Session event log and its export
+use crate::gui::session_log::{default_log_path, drive_changes, format_event, EventKind, SessionLog};
+// Charts saved as PNG images
+use crate::gui::chart_export::{crop_chart, default_chart_path, save_png};
+// Environment details for the About window
+use crate::gui::diagnostics::Diagnostics;
+// Start-on-login desktop entry
+use crate::gui::autostart::Autostart;
+// Tray icon that brings back a window closed with "Keep monitoring when closed"
+use crate::gui::tray::{hide_window, Tray};
+// Fuzzy matching for the command palette
+use crate::gui::palette::{rank, PaletteState};
+// Alert rules evaluated on every scan
+use crate::gui::alerts::{
+    alarm_rules, drive_rules, local_minute_of_day, notify, play_alarm, AlertEngine, AlertLog, NotificationThrottle,
+    DEFAULT_ALARM_SOUND,
+};
+// Reports fetched from other machines for the fleet view
+use crate::gui::fleet::Fleet;
+// Persistent per-drive history for write/wear rates
+use crate::gui::history::{
+    drive_key, replacement_estimate, traffic_rate, usage_rates, HistorySample, HistoryStore, TrafficRate, UsageRates,
+};
+use crate::gui::system_sensors::{
+    batteries, cpu_sensors, cpu_temperature, fan_speeds, gpu_temperatures, on_battery, swap_devices, swap_drives, Battery,
+};
+use crate::gui::{
+    confirm_apply_button, confirm_button, event_timeline, line_graph, margin_bar, save_chart_button, stat_card, stat_card_with_note, temperature_graph,
+    usage_ring, warning_banner, warning_banner_with_action, warning_banner_with_progress,
+};
 // Import disk information models
-use crate::models::DiskInfo;
+use crate::models::{
+    event_counts, humanize_hours, mask_identifier, sorted_partitions, AtaSecurity, AttributeStatus, CapacityUsage, DiskInfo, EventCategory,
+    FirmwareSlots, HealthScore, HealthWeights, NvmeController, PartitionInfo, PartitionSort, PersistentEvent, QueueInfo, SsdWear, DRIVE_GROUPS,
+};
+// Flatpak hint when no drives show up
+use crate::sandbox::in_flatpak;
+// Thousands separators for counts, LBAs and raw values
+use crate::locale::{group_digits, group_raw_digits};
 // Import egui for UI rendering
 use eframe::egui;
-// Regex for parsing system command output
-use regex::Regex;
-// Command execution for reading system temperatures
-use std::process::Command;
-// Arc for thread-safe reference counting
+// HashMap for per-drive session baselines
+use std::collections::HashMap;
+// Arc for thread-safe reference counting, channel for the background startup scan
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
 use std::sync::Arc;
-// Duration and Instant for time-based operations
-use std::time::{Duration, Instant};
+// RAM and swap usage
+use sysinfo::System;
+// Duration and Instant for time-based operations, SystemTime for sample timestamps
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+// Report file paths
+use std::path::{Path, PathBuf};
+
+/// Maximum number of temperature samples kept per drive (about 14 hours at 5 s refresh)
+const MAX_TEMP_SAMPLES: usize = 10_000;
+
+/// Warranty length DWPD endurance ratings are quoted over when none was entered (the common SSD warranty)
+const DEFAULT_WARRANTY_YEARS: u32 = 5;
+
+/// Warranties ending within this many days are highlighted
+const WARRANTY_WARNING_DAYS: i64 = 90;
+
+/// Shortest automatic refresh interval in battery-saver mode
+const POWER_SAVER_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How often the window repaints by itself in battery-saver mode (every second otherwise)
+const POWER_SAVER_REPAINT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Seconds before a failed scheduled export is tried again (it is due on every scan until it succeeds)
+const SCHEDULED_EXPORT_RETRY_SECS: u64 = 3600;
+
+/// Temperature in °C shown as hot for drives without their own temperature alarm
+const DEFAULT_HOT_TEMP_C: i32 = 60;
+
+/// How long a drive that disappeared stays in the list with its last known data
+const DISCONNECTED_GRACE: Duration = Duration::from_secs(10 * 60);
+
+/// How often the fleet view refetches remote hosts while it is open
+const FLEET_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Partition table columns as (header, shown by default); the usage analyzer button is always shown
+const PARTITION_COLUMNS: &[(&str, bool)] = &[
+    ("Partition", true),
+    ("Label", true),
+    ("Mount point", true),
+    ("Type", true),
+    ("Total", true),
+    ("Used", true),
+    ("Free", true),
+    ("Free%", true),
+    ("Inodes", false),
+    ("Options", true),
+];
+
+/// SMART attribute table columns as (header, shown by default)
+const SMART_COLUMNS: &[(&str, bool)] = &[
+    ("ID", true),
+    ("Attribute", true),
+    ("Type", false),
+    ("Value", true),
+    ("Worst", true),
+    ("Thresh", true),
+    ("Margin", true),
+    ("Raw", true),
+    ("Δ session", true),
+    ("Status", true),
+];
+
+/// A column shown or hidden in the column picker: (table, column, visible)
+type ColumnToggle = (&'static str, &'static str, bool);
+
+/// Where the drive list came from when viewing an exported report instead of this machine.
+struct Snapshot {
+    /// Host name the report was taken on
+    host: String,
+    /// Unix time the report was taken
+    generated_at: u64,
+    /// Report file that was opened
+    path: PathBuf,
+}
+
+/// Message from a background scan.
+enum ScanMessage {
+    /// A device is about to be probed
+    Probing(String),
+    /// The scan finished
+    Done(ScanOutcome),
+}
+
+/// Result of a background scan.
+enum ScanOutcome {
+    /// Every drive (see `scan_disks`), and the SCT temperature log of each ATA drive not seen before,
+    /// keyed by `drive_identity`
+    All(Result<Vec<DiskInfo>, String>, HashMap<String, Vec<(u64, f32)>>),
+    /// One drive rescanned from its page, with its device path
+    Drive(String, Result<Box<DiskInfo>, String>),
+}
+
+/// A scan running in the background, so probing drives never blocks the UI;
+/// until the first one finishes, the window shows which drive is being probed.
+struct BackgroundScan {
+    /// Messages from the scan thread
+    rx: Receiver<ScanMessage>,
+    /// Device currently being probed
+    probing: Option<String>,
+}
+
+/// Which page the central panel shows.
+#[derive(Clone, Copy, PartialEq)]
+enum View {
+    /// Capacity of all drives at a glance, shown first
+    Overview,
+    /// Details of the drive selected in the sidebar
+    Drive,
+    /// Machine vitals: CPU/GPU temperatures, fans, memory, uptime
+    System,
+    /// Hosts × drives overview of this machine and remote `serve` instances
+    Fleet,
+    /// Alert rules from the config file and alerts fired this session
+    Alerts,
+    /// Scans, errors, SMART changes and alerts of this session in order
+    Log,
+}
+
+/// An entry of the Ctrl+K command palette, run once the palette closes.
+#[derive(Clone)]
+enum PaletteAction {
+    /// Rescan all drives now
+    Refresh,
+    /// Save the JSON report
+    Export,
+    /// Show the "Open report" window
+    OpenReport,
+    /// Pause or resume the automatic refresh
+    TogglePause,
+    /// Mask or unmask serial numbers and WWNs
+    TogglePrivacy,
+    /// Show the About window
+    About,
+    /// Switch to a page
+    Show(View),
+    /// Open a drive's details by index into `drives`
+    SelectDrive(usize),
+    /// Start a SMART self-test: device and kind ("short", "long" or "conveyance")
+    SelfTest(String, &'static str),
+}
 
 /// Main application state for the eframe app.
 /// Manages disk information, system temperatures, and UI state.
@@ -21,7 +211,7 @@ pub struct AppState {
     /// The discovered drives wrapped in Arc for efficient cloning
     drives: Vec<Arc<DiskInfo>>,
 
-    /// Index of currently selected drive in the drives vector
+    /// Index of currently selected drive in the drives vector; re-resolved by serial after each refresh
     selected: usize,
 
     /// Last error message if scanning drives failed
@@ -30,126 +220,3792 @@ pub struct AppState {
     /// Cached CPU temperature average in Celsius
     cpu_temp: Option<f32>,
 
-    /// Cached GPU temperature in Celsius
-    gpu_temp: Option<f32>,
+    /// Cached CPU sensor labels and temperatures in Celsius (cores, Tctl, package)
+    cpu_sensors: Vec<(String, f32)>,
+
+    /// Cached GPU names and temperatures in Celsius, one entry per GPU
+    gpu_temps: Vec<(String, f32)>,
+
+    /// Whether the machine ran on battery at the last system reading
+    on_battery: bool,
+
+    /// Unix time of the last failed scheduled export, so a broken export directory is not retried on every scan
+    scheduled_export_failed: Option<u64>,
+
+    /// Page shown in the central panel
+    view: View,
+
+    /// Whether the per-sensor CPU temperature list below the CPU card is expanded
+    cpu_list_open: bool,
+
+    /// Whether the per-GPU temperature list below the GPU card is expanded
+    gpu_list_open: bool,
+
+    /// Fan labels and speeds in RPM from hwmon
+    fans: Vec<(String, u32)>,
+
+    /// Laptop batteries from /sys/class/power_supply; empty on desktops
+    batteries: Vec<Battery>,
+
+    /// sysinfo handle used for RAM and swap usage
+    system: System,
+
+    /// Active swap devices and files from /proc/swaps
+    swap_devices: Vec<String>,
+
+    /// Drives holding swap (a swap partition's parent drive), for the per-drive swap card
+    swap_drives: Vec<String>,
+
+    /// Disk usage analysis shown in the "What's using space?" window
+    usage_analysis: Option<UsageAnalysis>,
+
+    /// Environment details shown in the About window while it is open
+    about: Option<Diagnostics>,
+
+    /// Start-on-login entry, None if the config directory is unknown
+    autostart: Option<Autostart>,
+
+    /// Minimize the window (or hide it to the tray) on the first frame (started with `gui --minimized`)
+    minimize_on_start: bool,
+
+    /// Set by the Quit button so the close request is not turned into minimizing
+    quitting: bool,
+
+    /// Tray icon while "Keep monitoring when closed" is on, None if off or the desktop has no tray
+    tray: Option<Tray>,
+
+    /// Query of the Ctrl+K command palette, None while it is closed
+    palette: Option<PaletteState>,
+
+    /// Read-only surface scan shown on its drive's page; kept after it ends to show the results
+    surface_scan: Option<SurfaceScan>,
+
+    /// Partition table sort column and direction (true = descending); None keeps the detected order
+    partition_sort: Option<(PartitionSort, bool)>,
+
+    /// Benchmark currently running, at most one at a time
+    benchmark_run: Option<BenchmarkRun>,
+
+    /// Where benchmark results are stored (None if no home directory)
+    benchmark_store: Option<BenchmarkStore>,
+
+    /// Benchmark results loaded from the store, keyed by drive key
+    benchmarks: HashMap<String, Vec<BenchmarkResult>>,
+
+    /// NVMe persistent event logs read this session, keyed by device path; None while still being read
+    event_logs: HashMap<String, Option<Result<Vec<PersistentEvent>, String>>>,
+
+    /// Sender cloned into the threads reading persistent event logs
+    event_log_tx: Sender<(String, Result<Vec<PersistentEvent>, String>)>,
+
+    /// Persistent event logs read in the background, as (device path, log)
+    event_log_rx: Receiver<(String, Result<Vec<PersistentEvent>, String>)>,
+
+    /// Chart waiting for the window screenshot it is cut out of, as (screen rectangle, name)
+    pending_chart: Option<(egui::Rect, String)>,
 
     /// Timestamp of the last automatic refresh
     last_refresh: Instant,
 
     /// How often to automatically refresh drive data
     refresh_interval: Duration,
+
+    /// Auto refresh is suspended; the refresh button still rescans on demand
+    paused: bool,
+
+    /// When drive data was last rescanned, shown as its age in the sidebar footer
+    last_updated: Option<Instant>,
+
+    /// Outcome of the last drive action (Ok = success message, Err = error message)
+    action_result: Option<Result<String, String>>,
+
+    /// Last `action_result` copied to the session log, so each failure is logged once
+    logged_result: Option<Result<String, String>>,
+
+    /// Sender cloned into background tasks (notifications, the alarm) to report their outcome
+    task_tx: Sender<Result<String, String>>,
+
+    /// Outcomes of background tasks, shown as `action_result` when they arrive
+    task_rx: Receiver<Result<String, String>>,
+
+    /// Scans, errors, SMART changes and alerts of this session
+    session_log: SessionLog,
+
+    /// Event kinds shown on the log page
+    log_kinds: Vec<EventKind>,
+
+    /// Raw SMART attribute values first seen this session, keyed by `drive_identity` then attribute ID
+    session_baseline: HashMap<String, HashMap<String, u64>>,
+
+    /// Temperature samples per `drive_identity` as (Unix seconds, °C), seeded from the SCT log
+    temp_history: HashMap<String, Vec<(u64, f32)>>,
+
+    /// On-disk store of hourly counter samples, None if no home directory is known
+    history_store: Option<HistoryStore>,
+
+    /// Loaded counter history per drive key (model + serial)
+    history: HashMap<String, Vec<HistorySample>>,
+
+    /// Whether serial numbers and WWNs are masked on screen
+    privacy_mode: bool,
+
+    /// Exported report being viewed; None while showing this machine's drives
+    snapshot: Option<Snapshot>,
+
+    /// Whether the "Open report" window is shown
+    open_report_dialog: bool,
+
+    /// Path typed into the "Open report" window
+    report_path_input: String,
+
+    /// Per-drive settings (endurance rating) from the config file
+    settings: Settings,
+
+    /// Full scan still running, None once its result is applied
+    scan: Option<BackgroundScan>,
+
+    /// Context the scan thread wakes the UI through
+    ctx: egui::Context,
+
+    /// When each drive that disappeared from the scan was last seen, by `drive_identity`
+    disconnected: HashMap<String, Instant>,
+
+    /// Last counter reading of each awake drive by `drive_identity`, for the per-refresh traffic rate
+    last_counters: HashMap<String, (Instant, HistorySample)>,
+
+    /// Host traffic of each drive between its last two readings, by `drive_identity`
+    traffic: HashMap<String, TrafficRate>,
+
+    /// Tracks which alert conditions are active so each one notifies once
+    alert_engine: AlertEngine,
+
+    /// Tracks failed self-assessments and critical attributes so the audible alarm sounds once per onset
+    alarm_engine: AlertEngine,
+
+    /// Applies quiet hours and the rate limit to notifications
+    notification_throttle: NotificationThrottle,
+
+    /// Persistent log of fired alerts with their acknowledged/snoozed state
+    alert_log: AlertLog,
+
+    /// Remote hosts shown in the fleet view
+    fleet: Fleet,
+
+    /// Address typed into the fleet view's "Add host" field
+    fleet_host_input: String,
+
+    /// When remote hosts were last fetched, None before the fleet view was first opened
+    last_fleet_refresh: Option<Instant>,
 }
 
 impl AppState {
     /// Creates a new application state instance.
-    /// Sets light theme, performs initial data collection, and starts refresh timer.
+    /// Sets light theme, starts the initial drive scan in the background, and collects system vitals.
     ///
     /// # Arguments
     /// * `cc` - eframe creation context containing egui context
-    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+    /// * `start_minimized` - Minimize the window once it is shown, e.g. when started on login
+    pub fn new(cc: &eframe::CreationContext<'_>, start_minimized: bool) -> Self {
         // Configure light theme for consistent appearance
         cc.egui_ctx.set_visuals(egui::Visuals::light());
 
+        let (task_tx, task_rx) = channel();
+        let (event_log_tx, event_log_rx) = channel();
         let mut s = Self {
             drives: Vec::new(),
             selected: 0,
             last_error: None,
             cpu_temp: None,
-            gpu_temp: None,
+            cpu_sensors: Vec::new(),
+            gpu_temps: Vec::new(),
+            on_battery: on_battery(),
+            scheduled_export_failed: None,
+            view: View::Overview,
+            cpu_list_open: false,
+            gpu_list_open: false,
+            fans: Vec::new(),
+            batteries: Vec::new(),
+            system: System::new(),
+            swap_devices: Vec::new(),
+            swap_drives: Vec::new(),
+            usage_analysis: None,
+            about: None,
+            autostart: Autostart::open_default(),
+            minimize_on_start: start_minimized,
+            quitting: false,
+            tray: None,
+            palette: None,
+            surface_scan: None,
+            partition_sort: None,
+            benchmark_run: None,
+            benchmark_store: BenchmarkStore::open_default(),
+            benchmarks: HashMap::new(),
+            event_logs: HashMap::new(),
+            event_log_tx,
+            event_log_rx,
+            pending_chart: None,
             // Force immediate refresh by setting last refresh to 10 seconds ago
             last_refresh: Instant::now() - Duration::from_secs(10),
             // Automatically refresh data every 5 seconds
             refresh_interval: Duration::from_secs(5),
+            paused: false,
+            last_updated: None,
+            action_result: None,
+            logged_result: None,
+            task_tx,
+            task_rx,
+            session_log: SessionLog::default(),
+            log_kinds: EventKind::ALL.to_vec(),
+            session_baseline: HashMap::new(),
+            temp_history: HashMap::new(),
+            history_store: HistoryStore::open_default(),
+            history: HashMap::new(),
+            settings: Settings::load_default(),
+            privacy_mode: false,
+            snapshot: None,
+            open_report_dialog: false,
+            report_path_input: String::new(),
+            scan: None,
+            ctx: cc.egui_ctx.clone(),
+            disconnected: HashMap::new(),
+            last_counters: HashMap::new(),
+            traffic: HashMap::new(),
+            alert_engine: AlertEngine::default(),
+            alert_log: AlertLog::open_default(),
+            notification_throttle: NotificationThrottle::default(),
+            alarm_engine: AlertEngine::default(),
+            fleet: Fleet::load_default(),
+            fleet_host_input: String::new(),
+            last_fleet_refresh: None,
         };
 
-        // Perform initial data collection
+        // Without a tray closing the window falls back to minimizing, so a missing tray is not an error here
+        if s.settings.minimize_on_close {
+            s.tray = Tray::start(s.ctx.clone()).ok();
+        }
+
+        // Probe drives in the background; the first frames show which device is being probed
         s.refresh();
         s.update_system_temps();
 
         s
     }
 
-    /// Refreshes the disk list by calling scan_disks.
-    /// On success, updates the drives vector and adjusts selection if needed.
-    /// On error, clears the drives vector and stores the error message.
-    fn refresh(&mut self) {
-        self.last_error = None;
-        match scan_disks() {
-            Ok(list) => {
-                // Wrap each DiskInfo in Arc for efficient sharing
-                self.drives = list.into_iter().map(Arc::new).collect();
-
-                // Clamp selection to valid range if drives changed
-                if !self.drives.is_empty() && self.selected >= self.drives.len() {
-                    self.selected = 0;
-                }
+    /// Finds a drive in a new scan: by model and serial, or by device path for drives without a serial.
+    /// Device paths alone are not stable: plugging in a USB disk can shift them.
+    ///
+    /// # Arguments
+    /// * `drives` - Drives of the new scan
+    /// * `drive` - Drive from the previous scan
+    ///
+    /// # Returns
+    /// The drive's index in `drives`, or None if it is no longer present.
+    fn find_same_drive(drives: &[Arc<DiskInfo>], drive: &DiskInfo) -> Option<usize> {
+        match drive_key(drive) {
+            Some(key) => drives.iter().position(|d| drive_key(d).as_ref() == Some(&key)),
+            None => drives.iter().position(|d| drive_key(d).is_none() && d.dev == drive.dev),
+        }
+    }
+
+    /// Identifies a drive across scans: model and serial, or the device path for drives without a serial.
+    fn drive_identity(d: &DiskInfo) -> String {
+        drive_key(d).unwrap_or_else(|| d.dev.clone())
+    }
+
+    /// Names a drive in the session log: device path and model (e.g., "/dev/sda (CT1000MX500SSD1)").
+    fn log_name(d: &DiskInfo) -> String {
+        match &d.model {
+            Some(model) => format!("{} ({})", d.dev, model),
+            None => d.dev.clone(),
+        }
+    }
+
+    /// Whether a drive in the list is one that disappeared and is kept for the grace period.
+    fn is_disconnected(&self, d: &DiskInfo) -> bool {
+        d.cached && self.disconnected.contains_key(&Self::drive_identity(d))
+    }
+
+    /// Starts a full scan in the background; its result is applied by `update` when it arrives.
+    /// Does nothing while a scan is already running.
+    fn refresh(&mut self) {
+        // A report snapshot is static; rescanning would replace it with this machine's drives
+        if self.snapshot.is_some() || self.scan.is_some() {
+            return;
+        }
+        let (tx, rx) = channel();
+        let ctx = self.ctx.clone();
+        let root = self.scan_root();
+        // Drives whose temperature history already started; the others get their SCT log imported
+        let known: Vec<String> = self.temp_history.keys().cloned().collect();
+        std::thread::spawn(move || {
+            let result = scan_disks_from(&root, |dev| {
+                let _ = tx.send(ScanMessage::Probing(dev.to_string()));
+                ctx.request_repaint();
+            });
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+            let sct = result
+                .iter()
+                .flatten()
+                .filter(|d| !d.cached && d.kind != "NVMe")
+                .map(|d| (Self::drive_identity(d), d))
+                .filter(|(identity, _)| !known.contains(identity))
+                .map(|(identity, d)| (identity, probe_sct_temp_history(&d.dev, now)))
+                .collect();
+            let _ = tx.send(ScanMessage::Done(ScanOutcome::All(result, sct)));
+            ctx.request_repaint();
+        });
+        self.scan = Some(BackgroundScan { rx, probing: None });
+    }
+
+    /// Whether battery-saver polling applies now (see `PowerSaver`).
+    fn power_saving(&self) -> bool {
+        self.settings.power_saver.is_active(self.on_battery)
+    }
+
+    /// Interval between automatic refreshes, stretched to at least a minute in battery-saver mode.
+    fn effective_refresh_interval(&self) -> Duration {
+        if self.power_saving() {
+            self.refresh_interval.max(POWER_SAVER_REFRESH_INTERVAL)
+        } else {
+            self.refresh_interval
+        }
+    }
+
+    /// Where full scans look for drives; in battery-saver mode drives suspended by runtime power
+    /// management are left asleep like drives in standby.
+    fn scan_root(&self) -> SystemRoot {
+        SystemRoot { skip_suspended: self.power_saving(), ..SystemRoot::host() }
+    }
+
+    /// Runs a slow task (a notification, the alarm, a telemetry dump) on its own thread so it cannot stall the UI.
+    ///
+    /// # Arguments
+    /// * `task` - Work to run; a returned outcome is shown like a drive action's (and errors go to the session log)
+    fn spawn_task(&self, task: impl FnOnce() -> Option<Result<String, String>> + Send + 'static) {
+        let (tx, ctx) = (self.task_tx.clone(), self.ctx.clone());
+        std::thread::spawn(move || {
+            if let Some(result) = task() {
+                let _ = tx.send(result);
+                ctx.request_repaint();
+            }
+        });
+    }
+
+    /// Starts re-probing a single drive in the background; `apply_scan` replaces its entry in the list
+    /// when the result arrives. Does nothing while another scan is running, since that one probes the drive too.
+    ///
+    /// # Arguments
+    /// * `dev` - Device path of the drive to rescan
+    fn rescan_drive(&mut self, dev: &str) {
+        if self.snapshot.is_some() || self.scan.is_some() {
+            return;
+        }
+        if let Some(key) = self.drives.iter().find(|d| d.dev == dev).and_then(|d| drive_key(d)) {
+            forget_static_probes(Some(&key));
+        }
+        let (tx, rx) = channel();
+        let ctx = self.ctx.clone();
+        let dev = dev.to_string();
+        std::thread::spawn(move || {
+            let result = scan_disk(&dev).map(Box::new);
+            let _ = tx.send(ScanMessage::Done(ScanOutcome::Drive(dev, result)));
+            ctx.request_repaint();
+        });
+        self.scan = Some(BackgroundScan { rx, probing: None });
+    }
+
+    /// Replaces one drive's entry with its rescan, leaving the others untouched.
+    ///
+    /// # Arguments
+    /// * `dev` - Device path of the rescanned drive
+    /// * `result` - Result of `scan_disk`
+    fn apply_drive_rescan(&mut self, dev: &str, result: Result<Box<DiskInfo>, String>) {
+        match result {
+            Ok(d) => {
+                let Some(index) = self.drives.iter().position(|p| p.dev == dev) else {
+                    return;
+                };
+                // A sleeping drive was not probed; keep its last known SMART data like a full refresh does
+                let updated = if d.cached { Self::merge_sleeping(&self.drives[index], *d) } else { *d };
+                let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+                self.session_log.record(now, EventKind::Scan, format!("Rescanned {}", dev));
+                for change in drive_changes(&self.drives[index], &updated) {
+                    self.session_log.record(now, EventKind::Change, change);
+                }
+                self.drives[index] = Arc::new(updated);
+                self.action_result = Some(Ok(format!("Rescanned {}", dev)));
+            }
+            Err(e) => self.action_result = Some(Err(e)),
+        }
+    }
+
+    /// Combines the last known data of a drive with a scan taken while it was asleep.
+    /// SMART data comes from `previous`; everything read without waking the drive comes from `asleep`.
+    ///
+    /// # Arguments
+    /// * `previous` - The drive as last probed awake
+    /// * `asleep` - Placeholder returned by the scanner for the sleeping drive
+    fn merge_sleeping(previous: &DiskInfo, asleep: DiskInfo) -> DiskInfo {
+        let mut merged = previous.clone();
+        merged.power_state = asleep.power_state;
+        merged.cached = true;
+        merged.partitions = asleep.partitions;
+        merged.unallocated_bytes = asleep.unallocated_bytes;
+        merged.hosts_root = asleep.hosts_root;
+        merged.hosts_esp = asleep.hosts_esp;
+        merged.queue = asleep.queue;
+        merged.by_id = asleep.by_id;
+        merged
+    }
+
+    /// Applies a scan result.
+    /// A full scan replaces the drives vector and keeps the same physical drive selected; on error it
+    /// clears the drives vector and stores the error message. A single-drive rescan only replaces that
+    /// drive's entry, and its errors are reported like a drive action.
+    ///
+    /// # Arguments
+    /// * `outcome` - Result of `scan_disks` or of a single-drive `scan_disk`
+    fn apply_scan(&mut self, outcome: ScanOutcome) {
+        let (result, mut sct) = match outcome {
+            ScanOutcome::All(result, sct) => (result, sct),
+            ScanOutcome::Drive(dev, result) => return self.apply_drive_rescan(&dev, result),
+        };
+        self.last_error = None;
+        self.last_updated = Some(Instant::now());
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        match result {
+            Ok(list) => {
+                let asleep = list.iter().filter(|d| d.cached).count();
+                let summary = if asleep > 0 { format!(" ({} asleep)", asleep) } else { String::new() };
+                self.session_log.record(now, EventKind::Scan, format!("Scanned {} drive(s){}", list.len(), summary));
+
+                // Wrap each DiskInfo in Arc for efficient sharing
+                let previous = std::mem::take(&mut self.drives);
+                self.drives = list
+                    .into_iter()
+                    .map(|d| {
+                        // Sleeping drives were not probed; keep showing their last known SMART data
+                        if d.cached {
+                            if let Some(prev) = previous.iter().find(|p| p.dev == d.dev) {
+                                return Arc::new(Self::merge_sleeping(prev, d));
+                            }
+                        }
+                        Arc::new(d)
+                    })
+                    .collect();
+
+                // Drives that vanished stay listed with their last data for a grace period; marking them
+                // cached keeps them out of history, alerts and traffic rates
+                let gone: Vec<&Arc<DiskInfo>> =
+                    previous.iter().filter(|p| Self::find_same_drive(&self.drives, p).is_none()).collect();
+                let mut still_gone = Vec::new();
+                for gone in gone {
+                    let identity = Self::drive_identity(gone);
+                    if !self.disconnected.contains_key(&identity) {
+                        self.session_log.record(now, EventKind::Change, format!("{} disappeared", Self::log_name(gone)));
+                    }
+                    let since = *self.disconnected.entry(identity.clone()).or_insert_with(Instant::now);
+                    if since.elapsed() < DISCONNECTED_GRACE {
+                        let mut kept = (**gone).clone();
+                        kept.cached = true;
+                        self.drives.push(Arc::new(kept));
+                        still_gone.push(identity);
+                    }
+                }
+                self.disconnected.retain(|identity, _| still_gone.contains(identity));
+
+                // Remember the first raw value seen for each attribute to compute session deltas
+                for d in &self.drives {
+                    let baseline = self.session_baseline.entry(Self::drive_identity(d)).or_default();
+                    for attr in &d.smart_attributes {
+                        if let Some(raw) = attr.raw_numeric() {
+                            baseline.entry(attr.id.clone()).or_insert(raw);
+                        }
+                    }
+                }
+
+                // SMART values that changed since the previous scan, and drives that were not there before
+                for d in self.drives.iter().filter(|d| !d.cached) {
+                    match previous.iter().find(|p| Self::find_same_drive(std::slice::from_ref(d), p).is_some()) {
+                        Some(before) => {
+                            for change in drive_changes(before, d) {
+                                self.session_log.record(now, EventKind::Change, change);
+                            }
+                        }
+                        None if !previous.is_empty() => {
+                            self.session_log.record(now, EventKind::Change, format!("{} appeared", Self::log_name(d)));
+                        }
+                        None => {}
+                    }
+                }
+
+                // Append the current temperature; the first sighting of an ATA drive starts from its SCT log,
+                // read by the scan thread
+                for d in &self.drives {
+                    if d.cached {
+                        continue;
+                    }
+                    let identity = Self::drive_identity(d);
+                    let history =
+                        self.temp_history.entry(identity.clone()).or_insert_with(|| sct.remove(&identity).unwrap_or_default());
+                    if let Some(t) = d.temp_c {
+                        history.push((now, t as f32));
+                    }
+                    // Bound memory use: keep the most recent samples only
+                    if history.len() > MAX_TEMP_SAMPLES {
+                        history.drain(..history.len() - MAX_TEMP_SAMPLES);
+                    }
+                }
+
+                // Traffic since the previous reading; sleeping drives return cached data, so they are skipped
+                for d in self.drives.iter().filter(|d| !d.cached) {
+                    let reading = (Instant::now(), HistorySample::from_disk(d, now));
+                    if let Some((at, previous)) = self.last_counters.insert(Self::drive_identity(d), reading.clone()) {
+                        let rate = traffic_rate(&previous, &reading.1, reading.0.duration_since(at).as_secs_f64());
+                        self.traffic.insert(Self::drive_identity(d), rate);
+                    }
+                }
+
+                // Store an hourly sample of each awake drive's counters; write errors are ignored
+                if let Some(store) = &self.history_store {
+                    for d in self.drives.iter().filter(|d| !d.cached) {
+                        if let Some(key) = drive_key(d) {
+                            let samples = self.history.entry(key.clone()).or_insert_with(|| store.load(&key));
+                            let _ = store.record(&key, samples, HistorySample::from_disk(d, now));
+                        }
+                    }
+                }
+
+                // Evaluate alert rules; notifications run in the background so slow channels cannot stall the UI
+                let rules: Vec<_> = self.settings.alert_rules().into_iter().flatten().collect();
+                // Every alert is logged; snoozed ones, quiet hours and the rate limit hold back notifications
+                let watched: Vec<&DiskInfo> =
+                    self.drives.iter().map(|d| d.as_ref()).filter(|d| !self.settings.is_hidden(d)).collect();
+                // A drive's own temperature alarm replaces the global temp_c rules for it
+                let fired = self.alert_engine.check_each(
+                    watched.iter().map(|d| (*d, drive_rules(&rules, self.settings.temp_alarm(d)))),
+                    now,
+                );
+                let schedule = &self.settings.schedule;
+                let minute = if fired.is_empty() || schedule.quiet_hours.is_none() { 0 } else { local_minute_of_day(now) };
+                let mut to_notify = Vec::new();
+                for alert in fired {
+                    self.session_log.record(now, EventKind::Alert, format!("{}: {}", alert.severity.name(), alert.message));
+                    let not_snoozed = self.alert_log.record(alert.clone()).unwrap_or_else(|e| {
+                        self.action_result = Some(Err(format!("Alert log: {}", e)));
+                        true
+                    });
+                    if not_snoozed && self.notification_throttle.allow(schedule, &alert, minute) {
+                        to_notify.push(alert);
+                    }
+                }
+                // Tracked even while the alarm is off, so switching it on does not sound for known failures
+                let failing = self.alarm_engine.check(&alarm_rules(), watched, now);
+                if let (false, Some(sound)) = (failing.is_empty(), self.settings.alarm.clone()) {
+                    self.spawn_task(move || play_alarm(&sound).err().map(|e| Err(format!("Alarm: {}", e))));
+                }
+                if !to_notify.is_empty() {
+                    let channels = self.settings.channels();
+                    self.spawn_task(move || {
+                        let errors: Vec<String> = to_notify.iter().flat_map(|alert| notify(&channels, alert)).collect();
+                        (!errors.is_empty()).then(|| Err(format!("Alert notification failed: {}", errors.join("; "))))
+                    });
+                }
+
+                // Follow the selected drive to its new position; if it is gone, fall back to the first drive
+                self.selected = previous
+                    .get(self.selected)
+                    .and_then(|sel| Self::find_same_drive(&self.drives, sel))
+                    .unwrap_or(0);
+
+                self.run_scheduled_export(now, false);
+            }
+            Err(e) => {
+                // Clear drives and store error for display
+                self.session_log.record(now, EventKind::Error, format!("Scan failed: {}", e));
+                self.drives.clear();
+                self.last_error = Some(e);
+            }
+        }
+    }
+
+    /// Updates CPU and GPU temperature readings, fan speeds, batteries and memory usage.
+    /// Reads hwmon sysfs for the CPU temperature and fans, nvidia-smi/amdgpu for every GPU, power_supply for batteries,
+    /// and sysinfo plus /proc/swaps for RAM and swap.
+    /// Failures are silently ignored, leaving temperature fields as None.
+    fn update_system_temps(&mut self) {
+        // Mains or battery decides whether battery-saver polling applies
+        self.on_battery = on_battery();
+
+        // Read CPU temperature from hwmon sysfs
+        if let Some(temp) = cpu_temperature() {
+            self.cpu_temp = Some(temp);
+        }
+        self.cpu_sensors = cpu_sensors();
+
+        // Read every GPU's temperature (nvidia-smi and amdgpu hwmon); nvidia-smi can wake a sleeping
+        // discrete GPU, so GPUs are not polled in battery-saver mode
+        if self.power_saving() {
+            self.gpu_temps.clear();
+        } else {
+            self.gpu_temps = gpu_temperatures();
+        }
+
+        // Read fan speeds from hwmon
+        self.fans = fan_speeds();
+
+        // Read laptop batteries from power_supply
+        self.batteries = batteries();
+
+        // Refresh RAM/swap usage and where swap lives
+        self.system.refresh_memory();
+        self.swap_devices = swap_devices();
+        self.swap_drives = swap_drives(&self.swap_devices);
+    }
+
+    /// Renders the SMART attributes table for the selected drive.
+    /// Includes a "Δ session" column showing how much each raw counter moved since app start.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    /// * `di` - Drive whose attributes are displayed
+    fn smart_table(&self, ui: &mut egui::Ui, di: &DiskInfo, toggle: &mut Option<ColumnToggle>) {
+        // Session deltas belong to this machine's drives, not to an opened report
+        let baseline = self.session_baseline.get(&Self::drive_identity(di)).filter(|_| self.snapshot.is_none());
+        let show = |column: &str| {
+            let default = SMART_COLUMNS.iter().any(|(name, default)| *name == column && *default);
+            self.settings.column_visible("smart", column, default)
+        };
+
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("SMART Attributes").size(14.0).strong());
+                        Self::column_picker(ui, "smart", SMART_COLUMNS, &self.settings, toggle);
+                    });
+                    ui.add_space(8.0);
+
+                    egui::Grid::new("smart_grid")
+                        .striped(true)
+                        .spacing([20.0, 6.0])
+                        .show(ui, |ui| {
+                            // Table headers
+                            for (header, _) in SMART_COLUMNS.iter().filter(|(name, _)| show(name)) {
+                                ui.label(egui::RichText::new(*header).strong().size(11.0));
+                            }
+                            ui.end_row();
+
+                            // Pre-fail attributes first: they are the ones that predict imminent failure
+                            let groups = [
+                                (true, "Pre-fail", "Crossing the threshold predicts imminent failure"),
+                                (false, "Old-age", "Wear and usage counters; a crossed threshold means the drive is worn, not failing"),
+                            ];
+                            for (pre_fail, heading, hint) in groups {
+                                if !di.smart_attributes.iter().any(|a| a.pre_fail == pre_fail) {
+                                    continue;
+                                }
+                                let color = if pre_fail { egui::Color32::from_rgb(146, 64, 14) } else { egui::Color32::from_gray(120) };
+                                ui.label("");
+                                ui.label(egui::RichText::new(heading).size(11.0).strong().color(color)).on_hover_text(hint);
+                                ui.end_row();
+                                for attr in di.smart_attributes.iter().filter(|a| a.pre_fail == pre_fail) {
+                                    let type_text = if attr.pre_fail { "Pre-fail" } else { "Old_age" }.to_string();
+                                    let plain = [
+                                        ("ID", &attr.id),
+                                        ("Attribute", &attr.name),
+                                        ("Type", &type_text),
+                                        ("Value", &attr.current),
+                                        ("Worst", &attr.worst),
+                                        ("Thresh", &attr.threshold),
+                                    ];
+                                    for (column, text) in plain {
+                                        if show(column) {
+                                            ui.label(egui::RichText::new(text).size(11.0));
+                                        }
+                                    }
+
+                                    // Status color and text from the threshold comparison; the color also fills the margin bar
+                                    let (color, text) = match attr.status {
+                                        AttributeStatus::Good => (egui::Color32::from_rgb(34, 197, 94), "Good"),
+                                        AttributeStatus::Warning => (egui::Color32::from_rgb(245, 158, 11), "Warning"),
+                                        AttributeStatus::Critical => (egui::Color32::from_rgb(239, 68, 68), "Critical"),
+                                    };
+
+                                    // How far the value (and its worst) sits above the threshold
+                                    if show("Margin") {
+                                        match attr.normalized() {
+                                            Some((current, worst, threshold)) => {
+                                                let hover = match attr.margin() {
+                                                    Some(margin) if margin < 0 => format!(
+                                                        "Value {}, worst {}, threshold {}: crossed the threshold by {}",
+                                                        current, worst, threshold, -margin
+                                                    ),
+                                                    Some(margin) => format!(
+                                                        "Value {}, worst {}, threshold {}: {} point(s) of margin at its worst",
+                                                        current, worst, threshold, margin
+                                                    ),
+                                                    None => format!("Value {}, worst {}; a threshold of 0 never trips", current, worst),
+                                                };
+                                                margin_bar(ui, current, worst, threshold, color).on_hover_text(hover);
+                                            }
+                                            None => {
+                                                ui.label(egui::RichText::new("--").size(11.0).color(egui::Color32::from_gray(150)));
+                                            }
+                                        }
+                                    }
+                                    if show("Raw") {
+                                        ui.label(egui::RichText::new(group_raw_digits(&attr.raw_value)).size(11.0));
+                                    }
+
+                                    // Change in raw value since the attribute was first seen this session
+                                    let start = baseline.and_then(|b| b.get(&attr.id)).copied();
+                                    match (show("Δ session"), start, attr.raw_numeric()) {
+                                        (false, _, _) => {}
+                                        (true, Some(start), Some(now)) if now != start => {
+                                            let delta = now as i128 - start as i128;
+                                            ui.colored_label(
+                                                egui::Color32::from_rgb(59, 130, 246),
+                                                egui::RichText::new(format!("{:+}", delta)).size(11.0).strong(),
+                                            );
+                                        }
+                                        _ => {
+                                            ui.label(egui::RichText::new("--").size(11.0).color(egui::Color32::from_gray(150)));
+                                        }
+                                    }
+
+                                    if show("Status") {
+                                        ui.colored_label(color, egui::RichText::new(text).size(11.0));
+                                    }
+                                    ui.end_row();
+                                }
+                            }
+                        });
+                });
+            ui.add_space(20.0);
+        });
+    }
+
+    /// Renders a "Columns" menu with a checkbox per table column.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    /// * `table` - Table name in the settings file ("partitions", "smart")
+    /// * `columns` - The table's columns as (header, shown by default)
+    /// * `settings` - Settings holding the current visibility
+    /// * `toggle` - Receives the column the user switched
+    fn column_picker(
+        ui: &mut egui::Ui,
+        table: &'static str,
+        columns: &[(&'static str, bool)],
+        settings: &Settings,
+        toggle: &mut Option<ColumnToggle>,
+    ) {
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            ui.menu_button(egui::RichText::new("☰ Columns").size(11.0), |ui| {
+                for (column, default) in columns {
+                    let mut visible = settings.column_visible(table, column, *default);
+                    if ui.checkbox(&mut visible, *column).changed() {
+                        *toggle = Some((table, column, visible));
+                    }
+                }
+            });
+        });
+    }
+
+    /// Renders the APM/AAM editor card for an ATA drive.
+    /// Slider positions are kept in egui temp memory until the user applies them.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    /// * `di` - Drive whose power management levels are displayed
+    /// * `action` - Receives the hdparm action when the user clicks Apply
+    fn power_management_card(ui: &mut egui::Ui, di: &DiskInfo, action: &mut Option<DriveAction>) {
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+
+                    ui.label(egui::RichText::new("Power Management").size(14.0).strong());
+                    ui.add_space(8.0);
+
+                    // Advanced Power Management: 1-127 permit spin-down, 128-254 do not
+                    if let Some(current) = di.apm_level {
+                        let id = ui.make_persistent_id(("apm", &di.dev));
+                        let mut level = ui.data_mut(|d| *d.get_temp_mut_or_insert_with(id, || current.min(254)));
+                        ui.horizontal(|ui| {
+                            let shown = if current == 255 { "off".to_string() } else { current.to_string() };
+                            ui.label(egui::RichText::new(format!("APM (current {})", shown)).size(11.0));
+                            ui.add(egui::Slider::new(&mut level, 1..=254));
+                            let warning = format!(
+                                "hdparm sets APM level {} on {} right away. Levels below 128 let the drive spin down and park its heads.",
+                                level, di.dev
+                            );
+                            if confirm_apply_button(ui, ("apm_apply", &di.dev), level != current, &warning) {
+                                *action = Some(DriveAction::SetApm(di.dev.clone(), level));
+                            }
+                        });
+                        ui.data_mut(|d| d.insert_temp(id, level));
+
+                        // Aggressive APM parks the heads constantly on many laptop HDDs
+                        if di.kind == "HDD" && level < 128 {
+                            let cycles = di.attribute("193").and_then(|a| a.raw_numeric());
+                            let detail = match cycles {
+                                Some(c) => format!(" Load_Cycle_Count is currently {}.", c),
+                                None => String::new(),
+                            };
+                            ui.colored_label(
+                                egui::Color32::from_rgb(245, 158, 11),
+                                egui::RichText::new(format!(
+                                    "⚠ APM below 128 lets the drive park its heads aggressively, which drives up Load_Cycle_Count \
+                                     (often rated for 300,000-600,000 cycles).{} Consider 128 or higher.",
+                                    detail
+                                ))
+                                .size(11.0),
+                            );
+                        }
+                        ui.add_space(6.0);
+                    }
+
+                    // Automatic Acoustic Management: 128 = quietest, 254 = fastest seeks
+                    if let Some(current) = di.aam_level {
+                        let id = ui.make_persistent_id(("aam", &di.dev));
+                        let mut level = ui.data_mut(|d| *d.get_temp_mut_or_insert_with(id, || current.max(128)));
+                        ui.horizontal(|ui| {
+                            ui.label(egui::RichText::new(format!("AAM (current {})", current)).size(11.0));
+                            ui.add(egui::Slider::new(&mut level, 128..=254));
+                            let warning = format!(
+                                "hdparm sets AAM level {} on {} right away. Lower levels make seeks quieter but slower.",
+                                level, di.dev
+                            );
+                            if confirm_apply_button(ui, ("aam_apply", &di.dev), level != current, &warning) {
+                                *action = Some(DriveAction::SetAam(di.dev.clone(), level));
+                            }
+                        });
+                        ui.data_mut(|d| d.insert_temp(id, level));
+                    }
+                });
+            ui.add_space(20.0);
+        });
+    }
+
+    /// Opens the breakdown of the health score below the health badge when it is clicked.
+    ///
+    /// # Arguments
+    /// * `ui` - UI the badge is in
+    /// * `response` - Response of the badge
+    /// * `dev` - Device path, keeps the popup of each drive apart
+    /// * `breakdown` - Score and the inputs that produced it
+    fn health_score_popup(ui: &mut egui::Ui, response: &egui::Response, dev: &str, breakdown: &HealthScore) {
+        let popup_id = ui.make_persistent_id(("health_popup", dev));
+        if response.clicked() {
+            ui.memory_mut(|m| m.toggle_popup(popup_id));
+        }
+
+        egui::popup_below_widget(ui, popup_id, response, egui::PopupCloseBehavior::CloseOnClickOutside, |ui| {
+            ui.set_min_width(300.0);
+            let gray = egui::Color32::from_gray(120);
+            // Whole points without decimals; CRC errors weigh 0.1 each by default
+            let format_points = |p: f64| if p.fract() == 0.0 { format!("{:.0}", p) } else { format!("{:.1}", p) };
+            ui.label(egui::RichText::new("How the score is computed").size(14.0).strong());
+            ui.add_space(6.0);
+            egui::Grid::new(("health_breakdown", dev))
+                .num_columns(3)
+                .spacing([15.0, 4.0])
+                .show(ui, |ui| {
+                    for input in &breakdown.inputs {
+                        ui.label(egui::RichText::new(input.label).size(11.0).color(gray));
+                        ui.label(egui::RichText::new(&input.value).size(11.0).monospace());
+                        let (points, color) = if input.points > 0.0 {
+                            (format!("−{}", format_points(input.points)), egui::Color32::from_rgb(239, 68, 68))
+                        } else {
+                            ("0".to_string(), gray)
+                        };
+                        ui.label(egui::RichText::new(format!("→ {}", points)).size(11.0).monospace().color(color));
+                        ui.end_row();
+                    }
+                });
+            ui.separator();
+            let total: f64 = breakdown.inputs.iter().map(|i| i.points).sum();
+            ui.label(
+                egui::RichText::new(format!("Score: 100 − {} = {}", format_points(total), breakdown.score)).size(12.0).strong(),
+            );
+            ui.label(egui::RichText::new("Weights can be changed on the Alerts page.").size(11.0).color(gray));
+        });
+    }
+
+    /// Shows a popover with a usage ring and GPT details for a partition when its name is clicked.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    /// * `response` - Response of the clickable partition name label
+    /// * `part` - Partition whose details are displayed
+    fn partition_details_popup(ui: &mut egui::Ui, response: &egui::Response, part: &PartitionInfo) {
+        let popup_id = ui.make_persistent_id(("partition_popup", &part.device));
+        if response.clicked() {
+            ui.memory_mut(|m| m.toggle_popup(popup_id));
+        }
+
+        egui::popup_below_widget(ui, popup_id, response, egui::PopupCloseBehavior::CloseOnClickOutside, |ui| {
+            ui.set_min_width(320.0);
+            ui.horizontal(|ui| {
+                usage_ring(ui, (part.used_percent / 100.0) as f32, 40.0, egui::Color32::from_rgb(59, 130, 246));
+                ui.vertical(|ui| {
+                    ui.label(egui::RichText::new(format!("{:.0}% used", part.used_percent)).size(14.0).strong());
+                    ui.label(
+                        egui::RichText::new(format!("{:.1} GB of {:.1} GB, {:.1} GB free", part.used_gb, part.total_gb, part.free_gb))
+                            .size(11.0)
+                            .color(egui::Color32::from_gray(120)),
+                    );
+                });
+            });
+            ui.add_space(6.0);
+            egui::Grid::new(("partition_details", &part.device))
+                .num_columns(2)
+                .spacing([15.0, 4.0])
+                .show(ui, |ui| {
+                    let gray = egui::Color32::from_gray(120);
+                    let type_text = part.part_type.as_deref().map(|t| match partition_type_name(t) {
+                        Some(name) => format!("{} ({})", name, t),
+                        None => t.to_string(),
+                    });
+                    let start_text = part.start_bytes.map(|b| format!("{} bytes", b));
+                    let rows = [
+                        ("Device", Some(format!("/dev/{}", part.device))),
+                        ("GPT name", part.part_label.clone()),
+                        ("PARTUUID", part.part_uuid.clone()),
+                        ("Type", type_text),
+                        ("Start", start_text),
+                    ];
+                    for (label, value) in rows {
+                        ui.label(egui::RichText::new(label).size(11.0).color(gray));
+                        ui.add(
+                            egui::Label::new(egui::RichText::new(value.as_deref().unwrap_or("--")).size(11.0).monospace())
+                                .selectable(true),
+                        );
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+
+    /// Renders the SCT Error Recovery Control card with read/write timeout editors.
+    /// Draft timeouts are kept in egui temp memory until the user applies them.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    /// * `di` - Drive whose ERC timeouts are displayed
+    /// * `action` - Receives the smartctl action when the user clicks Apply
+    fn erc_card(ui: &mut egui::Ui, di: &DiskInfo, action: &mut Option<DriveAction>) {
+        let Some(erc) = di.erc else {
+            return;
+        };
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+
+                    ui.label(egui::RichText::new("Error Recovery Control (SCT ERC / TLER)").size(14.0).strong());
+                    ui.add_space(8.0);
+
+                    let fmt = |v: Option<u16>| match v {
+                        Some(ds) => format!("{:.1} s", ds as f32 / 10.0),
+                        None => "Disabled".to_string(),
+                    };
+                    ui.label(
+                        egui::RichText::new(format!("Current: read {}, write {}", fmt(erc.read_ds), fmt(erc.write_ds)))
+                            .size(11.0),
+                    );
+
+                    // Draft timeouts in deciseconds; 0 disables the limit
+                    let id = ui.make_persistent_id(("erc", &di.dev));
+                    let current = (erc.read_ds.unwrap_or(0), erc.write_ds.unwrap_or(0));
+                    let (mut read, mut write) = ui.data_mut(|d| *d.get_temp_mut_or_insert_with(id, || current));
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("Read (×0.1 s)").size(11.0));
+                        ui.add(egui::DragValue::new(&mut read).range(0..=6000));
+                        ui.label(egui::RichText::new("Write (×0.1 s)").size(11.0));
+                        ui.add(egui::DragValue::new(&mut write).range(0..=6000));
+                        if ui.button("RAID preset (7 s)").clicked() {
+                            read = 70;
+                            write = 70;
+                        }
+                        let warning = format!(
+                            "smartctl sets the error recovery timeouts of {} to {} / {} right away. With a limit the drive \
+                             gives up on a bad sector sooner instead of retrying it.",
+                            di.dev,
+                            fmt(Some(read).filter(|ds| *ds > 0)),
+                            fmt(Some(write).filter(|ds| *ds > 0))
+                        );
+                        if confirm_apply_button(ui, ("erc_apply", &di.dev), (read, write) != current, &warning) {
+                            *action = Some(DriveAction::SetErc(di.dev.clone(), read, write));
+                        }
+                    });
+                    ui.data_mut(|d| d.insert_temp(id, (read, write)));
+
+                    // Without ERC a desktop drive may retry a bad sector for minutes and get kicked from the array
+                    ui.label(
+                        egui::RichText::new(
+                            "RAID controllers and mdadm drop drives that stop responding for more than ~8 s; \
+                             7 s is the usual setting for array members. Most drives forget this on power cycle.",
+                        )
+                        .size(11.0)
+                        .color(egui::Color32::from_gray(120)),
+                    );
+                });
+            ui.add_space(20.0);
+        });
+    }
+
+    /// Renders the endurance card: an editor for the datasheet TBW/DWPD rating and
+    /// the share of it already consumed by data_written_tb.
+    /// Useful for SATA SSDs, which rarely report a percentage used like NVMe does.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    /// * `di` - Drive whose endurance is displayed
+    /// * `key` - Settings key of the drive (model + serial)
+    /// * `settings` - The drive's current settings
+    /// * `edit` - Receives the drive's new settings when the user saves or clears the rating
+    fn endurance_card(
+        ui: &mut egui::Ui,
+        di: &DiskInfo,
+        key: &str,
+        settings: &DriveSettings,
+        edit: &mut Option<(String, DriveSettings)>,
+    ) {
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+
+                    ui.label(egui::RichText::new("Endurance").size(14.0).strong());
+                    ui.add_space(8.0);
+
+                    // Consumed share of the rating, if one was entered
+                    let warranty_years = settings.warranty_years.unwrap_or(DEFAULT_WARRANTY_YEARS);
+                    let rated_tb = settings.endurance.and_then(|r| r.tbw(di.capacity, warranty_years as f64));
+                    match (rated_tb, di.data_written_tb) {
+                        (Some(rated), Some(written)) if rated > 0.0 => {
+                            let consumed = written / rated;
+                            let color = if consumed >= 1.0 {
+                                egui::Color32::from_rgb(239, 68, 68)
+                            } else if consumed >= 0.8 {
+                                egui::Color32::from_rgb(245, 158, 11)
+                            } else {
+                                egui::Color32::from_rgb(34, 197, 94)
+                            };
+                            ui.label(
+                                egui::RichText::new(format!("Endurance consumed: {:.0}%", consumed * 100.0))
+                                    .size(13.0)
+                                    .strong()
+                                    .color(color),
+                            );
+                            ui.add(
+                                egui::ProgressBar::new(consumed.min(1.0) as f32)
+                                    .fill(color)
+                                    .desired_width(ui.available_width() * 0.5),
+                            );
+                            ui.label(
+                                egui::RichText::new(format!("{:.1} TB written of {:.0} TBW rated", written, rated))
+                                    .size(11.0)
+                                    .color(egui::Color32::from_gray(120)),
+                            );
+                        }
+                        (Some(_), None) => {
+                            ui.label(
+                                egui::RichText::new("This drive does not report total data written.")
+                                    .size(11.0)
+                                    .color(egui::Color32::from_gray(120)),
+                            );
+                        }
+                        _ => {
+                            ui.label(
+                                egui::RichText::new("Enter the rated endurance from the drive's datasheet to track how much is used up.")
+                                    .size(11.0)
+                                    .color(egui::Color32::from_gray(120)),
+                            );
+                        }
+                    }
+                    ui.add_space(6.0);
+
+                    // Draft rating as (text, is DWPD) held in temp memory until saved
+                    let id = ui.make_persistent_id(("endurance", key));
+                    let current = match settings.endurance {
+                        Some(EnduranceRating::Tbw(tb)) => (tb.to_string(), false),
+                        Some(EnduranceRating::Dwpd(dwpd)) => (dwpd.to_string(), true),
+                        None => (String::new(), false),
+                    };
+                    let (mut text, mut dwpd) = ui.data_mut(|d| d.get_temp_mut_or_insert_with(id, || current.clone()).clone());
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("Rated").size(11.0));
+                        ui.add(egui::TextEdit::singleline(&mut text).desired_width(70.0));
+                        ui.selectable_value(&mut dwpd, false, "TBW");
+                        ui.selectable_value(&mut dwpd, true, "DWPD");
+
+                        let parsed = text.trim().parse::<f64>().ok().filter(|v| *v > 0.0);
+                        let rating = parsed.map(|v| if dwpd { EnduranceRating::Dwpd(v) } else { EnduranceRating::Tbw(v) });
+                        if ui.add_enabled(rating.is_some() && rating != settings.endurance, egui::Button::new("Save")).clicked() {
+                            let mut updated = settings.clone();
+                            updated.endurance = rating;
+                            *edit = Some((key.to_string(), updated));
+                        }
+                        if settings.endurance.is_some() && ui.button("Clear").clicked() {
+                            text.clear();
+                            let mut updated = settings.clone();
+                            updated.endurance = None;
+                            *edit = Some((key.to_string(), updated));
+                        }
+                    });
+                    ui.data_mut(|d| d.insert_temp(id, (text, dwpd)));
+
+                    if dwpd {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "DWPD is converted using the drive's capacity over a {}-year warranty.",
+                                warranty_years
+                            ))
+                            .size(11.0)
+                            .color(egui::Color32::from_gray(120)),
+                        );
+                    }
+                });
+            ui.add_space(20.0);
+        });
+    }
+
+    /// Renders the warranty card: purchase date and warranty length editors plus the time left.
+    /// Warranties ending within 90 days are highlighted, since an RMA is easiest before they lapse.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    /// * `key` - Settings key of the drive (model + serial)
+    /// * `settings` - The drive's current settings
+    /// * `edit` - Receives the drive's new settings when the user saves
+    fn warranty_card(ui: &mut egui::Ui, key: &str, settings: &DriveSettings, edit: &mut Option<(String, DriveSettings)>) {
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+
+                    ui.label(egui::RichText::new("Warranty").size(14.0).strong());
+                    ui.add_space(8.0);
+
+                    match settings.warranty_end() {
+                        Some(end) => {
+                            let left = end - today();
+                            let (text, color) = if left < 0 {
+                                (format!("Expired on {}", format_date(end)), egui::Color32::from_gray(120))
+                            } else if left <= WARRANTY_WARNING_DAYS {
+                                (
+                                    format!("⚠ Expires in {} days ({})", left, format_date(end)),
+                                    egui::Color32::from_rgb(146, 64, 14),
+                                )
+                            } else {
+                                (
+                                    format!("{:.1} years left (until {})", left as f64 / 365.25, format_date(end)),
+                                    egui::Color32::from_rgb(34, 197, 94),
+                                )
+                            };
+                            ui.label(egui::RichText::new(text).size(13.0).strong().color(color));
+                        }
+                        None => {
+                            ui.label(
+                                egui::RichText::new("Enter the purchase date and warranty length to track the time left for an RMA.")
+                                    .size(11.0)
+                                    .color(egui::Color32::from_gray(120)),
+                            );
+                        }
+                    }
+                    ui.add_space(6.0);
+
+                    // Draft (date text, years) held in temp memory until saved
+                    let id = ui.make_persistent_id(("warranty", key));
+                    let current = (
+                        settings.purchased.map(format_date).unwrap_or_default(),
+                        settings.warranty_years.unwrap_or(DEFAULT_WARRANTY_YEARS),
+                    );
+                    let (mut date, mut years) = ui.data_mut(|d| d.get_temp_mut_or_insert_with(id, || current.clone()).clone());
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("Purchased").size(11.0));
+                        ui.add(egui::TextEdit::singleline(&mut date).hint_text("YYYY-MM-DD").desired_width(90.0));
+                        ui.label(egui::RichText::new("Warranty").size(11.0));
+                        ui.add(egui::DragValue::new(&mut years).range(1..=10).suffix(" years"));
+
+                        let purchased = parse_date(&date);
+                        let changed = purchased != settings.purchased || Some(years) != settings.warranty_years;
+                        if ui.add_enabled(purchased.is_some() && changed, egui::Button::new("Save")).clicked() {
+                            let mut updated = settings.clone();
+                            updated.purchased = purchased;
+                            updated.warranty_years = Some(years);
+                            *edit = Some((key.to_string(), updated));
+                        }
+                    });
+                    ui.data_mut(|d| d.insert_temp(id, (date, years)));
+                });
+            ui.add_space(20.0);
+        });
+    }
+
+    /// Renders the temperature alarm card: the drive's own alert temperature, which replaces the global
+    /// `temp_c` rules for it and colors its sidebar card.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    /// * `di` - The drive, for its current temperature
+    /// * `key` - Settings key of the drive (model + serial)
+    /// * `settings` - The drive's current settings
+    /// * `edit` - Receives the drive's new settings when the user saves or clears the alarm
+    fn temp_alarm_card(
+        ui: &mut egui::Ui,
+        di: &DiskInfo,
+        key: &str,
+        settings: &DriveSettings,
+        edit: &mut Option<(String, DriveSettings)>,
+    ) {
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+
+                    ui.label(egui::RichText::new("Temperature Alarm").size(14.0).strong());
+                    ui.add_space(8.0);
+
+                    let text = match settings.temp_alarm {
+                        Some(limit) => format!("Alerts at {}°C instead of the temp_c rules on the Alerts page.", limit),
+                        None => format!(
+                            "Uses the temp_c rules on the Alerts page; shown as hot from {}°C. Set a limit to suit this drive \
+                             (e.g., 55°C for an HDD, 75°C for a fast NVMe).",
+                            DEFAULT_HOT_TEMP_C
+                        ),
+                    };
+                    ui.label(egui::RichText::new(text).size(11.0).color(egui::Color32::from_gray(120)));
+                    ui.add_space(6.0);
+
+                    // Draft limit held in temp memory until saved; starts just above the current temperature
+                    let id = ui.make_persistent_id(("temp_alarm", key));
+                    let current = settings.temp_alarm.unwrap_or_else(|| di.temp_c.map_or(DEFAULT_HOT_TEMP_C, |t| (t + 10).max(40)));
+                    let mut limit = ui.data_mut(|d| *d.get_temp_mut_or_insert_with(id, || current));
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("Alert at").size(11.0));
+                        ui.add(egui::DragValue::new(&mut limit).range(30..=100).suffix("°C"));
+
+                        if ui.add_enabled(settings.temp_alarm != Some(limit), egui::Button::new("Save")).clicked() {
+                            let mut updated = settings.clone();
+                            updated.temp_alarm = Some(limit);
+                            *edit = Some((key.to_string(), updated));
+                        }
+                        if ui.add_enabled(settings.temp_alarm.is_some(), egui::Button::new("Clear")).clicked() {
+                            let mut updated = settings.clone();
+                            updated.temp_alarm = None;
+                            *edit = Some((key.to_string(), updated));
+                        }
+                    });
+                    ui.data_mut(|d| d.insert_temp(id, limit));
+                });
+            ui.add_space(20.0);
+        });
+    }
+
+    /// Renders the SSD Wear card of a SATA SSD: the vendor wear attribute as a percentage used
+    /// (the counterpart of NVMe "Percentage Used") and the block erase counts.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    /// * `wear` - Wear decoded from the drive's vendor attributes
+    fn ssd_wear_card(ui: &mut egui::Ui, wear: &SsdWear) {
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+
+                    ui.label(egui::RichText::new("SSD Wear").size(14.0).strong());
+                    ui.add_space(8.0);
+
+                    if let Some(used) = wear.used_percent {
+                        let color = if used >= 90 {
+                            egui::Color32::from_rgb(239, 68, 68)
+                        } else if used >= 70 {
+                            egui::Color32::from_rgb(245, 158, 11)
+                        } else {
+                            egui::Color32::from_rgb(34, 197, 94)
+                        };
+                        ui.label(egui::RichText::new(format!("Percentage used: {}%", used)).size(13.0).strong().color(color));
+                        ui.add(
+                            egui::ProgressBar::new(used.min(100) as f32 / 100.0)
+                                .fill(color)
+                                .desired_width(ui.available_width() * 0.5),
+                        );
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "From {} (normalized value {} of 100)",
+                                wear.source.as_deref().unwrap_or("vendor attribute"),
+                                100 - used.min(100)
+                            ))
+                            .size(11.0)
+                            .color(egui::Color32::from_gray(120)),
+                        );
+                    }
+
+                    let erase = match (wear.avg_erase_count, wear.max_erase_count) {
+                        (Some(avg), Some(max)) => Some(format!("Block erase count: {} average, {} maximum", avg, max)),
+                        (Some(avg), None) => Some(format!("Block erase count: {} average", avg)),
+                        (None, Some(max)) => Some(format!("Block erase count: {} maximum", max)),
+                        (None, None) => None,
+                    };
+                    if let Some(erase) = erase {
+                        ui.label(
+                            egui::RichText::new(erase)
+                                .size(11.0)
+                                .color(egui::Color32::from_gray(120)),
+                        )
+                        .on_hover_text("Compare with the NAND's rated program/erase cycles (often 1,000-3,000 for TLC)");
+                    }
+                });
+            ui.add_space(20.0);
+        });
+    }
+
+    /// Renders the helium level gauge of a helium-filled HDD.
+    /// Any drop below the nominal 100 is flagged: leaking helium is a failure mode unique to these drives.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    /// * `level` - Normalized Helium_Level value
+    /// * `threshold` - The attribute's failure threshold
+    fn helium_card(ui: &mut egui::Ui, level: u8, threshold: u8) {
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+
+                    ui.label(egui::RichText::new("Helium Level").size(14.0).strong());
+                    ui.add_space(8.0);
+
+                    let color = if level <= threshold {
+                        egui::Color32::from_rgb(239, 68, 68)
+                    } else if level < 100 {
+                        egui::Color32::from_rgb(245, 158, 11)
+                    } else {
+                        egui::Color32::from_rgb(34, 197, 94)
+                    };
+                    ui.label(egui::RichText::new(format!("{} / 100", level)).size(13.0).strong().color(color));
+                    ui.add(
+                        egui::ProgressBar::new(level.min(100) as f32 / 100.0)
+                            .fill(color)
+                            .desired_width(ui.available_width() * 0.5),
+                    );
+                    if level < 100 {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "⚠ The drive is losing helium (failure threshold {}). The heads need the helium to fly \
+                                 at the right height, so a leaking drive will fail; back it up and plan a replacement.",
+                                threshold
+                            ))
+                            .size(11.0)
+                            .color(egui::Color32::from_rgb(146, 64, 14)),
+                        );
+                    } else {
+                        ui.label(
+                            egui::RichText::new("Sealed at the nominal level")
+                                .size(11.0)
+                                .color(egui::Color32::from_gray(120)),
+                        );
+                    }
+                });
+            ui.add_space(20.0);
+        });
+    }
+
+    /// Renders the read-only surface scan card: a start button, or the running scan's progress,
+    /// speed and unreadable LBAs. Only one drive is scanned at a time.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    /// * `scan` - The current surface scan, of this or another drive
+    /// * `di` - Drive shown on the page
+    /// * `request` - Set to Some(true) to start a scan of this drive, Some(false) to stop the running one
+    fn surface_scan_card(ui: &mut egui::Ui, scan: Option<&SurfaceScan>, di: &DiskInfo, request: &mut Option<bool>) {
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+
+                    ui.label(egui::RichText::new("Surface Scan").size(14.0).strong());
+                    ui.add_space(8.0);
+
+                    let Some(scan) = scan.filter(|s| s.dev == di.dev) else {
+                        ui.label(
+                            egui::RichText::new(
+                                "Reads every sector of the drive to find ones that can no longer be read. \
+                                 Nothing is written; the drive stays usable, but a full pass takes hours on large HDDs.",
+                            )
+                            .size(11.0)
+                            .color(egui::Color32::from_gray(120)),
+                        );
+                        ui.add_space(6.0);
+                        let busy = scan.filter(|s| !s.finished).map(|s| s.dev.clone());
+                        let button = ui.add_enabled(busy.is_none(), egui::Button::new("▶ Start surface scan"));
+                        let button = match busy {
+                            Some(dev) => button.on_disabled_hover_text(format!("A surface scan of {} is running", dev)),
+                            None => button,
+                        };
+                        if button.clicked() {
+                            *request = Some(true);
+                        }
+                        return;
+                    };
+
+                    // Progress bar with speed and, while running, the time left at the current speed
+                    let done = scan.bytes_read.load(std::sync::atomic::Ordering::Relaxed);
+                    let speed = scan.mb_per_sec();
+                    let mut text = format!("{:.1}% · {:.0} MB/s", scan.progress() * 100.0, speed);
+                    if !scan.finished && speed > 0.0 {
+                        let left = (scan.total_bytes - done) as f64 / 1_000_000.0 / speed;
+                        text.push_str(&format!(" · about {} min left", (left / 60.0).ceil() as u64));
+                    }
+                    ui.add(egui::ProgressBar::new(scan.progress()).text(text));
+                    ui.add_space(6.0);
+
+                    ui.horizontal(|ui| {
+                        let status = match (scan.finished, scan.stopped()) {
+                            (false, _) => "Reading…",
+                            (true, true) => "Stopped",
+                            (true, false) => "Finished",
+                        };
+                        ui.label(egui::RichText::new(status).size(12.0).strong());
+                        if !scan.finished {
+                            if ui.button("⏹ Stop").clicked() {
+                                *request = Some(false);
+                            }
+                        } else if ui.button("▶ Scan again").clicked() {
+                            *request = Some(true);
+                        }
+                    });
+
+                    if scan.unreadable.is_empty() {
+                        let clean = if scan.finished && !scan.stopped() { "✔ No unreadable sectors" } else { "✔ No unreadable sectors so far" };
+                        ui.label(
+                            egui::RichText::new(clean)
+                                .size(12.0)
+                                .color(egui::Color32::from_rgb(34, 197, 94)),
+                        );
+                    } else {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "⚠ {} unreadable sector(s). Back up the drive; the firmware remaps these only when \
+                                 they are written.",
+                                scan.unreadable.len()
+                            ))
+                            .size(12.0)
+                            .color(egui::Color32::from_rgb(239, 68, 68)),
+                        );
+                        egui::ScrollArea::vertical().id_salt("surface_scan_lbas").max_height(120.0).show(ui, |ui| {
+                            for lba in &scan.unreadable {
+                                ui.add(
+                                    egui::Label::new(
+                                        egui::RichText::new(format!("LBA {} ({} bytes)", lba, scan.sector_bytes))
+                                            .size(11.0)
+                                            .monospace(),
+                                    )
+                                    .selectable(true),
+                                );
+                            }
+                        });
+                    }
+                });
+            ui.add_space(20.0);
+        });
+    }
+
+    /// Renders the benchmark card: a run button, the latest result, its change since the first run,
+    /// and a chart of sequential read speed over all runs.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    /// * `results` - This drive's stored results, oldest first
+    /// * `running` - Device of the benchmark currently running, if any
+    /// * `di` - Drive shown on the page
+    /// * `storable` - Whether the drive has a serial number to store results under
+    /// * `request` - Set to true when the user starts a benchmark
+    /// * `save_chart` - Receives the speed chart when the user saves it
+    fn benchmark_card(
+        ui: &mut egui::Ui,
+        results: &[BenchmarkResult],
+        running: Option<&str>,
+        di: &DiskInfo,
+        storable: bool,
+        request: &mut bool,
+        save_chart: &mut Option<(egui::Rect, String)>,
+    ) {
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+
+                    ui.label(egui::RichText::new("Benchmark").size(14.0).strong());
+                    ui.add_space(8.0);
+
+                    ui.horizontal(|ui| {
+                        if running == Some(di.dev.as_str()) {
+                            ui.spinner();
+                            ui.label(egui::RichText::new("Measuring read speed…").size(12.0));
+                        } else {
+                            let hint = match (running, storable) {
+                                (Some(dev), _) => format!("A benchmark of {} is running", dev),
+                                (None, false) => "Drives without a serial number have no benchmark history".to_string(),
+                                (None, true) => String::new(),
+                            };
+                            let button = ui.add_enabled(hint.is_empty(), egui::Button::new("▶ Run benchmark"));
+                            if button.on_disabled_hover_text(hint).clicked() {
+                                *request = true;
+                            }
+                        }
+                        ui.label(
+                            egui::RichText::new("Read-only: 256 MB sequential, then 3 s of random 4K reads")
+                                .size(11.0)
+                                .color(egui::Color32::from_gray(120)),
+                        );
+                    });
+
+                    let Some(latest) = results.last() else {
+                        return;
+                    };
+                    ui.add_space(8.0);
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "Sequential read {:.0} MB/s · Random 4K read {:.0} IOPS ({})",
+                            latest.seq_read_mb_per_sec,
+                            latest.random_read_iops,
+                            format_timestamp(latest.timestamp)
+                        ))
+                        .size(13.0)
+                        .strong(),
+                    );
+
+                    // Compared with the first run, when the drive was newest; a 20% drop is worth a look
+                    if let Some((seq, random)) = change_since_first(results) {
+                        let color = if seq <= -20.0 || random <= -20.0 {
+                            egui::Color32::from_rgb(245, 158, 11)
+                        } else {
+                            egui::Color32::from_gray(120)
+                        };
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "Since the first run on {}: sequential {:+.0}%, random {:+.0}%",
+                                format_timestamp(results[0].timestamp),
+                                seq,
+                                random
+                            ))
+                            .size(11.0)
+                            .color(color),
+                        );
+                        ui.add_space(6.0);
+                        let samples: Vec<(u64, f32)> =
+                            results.iter().map(|r| (r.timestamp, r.seq_read_mb_per_sec as f32)).collect();
+                        let chart = line_graph(ui, &samples, 120.0, |v| format!("{:.0} MB/s", v));
+                        let name = format!("{}-benchmark", di.dev.trim_start_matches("/dev/"));
+                        save_chart_button(ui, chart, &name, save_chart);
+                    }
+                });
+            ui.add_space(20.0);
+        });
+    }
+
+    /// Renders the ATA Security card showing enabled/locked/frozen flags.
+    /// Adds guidance when the state blocks a secure erase.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    /// * `security` - Security state parsed from hdparm -I
+    fn security_card(ui: &mut egui::Ui, security: &AtaSecurity) {
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+
+                    ui.label(egui::RichText::new("ATA Security").size(14.0).strong());
+                    ui.add_space(8.0);
+
+                    // One status chip per flag, highlighted when it matters for a secure erase
+                    let amber = egui::Color32::from_rgb(245, 158, 11);
+                    let green = egui::Color32::from_rgb(34, 197, 94);
+                    let red = egui::Color32::from_rgb(239, 68, 68);
+                    ui.horizontal(|ui| {
+                        let flags = [
+                            ("Password", security.enabled, if security.enabled { "set" } else { "not set" }, amber),
+                            ("Locked", security.locked, if security.locked { "yes" } else { "no" }, red),
+                            ("Frozen", security.frozen, if security.frozen { "yes" } else { "no" }, amber),
+                            (
+                                "Enhanced erase",
+                                security.enhanced_erase,
+                                if security.enhanced_erase { "supported" } else { "not supported" },
+                                green,
+                            ),
+                        ];
+                        for (label, highlight, value, color) in flags {
+                            ui.label(egui::RichText::new(format!("{}:", label)).size(11.0).color(egui::Color32::from_gray(120)));
+                            let value_color = if highlight { color } else { egui::Color32::from_gray(60) };
+                            ui.label(egui::RichText::new(value).size(11.0).strong().color(value_color));
+                            ui.add_space(16.0);
+                        }
+                    });
+
+                    if let Some(time) = &security.erase_time {
+                        ui.label(egui::RichText::new(format!("Estimated erase time: {}", time)).size(11.0));
+                    }
+
+                    // Explain why a secure erase would currently fail
+                    let guidance = if security.locked {
+                        Some("The drive is locked: it refuses all reads and writes until unlocked with the user or master \
+                              password (hdparm --security-unlock).")
+                    } else if security.frozen {
+                        Some("The drive is frozen, so SECURITY ERASE is rejected. Firmware usually freezes drives at boot; \
+                              suspending and resuming the system (or hot-plugging the drive) typically clears it.")
+                    } else if security.enabled {
+                        Some("A user password is set. Secure erase needs that password; losing it may make the drive unusable.")
+                    } else {
+                        None
+                    };
+                    if let Some(text) = guidance {
+                        ui.add_space(4.0);
+                        ui.colored_label(egui::Color32::from_rgb(146, 64, 14), egui::RichText::new(text).size(11.0));
+                    }
+                });
+            ui.add_space(20.0);
+        });
+    }
+
+    /// Renders the NVMe controller card with the Identify Controller details nvme-cli would print,
+    /// including the Host Memory Buffer of DRAM-less drives, the APST configuration and a telemetry log capture button.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    /// * `dev` - Device path the controller belongs to
+    /// * `controller` - Identify Controller fields parsed from smartctl and nvme-cli
+    /// * `action` - Receives the telemetry capture when the user clicks it
+    fn nvme_controller_card(ui: &mut egui::Ui, dev: &str, controller: &NvmeController, action: &mut Option<DriveAction>) {
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+
+                    ui.label(egui::RichText::new("NVMe Controller").size(14.0).strong());
+                    ui.add_space(8.0);
+
+                    let fmt = |v: Option<String>| v.unwrap_or_else(|| "--".to_string());
+                    egui::Grid::new(("nvme_controller_grid", dev))
+                        .num_columns(4)
+                        .spacing([40.0, 6.0])
+                        .show(ui, |ui| {
+                            for header in &["NVMe version", "Controller ID", "IEEE OUI", "Namespaces"] {
+                                ui.label(egui::RichText::new(*header).size(11.0).color(egui::Color32::from_gray(120)));
+                            }
+                            ui.end_row();
+
+                            ui.label(egui::RichText::new(fmt(controller.nvme_version.clone())).size(11.0));
+                            ui.label(egui::RichText::new(fmt(controller.controller_id.map(|id| id.to_string()))).size(11.0));
+                            ui.label(egui::RichText::new(fmt(controller.ieee_oui.clone())).size(11.0).monospace());
+                            ui.label(egui::RichText::new(fmt(controller.namespaces.map(|n| n.to_string()))).size(11.0));
+                            ui.end_row();
+                        });
+
+                    // DRAM-less drives keep their mapping tables in host RAM; without it they slow down badly under load
+                    if let Some(preferred) = controller.hmb_preferred_bytes {
+                        let mib = |bytes: u64| format!("{} MiB", bytes / (1024 * 1024));
+                        let (text, color) = match controller.hmb_allocated_bytes {
+                            _ if preferred == 0 => ("Not used (the drive has its own DRAM cache)".to_string(), egui::Color32::from_gray(60)),
+                            Some(0) => (
+                                format!("Not allocated (the drive asks for {}); expect slow random I/O under load", mib(preferred)),
+                                egui::Color32::from_rgb(146, 64, 14),
+                            ),
+                            Some(allocated) => (
+                                format!("{} allocated of {} requested (DRAM-less drive)", mib(allocated), mib(preferred)),
+                                egui::Color32::from_gray(60),
+                            ),
+                            None => (format!("Requested {} (DRAM-less drive)", mib(preferred)), egui::Color32::from_gray(60)),
+                        };
+                        ui.add_space(8.0);
+                        ui.horizontal(|ui| {
+                            ui.label(egui::RichText::new("Host Memory Buffer:").size(11.0).color(egui::Color32::from_gray(120)));
+                            ui.label(egui::RichText::new(text).size(11.0).color(color)).on_hover_text(
+                                "DRAM-less NVMe drives borrow a little host RAM (HMB) for their mapping tables. \
+                                 The kernel caps it with the nvme.max_host_mem_size_mb module parameter.",
+                            );
+                        });
+                    }
+
+                    // Autonomous power state transitions; deep idle states are a frequent cause of NVMe resets
+                    if controller.apst.is_some() || controller.apst_supported == Some(false) {
+                        let text = match &controller.apst {
+                            Some(apst) => apst.summary(),
+                            None => "not supported by the drive".to_string(),
+                        };
+                        ui.add_space(8.0);
+                        ui.horizontal(|ui| {
+                            ui.label(egui::RichText::new("APST:").size(11.0).color(egui::Color32::from_gray(120)));
+                            ui.label(egui::RichText::new(text).size(11.0).color(egui::Color32::from_gray(60))).on_hover_text(
+                                "Autonomous Power State Transitions let the drive enter low-power states on its own after being idle. \
+                                 The kernel only uses states whose entry + exit latency fits the latency limit. If the drive \
+                                 drops off the bus (\"controller is down; will reset\"), booting with \
+                                 nvme_core.default_ps_max_latency_us=0 turns APST off.",
+                            );
+                        });
+                    }
+
+                    // The NQN is long and mostly copied into nvme-cli or NVMe-oF configs, so it is selectable
+                    ui.add_space(8.0);
+                    ui.label(egui::RichText::new("Subsystem NQN").size(11.0).color(egui::Color32::from_gray(120)));
+                    match &controller.subsystem_nqn {
+                        Some(nqn) => {
+                            ui.add(egui::Label::new(egui::RichText::new(nqn).size(11.0).monospace()).selectable(true));
+                        }
+                        None => {
+                            ui.label(
+                                egui::RichText::new("-- (install nvme-cli to read it)").size(11.0).color(egui::Color32::from_gray(120)),
+                            );
+                        }
+                    }
+
+                    // Binary telemetry dump for the manufacturer's support; it is opaque to everyone else
+                    if controller.supports_telemetry() {
+                        ui.add_space(8.0);
+                        let button = ui.button("📦 Save telemetry log").on_hover_text(
+                            "Asks the drive for a fresh host-initiated telemetry log and saves the binary dump to \
+                             ~/nvme-telemetry-<device>-<time>.bin (nvme telemetry-log). Vendors ask for it during RMA triage. \
+                             Takes a few seconds; needs nvme-cli and root.",
+                        );
+                        if button.clicked() {
+                            let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+                            *action = Some(DriveAction::CaptureTelemetry(dev.to_string(), default_telemetry_path(dev, now)));
+                        }
+                    }
+                });
+            ui.add_space(20.0);
+        });
+    }
+
+    /// Renders the firmware slot card: the revision stored in each slot, which one runs,
+    /// and an Activate button for the other slots (e.g., to finish an update that was downloaded but not committed).
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    /// * `dev` - Device path the controller belongs to
+    /// * `controller` - Controller details, for the slot count and activation capabilities
+    /// * `firmware` - Slot log read with nvme fw-log
+    /// * `action` - Receives the fw-commit when the user confirms an activation
+    fn firmware_slots_card(
+        ui: &mut egui::Ui,
+        dev: &str,
+        controller: &NvmeController,
+        firmware: &FirmwareSlots,
+        action: &mut Option<DriveAction>,
+    ) {
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+
+                    ui.label(egui::RichText::new("Firmware Slots").size(14.0).strong());
+                    ui.add_space(8.0);
+
+                    let gray = egui::Color32::from_gray(120);
+                    let immediate = controller.activates_without_reset();
+                    egui::Grid::new(("firmware_slots_grid", dev))
+                        .num_columns(3)
+                        .spacing([40.0, 6.0])
+                        .show(ui, |ui| {
+                            for header in &["Slot", "Revision", "State"] {
+                                ui.label(egui::RichText::new(*header).size(11.0).color(gray));
+                            }
+                            ui.end_row();
+
+                            for (slot, revision) in &firmware.slots {
+                                let read_only = *slot == 1 && controller.slot1_read_only();
+                                ui.label(egui::RichText::new(format!("{}{}", slot, if read_only { " (read-only)" } else { "" })).size(11.0));
+                                ui.label(egui::RichText::new(revision).size(11.0).monospace());
+                                if *slot == firmware.active {
+                                    ui.label(egui::RichText::new("Active").size(11.0).strong().color(egui::Color32::from_rgb(34, 197, 94)));
+                                } else if firmware.next_reset == Some(*slot) {
+                                    ui.label(egui::RichText::new("Active after next reset").size(11.0).color(egui::Color32::from_rgb(146, 64, 14)));
+                                } else {
+                                    let warning = if immediate {
+                                        "The controller switches to this firmware right away. Running I/O may stall briefly; \
+                                         close applications using the drive first."
+                                    } else {
+                                        "This firmware becomes active on the next controller reset (usually a reboot). \
+                                         Make sure the revision is one the vendor supports for this drive."
+                                    };
+                                    if confirm_button(ui, ("activate_slot", dev, *slot), "Activate", warning) {
+                                        *action = Some(DriveAction::ActivateFirmware(dev.to_string(), *slot, immediate));
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
+
+                    if let Some(next) = firmware.next_reset {
+                        ui.add_space(4.0);
+                        ui.colored_label(
+                            egui::Color32::from_rgb(146, 64, 14),
+                            egui::RichText::new(format!(
+                                "A firmware commit is pending: slot {} takes over after the next reset. Reboot to finish the update.",
+                                next
+                            ))
+                            .size(11.0),
+                        );
+                    }
+                });
+            ui.add_space(20.0);
+        });
+    }
+
+    /// Renders the endurance groups card: wear of each group and how its capacity is split between namespaces.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    /// * `controller` - Controller details holding the groups and namespace usage
+    fn endurance_groups_card(ui: &mut egui::Ui, controller: &NvmeController) {
+        let size = |bytes: u64| {
+            if bytes >= 1_000_000_000_000 {
+                format!("{:.2} TB", bytes as f64 / 1e12)
+            } else {
+                format!("{:.1} GB", bytes as f64 / 1e9)
+            }
+        };
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+
+                    ui.label(egui::RichText::new("Endurance Groups").size(14.0).strong());
+                    ui.add_space(8.0);
+
+                    let gray = egui::Color32::from_gray(120);
+                    for (i, group) in controller.endurance_groups.iter().enumerate() {
+                        if i > 0 {
+                            ui.add_space(10.0);
+                        }
+                        ui.label(egui::RichText::new(format!("Group {}", group.id)).size(13.0).strong());
+
+                        if let Some(used) = group.percent_used {
+                            let color = if used >= 90 {
+                                egui::Color32::from_rgb(239, 68, 68)
+                            } else if used >= 70 {
+                                egui::Color32::from_rgb(245, 158, 11)
+                            } else {
+                                egui::Color32::from_rgb(34, 197, 94)
+                            };
+                            ui.horizontal(|ui| {
+                                ui.add(
+                                    egui::ProgressBar::new(used.min(100) as f32 / 100.0)
+                                        .fill(color)
+                                        .desired_width(ui.available_width() * 0.5),
+                                );
+                                ui.label(egui::RichText::new(format!("{}% of rated endurance used", used)).size(11.0).color(color));
+                            });
+                        }
+
+                        let mut details = Vec::new();
+                        details.extend(group.available_spare.map(|s| format!("spare {}%", s)));
+                        details.extend(group.host_bytes_written.map(|b| format!("host writes {}", size(b))));
+                        details.extend(group.media_bytes_written.map(|b| format!("media writes {}", size(b))));
+                        details.extend(group.write_amplification().map(|w| format!("write amplification {:.2}", w)));
+                        if !details.is_empty() {
+                            ui.label(egui::RichText::new(details.join(" · ")).size(11.0).color(gray));
+                        }
+
+                        // Capacity allocation: each namespace's share of the group, then what is left unallocated
+                        let namespaces = controller.group_namespaces(group.id);
+                        if let Some(total) = group.total_bytes {
+                            let unallocated = group.unallocated_bytes.unwrap_or(0);
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "Capacity: {} allocated to namespaces, {} unallocated of {}",
+                                    size(total.saturating_sub(unallocated)),
+                                    size(unallocated),
+                                    size(total)
+                                ))
+                                .size(11.0),
+                            );
+                        }
+                        for ns in namespaces {
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new(format!("Namespace {}", ns.nsid)).size(11.0));
+                                let fraction = if ns.size_bytes > 0 { ns.used_bytes as f64 / ns.size_bytes as f64 } else { 0.0 };
+                                ui.add(egui::ProgressBar::new(fraction.min(1.0) as f32).desired_width(160.0));
+                                let share = group
+                                    .total_bytes
+                                    .map(|total| format!(", {:.0}% of the group", ns.size_bytes as f64 / total as f64 * 100.0))
+                                    .unwrap_or_default();
+                                ui.label(
+                                    egui::RichText::new(format!("{} used of {}{}", size(ns.used_bytes), size(ns.size_bytes), share))
+                                        .size(11.0)
+                                        .color(gray),
+                                );
+                            });
+                        }
+                    }
+                });
+            ui.add_space(20.0);
+        });
+    }
+
+    /// Renders the persistent event log card: counts of significant events and a timeline of the dated ones.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    /// * `log` - Events read with nvme persistent-event-log, or the error reading it; None while it is being read
+    /// * `reload` - Set to true when the user asks to read the log again
+    fn persistent_events_card(ui: &mut egui::Ui, log: Option<&Result<Vec<PersistentEvent>, String>>, reload: &mut bool) {
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("Persistent Event Log").size(14.0).strong());
+                        if ui.small_button("🔄").on_hover_text("Read the log again").clicked() {
+                            *reload = true;
+                        }
+                    });
+                    ui.add_space(8.0);
+
+                    let gray = egui::Color32::from_gray(120);
+                    let events = match log {
+                        None => {
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+                                ui.label(egui::RichText::new("Reading the log…").size(11.0).color(gray));
+                            });
+                            return;
+                        }
+                        Some(Ok(events)) => events,
+                        Some(Err(e)) => {
+                            ui.label(egui::RichText::new(format!("Could not read the log: {}", e)).size(11.0).color(gray));
+                            return;
+                        }
+                    };
+                    if events.is_empty() {
+                        ui.label(egui::RichText::new("No significant events recorded").size(11.0).color(gray));
+                        return;
+                    }
+
+                    let color = |category: EventCategory| match category {
+                        EventCategory::ThermalExcursion | EventCategory::HardwareError => egui::Color32::from_rgb(239, 68, 68),
+                        EventCategory::FirmwareCommit => egui::Color32::from_rgb(59, 130, 246),
+                        EventCategory::Sanitize | EventCategory::Format => egui::Color32::from_rgb(245, 158, 11),
+                        EventCategory::PowerOnReset => egui::Color32::from_gray(150),
+                    };
+                    ui.horizontal_wrapped(|ui| {
+                        for (category, count) in event_counts(events) {
+                            ui.label(egui::RichText::new("●").size(11.0).color(color(category)));
+                            ui.label(egui::RichText::new(format!("{}: {}", category.label(), count)).size(11.0));
+                            ui.add_space(12.0);
+                        }
+                    });
+
+                    // Only events logged while the drive's clock was set can be placed in time
+                    let dated: Vec<(u64, egui::Color32, String)> = events
+                        .iter()
+                        .filter_map(|e| {
+                            let t = e.timestamp?;
+                            Some((t, color(e.category), format!("{} — {}", e.category.label(), format_timestamp(t))))
+                        })
+                        .collect();
+                    if !dated.is_empty() {
+                        ui.add_space(6.0);
+                        event_timeline(ui, &dated, |t| format_date((t / 86_400) as i64));
+                    }
+                    if dated.len() < events.len() {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "{} event(s) have no date: the drive's clock was not set when they were logged",
+                                events.len() - dated.len()
+                            ))
+                            .size(11.0)
+                            .color(gray),
+                        );
+                    }
+                });
+            ui.add_space(20.0);
+        });
+    }
+
+    /// Renders the I/O queue card with the active scheduler and queue sizes.
+    /// The scheduler picker is kept in egui temp memory until the user applies it.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    /// * `dev` - Device path the queue belongs to
+    /// * `queue` - Queue settings read from sysfs
+    /// * `action` - Receives the scheduler change when the user clicks Apply
+    fn io_queue_card(ui: &mut egui::Ui, dev: &str, queue: &QueueInfo, action: &mut Option<DriveAction>) {
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+
+                    ui.label(egui::RichText::new("I/O Queue").size(14.0).strong());
+                    ui.add_space(8.0);
+
+                    let fmt = |v: Option<u32>| v.map(|n| n.to_string()).unwrap_or_else(|| "--".to_string());
+                    egui::Grid::new(("io_queue_grid", dev))
+                        .num_columns(4)
+                        .spacing([40.0, 6.0])
+                        .show(ui, |ui| {
+                            for header in &["Scheduler", "nr_requests", "Queue depth", "Hardware queues"] {
+                                ui.label(egui::RichText::new(*header).size(11.0).color(egui::Color32::from_gray(120)));
+                            }
+                            ui.end_row();
+
+                            ui.label(egui::RichText::new(queue.scheduler.as_deref().unwrap_or("--")).size(11.0));
+                            ui.label(egui::RichText::new(fmt(queue.nr_requests)).size(11.0));
+                            ui.label(egui::RichText::new(fmt(queue.queue_depth)).size(11.0));
+                            let hw = queue.hw_queues.map(|n| n.to_string()).unwrap_or_else(|| "--".to_string());
+                            ui.label(egui::RichText::new(hw).size(11.0));
+                            ui.end_row();
+                        });
+
+                    // Scheduler switch; only offered when the kernel lists alternatives
+                    if queue.available_schedulers.len() > 1 {
+                        ui.add_space(8.0);
+                        let id = ui.make_persistent_id(("scheduler", dev));
+                        let current = queue.scheduler.clone().unwrap_or_default();
+                        let mut choice = ui.data_mut(|d| d.get_temp_mut_or_insert_with(id, || current.clone()).clone());
+                        ui.horizontal(|ui| {
+                            egui::ComboBox::from_id_salt(("scheduler_combo", dev))
+                                .selected_text(&choice)
+                                .show_ui(ui, |ui| {
+                                    for name in &queue.available_schedulers {
+                                        ui.selectable_value(&mut choice, name.clone(), name);
+                                    }
+                                });
+                            let warning = format!("All I/O to {} switches to the {} scheduler right away.", dev, choice);
+                            if confirm_apply_button(ui, ("scheduler_apply", dev), choice != current, &warning) {
+                                *action = Some(DriveAction::SetScheduler(dev.to_string(), choice.clone()));
+                            }
+                        });
+                        ui.data_mut(|d| d.insert_temp(id, choice));
+                        ui.label(
+                            egui::RichText::new("NVMe usually performs best with \"none\"; HDDs benefit from mq-deadline or bfq. Resets on reboot.")
+                                .size(11.0)
+                                .color(egui::Color32::from_gray(120)),
+                        );
+                    }
+                });
+            ui.add_space(20.0);
+        });
+    }
+
+    /// Renders the System page: CPU/GPU temperatures, fans, batteries, memory, swap and uptime.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    /// * `toggle_cpu_list` - Set when the user clicks the CPU card to expand or collapse the sensor list
+    /// * `toggle_gpu_list` - Set when the user clicks the GPU card to expand or collapse the GPU list
+    fn system_dashboard(&self, ui: &mut egui::Ui, toggle_cpu_list: &mut bool, toggle_gpu_list: &mut bool) {
+        let card_width = 283.0;
+        let card_spacing = 11.0;
+        let card_height = 75.0;
+        let gb = |bytes: u64| bytes as f64 / 1_000_000_000.0;
+
+        ui.add_space(20.0);
+
+        // Machine header: host name, OS and kernel
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            ui.vertical(|ui| {
+                ui.label(egui::RichText::new(System::host_name().unwrap_or_else(|| "System".into())).size(20.0).strong());
+                let os = System::long_os_version().unwrap_or_default();
+                let kernel = System::kernel_version().map(|k| format!("Linux {}", k)).unwrap_or_default();
+                ui.label(
+                    egui::RichText::new(format!("{}  ·  {}", os, kernel))
+                        .size(11.0)
+                        .color(egui::Color32::from_gray(120)),
+                );
+            });
+        });
+
+        ui.add_space(15.0);
+
+        // Row 1: CPU and GPU temperatures plus uptime
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+
+            // Average CPU temperature from hwmon; click to list every sensor, since the average can hide one hot core
+            let hottest = self.cpu_sensors.iter().reduce(|a, b| if b.1 > a.1 { b } else { a });
+            let (cpu_label, cpu_note) = match hottest {
+                Some((label, temp)) if self.cpu_sensors.len() > 1 => (
+                    format!("CPU Temp (average) {}", if self.cpu_list_open { "▲" } else { "▼" }),
+                    format!("Hottest: {} {:.1}°C", label, temp),
+                ),
+                _ => ("CPU Temp".to_string(), String::new()),
+            };
+            let cpu_card = ui
+                .scope(|ui| {
+                    stat_card_with_note(
+                        ui,
+                        card_width,
+                        card_height,
+                        &cpu_label,
+                        &self.cpu_temp.map(|t| format!("{:.1}°C", t)).unwrap_or("--".into()),
+                        &cpu_note,
+                        egui::Color32::from_rgb(139, 92, 246),
+                    );
+                })
+                .response
+                .interact(egui::Sense::click());
+            if self.cpu_sensors.len() > 1 && cpu_card.on_hover_cursor(egui::CursorIcon::PointingHand).clicked() {
+                *toggle_cpu_list = true;
+            }
+
+            ui.add_space(card_spacing);
+
+            // Hottest GPU temperature; click to list every GPU when there are several
+            let hottest = self.gpu_temps.iter().map(|g| g.1).reduce(f32::max);
+            let gpu_label = if self.gpu_temps.len() > 1 {
+                format!("GPU Temp (hottest of {}) {}", self.gpu_temps.len(), if self.gpu_list_open { "▲" } else { "▼" })
+            } else {
+                "GPU Temp".to_string()
+            };
+            let gpu_note = if self.power_saving() { "Not polled in battery saver" } else { "" };
+            let gpu_card = ui
+                .scope(|ui| {
+                    stat_card_with_note(
+                        ui,
+                        card_width,
+                        card_height,
+                        &gpu_label,
+                        &hottest.map(|t| format!("{:.1}°C", t)).unwrap_or("--".into()),
+                        gpu_note,
+                        egui::Color32::from_rgb(236, 72, 153),
+                    );
+                })
+                .response
+                .interact(egui::Sense::click());
+            if self.gpu_temps.len() > 1 && gpu_card.on_hover_cursor(egui::CursorIcon::PointingHand).clicked() {
+                *toggle_gpu_list = true;
+            }
+
+            ui.add_space(card_spacing);
+
+            // Time since boot
+            let uptime = System::uptime();
+            stat_card(
+                ui,
+                card_width,
+                card_height,
+                "Uptime",
+                &format!("{}d {}h {}m", uptime / 86_400, uptime % 86_400 / 3600, uptime % 3600 / 60),
+                egui::Color32::from_rgb(59, 130, 246),
+            );
+        });
+
+        // Expanded per-sensor CPU temperature list
+        if self.cpu_list_open && self.cpu_sensors.len() > 1 {
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                ui.add_space(20.0);
+                egui::Frame::none()
+                    .fill(egui::Color32::WHITE)
+                    .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                    .rounding(10.0)
+                    .inner_margin(12.0)
+                    .show(ui, |ui| {
+                        ui.set_width(ui.available_width() - 40.0);
+                        egui::Grid::new("cpu_sensor_grid").num_columns(2).spacing([40.0, 4.0]).show(ui, |ui| {
+                            for (label, temp) in &self.cpu_sensors {
+                                ui.label(egui::RichText::new(label).size(11.0));
+                                ui.label(egui::RichText::new(format!("{:.1}°C", temp)).size(11.0).strong());
+                                ui.end_row();
+                            }
+                        });
+                    });
+                ui.add_space(20.0);
+            });
+        }
+
+        // Expanded per-GPU temperature list
+        if self.gpu_list_open && self.gpu_temps.len() > 1 {
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                ui.add_space(20.0);
+                egui::Frame::none()
+                    .fill(egui::Color32::WHITE)
+                    .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                    .rounding(10.0)
+                    .inner_margin(12.0)
+                    .show(ui, |ui| {
+                        ui.set_width(ui.available_width() - 40.0);
+                        egui::Grid::new("gpu_grid").num_columns(2).spacing([40.0, 4.0]).show(ui, |ui| {
+                            for (name, temp) in &self.gpu_temps {
+                                ui.label(egui::RichText::new(name).size(11.0));
+                                ui.label(egui::RichText::new(format!("{:.1}°C", temp)).size(11.0).strong());
+                                ui.end_row();
+                            }
+                        });
+                    });
+                ui.add_space(20.0);
+            });
+        }
+
+        ui.add_space(10.0);
+
+        // Row 2: Memory and swap usage
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+
+            let total_mem = self.system.total_memory();
+            stat_card(
+                ui,
+                card_width,
+                card_height,
+                "Memory used",
+                &if total_mem > 0 {
+                    format!("{:.1} / {:.1} GB", gb(self.system.used_memory()), gb(total_mem))
+                } else {
+                    "--".into()
+                },
+                egui::Color32::from_rgb(20, 184, 166),
+            );
+
+            ui.add_space(card_spacing);
+
+            let total_swap = self.system.total_swap();
+            stat_card(
+                ui,
+                card_width,
+                card_height,
+                "Swap used",
+                &if total_swap > 0 {
+                    format!("{:.1} / {:.1} GB", gb(self.system.used_swap()), gb(total_swap))
+                } else {
+                    "No swap".into()
+                },
+                egui::Color32::from_rgb(20, 184, 166),
+            );
+
+            ui.add_space(card_spacing);
+
+            // 1/5/15 minute load averages
+            let load = System::load_average();
+            stat_card(
+                ui,
+                card_width,
+                card_height,
+                "Load average",
+                &format!("{:.2}  {:.2}  {:.2}", load.one, load.five, load.fifteen),
+                egui::Color32::from_rgb(245, 158, 11),
+            );
+        });
+
+        // System fans, so users watching a hot drive can confirm the case fans spin
+        if !self.fans.is_empty() {
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                ui.add_space(20.0);
+                egui::Frame::none()
+                    .fill(egui::Color32::WHITE)
+                    .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                    .rounding(10.0)
+                    .inner_margin(12.0)
+                    .show(ui, |ui| {
+                        ui.set_width(ui.available_width() - 40.0);
+                        ui.label(egui::RichText::new("System Fans").size(14.0).strong());
+                        ui.add_space(6.0);
+                        ui.horizontal_wrapped(|ui| {
+                            for (label, rpm) in &self.fans {
+                                // A stopped fan is worth a second look (some stop at idle by design)
+                                let color = if *rpm == 0 {
+                                    egui::Color32::from_rgb(245, 158, 11)
+                                } else {
+                                    egui::Color32::from_gray(60)
+                                };
+                                ui.label(egui::RichText::new(format!("{}:", label)).size(11.0).color(egui::Color32::from_gray(120)));
+                                ui.label(egui::RichText::new(format!("{} RPM", rpm)).size(11.0).strong().color(color));
+                                ui.add_space(16.0);
+                            }
+                        });
+                    });
+                ui.add_space(20.0);
+            });
+        }
+
+        // Laptop batteries: charge, wear against the design capacity and cycle count
+        for battery in &self.batteries {
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                ui.add_space(20.0);
+                egui::Frame::none()
+                    .fill(egui::Color32::WHITE)
+                    .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                    .rounding(10.0)
+                    .inner_margin(12.0)
+                    .show(ui, |ui| {
+                        ui.set_width(ui.available_width() - 40.0);
+                        let gray = egui::Color32::from_gray(120);
+                        ui.horizontal(|ui| {
+                            ui.label(egui::RichText::new(format!("Battery {}", battery.name)).size(14.0).strong());
+                            if let Some(model) = &battery.model {
+                                ui.label(egui::RichText::new(model).size(11.0).color(gray));
+                            }
+                        });
+                        ui.add_space(6.0);
+                        ui.horizontal(|ui| {
+                            let charge = battery.charge_percent.unwrap_or(0);
+                            usage_ring(ui, f32::from(charge) / 100.0, 40.0, egui::Color32::from_rgb(34, 197, 94));
+                            ui.add_space(8.0);
+                            ui.vertical(|ui| {
+                                let charge_text = match (battery.charge_percent, &battery.status) {
+                                    (Some(p), Some(status)) => format!("{}% · {}", p, status),
+                                    (Some(p), None) => format!("{}%", p),
+                                    (None, status) => status.clone().unwrap_or("--".into()),
+                                };
+                                ui.label(egui::RichText::new(charge_text).size(14.0).strong());
+
+                                // Batteries are usually considered worn out below 80% of their design capacity
+                                let (full, design) = (battery.full_capacity, battery.design_capacity);
+                                let health = battery.health_percent();
+                                let color = match health {
+                                    Some(h) if h >= 80.0 => egui::Color32::from_rgb(34, 197, 94),
+                                    Some(h) if h >= 60.0 => egui::Color32::from_rgb(245, 158, 11),
+                                    Some(_) => egui::Color32::from_rgb(239, 68, 68),
+                                    None => gray,
+                                };
+                                ui.horizontal_wrapped(|ui| {
+                                    if let (Some(full), Some(design), Some(health)) = (full, design, health) {
+                                        ui.label(egui::RichText::new("Capacity:").size(11.0).color(gray));
+                                        ui.label(
+                                            egui::RichText::new(format!(
+                                                "{:.1} of {:.1} {} design ({:.0}%)",
+                                                full, design, battery.capacity_unit, health
+                                            ))
+                                            .size(11.0)
+                                            .strong()
+                                            .color(color),
+                                        );
+                                        ui.add_space(16.0);
+                                    }
+                                    if let Some(cycles) = battery.cycle_count {
+                                        ui.label(egui::RichText::new("Cycles:").size(11.0).color(gray));
+                                        ui.label(egui::RichText::new(group_digits(u64::from(cycles))).size(11.0).strong());
+                                    }
+                                });
+                            });
+                        });
+                    });
+                ui.add_space(20.0);
+            });
+        }
+
+        // Swap placement matters for SSD wear
+        if !self.swap_devices.is_empty() {
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                ui.add_space(20.0);
+                ui.label(
+                    egui::RichText::new(format!("Swap devices: {}", self.swap_devices.join(", ")))
+                        .size(11.0)
+                        .color(egui::Color32::from_gray(120)),
+                );
+            });
+        }
+
+        ui.add_space(15.0);
+    }
+
+    /// Renders the session log page: every scan, error, SMART change and alert of this session, oldest first,
+    /// with a filter per event kind and an export to a text file.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    fn log_page(&mut self, ui: &mut egui::Ui) {
+        let mut save = false;
+
+        ui.add_space(20.0);
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("Session log").size(14.0).strong());
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            save = ui
+                                .add_enabled(!self.session_log.entries.is_empty(), egui::Button::new("💾 Save log"))
+                                .on_hover_text("Save the whole log as a text file in your home directory")
+                                .clicked();
+                        });
+                    });
+                    ui.label(
+                        egui::RichText::new("Scans, errors, SMART changes and alerts since the app started (times in UTC)")
+                            .size(11.0)
+                            .color(egui::Color32::from_gray(120)),
+                    );
+                    ui.add_space(6.0);
+
+                    // One filter per kind, with how many events it has
+                    ui.horizontal(|ui| {
+                        for kind in EventKind::ALL {
+                            let count = self.session_log.entries.iter().filter(|e| e.kind == kind).count();
+                            let mut shown = self.log_kinds.contains(&kind);
+                            if ui.checkbox(&mut shown, format!("{} ({})", kind.label(), count)).changed() {
+                                self.log_kinds.retain(|k| *k != kind);
+                                if shown {
+                                    self.log_kinds.push(kind);
+                                }
+                            }
+                        }
+                    });
+                    ui.add_space(6.0);
+
+                    let rows: Vec<_> = self.session_log.entries.iter().filter(|e| self.log_kinds.contains(&e.kind)).collect();
+                    if rows.is_empty() {
+                        ui.label(egui::RichText::new("Nothing logged yet.").size(12.0).color(egui::Color32::from_gray(120)));
+                        return;
+                    }
+                    // Only the visible rows are laid out, so a long overnight log stays cheap to show
+                    let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+                    egui::ScrollArea::vertical().stick_to_bottom(true).auto_shrink([false, true]).show_rows(
+                        ui,
+                        row_height,
+                        rows.len(),
+                        |ui, range| {
+                            for event in &rows[range] {
+                                let color = match event.kind {
+                                    EventKind::Scan => egui::Color32::from_gray(120),
+                                    EventKind::Error => egui::Color32::from_rgb(239, 68, 68),
+                                    EventKind::Change => egui::Color32::from_rgb(59, 130, 246),
+                                    EventKind::Alert => egui::Color32::from_rgb(245, 158, 11),
+                                };
+                                ui.label(egui::RichText::new(format_event(event)).monospace().color(color));
+                            }
+                        },
+                    );
+                });
+            ui.add_space(20.0);
+        });
+
+        if save {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+            let path = default_log_path(now);
+            self.action_result = Some(self.session_log.save(&path).map(|_| format!("Session log saved to {}", path.display())));
+        }
+    }
+
+    /// Renders the Overview page: capacity totals across all drives, then one stacked bar per drive
+    /// splitting it into used and free space per partition, other partitioned space and unallocated space.
+    /// Clicking a drive opens its details.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    fn overview_page(&mut self, ui: &mut egui::Ui) {
+        let size = |bytes: u64| {
+            if bytes >= 1_000_000_000_000 {
+                format!("{:.2} TB", bytes as f64 / 1e12)
+            } else {
+                format!("{:.1} GB", bytes as f64 / 1e9)
+            }
+        };
+        let used_color = egui::Color32::from_rgb(59, 130, 246);
+        let free_color = egui::Color32::from_rgb(191, 219, 254);
+        let other_color = egui::Color32::from_gray(190);
+        let unallocated_color = egui::Color32::from_gray(235);
+        let usages: Vec<Option<CapacityUsage>> = self.drives.iter().map(|d| d.capacity_usage()).collect();
+        let total = CapacityUsage::sum(usages.iter().flatten());
+        let mut open_drive: Option<usize> = None;
+
+        ui.add_space(20.0);
+
+        // Totals across every drive with a known capacity
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            let card_spacing = 11.0;
+            let card_width = ((ui.available_width() - 20.0 - 3.0 * card_spacing) / 4.0 - 24.0).max(120.0);
+            let filesystems = total.used + total.free;
+            let used_share = if filesystems > 0 { total.used as f64 / filesystems as f64 * 100.0 } else { 0.0 };
+            let cards = [
+                ("Total capacity", format!("{} · {} drive(s)", size(total.total), self.drives.len()), egui::Color32::from_gray(40)),
+                ("Used", format!("{} ({:.0}%)", size(total.used), used_share), used_color),
+                ("Free in filesystems", size(total.free), egui::Color32::from_rgb(34, 197, 94)),
+                ("Unallocated", size(total.unallocated), egui::Color32::from_gray(120)),
+            ];
+            for (i, (label, value, color)) in cards.iter().enumerate() {
+                if i > 0 {
+                    ui.add_space(card_spacing);
+                }
+                stat_card(ui, card_width, 55.0, label, value, *color);
+            }
+        });
+
+        ui.add_space(15.0);
+
+        // One stacked bar per drive
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+                    ui.label(egui::RichText::new("Storage Overview").size(14.0).strong());
+                    ui.add_space(4.0);
+
+                    // Legend
+                    ui.horizontal(|ui| {
+                        for (color, label) in [
+                            (used_color, "Used"),
+                            (free_color, "Free"),
+                            (other_color, "Other partitions (swap, unmounted, encrypted)"),
+                            (unallocated_color, "Unallocated"),
+                        ] {
+                            let (rect, _) = ui.allocate_exact_size(egui::vec2(10.0, 10.0), egui::Sense::hover());
+                            ui.painter().rect_filled(rect, 2.0, color);
+                            ui.label(egui::RichText::new(label).size(11.0).color(egui::Color32::from_gray(120)));
+                            ui.add_space(8.0);
+                        }
+                    });
+
+                    for (i, (d, usage)) in self.drives.iter().zip(&usages).enumerate() {
+                        ui.add_space(12.0);
+                        ui.horizontal(|ui| {
+                            let name = ui
+                                .add(
+                                    egui::Label::new(egui::RichText::new(&d.dev).size(13.0).strong())
+                                        .sense(egui::Sense::click()),
+                                )
+                                .on_hover_text("Open this drive's details");
+                            if name.clicked() {
+                                open_drive = Some(i);
+                            }
+                            if let Some(model) = &d.model {
+                                ui.label(egui::RichText::new(model).size(11.0).color(egui::Color32::from_gray(120)));
+                            }
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                let summary = match usage {
+                                    Some(u) if u.used + u.free > 0 => format!(
+                                        "{} used of {} ({:.0}% of mounted space)",
+                                        size(u.used),
+                                        size(u.total),
+                                        u.used as f64 / (u.used + u.free) as f64 * 100.0
+                                    ),
+                                    Some(u) => format!("{}, nothing mounted", size(u.total)),
+                                    None => "Capacity unknown".to_string(),
+                                };
+                                ui.label(egui::RichText::new(summary).size(11.0));
+                            });
+                        });
+                        ui.add_space(4.0);
+
+                        let Some(usage) = usage.filter(|u| u.total > 0) else {
+                            continue;
+                        };
+                        // Segments in disk order: each filesystem's used then free part, then the rest
+                        let mut segments: Vec<(u64, egui::Color32, String)> = Vec::new();
+                        for part in d.distinct_partitions() {
+                            let name = if part.device.is_empty() { part.mount_point.clone() } else { format!("{} ({})", part.mount_point, part.device) };
+                            segments.push(((part.used_gb * 1e9) as u64, used_color, format!("{}: {:.1} GB used", name, part.used_gb)));
+                            segments.push(((part.free_gb * 1e9) as u64, free_color, format!("{}: {:.1} GB free", name, part.free_gb)));
+                        }
+                        segments.push((usage.other(), other_color, format!("Other partitions: {}", size(usage.other()))));
+                        segments.push((usage.unallocated, unallocated_color, format!("Unallocated: {}", size(usage.unallocated))));
+
+                        let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), 18.0), egui::Sense::click());
+                        let painter = ui.painter();
+                        painter.rect_filled(rect, 4.0, unallocated_color);
+                        let mut x = rect.left();
+                        let mut hovered = None;
+                        for (bytes, color, label) in &segments {
+                            let width = rect.width() * (*bytes as f32 / usage.total as f32);
+                            let segment = egui::Rect::from_min_max(egui::pos2(x, rect.top()), egui::pos2((x + width).min(rect.right()), rect.bottom()));
+                            painter.rect_filled(segment, 0.0, *color);
+                            if response.hover_pos().is_some_and(|pos| segment.contains(pos)) {
+                                hovered = Some(label.clone());
+                            }
+                            x += width;
+                        }
+                        painter.rect_stroke(rect, 4.0, egui::Stroke::new(1.0, egui::Color32::from_gray(220)));
+                        if response.clicked() {
+                            open_drive = Some(i);
+                        }
+                        if let Some(label) = hovered {
+                            response.on_hover_text(label);
+                        }
+                    }
+                });
+            ui.add_space(20.0);
+        });
+
+        ui.add_space(15.0);
+
+        if let Some(i) = open_drive {
+            self.selected = i;
+            self.view = View::Drive;
+        }
+    }
+
+    /// Renders the Fleet page: one row per host (this machine first) with a colored cell per drive,
+    /// plus controls to add, remove and refetch remote hosts running `ssd_info_cli serve`.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    fn fleet_dashboard(&mut self, ui: &mut egui::Ui) {
+        let mut add_host = false;
+        let mut remove_host: Option<usize> = None;
+        let mut refresh_now = false;
+
+        ui.add_space(20.0);
+
+        // Header with the "Add host" field
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+                    ui.label(egui::RichText::new("Fleet").size(14.0).strong());
+                    ui.add_space(4.0);
+                    ui.label(
+                        egui::RichText::new(
+                            "Drives of other machines running `ssd_info_cli serve --listen 0.0.0.0:8190`, refreshed every 30 seconds",
+                        )
+                        .size(11.0)
+                        .color(egui::Color32::from_gray(120)),
+                    );
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        let response = ui.add(
+                            egui::TextEdit::singleline(&mut self.fleet_host_input)
+                                .hint_text("nas.local or 192.168.1.20:8190")
+                                .desired_width(240.0),
+                        );
+                        add_host = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                        add_host |= ui
+                            .add_enabled(!self.fleet_host_input.trim().is_empty(), egui::Button::new("➕ Add host"))
+                            .clicked();
+                        refresh_now = ui.button("🔄 Refresh all").clicked();
+                    });
+                });
+            ui.add_space(20.0);
+        });
+
+        ui.add_space(15.0);
+
+        // Hosts × drives grid; this machine's live data comes first unless a report is open
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Grid::new("fleet_grid").spacing([8.0, 8.0]).show(ui, |ui| {
+                if self.snapshot.is_none() {
+                    let host = System::host_name().unwrap_or_else(|| "This machine".into());
+                    ui.label(egui::RichText::new(format!("🖥 {}", host)).size(12.0).strong())
+                        .on_hover_text("This machine");
+                    ui.label("");
+                    for d in &self.drives {
+                        self.fleet_cell(ui, d);
+                    }
+                    ui.end_row();
+                }
+
+                for (i, host) in self.fleet.hosts.iter().enumerate() {
+                    ui.vertical(|ui| {
+                        let name = match &host.report {
+                            Some(Ok(report)) => format!("🌐 {}", report.host),
+                            _ => format!("🌐 {}", host.address),
+                        };
+                        ui.label(egui::RichText::new(name).size(12.0).strong()).on_hover_text(&host.address);
+                        let age = match (host.pending, host.fetched_at) {
+                            (true, _) => "fetching…".to_string(),
+                            (false, Some(t)) => format!("{} s ago", t.elapsed().as_secs()),
+                            (false, None) => String::new(),
+                        };
+                        ui.label(egui::RichText::new(age).size(10.0).color(egui::Color32::from_gray(120)));
+                    });
+                    if ui.small_button("✖").on_hover_text("Remove host").clicked() {
+                        remove_host = Some(i);
+                    }
+                    match &host.report {
+                        Some(Ok(report)) => {
+                            for d in &report.drives {
+                                self.fleet_cell(ui, d);
+                            }
+                        }
+                        Some(Err(e)) => {
+                            ui.label(egui::RichText::new(e).size(11.0).color(egui::Color32::from_rgb(239, 68, 68)));
+                        }
+                        None => {
+                            ui.spinner();
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+
+        if self.fleet.hosts.is_empty() {
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                ui.add_space(20.0);
+                ui.label(
+                    egui::RichText::new("No remote hosts yet. Add one above to see its drives here.")
+                        .size(11.0)
+                        .color(egui::Color32::from_gray(120)),
+                );
+            });
+        }
+        ui.add_space(15.0);
+
+        if add_host {
+            let address = std::mem::take(&mut self.fleet_host_input);
+            if let Err(e) = self.fleet.add(&address) {
+                self.action_result = Some(Err(e));
+            }
+            refresh_now = true;
+        }
+        if let Some(i) = remove_host {
+            if let Err(e) = self.fleet.remove(i) {
+                self.action_result = Some(Err(e));
+            }
+        }
+        if refresh_now {
+            self.fleet.refresh();
+            self.last_fleet_refresh = Some(Instant::now());
+        }
+    }
+
+    /// Renders the Alerts page: the rules from the `[alerts]` config section with validation results,
+    /// the drives each rule currently matches, a test-fire button per rule, and the alert history
+    /// with per-alert acknowledge and snooze buttons.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    fn alerts_page(&mut self, ui: &mut egui::Ui) {
+        let mut reload = false;
+        let mut acknowledge: Option<usize> = None;
+        let mut snooze: Option<(usize, u64)> = None;
+        let mut test_rule: Option<crate::gui::alerts::AlertRule> = None;
+        let mut alarm_toggled = false;
+        let mut test_alarm = false;
+        let mut autostart_toggled = false;
+        let mut minimize_toggled = false;
+        let mut power_saver: Option<PowerSaver> = None;
+        let rules = self.settings.alert_rules();
+        let channels = self.settings.channels();
+
+        ui.add_space(20.0);
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("Alert rules").size(14.0).strong());
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            reload = ui.button("🔄 Reload config").clicked();
+                        });
+                    });
+                    let path = self.settings.path().map(|p| p.display().to_string()).unwrap_or_default();
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "Rules live in the [alerts] section of {} as `name = [critical:] field > number`",
+                            path
+                        ))
+                        .size(11.0)
+                        .color(egui::Color32::from_gray(120)),
+                    );
+                    ui.add_space(8.0);
+
+                    if rules.is_empty() {
+                        ui.label(
+                            egui::RichText::new(
+                                "No rules yet. Example:\n[alerts]\nhot = temp_c > 65\npending_sectors = critical: attribute(197).raw > 0\nworn = health_percent < 70",
+                            )
+                            .size(11.0)
+                            .monospace(),
+                        );
+                    }
+
+                    egui::Grid::new("alert_rules").num_columns(4).spacing([16.0, 6.0]).show(ui, |ui| {
+                        for rule in &rules {
+                            match rule {
+                                Ok(rule) => {
+                                    let color = if rule.severity == crate::gui::alerts::Severity::Critical {
+                                        egui::Color32::from_rgb(239, 68, 68)
+                                    } else {
+                                        egui::Color32::from_rgb(245, 158, 11)
+                                    };
+                                    ui.label(egui::RichText::new(format!("● {}", rule.name)).size(11.0).strong().color(color));
+                                    ui.label(egui::RichText::new(&rule.condition).size(11.0).monospace());
+                                    let matching: Vec<&str> = self
+                                        .drives
+                                        .iter()
+                                        .filter(|d| rule.matches(d).is_some())
+                                        .map(|d| d.dev.as_str())
+                                        .collect();
+                                    ui.label(
+                                        egui::RichText::new(if matching.is_empty() {
+                                            "not matching".to_string()
+                                        } else {
+                                            format!("matching: {}", matching.join(", "))
+                                        })
+                                        .size(11.0)
+                                        .color(egui::Color32::from_gray(120)),
+                                    );
+                                    if ui.small_button("🔔 Test").on_hover_text("Send a test notification for this rule").clicked() {
+                                        test_rule = Some(rule.clone());
+                                    }
+                                }
+                                Err(e) => {
+                                    ui.label(egui::RichText::new("✖ invalid").size(11.0).color(egui::Color32::from_rgb(239, 68, 68)));
+                                    ui.label(egui::RichText::new(e).size(11.0).color(egui::Color32::from_rgb(239, 68, 68)));
+                                    ui.label("");
+                                    ui.label("");
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+
+                    ui.add_space(8.0);
+                    let names: Vec<String> = channels.iter().map(|c| c.to_line()).collect();
+                    let names = if names.is_empty() { "off".to_string() } else { names.join(", ") };
+                    ui.label(
+                        egui::RichText::new(format!("Notifications: {}", names))
+                            .size(11.0)
+                            .color(egui::Color32::from_gray(120)),
+                    );
+                    for e in &self.settings.schedule_errors {
+                        ui.label(egui::RichText::new(format!("✖ {}", e)).size(11.0).color(egui::Color32::from_rgb(239, 68, 68)));
+                    }
+                    ui.horizontal(|ui| {
+                        let mut alarm = self.settings.alarm.is_some();
+                        alarm_toggled = ui
+                            .checkbox(&mut alarm, "🔊 Audible alarm")
+                            .on_hover_text("Play a sound when a drive fails its SMART self-assessment or an attribute turns critical")
+                            .changed();
+                        if alarm && ui.small_button("▶ Test").clicked() {
+                            test_alarm = true;
+                        }
+                        if let Some(autostart) = &self.autostart {
+                            let mut enabled = autostart.is_enabled();
+                            autostart_toggled = ui
+                                .checkbox(&mut enabled, "🚀 Start minimized on login")
+                                .on_hover_text("Add an autostart entry so drives are monitored and alerts fire from login on")
+                                .changed();
+                        }
+                        let mut minimize = self.settings.minimize_on_close;
+                        minimize_toggled = ui
+                            .checkbox(&mut minimize, "Keep monitoring when closed")
+                            .on_hover_text("Closing the window hides it to the tray (or minimizes it without one) instead of quitting; use ⏻ in the sidebar to quit")
+                            .changed();
+                    });
+                    ui.horizontal(|ui| {
+                        let describe = |mode: PowerSaver| match mode {
+                            PowerSaver::Auto => "On battery",
+                            PowerSaver::Always => "Always",
+                            PowerSaver::Never => "Never",
+                        };
+                        ui.label("🔋 Battery saver")
+                            .on_hover_text("Refresh at most once a minute, stop polling GPUs and leave suspended drives asleep");
+                        egui::ComboBox::from_id_salt("power_saver")
+                            .selected_text(describe(self.settings.power_saver))
+                            .show_ui(ui, |ui| {
+                                for mode in PowerSaver::ALL {
+                                    if ui.selectable_label(self.settings.power_saver == mode, describe(mode)).clicked() {
+                                        power_saver = Some(mode);
+                                    }
+                                }
+                            });
+                        if self.power_saving() {
+                            ui.label(egui::RichText::new("active").size(11.0).color(egui::Color32::from_rgb(34, 197, 94)));
+                        }
+                    });
+                });
+            ui.add_space(20.0);
+        });
+
+        ui.add_space(15.0);
+
+        // Health score weights, edited in place and saved when an edit is finished
+        let mut weights = self.settings.health;
+        let mut save_weights = false;
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("Health score").size(14.0).strong());
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui.button("Reset to defaults").clicked() {
+                                weights = HealthWeights::default();
+                                save_weights = true;
+                            }
+                        });
+                    });
+                    ui.label(
+                        egui::RichText::new(
+                            "Each drive starts at 100 and loses these points per unit; the weights are saved in the [health] section",
+                        )
+                        .size(11.0)
+                        .color(egui::Color32::from_gray(120)),
+                    );
+                    ui.add_space(8.0);
+
+                    egui::Grid::new("health_weights").num_columns(2).spacing([16.0, 6.0]).show(ui, |ui| {
+                        let rows = [
+                            ("Per % of endurance used", &mut weights.wear),
+                            ("Per pending sector", &mut weights.pending_sector),
+                            ("Per uncorrectable sector", &mut weights.uncorrectable_sector),
+                            ("Per reallocated sector", &mut weights.reallocated_sector),
+                            ("Per NVMe media error", &mut weights.media_error),
+                            ("Per UDMA CRC error", &mut weights.crc_error),
+                            ("Failed SMART self-assessment", &mut weights.smart_failed),
+                        ];
+                        for (label, value) in rows {
+                            ui.label(egui::RichText::new(label).size(11.0));
+                            let response = ui.add(egui::DragValue::new(value).speed(0.1).range(0.0..=100.0).max_decimals(2));
+                            save_weights |= response.drag_stopped() || response.lost_focus();
+                            ui.end_row();
+                        }
+                    });
+                });
+            ui.add_space(20.0);
+        });
+        ui.add_space(15.0);
+        self.settings.health = weights;
+        if save_weights {
+            if let Err(e) = self.settings.save() {
+                self.action_result = Some(Err(e));
+            }
+        }
+
+        // Scheduled exports, edited in place like the weights; text and number edits save when finished
+        let mut export = self.settings.export.clone();
+        let mut save_export = false;
+        let mut export_now = false;
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("Scheduled exports").size(14.0).strong());
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            let mut enabled = export.is_some();
+                            if ui.checkbox(&mut enabled, "Enabled").changed() {
+                                export = enabled.then(ExportSchedule::default);
+                                save_export = true;
+                            }
+                        });
+                    });
+                    ui.label(
+                        egui::RichText::new(
+                            "Writes the full report into a directory on a schedule and deletes the oldest exports; saved in the [export] section",
+                        )
+                        .size(11.0)
+                        .color(egui::Color32::from_gray(120)),
+                    );
+                    let Some(schedule) = export.as_mut() else {
+                        return;
+                    };
+                    ui.add_space(8.0);
+                    egui::Grid::new("scheduled_export").num_columns(2).spacing([16.0, 6.0]).show(ui, |ui| {
+                        ui.label(egui::RichText::new("Every").size(11.0));
+                        egui::ComboBox::from_id_salt("export_interval")
+                            .selected_text(schedule.interval.name())
+                            .show_ui(ui, |ui| {
+                                for interval in ExportInterval::ALL {
+                                    save_export |= ui.selectable_value(&mut schedule.interval, interval, interval.name()).clicked();
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label(egui::RichText::new("Format").size(11.0));
+                        egui::ComboBox::from_id_salt("export_format")
+                            .selected_text(schedule.format.name())
+                            .show_ui(ui, |ui| {
+                                for format in ExportFormat::ALL {
+                                    save_export |= ui.selectable_value(&mut schedule.format, format, format.name()).clicked();
+                                }
+                            });
+                        ui.end_row();
+
+                        ui.label(egui::RichText::new("Directory").size(11.0));
+                        let mut dir = schedule.dir.display().to_string();
+                        let response = ui.add(egui::TextEdit::singleline(&mut dir).desired_width(320.0));
+                        if response.changed() {
+                            schedule.dir = PathBuf::from(dir);
+                        }
+                        save_export |= response.lost_focus();
+                        ui.end_row();
+
+                        ui.label(egui::RichText::new("Exports kept").size(11.0));
+                        let response = ui.add(egui::DragValue::new(&mut schedule.keep).range(1..=1000));
+                        save_export |= response.drag_stopped() || response.lost_focus();
+                        ui.end_row();
+                    });
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        let last = schedule.last_export();
+                        let status = match last {
+                            Some(last) => format!(
+                                "Last export {}, next after {}",
+                                format_timestamp(last),
+                                format_timestamp(last + schedule.interval.seconds())
+                            ),
+                            None => "No export yet; the next scan writes one".to_string(),
+                        };
+                        ui.label(egui::RichText::new(status).size(11.0).color(egui::Color32::from_gray(120)));
+                        export_now = self.snapshot.is_none() && ui.small_button("💾 Export now").clicked();
+                    });
+                });
+            ui.add_space(20.0);
+        });
+        ui.add_space(15.0);
+        self.settings.export = export;
+        if save_export {
+            if let Err(e) = self.settings.save() {
+                self.action_result = Some(Err(e));
+            }
+        }
+        if export_now {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+            self.run_scheduled_export(now, true);
+        }
+
+        // Logged alerts, newest first
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            egui::Frame::none()
+                .fill(egui::Color32::WHITE)
+                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                .rounding(10.0)
+                .inner_margin(15.0)
+                .show(ui, |ui| {
+                    ui.set_width(ui.available_width() - 40.0);
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new("Alert history").size(14.0).strong());
+                        let open = self.alert_log.unacknowledged();
+                        if open > 0 {
+                            ui.label(
+                                egui::RichText::new(format!("{} unacknowledged", open))
+                                    .size(11.0)
+                                    .color(egui::Color32::from_rgb(239, 68, 68)),
+                            );
+                        }
+                    });
+                    ui.add_space(8.0);
+                    if self.alert_log.entries.is_empty() {
+                        ui.label(egui::RichText::new("No alerts fired").size(11.0).color(egui::Color32::from_gray(120)));
+                    }
+                    egui::Grid::new("alert_history").num_columns(3).spacing([16.0, 6.0]).show(ui, |ui| {
+                        for (i, entry) in self.alert_log.entries.iter().enumerate().rev() {
+                            let alert = &entry.alert;
+                            // Acknowledged alerts are grayed out so new problems stand out
+                            let color = if entry.acknowledged {
+                                egui::Color32::from_gray(150)
+                            } else if alert.severity == crate::gui::alerts::Severity::Critical {
+                                egui::Color32::from_rgb(239, 68, 68)
+                            } else {
+                                egui::Color32::from_rgb(146, 64, 14)
+                            };
+                            ui.label(egui::RichText::new(format_timestamp(alert.timestamp)).size(11.0).color(egui::Color32::from_gray(120)));
+                            ui.label(egui::RichText::new(&alert.message).size(11.0).color(color));
+                            ui.horizontal(|ui| {
+                                if !entry.acknowledged && ui.small_button("✔ Acknowledge").clicked() {
+                                    acknowledge = Some(i);
+                                }
+                                match entry.snoozed_until {
+                                    Some(until) if until > now => {
+                                        ui.label(
+                                            egui::RichText::new(format!("💤 until {}", format_timestamp(until)))
+                                                .size(11.0)
+                                                .color(egui::Color32::from_gray(120)),
+                                        );
+                                    }
+                                    _ => {
+                                        ui.menu_button("💤 Snooze", |ui| {
+                                            for (label, days) in [("1 day", 1), ("1 week", 7), ("30 days", 30)] {
+                                                if ui.button(label).clicked() {
+                                                    snooze = Some((i, now + days * 86_400));
+                                                    ui.close_menu();
+                                                }
+                                            }
+                                        })
+                                        .response
+                                        .on_hover_text("Silence this rule on this drive");
+                                    }
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+                });
+            ui.add_space(20.0);
+        });
+        ui.add_space(15.0);
+
+        if reload {
+            self.settings = Settings::load_default();
+        }
+        if alarm_toggled {
+            self.settings.alarm = match self.settings.alarm {
+                Some(_) => None,
+                None => Some(DEFAULT_ALARM_SOUND.to_string()),
+            };
+            if let Err(e) = self.settings.save() {
+                self.action_result = Some(Err(e));
+            }
+        }
+        if minimize_toggled {
+            self.settings.minimize_on_close = !self.settings.minimize_on_close;
+            self.tray = None;
+            if self.settings.minimize_on_close {
+                match Tray::start(self.ctx.clone()) {
+                    Ok(tray) => self.tray = Some(tray),
+                    Err(e) => self.action_result = Some(Ok(format!("{}; closing the window will minimize it", e))),
+                }
+            }
+            if let Err(e) = self.settings.save() {
+                self.action_result = Some(Err(e));
+            }
+        }
+        if let Some(mode) = power_saver.filter(|m| *m != self.settings.power_saver) {
+            self.settings.power_saver = mode;
+            if let Err(e) = self.settings.save() {
+                self.action_result = Some(Err(e));
+            }
+        }
+        if let (true, Some(autostart)) = (autostart_toggled, &self.autostart) {
+            let result = std::env::current_exe()
+                .map_err(|e| format!("Cannot find the running executable: {}", e))
+                .and_then(|exe| autostart.set_enabled(!autostart.is_enabled(), &exe));
+            if let Err(e) = result {
+                self.action_result = Some(Err(e));
+            }
+        }
+        if let (true, Some(sound)) = (test_alarm, self.settings.alarm.clone()) {
+            self.spawn_task(move || play_alarm(&sound).err().map(|e| Err(format!("Alarm: {}", e))));
+        }
+        let logged = match (acknowledge, snooze) {
+            (Some(i), _) => Some(self.alert_log.acknowledge(i)),
+            (_, Some((i, until))) => Some(self.alert_log.snooze(i, until)),
+            _ => None,
+        };
+        if let Some(Err(e)) = logged {
+            self.action_result = Some(Err(e));
+        }
+        // Test-fire on the selected drive, or a placeholder when no drive is present
+        if let Some(rule) = test_rule {
+            let di = self.drives.get(self.selected).map(|d| (**d).clone()).unwrap_or_else(|| DiskInfo::empty("/dev/test"));
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+            let mut alert = rule.alert(&di, rule.value(&di).unwrap_or(0.0), now);
+            alert.message = format!("[TEST] {}", alert.message);
+            // Webhooks and commands can take a while; the outcome is shown when they finish
+            self.action_result = Some(Ok(format!("Sending test alert '{}'…", rule.name)));
+            self.spawn_task(move || {
+                let errors = notify(&channels, &alert);
+                Some(if errors.is_empty() { Ok(format!("Test alert '{}' sent", rule.name)) } else { Err(errors.join("\n")) })
+            });
+        }
+    }
+
+    /// Temperature at which a drive counts as hot: its own alarm, or `DEFAULT_HOT_TEMP_C`.
+    fn hot_temp(&self, d: &DiskInfo) -> i32 {
+        self.settings.temp_alarm(d).unwrap_or(DEFAULT_HOT_TEMP_C)
+    }
+
+    /// Renders one drive cell of the fleet grid, colored by the drive's state:
+    /// red for failing drives, amber for warnings or worn/hot drives, green when healthy, gray without data.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    /// * `d` - Drive to show
+    fn fleet_cell(&self, ui: &mut egui::Ui, d: &DiskInfo) {
+        let problems = d.problems();
+        let has_warning = d.smart_attributes.iter().any(|a| a.status == AttributeStatus::Warning);
+        let score = d.health_score(&self.settings.health).map(|h| h.score);
+        let (fill, accent) = if d.cached || (score.is_none() && d.temp_c.is_none()) {
+            (egui::Color32::from_rgb(243, 244, 246), egui::Color32::from_gray(120))
+        } else if !problems.is_empty() || score.is_some_and(|h| h < 50) {
+            (egui::Color32::from_rgb(254, 226, 226), egui::Color32::from_rgb(239, 68, 68))
+        } else if has_warning || score.is_some_and(|h| h <= 84) || d.temp_c.is_some_and(|t| t >= self.hot_temp(d)) {
+            (egui::Color32::from_rgb(254, 243, 199), egui::Color32::from_rgb(146, 64, 14))
+        } else {
+            (egui::Color32::from_rgb(220, 252, 231), egui::Color32::from_rgb(21, 128, 61))
+        };
+
+        let response = egui::Frame::none()
+            .fill(fill)
+            .stroke(egui::Stroke::new(1.0, accent))
+            .rounding(6.0)
+            .inner_margin(8.0)
+            .show(ui, |ui| {
+                ui.set_width(110.0);
+                ui.label(egui::RichText::new(d.dev.trim_start_matches("/dev/")).size(11.0).strong().color(accent));
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{}  ·  {}",
+                        score.map(|h| format!("{}%", h)).unwrap_or("--".into()),
+                        d.temp_c.map(|t| format!("{}°C", t)).unwrap_or("--".into())
+                    ))
+                    .size(11.0),
+                );
+            })
+            .response;
+
+        // Details on hover: model, serial (masked in privacy mode) and any problems
+        let mut details = vec![d.model.clone().unwrap_or_else(|| d.kind.clone())];
+        if let Some(serial) = &d.serial {
+            details.push(if self.privacy_mode { mask_identifier(serial) } else { serial.clone() });
+        }
+        details.extend(problems);
+        response.on_hover_text(details.join("\n"));
+    }
+
+    /// Renders a one-line summary of the drive's average write rate, the last week's rate, wear per month
+    /// and controller busy time per day.
+    /// The recent rate is highlighted when it is more than twice the long-term average,
+    /// which usually means a new workload started wearing the drive.
+    ///
+    /// # Arguments
+    /// * `ui` - The egui UI context to render into
+    /// * `rates` - Rates computed from the drive's history
+    fn usage_rates_line(ui: &mut egui::Ui, rates: &UsageRates) {
+        ui.horizontal(|ui| {
+            ui.add_space(20.0);
+            let gray = egui::Color32::from_gray(120);
+            ui.label(
+                egui::RichText::new(format!(
+                    "Writing {:.2} TB/day ({:.1} TB/month) on average",
+                    rates.written_tb_per_day,
+                    rates.written_tb_per_day * 30.0
+                ))
+                .size(11.0)
+                .color(gray),
+            );
+
+            if let Some(recent) = rates.recent_written_tb_per_day {
+                let surge = rates.written_tb_per_day > 0.0 && recent > rates.written_tb_per_day * 2.0;
+                let (text, color) = if surge {
+                    (
+                        format!("· last 7 days: {:.2} TB/day ⚠ {:.1}× the usual rate", recent, recent / rates.written_tb_per_day),
+                        egui::Color32::from_rgb(146, 64, 14),
+                    )
+                } else {
+                    (format!("· last 7 days: {:.2} TB/day", recent), gray)
+                };
+                ui.label(egui::RichText::new(text).size(11.0).color(color));
+            }
+
+            if let Some(wear) = rates.wear_percent_per_month {
+                ui.label(egui::RichText::new(format!("· wear: {:.2}% per month", wear)).size(11.0).color(gray));
+            }
+
+            if let Some(busy) = rates.busy_minutes_per_day {
+                ui.label(egui::RichText::new(format!("· busy: {:.0} min/day", busy)).size(11.0).color(gray));
+            }
+        });
+    }
+
+    /// Lists the command palette entries: refresh, export, pages, every drive, and self-tests of the selected drive.
+    fn palette_entries(&self) -> Vec<(String, PaletteAction)> {
+        let pause = if self.paused { "▶ Resume auto refresh" } else { "⏸ Pause auto refresh" };
+        let mut entries = vec![
+            ("🔄 Refresh all drives".to_string(), PaletteAction::Refresh),
+            (pause.to_string(), PaletteAction::TogglePause),
+            ("📊 Go to Overview".to_string(), PaletteAction::Show(View::Overview)),
+            ("🖥 Go to System".to_string(), PaletteAction::Show(View::System)),
+            ("🌐 Go to Fleet".to_string(), PaletteAction::Show(View::Fleet)),
+            ("🔔 Open alerts and settings".to_string(), PaletteAction::Show(View::Alerts)),
+            ("📜 Go to Session log".to_string(), PaletteAction::Show(View::Log)),
+            ("📂 Open report…".to_string(), PaletteAction::OpenReport),
+            ("🕶 Toggle privacy mode".to_string(), PaletteAction::TogglePrivacy),
+            ("ℹ About and diagnostics".to_string(), PaletteAction::About),
+        ];
+        if !self.drives.is_empty() {
+            entries.insert(1, ("📤 Export report".to_string(), PaletteAction::Export));
+        }
+        for (i, d) in self.drives.iter().enumerate() {
+            let model = d.model.as_deref().map(|m| format!(" — {}", m)).unwrap_or_default();
+            entries.push((format!("💾 Switch to {}{}", d.dev, model), PaletteAction::SelectDrive(i)));
+        }
+
+        // Self-tests only run on live, connected drives; NVMe has no conveyance test
+        let selected = self.drives.get(self.selected).filter(|d| self.snapshot.is_none() && !self.is_disconnected(d));
+        if let Some(d) = selected {
+            let kinds: &[&'static str] = if d.kind == "NVMe" { &["short", "long"] } else { &["short", "long", "conveyance"] };
+            for kind in kinds {
+                entries.push((format!("🧪 Start {} self-test on {}", kind, d.dev), PaletteAction::SelfTest(d.dev.clone(), kind)));
+            }
+        }
+        entries
+    }
+
+    /// Opens and closes the command palette on Ctrl+K and renders it while open.
+    /// Typing filters the entries fuzzily; ↑/↓ move the highlight, Enter or a click runs an entry, Esc closes.
+    ///
+    /// # Arguments
+    /// * `ctx` - The egui context to render the palette into
+    ///
+    /// # Returns
+    /// The chosen entry's action, on the frame it is chosen.
+    fn command_palette(&mut self, ctx: &egui::Context) -> Option<PaletteAction> {
+        let shortcut = egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::K);
+        if ctx.input_mut(|i| i.consume_shortcut(&shortcut)) {
+            self.palette = match self.palette {
+                Some(_) => None,
+                None => Some(PaletteState::default()),
+            };
+        }
+        let entries = self.palette_entries();
+        let state = self.palette.as_mut()?;
+
+        // Taken before the text field sees them: it would otherwise drop focus on Enter and Esc
+        let (up, down, enter, escape) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+            )
+        });
+        if escape {
+            self.palette = None;
+            return None;
+        }
+
+        let mut order = rank(&state.query, entries.iter().map(|(label, _)| label.as_str()));
+        if down {
+            state.highlighted = (state.highlighted + 1).min(order.len().saturating_sub(1));
+        }
+        if up {
+            state.highlighted = state.highlighted.saturating_sub(1);
+        }
+        let mut chosen = None;
+        egui::Window::new("Command palette")
+            .title_bar(false)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 80.0])
+            .fixed_size([440.0, 0.0])
+            .show(ctx, |ui| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut state.query)
+                        .hint_text("Type a command or drive…")
+                        .desired_width(f32::INFINITY),
+                );
+                response.request_focus();
+                if response.changed() {
+                    state.highlighted = 0;
+                    order = rank(&state.query, entries.iter().map(|(label, _)| label.as_str()));
+                }
+                ui.separator();
+                egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    for (row, &i) in order.iter().enumerate() {
+                        let label = ui.selectable_label(row == state.highlighted, &entries[i].0);
+                        if row == state.highlighted && (up || down) {
+                            label.scroll_to_me(None);
+                        }
+                        if label.clicked() {
+                            chosen = Some(i);
+                        }
+                    }
+                    if order.is_empty() {
+                        ui.label(egui::RichText::new("No matching command").size(11.0).color(egui::Color32::from_gray(120)));
+                    }
+                });
+            });
+
+        if enter {
+            chosen = order.get(state.highlighted).copied();
+        }
+        if chosen.is_some() {
+            self.palette = None;
+        }
+        chosen.map(|i| entries[i].1.clone())
+    }
+
+    /// Runs a command palette entry.
+    ///
+    /// # Arguments
+    /// * `action` - The chosen entry's action
+    fn run_palette_action(&mut self, action: PaletteAction) {
+        match action {
+            PaletteAction::Refresh => self.manual_refresh(),
+            PaletteAction::Export => self.export_report(),
+            PaletteAction::OpenReport => self.open_report_dialog = true,
+            PaletteAction::TogglePause => {
+                self.paused = !self.paused;
+                self.last_refresh = Instant::now();
+            }
+            PaletteAction::TogglePrivacy => self.privacy_mode = !self.privacy_mode,
+            PaletteAction::About => self.about = Some(Diagnostics::collect()),
+            PaletteAction::Show(view) => self.view = view,
+            PaletteAction::SelectDrive(i) => {
+                self.selected = i;
+                self.view = View::Drive;
+            }
+            PaletteAction::SelfTest(dev, kind) => {
+                self.action_result = Some(DriveAction::StartSelfTest(dev, kind.to_string()).run());
+                self.manual_refresh();
+            }
+        }
+    }
+
+    /// Renders the About window: app version, smartctl, kernel, root status and how many of the
+    /// detected drives were probed, with a button to copy it all for a bug report.
+    ///
+    /// # Arguments
+    /// * `ctx` - The egui context to render the window into
+    fn about_window(&mut self, ctx: &egui::Context) {
+        let Some(diagnostics) = &self.about else {
+            return;
+        };
+        let probed: Vec<&str> = self.drives.iter().filter(|d| !d.cached).map(|d| d.dev.as_str()).collect();
+        let probed = self.snapshot.is_none().then_some(probed.as_slice());
+
+        let mut open = true;
+        egui::Window::new("About SSD Health Checker")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(egui::RichText::new(format!("SSD Health Checker {}", diagnostics.version)).size(14.0).strong());
+                ui.add_space(6.0);
+                egui::Grid::new("about_grid").num_columns(2).spacing([15.0, 4.0]).show(ui, |ui| {
+                    for (label, value) in diagnostics.rows(probed) {
+                        ui.label(egui::RichText::new(label).size(11.0).color(egui::Color32::from_gray(120)));
+                        ui.add(egui::Label::new(egui::RichText::new(value).size(11.0).monospace()).selectable(true));
+                        ui.end_row();
+                    }
+                });
+                ui.add_space(8.0);
+                if ui.button("📋 Copy").on_hover_text("Copy these details for a bug report").clicked() {
+                    ui.ctx().copy_text(diagnostics.summary(probed));
+                }
+            });
+        if !open {
+            self.about = None;
+        }
+    }
+
+    /// Renders the "What's using space?" window for the running or finished usage analysis.
+    /// Closing the window drops the analysis, which stops its worker thread.
+    ///
+    /// # Arguments
+    /// * `ctx` - The egui context to render the window into
+    fn usage_window(&mut self, ctx: &egui::Context) {
+        let Some(analysis) = &mut self.usage_analysis else {
+            return;
+        };
+        let done = analysis.poll();
+        if !done {
+            // Keep the file counter moving while the walk runs
+            ctx.request_repaint_after(Duration::from_millis(200));
+        }
+
+        let mut open = true;
+        egui::Window::new(format!("What's using space? — {}", analysis.root.display()))
+            .open(&mut open)
+            .collapsible(false)
+            .default_size([560.0, 440.0])
+            .show(ctx, |ui| {
+                let scanned = analysis.files_scanned.load(std::sync::atomic::Ordering::Relaxed);
+                let Some(report) = &analysis.report else {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(egui::RichText::new(format!("Scanning… {} files", scanned)).size(11.0));
+                    });
+                    return;
+                };
+
+                let gb = |bytes: u64| bytes as f64 / 1_000_000_000.0;
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{:.2} GB in {} files{}",
+                        gb(report.total_bytes),
+                        scanned,
+                        if report.errors > 0 { format!(" ({} entries unreadable)", report.errors) } else { String::new() }
+                    ))
+                    .size(11.0)
+                    .color(egui::Color32::from_gray(120)),
+                );
+                ui.add_space(6.0);
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    // Direct children of the mount point, with a bar relative to the total
+                    ui.label(egui::RichText::new("Largest folders and files").size(13.0).strong());
+                    egui::Grid::new("usage_entries").num_columns(3).spacing([12.0, 4.0]).show(ui, |ui| {
+                        for entry in report.entries.iter().take(30) {
+                            let name = entry.path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+                            let icon = if entry.is_dir { "📁" } else { "📄" };
+                            ui.label(egui::RichText::new(format!("{} {}", icon, name)).size(11.0));
+                            let fraction = if report.total_bytes > 0 { entry.bytes as f32 / report.total_bytes as f32 } else { 0.0 };
+                            ui.add(egui::ProgressBar::new(fraction).desired_width(200.0));
+                            ui.label(egui::RichText::new(format!("{:.2} GB", gb(entry.bytes))).size(11.0));
+                            ui.end_row();
+                        }
+                    });
+
+                    ui.add_space(10.0);
+
+                    // Individual files anywhere below the mount point
+                    ui.label(egui::RichText::new("Largest individual files").size(13.0).strong());
+                    egui::Grid::new("usage_files").num_columns(2).spacing([12.0, 4.0]).show(ui, |ui| {
+                        for (path, bytes) in &report.largest_files {
+                            ui.add(egui::Label::new(egui::RichText::new(path.display().to_string()).size(11.0).monospace()).selectable(true));
+                            ui.label(egui::RichText::new(format!("{:.2} GB", gb(*bytes))).size(11.0));
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+
+        if !open {
+            self.usage_analysis = None;
+        }
+    }
+
+    /// Builds a report of every drive that is not hidden.
+    /// Serial numbers, WWNs and the host name are masked in privacy mode.
+    ///
+    /// # Arguments
+    /// * `now` - Unix time the report is taken at, in seconds
+    fn current_report(&self, now: u64) -> Report {
+        let host = System::host_name().unwrap_or_else(|| "localhost".to_string());
+        Report {
+            host: if self.privacy_mode { "host".to_string() } else { host },
+            generated_at: now,
+            drives: self
+                .drives
+                .iter()
+                .filter(|d| !self.settings.is_hidden(d))
+                .map(|d| if self.privacy_mode { d.masked() } else { (**d).clone() })
+                .collect(),
+        }
+    }
+
+    /// Exports every drive to a JSON report in the home directory.
+    fn export_report(&mut self) {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        let report = self.current_report(now);
+        let path = default_report_path(&report.host, now);
+        self.action_result = Some(report.save(&path).map(|_| format!("Report saved to {}", path.display())));
+    }
 
-                // Reset selection if no drives found
-                if self.drives.is_empty() {
-                    self.selected = 0;
+    /// Writes a scheduled export if one is due (see `ExportSchedule`); failures are shown and retried after
+    /// `SCHEDULED_EXPORT_RETRY_SECS`.
+    ///
+    /// # Arguments
+    /// * `now` - Unix time of the scan that was just applied, in seconds
+    /// * `force` - Export even if none is due (the "Export now" button)
+    fn run_scheduled_export(&mut self, now: u64, force: bool) {
+        let Some(schedule) = &self.settings.export else {
+            return;
+        };
+        let retry_wait = self.scheduled_export_failed.is_some_and(|failed| now < failed + SCHEDULED_EXPORT_RETRY_SECS);
+        if !force && (retry_wait || !schedule.is_due(schedule.last_export(), now)) {
+            return;
+        }
+        match schedule.export(&self.current_report(now)) {
+            Ok(files) => {
+                self.scheduled_export_failed = None;
+                let names: Vec<String> = files.iter().map(|f| f.display().to_string()).collect();
+                self.session_log.record(now, EventKind::Scan, format!("Scheduled export saved to {}", names.join(", ")));
+                if force {
+                    self.action_result = Some(Ok(format!("Report saved to {}", names.join(", "))));
                 }
             }
             Err(e) => {
-                // Clear drives and store error for display
-                self.drives.clear();
-                self.last_error = Some(e);
+                self.scheduled_export_failed = Some(now);
+                self.action_result = Some(Err(format!("Scheduled export failed: {}", e)));
             }
         }
     }
 
-    /// Updates CPU and GPU temperature readings using external commands.
-    /// Parses output from 'sensors' for CPU temperature and 'nvidia-smi' for GPU.
-    /// Failures are silently ignored, leaving temperature fields as None.
-    fn update_system_temps(&mut self) {
-        // Parse CPU temperature from lm-sensors output
-        if let Ok(output) = Command::new("sensors").output() {
-            if let Ok(text) = String::from_utf8(output.stdout) {
-                // Regex to match temperature values like +47.0°C or +47°C
-                let temp_re = Regex::new(r"\+([0-9]+(?:\.[0-9]+)?)°C").unwrap();
-                let mut temps: Vec<f32> = Vec::new();
-
-                // Look for common CPU temperature labels
-                for line in text.lines() {
-                    let lower = line.to_lowercase();
-                    // Filter for lines containing CPU-related keywords
-                    if lower.contains("tctl")
-                        || lower.contains("tdie")
-                        || lower.contains("package")
-                        || lower.contains("core")
-                    {
-                        if let Some(caps) = temp_re.captures(line) {
-                            if let Some(m) = caps.get(1) {
-                                if let Ok(v) = m.as_str().parse::<f32>() {
-                                    temps.push(v);
-                                }
-                            }
-                        }
-                    }
-                }
-
-                // Compute average of all found temperature values
-                if !temps.is_empty() {
-                    self.cpu_temp = Some(temps.iter().sum::<f32>() / temps.len() as f32);
-                }
+    /// Opens an exported report and shows its drives instead of this machine's.
+    /// Automatic refresh and drive actions stay off until the snapshot is closed.
+    ///
+    /// # Arguments
+    /// * `path` - JSON report file
+    fn open_report(&mut self, path: &Path) {
+        match Report::load(path) {
+            Ok(report) => {
+                self.drives = report.drives.into_iter().map(Arc::new).collect();
+                self.selected = 0;
+                self.view = View::Drive;
+                self.usage_analysis = None;
+                self.snapshot = Some(Snapshot {
+                    host: report.host,
+                    generated_at: report.generated_at,
+                    path: path.to_path_buf(),
+                });
+                self.action_result = None;
             }
+            Err(e) => self.action_result = Some(Err(e)),
         }
+    }
 
-        // Parse GPU temperature from nvidia-smi
-        if let Ok(output) = Command::new("nvidia-smi")
-            .args(&["--query-gpu=temperature.gpu", "--format=csv,noheader,nounits"])
-            .output()
-        {
-            if let Ok(text) = String::from_utf8(output.stdout) {
-                if let Ok(temp) = text.trim().parse::<f32>() {
-                    self.gpu_temp = Some(temp);
-                }
-            }
+    /// Leaves snapshot mode and rescans this machine's drives.
+    fn close_snapshot(&mut self) {
+        self.snapshot = None;
+        self.manual_refresh();
+    }
+
+    /// Renders the "Open report" window with a path field.
+    /// Reports can also be dropped onto the window.
+    ///
+    /// # Arguments
+    /// * `ctx` - The egui context to render the window into
+    fn open_report_window(&mut self, ctx: &egui::Context) {
+        if !self.open_report_dialog {
+            return;
+        }
+        let mut open = true;
+        let mut submitted = false;
+        egui::Window::new("Open report")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(egui::RichText::new("Path of a JSON report exported by this program:").size(11.0));
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.report_path_input)
+                        .hint_text("/home/user/ssd-report-host-1700000000.json")
+                        .desired_width(360.0),
+                );
+                submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                ui.horizontal(|ui| {
+                    submitted |= ui.add_enabled(!self.report_path_input.trim().is_empty(), egui::Button::new("Open")).clicked();
+                    ui.label(
+                        egui::RichText::new("Tip: drop a report file onto the window")
+                            .size(11.0)
+                            .color(egui::Color32::from_gray(120)),
+                    );
+                });
+            });
+
+        if submitted {
+            let path = PathBuf::from(self.report_path_input.trim());
+            self.open_report(&path);
+            open = false;
         }
+        self.open_report_dialog = open;
     }
 
     /// Triggers a manual refresh of disk data and system temperatures.
     /// Also updates the last_refresh timestamp to reset the auto-refresh timer.
     fn manual_refresh(&mut self) {
+        // Automatic refreshes reuse the static NVMe probes; a manual one reads everything again
+        if self.scan.is_none() {
+            forget_static_probes(None);
+        }
         self.refresh();
         self.update_system_temps();
         self.last_refresh = Instant::now();
@@ -164,16 +4020,174 @@ impl eframe::App for AppState {
     /// * `ctx` - egui context for rendering
     /// * `_frame` - eframe frame (unused)
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Request repaint every second to keep UI responsive
-        ctx.request_repaint_after(Duration::from_secs(1));
+        // Request repaint every second to keep UI responsive, less often in battery-saver mode
+        ctx.request_repaint_after(if self.power_saving() { POWER_SAVER_REPAINT_INTERVAL } else { Duration::from_secs(1) });
+
+        // The window only exists once the first frame runs, so minimizing has to wait until here
+        if std::mem::take(&mut self.minimize_on_start) {
+            if self.tray.is_some() {
+                hide_window(ctx);
+            } else {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
+            }
+        }
+
+        // Ctrl+K command palette
+        if let Some(action) = self.command_palette(ctx) {
+            self.run_palette_action(action);
+        }
+
+        // Closing the window only hides it to the tray (or minimizes it) when configured, so scans and alerts keep running
+        if ctx.input(|i| i.viewport().close_requested()) && self.settings.minimize_on_close && !self.quitting {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            if self.tray.is_some() {
+                hide_window(ctx);
+            } else {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
+            }
+        }
+
+        // Collect unreadable sectors from a running surface scan and animate its progress bar
+        if let Some(scan) = &mut self.surface_scan {
+            if !scan.poll() {
+                ctx.request_repaint_after(Duration::from_millis(500));
+            }
+        }
+
+        // Store a finished benchmark; failures are reported like a drive action
+        if let Some(run) = &self.benchmark_run {
+            match run.poll() {
+                Some(Ok(result)) => {
+                    if let Some(store) = &self.benchmark_store {
+                        if let Err(e) = store.record(&run.key, &result) {
+                            self.action_result = Some(Err(format!("Cannot save benchmark result: {}", e)));
+                        }
+                    }
+                    self.benchmarks.entry(run.key.clone()).or_default().push(result);
+                    self.benchmark_run = None;
+                }
+                Some(Err(e)) => {
+                    self.action_result = Some(Err(e));
+                    self.benchmark_run = None;
+                }
+                None => ctx.request_repaint_after(Duration::from_millis(500)),
+            }
+        }
+        // Load the selected drive's benchmark history the first time its page is shown
+        if let (Some(store), Some(key)) = (&self.benchmark_store, self.drives.get(self.selected).and_then(|d| drive_key(d))) {
+            self.benchmarks.entry(key.clone()).or_insert_with(|| store.load(&key));
+        }
+
+        // Read the selected NVMe drive's persistent event log the first time its page is shown (live data only)
+        if self.snapshot.is_none() {
+            let pel_dev = self
+                .drives
+                .get(self.selected)
+                .filter(|d| d.nvme_controller.as_ref().is_some_and(|c| c.supports_persistent_event_log()))
+                .map(|d| d.dev.clone());
+            // nvme-cli can take a while on a long log, so it is read in the background
+            if let Some(dev) = pel_dev.filter(|dev| !self.event_logs.contains_key(dev)) {
+                self.event_logs.insert(dev.clone(), None);
+                let (tx, ctx) = (self.event_log_tx.clone(), self.ctx.clone());
+                std::thread::spawn(move || {
+                    let log = probe_persistent_event_log(&dev);
+                    let _ = tx.send((dev, log));
+                    ctx.request_repaint();
+                });
+            }
+        }
+        while let Ok((dev, log)) = self.event_log_rx.try_recv() {
+            self.event_logs.insert(dev, Some(log));
+        }
+
+        // A requested screenshot arrived: cut the chart out of it and save it
+        let screenshot = ctx.input(|i| {
+            i.events.iter().find_map(|e| match e {
+                egui::Event::Screenshot { image, .. } => Some(image.clone()),
+                _ => None,
+            })
+        });
+        if let (Some(screenshot), Some((rect, name))) = (screenshot, self.pending_chart.take()) {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+            let path = default_chart_path(&name, now);
+            self.action_result = Some(
+                crop_chart(&screenshot, rect, ctx.pixels_per_point())
+                    .ok_or_else(|| "The chart is not on screen; scroll to it and try again".to_string())
+                    .and_then(|chart| save_png(&chart, &path))
+                    .map(|_| format!("Chart saved to {}", path.display())),
+            );
+        }
+
+        // Outcomes of background notifications and the alarm
+        while let Ok(result) = self.task_rx.try_recv() {
+            self.action_result = Some(result);
+        }
+
+        // Failed drive actions go to the session log once each
+        if self.action_result != self.logged_result {
+            if let Some(Err(e)) = &self.action_result {
+                let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+                self.session_log.record(now, EventKind::Error, e.clone());
+            }
+            self.logged_result = self.action_result.clone();
+        }
+
+        // A report file dropped onto the window opens as a snapshot
+        if let Some(path) = ctx.input(|i| i.raw.dropped_files.iter().find_map(|f| f.path.clone())) {
+            self.open_report(&path);
+        }
+
+        // Remote hosts are only fetched while the fleet view is open
+        if self.view == View::Fleet {
+            if self.last_fleet_refresh.is_none_or(|t| t.elapsed() >= FLEET_REFRESH_INTERVAL) {
+                self.fleet.refresh();
+                self.last_fleet_refresh = Some(Instant::now());
+            }
+            if self.fleet.poll() {
+                ctx.request_repaint_after(Duration::from_millis(200));
+            }
+        }
+
+        // Progress and result of the background scan
+        let mut scanned = None;
+        if let Some(scan) = &mut self.scan {
+            loop {
+                match scan.rx.try_recv() {
+                    Ok(ScanMessage::Probing(dev)) => scan.probing = Some(dev),
+                    Ok(ScanMessage::Done(result)) => scanned = Some(result),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        scanned.get_or_insert(ScanOutcome::All(
+                            Err("The drive scan stopped unexpectedly".to_string()),
+                            HashMap::new(),
+                        ));
+                        break;
+                    }
+                }
+            }
+        }
+        if let Some(outcome) = scanned {
+            self.scan = None;
+            // A single-drive rescan does not postpone the next full refresh
+            if matches!(outcome, ScanOutcome::All(..)) {
+                self.last_refresh = Instant::now();
+            }
+            // A report opened meanwhile stays on screen
+            if self.snapshot.is_none() {
+                self.apply_scan(outcome);
+            }
+        }
 
         // Check if it's time for automatic refresh
-        if self.last_refresh.elapsed() >= self.refresh_interval {
+        if self.scan.is_none() && !self.paused && self.last_refresh.elapsed() >= self.effective_refresh_interval() {
             self.refresh();
             self.update_system_temps();
             self.last_refresh = Instant::now();
         }
 
+        // Drive whose rescan button was clicked, re-probed after rendering
+        let mut rescan: Option<String> = None;
+
         // LEFT SIDEBAR: Drive list with modern design similar to reference
         egui::SidePanel::left("drive_panel")
             .resizable(false)
@@ -194,15 +4208,110 @@ impl eframe::App for AppState {
                         if ui.add(refresh_btn).on_hover_text("Refresh").clicked() {
                             self.manual_refresh();
                         }
+
+                        // Pause/play suspends the automatic refresh without disabling the button above
+                        let (icon, hint) = if self.paused {
+                            ("▶", "Resume auto refresh")
+                        } else {
+                            ("⏸", "Pause auto refresh")
+                        };
+                        let pause_btn = egui::Button::new(egui::RichText::new(icon).size(14.0)).frame(false);
+                        if ui.add(pause_btn).on_hover_text(hint).clicked() {
+                            self.paused = !self.paused;
+                            // Resuming waits a full interval instead of refreshing immediately
+                            self.last_refresh = Instant::now();
+                        }
+
+                        // With minimize-on-close the window button no longer exits, so offer it here
+                        if self.settings.minimize_on_close {
+                            let quit_btn = egui::Button::new(egui::RichText::new("⏻").size(14.0)).frame(false);
+                            if ui.add(quit_btn).on_hover_text("Quit and stop monitoring").clicked() {
+                                self.quitting = true;
+                                ui.ctx().send_viewport_cmd(egui::ViewportCommand::Close);
+                            }
+                        }
+
+                        // Version and environment details for bug reports
+                        let about_btn = egui::Button::new(egui::RichText::new("ℹ").size(14.0)).frame(false);
+                        if ui.add(about_btn).on_hover_text("About and diagnostics").clicked() {
+                            self.about = Some(Diagnostics::collect());
+                        }
+
+                        // Privacy mode masks serial numbers and WWNs for screenshots
+                        ui.toggle_value(&mut self.privacy_mode, egui::RichText::new("🕶").size(14.0))
+                            .on_hover_text("Privacy mode: mask serial numbers and WWNs");
                     });
                 });
 
+                ui.add_space(8.0);
+
+                // Page tabs: capacity overview, drive details, system vitals or the fleet of remote hosts
+                ui.horizontal_wrapped(|ui| {
+                    ui.selectable_value(&mut self.view, View::Overview, "📊 Overview");
+                    ui.selectable_value(&mut self.view, View::Drive, "💾 Drives");
+                    ui.selectable_value(&mut self.view, View::System, "🖥 System");
+                    ui.selectable_value(&mut self.view, View::Fleet, "🌐 Fleet");
+                    // Unacknowledged alerts are counted on the tab
+                    let open = self.alert_log.unacknowledged();
+                    let alerts_label = if open > 0 { format!("🔔 Alerts ({})", open) } else { "🔔 Alerts".to_string() };
+                    ui.selectable_value(&mut self.view, View::Alerts, alerts_label);
+                    ui.selectable_value(&mut self.view, View::Log, "📜 Log");
+                });
+
+                // Report export and the snapshot viewer
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!self.drives.is_empty(), egui::Button::new("📤 Export"))
+                        .on_hover_text("Save every drive's data to a JSON report in your home directory")
+                        .clicked()
+                    {
+                        self.export_report();
+                    }
+                    if ui.button("📂 Open").on_hover_text("View a report exported on another machine").clicked() {
+                        self.open_report_dialog = true;
+                    }
+                });
+
                 ui.add_space(8.0);
                 ui.separator();
                 ui.add_space(8.0);
 
-                // Render each drive as a selectable card
-                for (i, d) in self.drives.iter().enumerate() {
+                // Render each drive as a selectable card, grouped by bus under collapsible headers;
+                // drives the user hid go last, under a "Hidden" header that starts collapsed
+                let group_of = |d: &DiskInfo| if self.settings.is_hidden(d) { "Hidden" } else { d.bus_group() };
+                let mut order: Vec<usize> = (0..self.drives.len()).collect();
+                order.sort_by_key(|&i| {
+                    DRIVE_GROUPS.iter().position(|g| *g == group_of(&self.drives[i])).unwrap_or(DRIVE_GROUPS.len())
+                });
+                let mut current_group = None;
+                let mut group_open = true;
+                let mut hide_toggle: Option<(String, bool)> = None;
+                for (i, d) in order.into_iter().map(|i| (i, &self.drives[i])) {
+                    let group = group_of(d);
+                    if current_group != Some(group) {
+                        current_group = Some(group);
+                        let count = self.drives.iter().filter(|d| group_of(d) == group).count();
+                        let id = ui.make_persistent_id(("drive_group", group));
+                        group_open = ui.data_mut(|data| *data.get_persisted_mut_or_insert_with(id, || group != "Hidden"));
+                        let arrow = if group_open { "▼" } else { "▶" };
+                        let header = ui.add(
+                            egui::Label::new(
+                                egui::RichText::new(format!("{} {} ({})", arrow, group, count))
+                                    .size(11.0)
+                                    .strong()
+                                    .color(egui::Color32::from_gray(120)),
+                            )
+                            .sense(egui::Sense::click()),
+                        );
+                        if header.clicked() {
+                            group_open = !group_open;
+                            ui.data_mut(|data| data.insert_persisted(id, group_open));
+                        }
+                        ui.add_space(4.0);
+                    }
+                    if !group_open {
+                        continue;
+                    }
                     let is_selected = self.selected == i;
 
                     // Change appearance based on selection state
@@ -222,15 +4331,65 @@ impl eframe::App for AppState {
                             .inner_margin(12.0)
                     };
 
+                    // Area of this card's rescan button; clicks inside it re-probe instead of selecting
+                    let mut rescan_rect: Option<egui::Rect> = None;
+                    let can_rescan = self.snapshot.is_none() && !self.is_disconnected(d);
+
                     // Render drive card showing device path, model, health, and temperature
                     let response = frame.show(ui, |ui| {
                         ui.vertical(|ui| {
-                            // Display device path (e.g., /dev/nvme0n1)
-                            ui.label(
-                                egui::RichText::new(&d.dev)
-                                    .strong()
-                                    .size(14.0)
-                            );
+                            // Display device path (e.g., /dev/nvme0n1), with a warning badge
+                            // when the drive has problems worth opening it for
+                            ui.horizontal(|ui| {
+                                ui.label(
+                                    egui::RichText::new(&d.dev)
+                                        .strong()
+                                        .size(14.0)
+                                );
+                                // Mark the drive the running system boots from, and drives served over the network
+                                let network = d.network_transport.as_ref().map(|t| format!("Network-attached ({})", t));
+                                for (shown, badge, hover) in [
+                                    (d.hosts_root, "OS", "Hosts the root filesystem (/)"),
+                                    (d.hosts_esp, "EFI", "Hosts the EFI system partition in use"),
+                                    (network.is_some(), "NET", network.as_deref().unwrap_or_default()),
+                                    (d.removable, "REMOVABLE", "Card reader or flash stick: SMART not available or not reliable"),
+                                ] {
+                                    if shown {
+                                        ui.label(
+                                            egui::RichText::new(badge)
+                                                .size(10.0)
+                                                .strong()
+                                                .color(egui::Color32::from_rgb(59, 130, 246))
+                                        )
+                                        .on_hover_text(hover);
+                                    }
+                                }
+                                let problems = d.problems();
+                                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                    if can_rescan {
+                                        let button = ui
+                                            .add(
+                                                egui::Label::new(
+                                                    egui::RichText::new("🔄")
+                                                        .size(12.0)
+                                                        .color(egui::Color32::from_gray(120))
+                                                )
+                                                .sense(egui::Sense::click())
+                                            )
+                                            .on_hover_text("Re-probe only this drive");
+                                        rescan_rect = Some(button.rect);
+                                    }
+                                    if !problems.is_empty() {
+                                        ui.label(
+                                            egui::RichText::new("⚠")
+                                                .size(14.0)
+                                                .strong()
+                                                .color(egui::Color32::from_rgb(239, 68, 68))
+                                        )
+                                        .on_hover_text(problems.join("\n"));
+                                    }
+                                });
+                            });
                             ui.add_space(2.0);
 
                             // Display truncated model name if available
@@ -247,7 +4406,7 @@ impl eframe::App for AppState {
                             // Health indicator and temperature display
                             ui.horizontal(|ui| {
                                 // Health status with colored dot and percentage
-                                let (color, text) = match d.health_percent {
+                                let (color, text) = match d.health_score(&self.settings.health).map(|h| h.score) {
                                     Some(p) if p > 84 => (egui::Color32::from_rgb(0, 160, 0), format!("{}%", p)),
                                     Some(p) if p >= 50 => (egui::Color32::from_rgb(220, 150, 0), format!("{}%", p)),
                                     Some(p) => (egui::Color32::from_rgb(200, 30, 30), format!("{}%", p)),
@@ -257,62 +4416,380 @@ impl eframe::App for AppState {
                                 ui.label(egui::RichText::new("●").color(color).size(12.0));
                                 ui.label(egui::RichText::new(text).size(11.0));
 
-                                // Temperature display on the right side
+                                // Temperature display on the right side, red at the drive's alarm temperature
                                 if let Some(temp) = d.temp_c {
+                                    let hot = temp >= self.hot_temp(d);
+                                    let color = if hot { egui::Color32::from_rgb(239, 68, 68) } else { egui::Color32::from_gray(100) };
                                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                                        ui.label(
-                                            egui::RichText::new(format!("{}°C", temp))
-                                                .size(11.0)
-                                                .color(egui::Color32::from_gray(100))
-                                        );
+                                        let text = if hot { format!("🌡 {}°C", temp) } else { format!("{}°C", temp) };
+                                        ui.label(egui::RichText::new(text).size(11.0).color(color))
+                                            .on_hover_text(format!("Alarm at {}°C", self.hot_temp(d)));
                                     });
                                 }
                             });
+
+                            // Warranty about to run out
+                            let warranty_left = drive_key(d)
+                                .and_then(|k| self.settings.drives.get(&k))
+                                .and_then(|s| s.warranty_end())
+                                .map(|end| end - today());
+                            if let Some(left) = warranty_left.filter(|l| (0..=WARRANTY_WARNING_DAYS).contains(l)) {
+                                ui.add_space(2.0);
+                                ui.label(
+                                    egui::RichText::new(format!("🛡 Warranty ends in {} days", left))
+                                        .size(10.0)
+                                        .color(egui::Color32::from_rgb(146, 64, 14))
+                                );
+                            }
+
+                            // Badge for drives that were unplugged, or whose SMART data was not re-read to avoid waking them
+                            if self.is_disconnected(d) {
+                                ui.add_space(2.0);
+                                ui.label(
+                                    egui::RichText::new("⏏ Disconnected — last known data")
+                                        .size(10.0)
+                                        .color(egui::Color32::from_rgb(146, 64, 14))
+                                );
+                            } else if d.cached {
+                                ui.add_space(2.0);
+                                ui.label(
+                                    egui::RichText::new("💤 Sleeping — cached data")
+                                        .size(10.0)
+                                        .color(egui::Color32::from_gray(120))
+                                );
+                            }
+                        });
+                    });
+
+                    // Right-click to hide the drive (or bring it back); the list is kept by serial number
+                    let card = response.response.interact(egui::Sense::click());
+                    if let Some(serial) = &d.serial {
+                        let hidden = group == "Hidden";
+                        card.context_menu(|ui| {
+                            let label = if hidden { "👁 Unhide this drive" } else { "🙈 Hide this drive" };
+                            if ui.button(label).on_hover_text("Hidden drives are left out of alerts and exports").clicked() {
+                                hide_toggle = Some((serial.clone(), !hidden));
+                                ui.close_menu();
+                            }
+                        });
+                    }
+
+                    // Handle click to select this drive, or to rescan it when the click landed on the button
+                    if card.clicked() {
+                        let pointer = ui.input(|input| input.pointer.interact_pos());
+                        if rescan_rect.zip(pointer).is_some_and(|(rect, pos)| rect.contains(pos)) {
+                            rescan = Some(d.dev.clone());
+                        } else {
+                            self.selected = i;
+                            self.view = View::Drive;
+                        }
+                    }
+
+                    ui.add_space(8.0);
+                }
+                if let Some((serial, hidden)) = hide_toggle {
+                    self.settings.set_hidden(&serial, hidden);
+                    if let Err(e) = self.settings.save() {
+                        self.action_result = Some(Err(e));
+                    }
+                }
+
+                // Display error message if present
+                if let Some(err) = &self.last_error {
+                    ui.add_space(10.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+                    ui.colored_label(egui::Color32::RED, err);
+                }
+
+                // Display the outcome of the last drive action
+                if let Some(result) = &self.action_result {
+                    ui.add_space(10.0);
+                    match result {
+                        Ok(msg) => ui.colored_label(egui::Color32::from_rgb(0, 160, 0), msg),
+                        Err(err) => ui.colored_label(egui::Color32::RED, err),
+                    };
+                }
+
+                // Footer: how fresh the displayed data is and when the next automatic refresh happens
+                if let (None, Some(updated)) = (&self.snapshot, self.last_updated) {
+                    ui.with_layout(egui::Layout::bottom_up(egui::Align::Min), |ui| {
+                        ui.add_space(8.0);
+                        let next = if self.paused {
+                            "auto refresh paused".to_string()
+                        } else {
+                            let left = self.effective_refresh_interval().saturating_sub(self.last_refresh.elapsed());
+                            let saver = if self.power_saving() { " (🔋 battery saver)" } else { "" };
+                            format!("next refresh in {}s{}", left.as_secs(), saver)
+                        };
+                        ui.label(
+                            egui::RichText::new(format!("Updated {}s ago — {}", updated.elapsed().as_secs(), next))
+                                .size(10.0)
+                                .color(egui::Color32::from_gray(120))
+                        );
+                    });
+                }
+            });
+
+        // Drive action requested from the central panel, executed after rendering
+        let mut requested_action: Option<DriveAction> = None;
+        // CPU sensor and GPU list expand/collapse, applied after rendering for the same reason
+        let mut toggle_cpu_list = false;
+        let mut toggle_gpu_list = false;
+        // Mount point whose disk usage analysis the user requested
+        let mut requested_usage_scan: Option<String> = None;
+        // Surface scan start (true) or stop (false) for the selected drive
+        let mut surface_request: Option<bool> = None;
+        // Benchmark of the selected drive requested
+        let mut benchmark_request = false;
+        // Persistent event log re-read requested
+        let mut reload_event_log = false;
+        // Partition table column header clicked
+        let mut partition_sort_click: Option<PartitionSort> = None;
+        // Column shown or hidden in a table's column picker
+        let mut column_toggle: Option<ColumnToggle> = None;
+        // Set when the user leaves the report snapshot
+        let mut close_snapshot = false;
+        // Edited settings of one drive (drive key, new settings), saved after rendering
+        let mut settings_edit: Option<(String, DriveSettings)> = None;
+        // Chart to save as PNG (screen rectangle, name)
+        let mut save_chart: Option<(egui::Rect, String)> = None;
+
+        // CENTRAL PANEL: Main content area with drive details
+        egui::CentralPanel::default()
+            .frame(egui::Frame::none().fill(egui::Color32::from_rgb(245, 247, 250)))
+            .show(ctx, |ui| {
+                // System vitals page does not depend on any drive being present
+                if self.view == View::System {
+                    egui::ScrollArea::vertical().show(ui, |ui| self.system_dashboard(ui, &mut toggle_cpu_list, &mut toggle_gpu_list));
+                    return;
+                }
+                if self.view == View::Fleet {
+                    egui::ScrollArea::both().show(ui, |ui| self.fleet_dashboard(ui));
+                    return;
+                }
+                if self.view == View::Overview && !self.drives.is_empty() {
+                    egui::ScrollArea::vertical().show(ui, |ui| self.overview_page(ui));
+                    return;
+                }
+                if self.view == View::Alerts {
+                    egui::ScrollArea::vertical().show(ui, |ui| self.alerts_page(ui));
+                    return;
+                }
+                if self.view == View::Log {
+                    self.log_page(ui);
+                    return;
+                }
+
+                // Placeholder while the first scan probes drives
+                if let Some(scan) = self.scan.as_ref().filter(|_| self.drives.is_empty()) {
+                    ui.centered_and_justified(|ui| {
+                        ui.vertical_centered(|ui| {
+                            ui.spinner();
+                            ui.add_space(8.0);
+                            ui.heading("Scanning drives");
+                            ui.add_space(4.0);
+                            let text = match &scan.probing {
+                                Some(dev) => format!("Probing {}…", dev),
+                                None => "Looking for drives…".to_string(),
+                            };
+                            ui.label(egui::RichText::new(text).color(egui::Color32::from_gray(120)));
+                        });
+                    });
+                    return;
+                }
+
+                // Show helpful message if no drives detected
+                if self.drives.is_empty() {
+                    ui.centered_and_justified(|ui| {
+                        ui.vertical_centered(|ui| {
+                            ui.heading("No drives detected");
+                            ui.add_space(8.0);
+                            if in_flatpak() {
+                                ui.label("Running inside Flatpak: smartctl must be installed on the host, and the app needs");
+                                ui.label("--device=all and --talk-name=org.freedesktop.Flatpak (e.g., via flatpak override)");
+                            } else {
+                                ui.label("Make sure you have smartctl installed and run with sudo");
+                            }
+                            if let Some(err) = &self.last_error {
+                                ui.add_space(6.0);
+                                ui.label(format!("Last error: {}", err));
+                            }
                         });
                     });
+                    return;
+                }
+
+                // Get currently selected drive information
+                let di = self.drives[self.selected].as_ref();
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    ui.add_space(20.0);
+
+                    // Banner while browsing an exported report instead of live data
+                    if let Some(snapshot) = &self.snapshot {
+                        ui.horizontal(|ui| {
+                            ui.add_space(20.0);
+                            egui::Frame::none()
+                                .fill(egui::Color32::from_rgb(219, 234, 254))
+                                .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(70, 130, 220)))
+                                .rounding(10.0)
+                                .inner_margin(12.0)
+                                .show(ui, |ui| {
+                                    ui.set_width(ui.available_width() - 40.0);
+                                    ui.horizontal(|ui| {
+                                        ui.vertical(|ui| {
+                                            ui.label(
+                                                egui::RichText::new(format!(
+                                                    "📄 Viewing snapshot of {} taken {}",
+                                                    snapshot.host,
+                                                    format_timestamp(snapshot.generated_at)
+                                                ))
+                                                .size(14.0)
+                                                .strong()
+                                                .color(egui::Color32::from_rgb(30, 64, 175)),
+                                            );
+                                            ui.label(
+                                                egui::RichText::new(format!(
+                                                    "{} — read-only, drive actions are disabled",
+                                                    snapshot.path.display()
+                                                ))
+                                                .size(11.0)
+                                                .color(egui::Color32::from_gray(120)),
+                                            );
+                                        });
+                                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                            if ui.button("Back to live data").clicked() {
+                                                close_snapshot = true;
+                                            }
+                                        });
+                                    });
+                                });
+                            ui.add_space(20.0);
+                        });
+                        ui.add_space(12.0);
+                    }
+
+                    // Warning banner while a sanitize/format operation is running
+                    if let Some(op) = &di.operation {
+                        // Progress summary with optional time estimate
+                        let eta = op
+                            .eta_secs
+                            .map(|s| format!(" — about {} min remaining", s.div_ceil(60)))
+                            .unwrap_or_default();
+                        warning_banner_with_progress(
+                            ui,
+                            &format!("⚠ {} in progress: {:.0}% complete{}", op.kind, op.percent, eta),
+                            op.percent / 100.0,
+                            "Do not power off, reboot, or disconnect this drive until the operation finishes.",
+                        );
+                        ui.add_space(15.0);
+                    }
+
+                    // Unplugged drive kept with its last known data
+                    if let Some(since) = self.disconnected.get(&Self::drive_identity(di)).filter(|_| di.cached) {
+                        warning_banner(
+                            ui,
+                            "⏏ Drive disconnected",
+                            &format!(
+                                "{} disappeared {} min ago. The values below are its last reading; they can still be \
+                                 exported. The drive leaves the list {} minutes after disconnecting unless it comes back.",
+                                di.dev,
+                                since.elapsed().as_secs() / 60,
+                                DISCONNECTED_GRACE.as_secs() / 60
+                            ),
+                        );
+                        ui.add_space(15.0);
+                    }
+
+                    // NVMe-oF and iSCSI drives: health belongs to the remote target, so its absence is expected
+                    if let Some(transport) = &di.network_transport {
+                        warning_banner(
+                            ui,
+                            &format!("🌐 Network-attached drive ({})", transport),
+                            "This drive is served over the network by a remote target. Its health, temperature and wear \
+                             belong to the storage on that target and are not read here; capacity and partitions are shown \
+                             as this machine sees them.",
+                        );
+                        ui.add_space(15.0);
+                    }
+
+                    // Card readers and flash sticks: no health data is the normal case, not a fault
+                    if di.removable {
+                        warning_banner(
+                            ui,
+                            "💾 Removable media: SMART not reliable",
+                            "This looks like a card reader or flash stick. SD cards, USB sticks and most card readers do not \
+                             report SMART, and what some of them report is not meaningful, so missing health data is expected. \
+                             Capacity and partitions are shown as usual; keep backups of anything stored only on such media.",
+                        );
+                        ui.add_space(15.0);
+                    }
+
+                    // SMART switched off: every SMART-derived field below stays empty until it is enabled
+                    if di.smart_enabled == Some(false) {
+                        let clicked = warning_banner_with_action(
+                            ui,
+                            "⚠ SMART is disabled on this drive",
+                            "The drive has SMART but it is switched off, so health, temperature and attributes cannot be read. \
+                             Enabling it (smartctl -s on) is safe and persists across power cycles on most drives.",
+                            "Enable SMART",
+                        );
+                        if clicked {
+                            requested_action = Some(DriveAction::EnableSmart(di.dev.clone()));
+                        }
+                        ui.add_space(15.0);
+                    }
 
-                    // Handle click to select this drive
-                    if response.response.interact(egui::Sense::click()).clicked() {
-                        self.selected = i;
+                    // Advisory banners for known problem models/firmware
+                    for advisory in &di.advisories {
+                        warning_banner(ui, &format!("⚠ {}", advisory.title), advisory.detail);
+                        ui.add_space(15.0);
+                    }
+
+                    // Warning banner when HPA/DCO hides part of the drive's capacity
+                    if let Some(limits) = di.capacity_limits.as_ref().filter(|l| l.hidden_bytes() > 0) {
+                        warning_banner(
+                            ui,
+                            "⚠ Hidden capacity detected (HPA/DCO)",
+                            &format!(
+                                "The drive reports {:.1} GB more than the system can see ({} visible vs {} native sectors). \
+                                 This can be left over from an OEM recovery area, or indicate a shrunk or fake-capacity drive.",
+                                limits.hidden_bytes() as f64 / 1_000_000_000.0,
+                                group_digits(limits.visible_sectors),
+                                group_digits(limits.native_sectors.max(limits.dco_sectors.unwrap_or(0))),
+                            ),
+                        );
+                        ui.add_space(15.0);
                     }
 
-                    ui.add_space(8.0);
-                }
-
-                // Display error message if present
-                if let Some(err) = &self.last_error {
-                    ui.add_space(10.0);
-                    ui.separator();
-                    ui.add_space(10.0);
-                    ui.colored_label(egui::Color32::RED, err);
-                }
-            });
+                    // Cabling hint when UDMA CRC errors grew during this session
+                    let crc_start = self
+                        .session_baseline
+                        .get(&Self::drive_identity(di))
+                        .filter(|_| self.snapshot.is_none())
+                        .and_then(|b| b.get("199"))
+                        .copied();
+                    if let (Some(start), Some(now)) = (crc_start, di.udma_crc_errors()) {
+                        if now > start {
+                            warning_banner(
+                                ui,
+                                &format!("⚠ UDMA CRC errors increased ({} → {})", start, now),
+                                "CRC errors are corrupted transfers between the drive and the controller. They usually mean \
+                                 a bad or loose SATA cable or port rather than a dying drive: reseat or replace the cable, \
+                                 or try another port.",
+                            );
+                            ui.add_space(15.0);
+                        }
+                    }
 
-        // CENTRAL PANEL: Main content area with drive details
-        egui::CentralPanel::default()
-            .frame(egui::Frame::none().fill(egui::Color32::from_rgb(245, 247, 250)))
-            .show(ctx, |ui| {
-                // Show helpful message if no drives detected
-                if self.drives.is_empty() {
-                    ui.centered_and_justified(|ui| {
-                        ui.vertical_centered(|ui| {
-                            ui.heading("No drives detected");
-                            ui.add_space(8.0);
-                            ui.label("Make sure you have smartctl installed and run with sudo");
-                            if let Some(err) = &self.last_error {
-                                ui.add_space(6.0);
-                                ui.label(format!("Last error: {}", err));
-                            }
-                        });
+                    // Replacement date from the stored history and the entered endurance rating
+                    let estimate = drive_key(di).and_then(|key| {
+                        let settings = self.settings.drive(&key);
+                        let warranty_years = settings.warranty_years.unwrap_or(DEFAULT_WARRANTY_YEARS) as f64;
+                        let rated_tb = settings.endurance.and_then(|r| r.tbw(di.capacity, warranty_years));
+                        replacement_estimate(self.history.get(&key)?, rated_tb, today())
                     });
-                    return;
-                }
-
-                // Get currently selected drive information
-                let di = self.drives[self.selected].as_ref();
-
-                egui::ScrollArea::vertical().show(ui, |ui| {
-                    ui.add_space(20.0);
 
                     // Header Card with model info and health badge
                     ui.horizontal(|ui| {
@@ -348,18 +4825,48 @@ impl eframe::App for AppState {
                                                 ui.label(egui::RichText::new(dtype).size(16.0).color(egui::Color32::from_gray(100)));
                                             }
                                         });
+
+                                        // Estimated replacement date; the hover explains how it was derived
+                                        if let Some(estimate) = &estimate {
+                                            let days_left = estimate.day - today();
+                                            let color = if days_left <= 90 {
+                                                egui::Color32::from_rgb(239, 68, 68)
+                                            } else if days_left <= 365 {
+                                                egui::Color32::from_rgb(245, 158, 11)
+                                            } else {
+                                                egui::Color32::from_gray(100)
+                                            };
+                                            ui.label(
+                                                egui::RichText::new(format!(
+                                                    "Estimated replace by {} ({} days) ⓘ",
+                                                    format_date(estimate.day),
+                                                    days_left
+                                                ))
+                                                .size(12.0)
+                                                .color(color),
+                                            )
+                                            .on_hover_text(format!(
+                                                "{}\n\nThe earliest of these is shown. Estimates extrapolate past rates and \
+                                                 assume the workload stays the same.",
+                                                estimate.explanation.join("\n")
+                                            ));
+                                        }
                                     });
 
                                     // Right side: Health badge
                                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                                        let (health_color, health_text) = match di.health_percent {
+                                        let breakdown = di.health_score(&self.settings.health);
+                                        let score = breakdown.as_ref().map(|h| h.score);
+                                        let (health_color, health_text) = match score {
                                             Some(p) if p > 84 => (egui::Color32::from_rgb(16, 185, 129), "Good"),
                                             Some(p) if p >= 50 => (egui::Color32::from_rgb(245, 158, 11), "Warning"),
                                             Some(_) => (egui::Color32::from_rgb(239, 68, 68), "Critical"),
+                                            None if di.is_network_attached() => (egui::Color32::from_gray(150), "Remote"),
+                                            None if di.removable => (egui::Color32::from_gray(150), "Removable"),
                                             None => (egui::Color32::from_gray(150), "Unknown"),
                                         };
 
-                                        egui::Frame::none()
+                                        let badge = egui::Frame::none()
                                             .fill(health_color)
                                             .rounding(8.0)
                                             .inner_margin(egui::vec2(20.0, 10.0))
@@ -371,7 +4878,7 @@ impl eframe::App for AppState {
                                                             .size(14.0)
                                                             .strong()
                                                     );
-                                                    if let Some(p) = di.health_percent {
+                                                    if let Some(p) = score {
                                                         ui.label(
                                                             egui::RichText::new(format!("{}%", p))
                                                                 .color(egui::Color32::WHITE)
@@ -380,7 +4887,13 @@ impl eframe::App for AppState {
                                                         );
                                                     }
                                                 });
-                                            });
+                                            })
+                                            .response
+                                            .interact(egui::Sense::click());
+                                        if let Some(breakdown) = &breakdown {
+                                            let badge = badge.on_hover_text("Click for the breakdown");
+                                            Self::health_score_popup(ui, &badge, &di.dev, breakdown);
+                                        }
                                     });
                                 });
                             });
@@ -390,7 +4903,7 @@ impl eframe::App for AppState {
                     ui.add_space(15.0);
 
                     // Partition table showing mount points and space usage
-                    if !di.partitions.is_empty() {
+                    if !di.partitions.is_empty() || di.unallocated_bytes.is_some() {
                         ui.horizontal(|ui| {
                             ui.add_space(20.0);
                             egui::Frame::none()
@@ -401,48 +4914,118 @@ impl eframe::App for AppState {
                                 .show(ui, |ui| {
                                     ui.set_width(ui.available_width() - 40.0);
 
-                                    ui.label(egui::RichText::new("Partitions").size(14.0).strong());
+                                    ui.horizontal(|ui| {
+                                        ui.label(egui::RichText::new("Partitions").size(14.0).strong());
+                                        Self::column_picker(ui, "partitions", PARTITION_COLUMNS, &self.settings, &mut column_toggle);
+                                    });
                                     ui.add_space(8.0);
 
+                                    let show = |column: &str| {
+                                        let default = PARTITION_COLUMNS.iter().any(|(name, default)| *name == column && *default);
+                                        self.settings.column_visible("partitions", column, default)
+                                    };
+
                                     // Grid layout for partition data
                                     egui::Grid::new("part_grid")
                                         .striped(true)
                                         .spacing([25.0, 10.0])
                                         .show(ui, |ui| {
-                                            // Calculate column widths
-                                            let total_cols = 7.0;
+                                            // Calculate column widths: the visible columns plus the analyzer button
+                                            let total_cols = PARTITION_COLUMNS.iter().filter(|(name, _)| show(name)).count() as f32 + 1.0;
                                             let col_width = ui.available_width() / total_cols;
 
-                                            // Table headers
-                                            for header in &["Partition", "Mount point", "Type", "Total", "Used", "Free", "Free%"] {
+                                            // Table headers; the mount point and space columns sort the table when clicked
+                                            let headers = [
+                                                ("Partition", None),
+                                                ("Label", None),
+                                                ("Mount point", Some(PartitionSort::MountPoint)),
+                                                ("Type", None),
+                                                ("Total", Some(PartitionSort::Size)),
+                                                ("Used", Some(PartitionSort::Used)),
+                                                ("Free", None),
+                                                ("Free%", Some(PartitionSort::FreePercent)),
+                                                ("Inodes", None),
+                                                ("Options", None),
+                                                ("", None),
+                                            ];
+                                            for (header, column) in headers {
+                                                if !header.is_empty() && !show(header) {
+                                                    continue;
+                                                }
                                                 ui.set_min_width(col_width);
-                                                ui.label(egui::RichText::new(*header).strong().size(11.0));
+                                                let Some(column) = column else {
+                                                    ui.label(egui::RichText::new(header).strong().size(11.0));
+                                                    continue;
+                                                };
+                                                let arrow = match self.partition_sort {
+                                                    Some((sorted, descending)) if sorted == column => {
+                                                        if descending { " ▼" } else { " ▲" }
+                                                    }
+                                                    _ => "",
+                                                };
+                                                let clicked = ui
+                                                    .add(
+                                                        egui::Label::new(
+                                                            egui::RichText::new(format!("{}{}", header, arrow)).strong().size(11.0),
+                                                        )
+                                                        .sense(egui::Sense::click()),
+                                                    )
+                                                    .on_hover_text("Sort by this column")
+                                                    .clicked();
+                                                if clicked {
+                                                    partition_sort_click = Some(column);
+                                                }
                                             }
                                             ui.end_row();
 
                                             // Each partition row with usage statistics
-                                            for part in &di.partitions {
+                                            for part in sorted_partitions(&di.partitions, self.partition_sort) {
                                                 // Extract partition name from mount point
                                                 let partition_name =
                                                     part.mount_point.rsplit('/').next().unwrap_or(&part.mount_point).to_string();
 
-                                                ui.set_min_width(col_width);
-                                                ui.label(egui::RichText::new(partition_name).size(11.0));
-
-                                                ui.set_min_width(col_width);
-                                                ui.label(egui::RichText::new(&part.mount_point).size(11.0));
-
-                                                ui.set_min_width(col_width);
-                                                ui.label(egui::RichText::new(&part.fs_type).size(11.0));
-
-                                                ui.set_min_width(col_width);
-                                                ui.label(egui::RichText::new(format!("{:.1} GB", part.total_gb)).size(11.0));
-
-                                                ui.set_min_width(col_width);
-                                                ui.label(egui::RichText::new(format!("{:.1} GB", part.used_gb)).size(11.0));
+                                                if show("Partition") {
+                                                    ui.set_min_width(col_width);
+                                                    let misaligned = part.is_aligned() == Some(false);
+                                                    let (name_text, name_color) = if misaligned {
+                                                        (format!("{} ⚠", partition_name), egui::Color32::from_rgb(245, 158, 11))
+                                                    } else {
+                                                        (partition_name, egui::Color32::from_gray(60))
+                                                    };
+                                                    let mut name_response = ui.add(
+                                                        egui::Label::new(egui::RichText::new(name_text).size(11.0).color(name_color))
+                                                            .sense(egui::Sense::click()),
+                                                    );
+                                                    if misaligned {
+                                                        // Misaligned partitions split writes across physical sectors or erase blocks
+                                                        let start = part.start_bytes.unwrap_or(0);
+                                                        let align = part.alignment_bytes.unwrap_or(0);
+                                                        name_response = name_response.on_hover_text(format!(
+                                                            "{} starts at byte {}, which is not a multiple of {} bytes.\n\
+                                                             Every write then touches two physical sectors (or erase blocks), which \
+                                                             costs performance and, on SSDs, extra write amplification and wear. \
+                                                             Re-partitioning with 1 MiB alignment fixes this.",
+                                                            part.device, start, align
+                                                        ));
+                                                    }
+                                                    Self::partition_details_popup(ui, &name_response, part);
+                                                }
 
-                                                ui.set_min_width(col_width);
-                                                ui.label(egui::RichText::new(format!("{:.1} GB", part.free_gb)).size(11.0));
+                                                // Plain text columns
+                                                let plain = [
+                                                    ("Label", part.fs_label.clone().unwrap_or("--".into())),
+                                                    ("Mount point", part.mount_point.clone()),
+                                                    ("Type", part.fs_type.clone()),
+                                                    ("Total", format!("{:.1} GB", part.total_gb)),
+                                                    ("Used", format!("{:.1} GB", part.used_gb)),
+                                                    ("Free", format!("{:.1} GB", part.free_gb)),
+                                                ];
+                                                for (column, text) in plain {
+                                                    if show(column) {
+                                                        ui.set_min_width(col_width);
+                                                        ui.label(egui::RichText::new(text).size(11.0));
+                                                    }
+                                                }
 
                                                 // Calculate free percentage and color code it
                                                 let free_pct = 100.0 - part.used_percent;
@@ -454,9 +5037,102 @@ impl eframe::App for AppState {
                                                     egui::Color32::from_rgb(34, 197, 94)   // Green: good
                                                 };
 
-                                                ui.set_min_width(col_width);
-                                                ui.colored_label(color, egui::RichText::new(format!("{:.1}%", free_pct)).size(11.0));
+                                                // Ring of the used share next to the free percentage
+                                                if show("Free%") {
+                                                    ui.set_min_width(col_width);
+                                                    ui.horizontal(|ui| {
+                                                        usage_ring(ui, (part.used_percent / 100.0) as f32, 14.0, color).on_hover_text(format!(
+                                                            "{:.1}% used: {:.1} of {:.1} GB",
+                                                            part.used_percent, part.used_gb, part.total_gb
+                                                        ));
+                                                        ui.colored_label(color, egui::RichText::new(format!("{:.1}%", free_pct)).size(11.0));
+                                                    });
+                                                }
+
+                                                // Inodes in use; a filesystem can run out of inodes long before it runs out of space
+                                                if show("Inodes") {
+                                                    ui.set_min_width(col_width);
+                                                    match (part.inodes_used, part.inodes_total) {
+                                                        (Some(used), Some(total)) => {
+                                                            let pct = used as f64 / total as f64 * 100.0;
+                                                            let color = if pct > 90.0 {
+                                                                egui::Color32::from_rgb(239, 68, 68)
+                                                            } else {
+                                                                egui::Color32::from_gray(60)
+                                                            };
+                                                            ui.colored_label(color, egui::RichText::new(format!("{:.0}%", pct)).size(11.0))
+                                                                .on_hover_text(format!("{} of {} inodes used", used, total));
+                                                        }
+                                                        _ => {
+                                                            ui.label(egui::RichText::new("--").size(11.0))
+                                                                .on_hover_text("This filesystem allocates inodes as needed");
+                                                        }
+                                                    }
+                                                }
+
+                                                // Mount options; an unexpected read-only mount usually means the
+                                                // filesystem was remounted after I/O errors
+                                                if show("Options") {
+                                                    let read_only = part.mount_options.iter().any(|o| o == "ro");
+                                                    let shown: Vec<&str> = part
+                                                        .mount_options
+                                                        .iter()
+                                                        .map(String::as_str)
+                                                        .filter(|o| matches!(*o, "ro" | "rw" | "noatime" | "relatime" | "discard" | "nodiscard" | "ssd" | "sync"))
+                                                        .collect();
+                                                    let options_color = if read_only {
+                                                        egui::Color32::from_rgb(239, 68, 68)
+                                                    } else {
+                                                        egui::Color32::from_gray(60)
+                                                    };
+                                                    ui.set_min_width(col_width);
+                                                    ui.colored_label(options_color, egui::RichText::new(shown.join(",")).size(11.0))
+                                                        .on_hover_text(part.mount_options.join(", "));
+                                                }
+
+                                                // Disk usage analyzer for this partition
+                                                if ui
+                                                    .small_button("🔍")
+                                                    .on_hover_text("What's using space? Find the largest folders and files")
+                                                    .clicked()
+                                                {
+                                                    requested_usage_scan = Some(part.mount_point.clone());
+                                                }
+
+                                                ui.end_row();
+                                            }
 
+                                            // Space not covered by any partition, e.g. after cloning to a larger drive
+                                            if let Some(free) = di.unallocated_bytes {
+                                                let gb = free as f64 / 1_000_000_000.0;
+                                                let cells = [
+                                                    ("Label", "--".to_string()),
+                                                    ("Mount point", "--".to_string()),
+                                                    ("Type", "free space".to_string()),
+                                                    ("Total", format!("{:.1} GB", gb)),
+                                                    ("Used", "--".to_string()),
+                                                    ("Free", format!("{:.1} GB", gb)),
+                                                    ("Free%", "--".to_string()),
+                                                    ("Inodes", "--".to_string()),
+                                                    ("Options", String::new()),
+                                                ];
+                                                if show("Partition") {
+                                                    ui.set_min_width(col_width);
+                                                    ui.colored_label(
+                                                        egui::Color32::from_rgb(245, 158, 11),
+                                                        egui::RichText::new("Unallocated").size(11.0),
+                                                    )
+                                                    .on_hover_text(
+                                                        "This space is not part of any partition and cannot be used. \
+                                                         Grow an existing partition or create a new one to use it.",
+                                                    );
+                                                }
+                                                for (column, cell) in cells {
+                                                    if show(column) {
+                                                        ui.set_min_width(col_width);
+                                                        ui.label(egui::RichText::new(cell).size(11.0));
+                                                    }
+                                                }
                                                 ui.end_row();
                                             }
                                         });
@@ -467,6 +5143,10 @@ impl eframe::App for AppState {
                         ui.add_space(12.0);
                     }
 
+                    // Identifiers as shown to the user: masked in privacy mode
+                    let masked = self.privacy_mode.then(|| di.masked());
+                    let ids = masked.as_ref().unwrap_or(di);
+
                     // Drive information card showing serial, firmware, and type
                     ui.horizontal(|ui| {
                         ui.add_space(20.0);
@@ -486,151 +5166,607 @@ impl eframe::App for AppState {
                                     .spacing([15.0, 6.0])
                                     .show(ui, |ui| {
                                         // Headers
-                                        for header in &["Serial no.", "Firmware", "Type"] {
+                                        for header in &["Serial no.", "Firmware", "Type", "Visible / native capacity", "Power state", "Write cache", "Encryption", "Write amplification"] {
                                             ui.label(egui::RichText::new(*header).strong().size(11.0));
                                         }
                                         ui.end_row();
 
                                         // Values
-                                        ui.label(egui::RichText::new(di.serial.as_deref().unwrap_or("--")).size(11.0));
+                                        ui.label(egui::RichText::new(ids.serial.as_deref().unwrap_or("--")).size(11.0));
                                         ui.label(egui::RichText::new(di.firmware.as_deref().unwrap_or("--")).size(11.0));
                                         ui.label(egui::RichText::new(di.device_type.as_deref().unwrap_or("--")).size(11.0));
+
+                                        // HPA/DCO capacity comparison in GB (512-byte sectors)
+                                        let capacity = di
+                                            .capacity_limits
+                                            .as_ref()
+                                            .map(|l| {
+                                                let native = l.native_sectors.max(l.dco_sectors.unwrap_or(0));
+                                                format!(
+                                                    "{:.1} GB / {:.1} GB",
+                                                    l.visible_sectors as f64 * 512.0 / 1_000_000_000.0,
+                                                    native as f64 * 512.0 / 1_000_000_000.0,
+                                                )
+                                            })
+                                            .unwrap_or("--".into());
+                                        ui.label(egui::RichText::new(capacity).size(11.0));
+                                        ui.label(egui::RichText::new(di.power_state.as_deref().unwrap_or("--")).size(11.0));
+                                        let write_cache = match di.write_cache {
+                                            Some(true) => "Enabled",
+                                            Some(false) => "Disabled",
+                                            // NVMe drives with power-loss protection often have no volatile cache to toggle
+                                            None if di.nvme_controller.as_ref().and_then(|c| c.volatile_write_cache) == Some(false) => {
+                                                "Not present"
+                                            }
+                                            None => "--",
+                                        };
+                                        ui.label(egui::RichText::new(write_cache).size(11.0));
+                                        // Self-encryption: SSC name plus whether Opal locking is active
+                                        let encryption = match &di.sed {
+                                            Some(sed) => {
+                                                let ssc = sed.ssc.as_deref().unwrap_or("SED capable");
+                                                if sed.locked {
+                                                    format!("{}, locked", ssc)
+                                                } else if sed.locking_enabled {
+                                                    format!("{}, locking on", ssc)
+                                                } else if sed.ssc.is_some() {
+                                                    format!("{}, locking off", ssc)
+                                                } else {
+                                                    ssc.to_string()
+                                                }
+                                            }
+                                            None => "--".to_string(),
+                                        };
+                                        ui.label(egui::RichText::new(encryption).size(11.0)).on_hover_text(
+                                            "TCG Opal/SED status from sedutil-cli. Self-encrypting drives always encrypt; \
+                                             \"locking on\" means a password protects the data at power-up.",
+                                        );
+                                        // NAND writes per host write; 1.0 is ideal, sustained values above ~3 wear the flash fast
+                                        let waf = di.write_amplification.map(|w| format!("{:.2}×", w)).unwrap_or("--".into());
+                                        ui.label(egui::RichText::new(waf).size(11.0)).on_hover_text(
+                                            "Write amplification factor: data written to flash divided by data written by the host, \
+                                             from vendor SMART attributes. Lower is better; small random writes and a full drive raise it.",
+                                        );
                                         ui.end_row();
                                     });
+
+                                // Stable identifiers that do not change when device letters shift
+                                if ids.by_id.is_some() || ids.wwn.is_some() {
+                                    ui.add_space(6.0);
+                                    ui.horizontal(|ui| {
+                                        let gray = egui::Color32::from_gray(120);
+                                        if let Some(by_id) = &ids.by_id {
+                                            ui.label(egui::RichText::new("By-id:").size(11.0).color(gray));
+                                            ui.add(egui::Label::new(egui::RichText::new(by_id).size(11.0).monospace()).selectable(true));
+                                            ui.add_space(20.0);
+                                        }
+                                        if let Some(wwn) = &ids.wwn {
+                                            ui.label(egui::RichText::new("WWN:").size(11.0).color(gray));
+                                            ui.add(egui::Label::new(egui::RichText::new(wwn).size(11.0).monospace()).selectable(true));
+                                        }
+                                    });
+                                }
+
+                                // SATA link power policy; aggressive settings are a common source of link resets and CRC errors
+                                if let Some(link) = &di.link_power {
+                                    ui.add_space(6.0);
+                                    ui.horizontal(|ui| {
+                                        ui.label(egui::RichText::new("Link power:").size(11.0).color(egui::Color32::from_gray(120)));
+                                        let color = if link.is_aggressive() {
+                                            egui::Color32::from_rgb(146, 64, 14)
+                                        } else {
+                                            egui::Color32::from_gray(60)
+                                        };
+                                        ui.label(egui::RichText::new(format!("{} ({})", link.policy, link.host)).size(11.0).monospace().color(color))
+                                            .on_hover_text(format!(
+                                                "{}. Set in /sys/class/scsi_host/{}/link_power_management_policy; \
+                                                 max_performance rules link power management out when chasing link errors.",
+                                                link.description(),
+                                                link.host
+                                            ));
+                                        if link.is_aggressive() && di.udma_crc_errors().is_some_and(|crc| crc > 0) {
+                                            ui.label(
+                                                egui::RichText::new("⚠ CRC errors with min_power: try max_performance before replacing the cable")
+                                                    .size(11.0)
+                                                    .color(egui::Color32::from_rgb(146, 64, 14)),
+                                            );
+                                        }
+                                    });
+                                }
+
+                                // Drive controls: spin down (HDD) and write cache toggle
+                                if di.kind == "HDD" || di.write_cache.is_some() {
+                                    ui.add_space(8.0);
+                                    ui.horizontal(|ui| {
+                                        if di.kind == "HDD" {
+                                            let asleep = di.power_state.as_deref().is_some_and(is_sleep_state);
+                                            let button = ui
+                                                .add_enabled(!asleep, egui::Button::new("Spin down now"))
+                                                .on_hover_text("Put the drive into standby immediately (hdparm -y)");
+                                            if button.clicked() {
+                                                requested_action = Some(DriveAction::SpinDown(di.dev.clone()));
+                                            }
+                                        }
+
+                                        // Toggling the cache trades data safety against write performance
+                                        let toggle = match di.write_cache {
+                                            Some(true) => Some((
+                                                "Disable write cache",
+                                                "Disabling the write cache makes writes durable immediately but can slow them down considerably.",
+                                            )),
+                                            Some(false) => Some((
+                                                "Enable write cache",
+                                                "With the write cache enabled, data not yet flushed can be lost on power failure \
+                                                 unless the drive has power-loss protection.",
+                                            )),
+                                            None => None,
+                                        };
+                                        if let (Some((label, warning)), Some(enabled)) = (toggle, di.write_cache) {
+                                            if confirm_button(ui, ("write_cache", &di.dev), label, warning) {
+                                                requested_action = Some(DriveAction::SetWriteCache(di.dev.clone(), !enabled));
+                                            }
+                                        }
+                                    });
+                                }
                             });
                         ui.add_space(20.0);
                     });
 
                     ui.add_space(12.0);
 
-                    // Statistics cards displayed in a 3-column grid
-                    let card_width = 283.0;
-                    let card_spacing = 11.0;
-                    let card_height = 75.0;
+                    // Rated endurance entered by the user, for SSDs with a known serial
+                    if let Some(key) = drive_key(di).filter(|_| di.device_type.as_deref() == Some("SSD")) {
+                        Self::endurance_card(ui, di, &key, &self.settings.drive(&key), &mut settings_edit);
+                        ui.add_space(12.0);
+                    }
 
-                    // Row 1: Temperature readings
-                    ui.horizontal(|ui| {
-                        ui.add_space(20.0);
+                    // Purchase date and warranty length entered by the user
+                    if let Some(key) = drive_key(di) {
+                        Self::warranty_card(ui, &key, &self.settings.drive(&key), &mut settings_edit);
+                        ui.add_space(12.0);
+                        Self::temp_alarm_card(ui, di, &key, &self.settings.drive(&key), &mut settings_edit);
+                        ui.add_space(12.0);
+                    }
 
-                        // SSD temperature from SMART data
-                        stat_card(
-                            ui,
-                            card_width,
-                            card_height,
-                            "SSD Temperature",
-                            &di.temp_c.map(|t| format!("{}°C", t)).unwrap_or("--".into()),
-                            egui::Color32::from_rgb(59, 130, 246),
-                        );
+                    // Vendor wear attributes of SATA SSDs; NVMe drives report Percentage Used in the header instead
+                    if let Some(wear) = di.ssd_wear().filter(|_| di.kind != "NVMe") {
+                        Self::ssd_wear_card(ui, &wear);
+                        ui.add_space(12.0);
+                    }
 
-                        ui.add_space(card_spacing);
+                    // Helium gauge for helium-filled HDDs
+                    if let Some((level, threshold)) = di.helium_level() {
+                        Self::helium_card(ui, level, threshold);
+                        ui.add_space(12.0);
+                    }
 
-                        // CPU temperature from sensors command
-                        stat_card(
-                            ui,
-                            card_width,
-                            card_height,
-                            "CPU Temp",
-                            &self.cpu_temp.map(|t| format!("{:.1}°C", t)).unwrap_or("--".into()),
-                            egui::Color32::from_rgb(139, 92, 246),
-                        );
+                    // APM/AAM editor for ATA drives that support either feature
+                    if di.apm_level.is_some() || di.aam_level.is_some() {
+                        Self::power_management_card(ui, di, &mut requested_action);
+                        ui.add_space(12.0);
+                    }
 
-                        ui.add_space(card_spacing);
+                    // SCT Error Recovery Control timeouts (TLER) for ATA drives
+                    if di.erc.is_some() {
+                        Self::erc_card(ui, di, &mut requested_action);
+                        ui.add_space(12.0);
+                    }
 
-                        // GPU temperature from nvidia-smi
-                        stat_card(
-                            ui,
-                            card_width,
-                            card_height,
-                            "GPU Temp",
-                            &self.gpu_temp.map(|t| format!("{:.1}°C", t)).unwrap_or("--".into()),
-                            egui::Color32::from_rgb(236, 72, 153),
-                        );
-                    });
+                    // ATA Security state with guidance for secure erase preparation
+                    if let Some(security) = di.security.as_ref().filter(|s| s.supported) {
+                        Self::security_card(ui, security);
+                        ui.add_space(12.0);
+                    }
 
-                    ui.add_space(10.0);
+                    // Identify Controller details for NVMe drives
+                    if let Some(controller) = &di.nvme_controller {
+                        Self::nvme_controller_card(ui, &di.dev, controller, &mut requested_action);
+                        ui.add_space(12.0);
 
-                    // Row 2: Data usage statistics
-                    ui.horizontal(|ui| {
-                        ui.add_space(20.0);
+                        // Stored firmware revisions, when nvme-cli could read the slot log
+                        if let Some(firmware) = controller.firmware_slots.as_ref().filter(|f| !f.slots.is_empty()) {
+                            Self::firmware_slots_card(ui, &di.dev, controller, firmware, &mut requested_action);
+                            ui.add_space(12.0);
+                        }
 
-                        // Total data written to drive
-                        stat_card(
-                            ui,
-                            card_width,
-                            card_height,
-                            "Data written",
-                            &di.data_written_tb.map(|t| format!("{:.1} TB", t)).unwrap_or("--".into()),
-                            egui::Color32::from_rgb(34, 197, 94),
-                        );
+                        if !controller.endurance_groups.is_empty() {
+                            Self::endurance_groups_card(ui, controller);
+                            ui.add_space(12.0);
+                        }
+                        // Thermal excursions, firmware commits, sanitizes and resets recorded by the drive itself
+                        if let Some(log) = self.event_logs.get(&di.dev).filter(|_| self.snapshot.is_none()) {
+                            Self::persistent_events_card(ui, log.as_ref(), &mut reload_event_log);
+                            ui.add_space(12.0);
+                        }
+                    }
 
-                        ui.add_space(card_spacing);
+                    // Block layer scheduler and queue settings from sysfs
+                    if let Some(queue) = &di.queue {
+                        Self::io_queue_card(ui, &di.dev, queue, &mut requested_action);
+                        ui.add_space(12.0);
+                    }
 
-                        // Total data read from drive
-                        stat_card(
-                            ui,
-                            card_width,
-                            card_height,
-                            "Data read",
-                            &di.data_read_tb.map(|t| format!("{:.1} TB", t)).unwrap_or("--".into()),
-                            egui::Color32::from_rgb(251, 146, 60),
-                        );
+                    // Statistics cards displayed in a 3-column grid; removable and network drives that report
+                    // no SMART data would only show dashes here, and their banner explains why
+                    if !(di.removable || di.is_network_attached()) || di.has_smart_data() {
+                        let card_width = 283.0;
+                        let card_spacing = 11.0;
+                        let card_height = 75.0;
 
-                        ui.add_space(card_spacing);
+                        // Row 1: Drive temperature, swap placed on this drive and the critical sector counters
+                        ui.horizontal(|ui| {
+                            ui.add_space(20.0);
 
-                        // Total hours drive has been powered on
-                        stat_card(
-                            ui,
-                            card_width,
-                            card_height,
-                            "Power on hours",
-                            &di.power_on_hours.map(|h| h.to_string()).unwrap_or("--".into()),
-                            egui::Color32::from_rgb(168, 85, 247),
-                        );
-                    });
+                            // SSD temperature from SMART data, with the extremes SATA drives keep in the raw value
+                            stat_card_with_note(
+                                ui,
+                                card_width,
+                                card_height,
+                                "SSD Temperature",
+                                &di.temp_c.map(|t| format!("{}°C", t)).unwrap_or("--".into()),
+                                &di.temperature_range().map(|r| r.describe()).unwrap_or_default(),
+                                egui::Color32::from_rgb(59, 130, 246),
+                            );
 
-                    ui.add_space(10.0);
+                            ui.add_space(card_spacing);
 
-                    // Row 3: Power and rotation statistics
-                    ui.horizontal(|ui| {
-                        ui.add_space(20.0);
+                            // Swap that lives on this drive adds write wear, so call it out
+                            let drive_name = di.dev.trim_start_matches("/dev/");
+                            let swap_here = self.swap_drives.iter().any(|d| d == drive_name);
+                            stat_card(
+                                ui,
+                                card_width,
+                                card_height,
+                                "Swap on this drive",
+                                &if swap_here {
+                                    format!("{:.1} GB used", self.system.used_swap() as f64 / 1_000_000_000.0)
+                                } else {
+                                    "None".into()
+                                },
+                                if swap_here && self.system.used_swap() > 0 {
+                                    egui::Color32::from_rgb(245, 158, 11)
+                                } else {
+                                    egui::Color32::from_rgb(20, 184, 166)
+                                },
+                            );
 
-                        // Number of power on/off cycles
-                        stat_card(
-                            ui,
-                            card_width,
-                            card_height,
-                            "Power cycles",
-                            &di.power_cycles.map(|c| c.to_string()).unwrap_or("--".into()),
-                            egui::Color32::from_rgb(59, 130, 246),
-                        );
+                            // Reallocated / pending / uncorrectable sectors: the most important HDD health signal
+                            if let Some(sectors) = di.critical_sectors() {
+                                ui.add_space(card_spacing);
+                                let count = |c: Option<u64>| c.map(group_digits).unwrap_or("--".into());
+                                ui.vertical(|ui| {
+                                    stat_card(
+                                        ui,
+                                        card_width,
+                                        card_height,
+                                        "Reallocated / Pending / Uncorrectable",
+                                        &format!(
+                                            "{} / {} / {}",
+                                            count(sectors.reallocated),
+                                            count(sectors.pending),
+                                            count(sectors.uncorrectable)
+                                        ),
+                                        if sectors.any_nonzero() {
+                                            egui::Color32::from_rgb(239, 68, 68)
+                                        } else {
+                                            egui::Color32::from_rgb(34, 197, 94)
+                                        },
+                                    );
+                                })
+                                .response
+                                .on_hover_text(
+                                    "Raw counts of Reallocated_Sector_Ct (5), Current_Pending_Sector (197) and \
+                                     Offline_Uncorrectable (198). Any non-zero value means the surface is degrading; \
+                                     back up and watch whether the counts grow.",
+                                );
+                            }
+                        });
 
-                        ui.add_space(card_spacing);
+                        // Interface transfer errors, kept out of the attribute table so cabling problems are noticed
+                        if let Some(crc) = di.udma_crc_errors() {
+                            ui.add_space(6.0);
+                            ui.horizontal(|ui| {
+                                ui.add_space(20.0);
+                                let color = if crc > 0 { egui::Color32::from_rgb(146, 64, 14) } else { egui::Color32::from_gray(120) };
+                                ui.label(egui::RichText::new(format!("UDMA CRC errors: {}", crc)).size(11.0).color(color))
+                                    .on_hover_text("Corrupted transfers on the SATA link; usually a cable or port problem");
+                            });
+                        }
 
-                        // Count of unsafe shutdowns (power loss events)
-                        stat_card(
-                            ui,
-                            card_width,
-                            card_height,
-                            "Unsafe shutdown",
-                            &di.unsafe_shutdowns.map(|us| us.to_string()).unwrap_or("--".into()),
-                            egui::Color32::from_rgb(239, 68, 68),
-                        );
+                        ui.add_space(10.0);
 
-                        ui.add_space(card_spacing);
+                        // Row 2: Data usage statistics
+                        ui.horizontal(|ui| {
+                            ui.add_space(20.0);
 
-                        // Rotation speed for HDDs, or "SSD Detected" for SSDs
-                        stat_card(
-                            ui,
-                            card_width,
-                            card_height,
-                            "HDD rotation speed",
-                            &di.rotation_rpm.map(|rpm| format!("{} RPM", rpm)).unwrap_or("SSD Detected".into()),
-                            egui::Color32::from_rgb(139, 92, 246),
-                        );
-                    });
+                            // Total data written to drive
+                            stat_card(
+                                ui,
+                                card_width,
+                                card_height,
+                                "Data written",
+                                &di.data_written_tb.map(|t| format!("{:.1} TB", t)).unwrap_or("--".into()),
+                                egui::Color32::from_rgb(34, 197, 94),
+                            );
 
-                    ui.add_space(15.0);
+                            ui.add_space(card_spacing);
+
+                            // Total data read from drive
+                            stat_card(
+                                ui,
+                                card_width,
+                                card_height,
+                                "Data read",
+                                &di.data_read_tb.map(|t| format!("{:.1} TB", t)).unwrap_or("--".into()),
+                                egui::Color32::from_rgb(251, 146, 60),
+                            );
+
+                            ui.add_space(card_spacing);
+
+                            // Total hours drive has been powered on, spelled out in years and days
+                            stat_card_with_note(
+                                ui,
+                                card_width,
+                                card_height,
+                                "Power on hours",
+                                &di.power_on_hours.map(group_digits).unwrap_or("--".into()),
+                                &di.power_on_hours.map(humanize_hours).unwrap_or_default(),
+                                egui::Color32::from_rgb(168, 85, 247),
+                            );
+                        });
+
+                        // Host traffic since the previous refresh
+                        if let Some(rate) =
+                            self.traffic.get(&Self::drive_identity(di)).filter(|r| self.snapshot.is_none() && **r != TrafficRate::default())
+                        {
+                            ui.add_space(6.0);
+                            ui.horizontal(|ui| {
+                                ui.add_space(20.0);
+                                let side = |mb: Option<f64>, what: &str| mb.map(|mb| format!("{:.1} MB/s {}", mb, what));
+                                let parts: Vec<String> =
+                                    [side(rate.read_mb_per_sec, "read"), side(rate.write_mb_per_sec, "written")].into_iter().flatten().collect();
+                                ui.label(
+                                    egui::RichText::new(format!("Current activity: {}", parts.join(" · ")))
+                                        .size(11.0)
+                                        .color(egui::Color32::from_gray(120)),
+                                )
+                                .on_hover_text("Change of the drive's total read/written counters since the last refresh (approximate)");
+                            });
+                        }
+
+                        // Lifetime utilization from the NVMe Controller Busy Time
+                        if let Some(minutes) = di.controller_busy_minutes {
+                            ui.add_space(6.0);
+                            ui.horizontal(|ui| {
+                                ui.add_space(20.0);
+                                let busy = if minutes >= 60 { format!("{:.1} h", minutes as f64 / 60.0) } else { format!("{} min", minutes) };
+                                let share = di
+                                    .busy_fraction()
+                                    .map(|f| format!(" ({:.1}% of power-on time)", f * 100.0))
+                                    .unwrap_or_default();
+                                ui.label(
+                                    egui::RichText::new(format!("Controller busy: {}{}", busy, share))
+                                        .size(11.0)
+                                        .color(egui::Color32::from_gray(120)),
+                                )
+                                .on_hover_text(
+                                    "Time the controller had I/O commands outstanding (NVMe Controller Busy Time). \
+                                     Unlike data written, it also counts small random I/O, so it shows how hard the drive has been worked.",
+                                );
+                            });
+                        }
+
+                        // Long-term write and wear rates from the stored history
+                        if let Some(rates) = drive_key(di).and_then(|k| self.history.get(&k)).and_then(|h| usage_rates(h)) {
+                            ui.add_space(6.0);
+                            Self::usage_rates_line(ui, &rates);
+                        }
+
+                        ui.add_space(10.0);
+
+                        // Row 3: Power and rotation statistics
+                        ui.horizontal(|ui| {
+                            ui.add_space(20.0);
+
+                            // Number of power on/off cycles and how long the drive stays on per cycle
+                            stat_card_with_note(
+                                ui,
+                                card_width,
+                                card_height,
+                                "Power cycles",
+                                &di.power_cycles.map(group_digits).unwrap_or("--".into()),
+                                &di
+                                    .hours_per_power_cycle()
+                                    .map(|h| format!("Average uptime: {} per cycle", humanize_hours(h.round() as u64)))
+                                    .unwrap_or_default(),
+                                egui::Color32::from_rgb(59, 130, 246),
+                            );
+
+                            ui.add_space(card_spacing);
+
+                            // Count of unsafe shutdowns (power loss events)
+                            stat_card(
+                                ui,
+                                card_width,
+                                card_height,
+                                "Unsafe shutdown",
+                                &di.unsafe_shutdowns.map(group_digits).unwrap_or("--".into()),
+                                egui::Color32::from_rgb(239, 68, 68),
+                            );
+
+                            ui.add_space(card_spacing);
+
+                            // Rotation speed for HDDs, or "SSD Detected" for SSDs
+                            stat_card(
+                                ui,
+                                card_width,
+                                card_height,
+                                "HDD rotation speed",
+                                &di.rotation_rpm.map(|rpm| format!("{} RPM", rpm)).unwrap_or("SSD Detected".into()),
+                                egui::Color32::from_rgb(139, 92, 246),
+                            );
+                        });
+
+                        ui.add_space(15.0);
+                    }
+
+                    // Temperature history graph (session samples plus the drive's own SCT log)
+                    if let Some(history) = self.temp_history.get(&Self::drive_identity(di)).filter(|_| self.snapshot.is_none()) {
+                        ui.horizontal(|ui| {
+                            ui.add_space(20.0);
+                            egui::Frame::none()
+                                .fill(egui::Color32::WHITE)
+                                .stroke(egui::Stroke::new(1.0, egui::Color32::from_gray(220)))
+                                .rounding(10.0)
+                                .inner_margin(15.0)
+                                .show(ui, |ui| {
+                                    ui.set_width(ui.available_width() - 40.0);
+                                    ui.label(egui::RichText::new("Temperature History").size(14.0).strong());
+                                    ui.add_space(8.0);
+                                    let chart = temperature_graph(ui, history, 140.0);
+                                    if history.len() >= 2 {
+                                        let name = format!("{}-temperature", di.dev.trim_start_matches("/dev/"));
+                                        save_chart_button(ui, chart, &name, &mut save_chart);
+                                    }
+                                });
+                            ui.add_space(20.0);
+                        });
+                        ui.add_space(15.0);
+                    }
+
+                    // SMART attribute table (ATA drives report these; NVMe uses the health log instead)
+                    if !di.smart_attributes.is_empty() {
+                        self.smart_table(ui, di, &mut column_toggle);
+                        ui.add_space(15.0);
+                    }
+
+                    // Read-only surface scan and benchmark; not offered for a snapshot from another machine
+                    if self.snapshot.is_none() {
+                        Self::surface_scan_card(ui, self.surface_scan.as_ref(), di, &mut surface_request);
+                        ui.add_space(15.0);
+                        let key = drive_key(di);
+                        let results = key.as_ref().and_then(|k| self.benchmarks.get(k)).map(Vec::as_slice).unwrap_or(&[]);
+                        let running = self.benchmark_run.as_ref().map(|run| run.dev.as_str());
+                        Self::benchmark_card(ui, results, running, di, key.is_some(), &mut benchmark_request, &mut save_chart);
+                        ui.add_space(15.0);
+                    }
                 });
             });
+
+        if toggle_cpu_list {
+            self.cpu_list_open = !self.cpu_list_open;
+        }
+        if toggle_gpu_list {
+            self.gpu_list_open = !self.gpu_list_open;
+        }
+
+        // Clicking the sorted column again flips the direction; another column starts in its natural order
+        if let Some(column) = partition_sort_click {
+            self.partition_sort = match self.partition_sort {
+                Some((sorted, descending)) if sorted == column => Some((column, !descending)),
+                _ => Some((column, column.default_descending())),
+            };
+        }
+
+        // Persist column visibility like the other settings
+        if let Some((table, column, visible)) = column_toggle {
+            let all = if table == "smart" { SMART_COLUMNS } else { PARTITION_COLUMNS };
+            self.settings.set_column_visible(table, all, column, visible);
+            if let Err(e) = self.settings.save() {
+                self.action_result = Some(Err(e));
+            }
+        }
+
+        // Persist edited drive settings; a failed save is reported like a drive action
+        if let Some((key, drive)) = settings_edit {
+            self.settings.drives.insert(key, drive);
+            if let Err(e) = self.settings.save() {
+                self.action_result = Some(Err(e));
+            }
+        }
+
+        // The chart is cut out of a screenshot of the next frame, which arrives as an input event
+        if let Some(chart) = save_chart {
+            self.pending_chart = Some(chart);
+            ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot);
+        }
+
+        if close_snapshot {
+            self.close_snapshot();
+        }
+        self.open_report_window(ctx);
+
+        if let Some(dev) = rescan {
+            self.rescan_drive(&dev);
+        }
+
+        // Snapshot drives belong to another machine; acting on the local device with the same path would be wrong
+        if self.snapshot.is_some() && (requested_action.is_some() || requested_usage_scan.is_some() || surface_request.is_some() || benchmark_request) {
+            self.action_result = Some(Err("Drive actions are disabled while viewing a report snapshot".to_string()));
+            return;
+        }
+        // The device path of an unplugged drive may already belong to another drive
+        // Only requests that start something are dropped; stopping a surface scan still goes through
+        let selected_gone = self.drives.get(self.selected).is_some_and(|d| self.is_disconnected(d));
+        if selected_gone && (requested_action.is_some() || surface_request == Some(true) || benchmark_request) {
+            self.action_result = Some(Err("Drive actions are disabled for a disconnected drive".to_string()));
+            requested_action = None;
+            benchmark_request = false;
+            surface_request = None;
+        }
+
+        // Start a new usage analysis (replacing any running one) and draw its window
+        if let Some(mount_point) = requested_usage_scan {
+            self.usage_analysis = Some(UsageAnalysis::start(&mount_point));
+        }
+        self.usage_window(ctx);
+        self.about_window(ctx);
+
+        // Read the persistent event log again: removing the entry makes the next frame start a new read
+        if reload_event_log {
+            if let Some(d) = self.drives.get(self.selected) {
+                self.event_logs.remove(&d.dev);
+            }
+        }
+
+        // Start a benchmark of the selected drive; the card only offers it for drives with a serial number
+        if benchmark_request && self.benchmark_run.is_none() {
+            if let Some(d) = self.drives.get(self.selected) {
+                if let Some(key) = drive_key(d) {
+                    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+                    self.benchmark_run = Some(BenchmarkRun::start(&d.dev, &key, now));
+                }
+            }
+        }
+
+        // Start or stop the surface scan; starting replaces a finished scan of any drive
+        match surface_request {
+            Some(true) => {
+                if let Some(d) = self.drives.get(self.selected) {
+                    match SurfaceScan::start(&d.dev) {
+                        Ok(scan) => self.surface_scan = Some(scan),
+                        Err(e) => self.action_result = Some(Err(e)),
+                    }
+                }
+            }
+            Some(false) => {
+                if let Some(scan) = &self.surface_scan {
+                    scan.stop();
+                }
+            }
+            None => {}
+        }
+
+        // Run the requested action, then rescan so the UI reflects the new state. A telemetry dump can take
+        // many seconds and changes nothing on the drive, so it runs in the background without a rescan
+        if let Some(action) = requested_action {
+            if let DriveAction::CaptureTelemetry(dev, _) = &action {
+                self.action_result = Some(Ok(format!("Capturing telemetry of {}…", dev)));
+                self.spawn_task(move || Some(action.run()));
+            } else {
+                self.action_result = Some(action.run());
+                self.manual_refresh();
+            }
+        }
     }
 }
\ No newline at end of file
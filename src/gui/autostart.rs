@@ -0,0 +1,89 @@
+// XDG autostart entry that starts the GUI minimized on login, so alerts keep working in the background
+
+// Entry file location and the executable path written into it
+use std::path::{Path, PathBuf};
+
+/// Name of the entry in the autostart directory
+const DESKTOP_FILE: &str = "ssd_info_cli.desktop";
+
+/// The autostart directory of the current user, where the desktop environment looks for entries to start on login.
+pub struct Autostart {
+    /// Directory holding the autostart entries
+    dir: PathBuf,
+}
+
+impl Autostart {
+    /// Opens `$XDG_CONFIG_HOME/autostart` (or `~/.config/autostart`).
+    /// Returns None if neither variable is set.
+    pub fn open_default() -> Option<Self> {
+        let base = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|p| p.is_absolute())
+            .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))?;
+        Some(Self::new(base.join("autostart")))
+    }
+
+    /// Uses an explicit autostart directory (created when the entry is installed).
+    ///
+    /// # Arguments
+    /// * `dir` - Directory holding the autostart entries
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Whether the entry is installed.
+    pub fn is_enabled(&self) -> bool {
+        self.path().is_file()
+    }
+
+    /// Installs or removes the entry.
+    ///
+    /// # Arguments
+    /// * `enabled` - True to start on login
+    /// * `exe` - Executable the entry runs (usually `std::env::current_exe()`)
+    ///
+    /// # Returns
+    /// Ok(()) on success, Err with a message otherwise.
+    pub fn set_enabled(&self, enabled: bool, exe: &Path) -> Result<(), String> {
+        let path = self.path();
+        if !enabled {
+            return match std::fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                    Err(format!("Failed to remove {}: {}", path.display(), e))
+                }
+                _ => Ok(()),
+            };
+        }
+        std::fs::create_dir_all(&self.dir).map_err(|e| format!("Failed to create {}: {}", self.dir.display(), e))?;
+        std::fs::write(&path, desktop_entry(exe)).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Path of the entry file.
+    fn path(&self) -> PathBuf {
+        self.dir.join(DESKTOP_FILE)
+    }
+}
+
+/// Formats the desktop entry that starts the GUI minimized.
+///
+/// # Arguments
+/// * `exe` - Executable to run; quoted as the Desktop Entry specification requires
+pub fn desktop_entry(exe: &Path) -> String {
+    let mut quoted = String::new();
+    for c in exe.display().to_string().chars() {
+        if matches!(c, '"' | '`' | '$' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name=SSD Health Checker\n\
+         Comment=Monitor drive health and alert on failures\n\
+         Exec=\"{}\" gui --minimized\n\
+         Terminal=false\n\
+         X-GNOME-Autostart-enabled=true\n",
+        quoted
+    )
+}
@@ -0,0 +1,219 @@
+// Read-only drive benchmark and its per-drive history, used to spot performance degrading over time
+
+// Shared O_DIRECT helpers with the surface scan
+use crate::gui::surface_scan::{aligned_buffer, open_direct_only};
+// History file location
+use crate::gui::history::data_dir;
+// Appending results and finding the device size
+use std::io::{Seek, SeekFrom, Write};
+// Positioned reads on the device file
+use std::os::unix::fs::FileExt;
+// Benchmark files location
+use std::path::PathBuf;
+// Channel delivering the result to the UI thread
+use std::sync::mpsc::{channel, Receiver};
+// Timing the reads
+use std::time::{Duration, Instant};
+
+/// Bytes read sequentially from the start of the drive
+const SEQUENTIAL_BYTES: u64 = 256 << 20;
+
+/// Request size of the sequential pass
+const SEQUENTIAL_CHUNK: usize = 1 << 20;
+
+/// Request size of the random pass
+const RANDOM_CHUNK: usize = 4096;
+
+/// How long the random pass runs
+const RANDOM_DURATION: Duration = Duration::from_secs(3);
+
+/// Outcome of one benchmark run.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchmarkResult {
+    /// Unix time of the run in seconds
+    pub timestamp: u64,
+    /// Sequential read throughput in MB/s (1 MiB requests)
+    pub seq_read_mb_per_sec: f64,
+    /// Random read rate in IOPS (4 KiB requests, queue depth 1)
+    pub random_read_iops: f64,
+}
+
+/// A benchmark running in a background thread.
+pub struct BenchmarkRun {
+    /// Device being benchmarked
+    pub dev: String,
+    /// Key of the drive's benchmark history, from `drive_key`
+    pub key: String,
+    /// Receives the result once the run finishes
+    rx: Receiver<Result<BenchmarkResult, String>>,
+}
+
+impl BenchmarkRun {
+    /// Starts benchmarking a drive in a background thread.
+    ///
+    /// # Arguments
+    /// * `dev` - Whole-drive device path (e.g., "/dev/nvme0n1")
+    /// * `key` - Drive key the result will be stored under
+    /// * `now` - Current Unix time in seconds, recorded as the run's timestamp
+    pub fn start(dev: &str, key: &str, now: u64) -> Self {
+        let (tx, rx) = channel();
+        let thread_dev = dev.to_string();
+        std::thread::spawn(move || {
+            // The receiver is gone if the UI dropped the run; nothing to do then
+            let _ = tx.send(run_benchmark(&thread_dev, now));
+        });
+        Self { dev: dev.to_string(), key: key.to_string(), rx }
+    }
+
+    /// Picks up the result if the worker thread has sent it.
+    pub fn poll(&self) -> Option<Result<BenchmarkResult, String>> {
+        self.rx.try_recv().ok()
+    }
+}
+
+/// Measures sequential and random read speed of a drive. Nothing is written.
+///
+/// # Arguments
+/// * `dev` - Whole-drive device path
+/// * `now` - Current Unix time in seconds
+///
+/// # Returns
+/// The measured speeds, or an error if the device cannot be opened with O_DIRECT, is too small or cannot be read.
+pub fn run_benchmark(dev: &str, now: u64) -> Result<BenchmarkResult, String> {
+    // Buffered reads would measure the page cache, so there is no fallback without O_DIRECT
+    let mut file = open_direct_only(dev).map_err(|e| {
+        format!("Cannot open {} for direct I/O: {} (benchmarks need root and a device that supports O_DIRECT)", dev, e)
+    })?;
+    let size = file.seek(SeekFrom::End(0)).map_err(|e| format!("Cannot read the size of {}: {}", dev, e))?;
+    if size < SEQUENTIAL_CHUNK as u64 {
+        return Err(format!("{} is too small to benchmark ({} bytes)", dev, size));
+    }
+    let read_error = |e: std::io::Error| format!("Read error on {}: {}", dev, e);
+    let mut storage = Vec::new();
+
+    // Sequential pass from the start of the drive
+    let buf = aligned_buffer(&mut storage, SEQUENTIAL_CHUNK);
+    let total = SEQUENTIAL_BYTES.min(size - size % SEQUENTIAL_CHUNK as u64);
+    let started = Instant::now();
+    let mut offset = 0;
+    while offset < total {
+        file.read_exact_at(buf, offset).map_err(read_error)?;
+        offset += SEQUENTIAL_CHUNK as u64;
+    }
+    let seq_secs = started.elapsed().as_secs_f64().max(1e-6);
+
+    // Random pass over the whole drive; xorshift keeps offsets spread without a rand dependency
+    let buf = aligned_buffer(&mut storage, RANDOM_CHUNK);
+    let blocks = (size / RANDOM_CHUNK as u64).max(1);
+    let mut state = now | 1;
+    let mut reads = 0u64;
+    let started = Instant::now();
+    while started.elapsed() < RANDOM_DURATION {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        file.read_exact_at(buf, (state % blocks) * RANDOM_CHUNK as u64).map_err(read_error)?;
+        reads += 1;
+    }
+
+    Ok(BenchmarkResult {
+        timestamp: now,
+        seq_read_mb_per_sec: total as f64 / 1_000_000.0 / seq_secs,
+        random_read_iops: reads as f64 / started.elapsed().as_secs_f64(),
+    })
+}
+
+/// Directory of per-drive benchmark files, one CSV file per serial number.
+pub struct BenchmarkStore {
+    /// Directory holding the benchmark files
+    dir: PathBuf,
+}
+
+impl BenchmarkStore {
+    /// Opens the store in `$XDG_DATA_HOME/ssd_info_cli/benchmarks` (or `~/.local/share/...`).
+    /// Returns None if neither variable is set.
+    pub fn open_default() -> Option<Self> {
+        Some(Self::new(data_dir()?.join("benchmarks")))
+    }
+
+    /// Creates a store rooted at an explicit directory (created on first write).
+    ///
+    /// # Arguments
+    /// * `dir` - Directory holding the benchmark files
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Loads all results of a drive, oldest first.
+    /// A missing or unreadable file yields an empty list.
+    ///
+    /// # Arguments
+    /// * `key` - Drive key from `drive_key`
+    pub fn load(&self, key: &str) -> Vec<BenchmarkResult> {
+        std::fs::read_to_string(self.path(key))
+            .map(|text| parse_benchmarks(&text))
+            .unwrap_or_default()
+    }
+
+    /// Appends a result to a drive's file.
+    ///
+    /// # Arguments
+    /// * `key` - Drive key from `drive_key`
+    /// * `result` - Result to store
+    pub fn record(&self, key: &str, result: &BenchmarkResult) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.path(key))?;
+        writeln!(
+            file,
+            "{},{:.1},{:.0}",
+            result.timestamp, result.seq_read_mb_per_sec, result.random_read_iops
+        )
+    }
+
+    /// Path of a drive's benchmark file.
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.csv", key))
+    }
+}
+
+/// Parses benchmark file contents.
+/// Format per line: "timestamp,seq_read_mb_per_sec,random_read_iops". Malformed lines are skipped.
+///
+/// # Arguments
+/// * `text` - Contents of a benchmark file
+pub fn parse_benchmarks(text: &str) -> Vec<BenchmarkResult> {
+    text.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.trim().split(',').collect();
+            if fields.len() != 3 {
+                return None;
+            }
+            Some(BenchmarkResult {
+                timestamp: fields[0].parse().ok()?,
+                seq_read_mb_per_sec: fields[1].parse().ok()?,
+                random_read_iops: fields[2].parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Change of the latest run against the first one, when the drive was newest.
+///
+/// # Arguments
+/// * `results` - Benchmark history, oldest first
+///
+/// # Returns
+/// Percentage change of (sequential MB/s, random IOPS), negative when slower; None with fewer than two runs.
+pub fn change_since_first(results: &[BenchmarkResult]) -> Option<(f64, f64)> {
+    let (first, last) = (results.first()?, results.last()?);
+    if results.len() < 2 || first.seq_read_mb_per_sec <= 0.0 || first.random_read_iops <= 0.0 {
+        return None;
+    }
+    Some((
+        (last.seq_read_mb_per_sec / first.seq_read_mb_per_sec - 1.0) * 100.0,
+        (last.random_read_iops / first.random_read_iops - 1.0) * 100.0,
+    ))
+}
@@ -0,0 +1,57 @@
+// Saving a chart as a PNG image, cut out of a screenshot of the window
+
+// Screenshot pixels and the chart's screen rectangle
+use eframe::egui::{self, ColorImage, Rect};
+// Chart file location
+use crate::gui::settings::home_dir;
+use std::path::{Path, PathBuf};
+
+/// Default location for a saved chart: `~/ssd-chart-<name>-<unix time>.png`
+///
+/// # Arguments
+/// * `name` - What the chart shows, e.g. "nvme0n1-temperature"
+/// * `now` - Current Unix time in seconds
+pub fn default_chart_path(name: &str, now: u64) -> PathBuf {
+    let dir = home_dir();
+    let name: String = name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' }).collect();
+    dir.join(format!("ssd-chart-{}-{}.png", name, now))
+}
+
+/// Cuts a chart out of a window screenshot.
+///
+/// # Arguments
+/// * `screenshot` - The whole window, in physical pixels
+/// * `chart` - Where the chart was drawn, in points
+/// * `pixels_per_point` - Display scale the screenshot was taken at
+///
+/// # Returns
+/// The chart's pixels, or None if the chart was not on screen.
+pub fn crop_chart(screenshot: &ColorImage, chart: Rect, pixels_per_point: f32) -> Option<ColorImage> {
+    let [width, height] = screenshot.size;
+    let visible = chart.intersect(Rect::from_min_size(
+        egui::Pos2::ZERO,
+        egui::vec2(width as f32, height as f32) / pixels_per_point,
+    ));
+    if !visible.is_positive() {
+        return None;
+    }
+    Some(screenshot.region(&visible, Some(pixels_per_point)))
+}
+
+/// Writes an image as a PNG file.
+///
+/// # Arguments
+/// * `image` - Chart pixels
+/// * `path` - Destination file
+///
+/// # Returns
+/// Ok(()) on success, Err with a message otherwise.
+pub fn save_png(image: &ColorImage, path: &Path) -> Result<(), String> {
+    let [width, height] = image.size;
+    let rgba: Vec<u8> = image.pixels.iter().flat_map(|p| p.to_srgba_unmultiplied()).collect();
+    let buffer = image::RgbaImage::from_raw(width as u32, height as u32, rgba)
+        .ok_or_else(|| format!("Chart image has the wrong size ({}×{})", width, height))?;
+    buffer
+        .save_with_format(path, image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
@@ -14,6 +14,20 @@ use eframe::egui;
 /// * `value` - Main value displayed prominently (e.g., "45°C")
 /// * `color` - Color used for the value text
 pub fn stat_card(ui: &mut egui::Ui, width: f32, height: f32, label: &str, value: &str, color: egui::Color32) {
+    stat_card_with_note(ui, width, height, label, value, "", color);
+}
+
+/// Renders a statistics card like `stat_card` with a small gray note under the value.
+///
+/// # Arguments
+/// * `ui` - The egui UI context to render into
+/// * `width` - Card width in pixels
+/// * `height` - Card height in pixels
+/// * `label` - Descriptive text shown at the top (e.g., "SSD Temperature")
+/// * `value` - Main value displayed prominently (e.g., "45°C")
+/// * `note` - Context for the value (e.g., "Lifetime range: 19–55°C"); nothing is shown if empty
+/// * `color` - Color used for the value text
+pub fn stat_card_with_note(ui: &mut egui::Ui, width: f32, height: f32, label: &str, value: &str, note: &str, color: egui::Color32) {
     // Create a white card with rounded corners and a subtle border
     egui::Frame::none()
         .fill(egui::Color32::WHITE)
@@ -34,6 +48,352 @@ pub fn stat_card(ui: &mut egui::Ui, width: f32, height: f32, label: &str, value:
                 ui.add_space(8.0);
                 // Display value in large colored text
                 ui.label(egui::RichText::new(value).size(22.0).color(color).strong());
+                if !note.is_empty() {
+                    ui.label(egui::RichText::new(note).size(10.0).color(egui::Color32::from_gray(120)));
+                }
+            });
+        });
+}
+
+/// Renders a small ring chart: a gray track with an arc covering `fraction` of it, starting at 12 o'clock.
+///
+/// # Arguments
+/// * `ui` - The egui UI context to render into
+/// * `fraction` - Filled share of the ring, clamped to 0.0-1.0
+/// * `diameter` - Outer size of the ring in pixels
+/// * `color` - Color of the filled arc
+///
+/// # Returns
+/// The ring's response, for hover text.
+pub fn usage_ring(ui: &mut egui::Ui, fraction: f32, diameter: f32, color: egui::Color32) -> egui::Response {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(diameter, diameter), egui::Sense::hover());
+    let thickness = (diameter / 5.0).max(2.0);
+    let radius = (diameter - thickness) / 2.0;
+    let painter = ui.painter();
+    painter.circle_stroke(rect.center(), radius, egui::Stroke::new(thickness, egui::Color32::from_gray(225)));
+
+    // Approximate the arc with short segments, clockwise from the top
+    let fraction = fraction.clamp(0.0, 1.0);
+    if fraction > 0.0 {
+        let steps = ((fraction * 48.0).ceil() as usize).max(2);
+        let points: Vec<egui::Pos2> = (0..=steps)
+            .map(|i| {
+                let angle = std::f32::consts::TAU * fraction * i as f32 / steps as f32 - std::f32::consts::FRAC_PI_2;
+                rect.center() + radius * egui::vec2(angle.cos(), angle.sin())
+            })
+            .collect();
+        painter.add(egui::Shape::line(points, egui::Stroke::new(thickness, color)));
+    }
+    response
+}
+
+/// Renders a small horizontal bar comparing a SMART attribute's normalized value with its worst value and threshold.
+/// The fill shows the current value, a dark tick marks the worst value and the red section below the threshold
+/// is where the attribute trips, so the gap between the tick and the red section is the margin left.
+///
+/// # Arguments
+/// * `ui` - The egui UI context to render into
+/// * `current` - Current normalized value
+/// * `worst` - Lowest normalized value recorded
+/// * `threshold` - Failure threshold, 0 if the attribute never trips
+/// * `color` - Fill color, usually the attribute's status color
+///
+/// # Returns
+/// The bar's response, for hover text.
+pub fn margin_bar(ui: &mut egui::Ui, current: u16, worst: u16, threshold: u16, color: egui::Color32) -> egui::Response {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(90.0, 10.0), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    // Most attributes are normalized to 100; some start at 200 or 253
+    let scale = f32::from(current.max(worst).max(threshold).max(100));
+    let x_of = |value: u16| rect.left() + rect.width() * f32::from(value) / scale;
+
+    painter.rect_filled(rect, 3.0, egui::Color32::from_gray(230));
+    painter.rect_filled(egui::Rect::from_min_max(rect.min, egui::pos2(x_of(current), rect.bottom())), 3.0, color);
+    if threshold > 0 {
+        let tripped = egui::Rect::from_min_max(rect.min, egui::pos2(x_of(threshold), rect.bottom()));
+        painter.rect_filled(tripped, 3.0, egui::Color32::from_rgba_unmultiplied(239, 68, 68, 170));
+    }
+    let x = x_of(worst).clamp(rect.left() + 1.0, rect.right() - 1.0);
+    painter.line_segment([egui::pos2(x, rect.top()), egui::pos2(x, rect.bottom())], egui::Stroke::new(2.0, egui::Color32::from_gray(60)));
+    response
+}
+
+/// Renders a full-width amber warning banner with a bold title and a detail line.
+/// Used to flag conditions that need the user's attention (e.g., hidden capacity).
+///
+/// # Arguments
+/// * `ui` - The egui UI context to render into
+/// * `title` - Bold headline text (e.g., "⚠ Hidden capacity detected")
+/// * `detail` - Smaller explanatory text shown below the title
+pub fn warning_banner(ui: &mut egui::Ui, title: &str, detail: &str) {
+    banner(ui, title, None, detail, None);
+}
+
+/// Renders the amber warning banner with a progress bar between the title and the detail line,
+/// for long-running operations on the drive (e.g., a sanitize).
+///
+/// # Arguments
+/// * `ui` - The egui UI context to render into
+/// * `title` - Bold headline text (e.g., "⚠ Sanitize in progress: 40% complete")
+/// * `progress` - Fraction complete, 0.0 to 1.0
+/// * `detail` - Smaller explanatory text shown below the progress bar
+pub fn warning_banner_with_progress(ui: &mut egui::Ui, title: &str, progress: f32, detail: &str) {
+    banner(ui, title, Some(progress), detail, None);
+}
+
+/// Renders the amber warning banner with a button below the detail line, for conditions the app can fix.
+///
+/// # Arguments
+/// * `ui` - The egui UI context to render into
+/// * `title` - Bold headline text (e.g., "⚠ SMART is disabled")
+/// * `detail` - Smaller explanatory text shown below the title
+/// * `button` - Label of the fixing action (e.g., "Enable SMART")
+///
+/// # Returns
+/// True on the frame the button is clicked.
+pub fn warning_banner_with_action(ui: &mut egui::Ui, title: &str, detail: &str, button: &str) -> bool {
+    banner(ui, title, None, detail, Some(button))
+}
+
+/// Shared layout of the warning banners; returns whether the optional button was clicked.
+fn banner(ui: &mut egui::Ui, title: &str, progress: Option<f32>, detail: &str, button: Option<&str>) -> bool {
+    let text_color = egui::Color32::from_rgb(146, 64, 14);
+    let mut clicked = false;
+    ui.horizontal(|ui| {
+        ui.add_space(20.0);
+        egui::Frame::none()
+            .fill(egui::Color32::from_rgb(254, 243, 199))
+            .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(245, 158, 11)))
+            .rounding(10.0)
+            .inner_margin(12.0)
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width() - 40.0);
+                ui.label(egui::RichText::new(title).size(14.0).strong().color(text_color));
+                ui.add_space(4.0);
+                if let Some(progress) = progress {
+                    ui.add(egui::ProgressBar::new(progress));
+                    ui.add_space(4.0);
+                }
+                ui.label(egui::RichText::new(detail).size(11.0).color(text_color));
+                if let Some(label) = button {
+                    ui.add_space(8.0);
+                    clicked = ui.button(label).clicked();
+                }
+            });
+        ui.add_space(20.0);
+    });
+    clicked
+}
+
+
+/// Renders a button that needs a second, explicit confirmation before it fires.
+/// The first click reveals the warning text together with Confirm and Cancel buttons.
+///
+/// # Arguments
+/// * `ui` - The egui UI context to render into
+/// * `id_source` - Unique identifier for this button's confirmation state
+/// * `label` - Button text (e.g., "Disable write cache")
+/// * `warning` - Explanation shown before the user confirms
+///
+/// # Returns
+/// True on the frame the user clicks Confirm.
+pub fn confirm_button(ui: &mut egui::Ui, id_source: impl std::hash::Hash, label: &str, warning: &str) -> bool {
+    let id = ui.make_persistent_id(id_source);
+    let mut armed = ui.data_mut(|d| d.get_temp::<bool>(id).unwrap_or(false));
+    let mut confirmed = false;
+
+    if !armed {
+        // First step: plain button that arms the confirmation
+        if ui.button(label).clicked() {
+            armed = true;
+        }
+    } else {
+        // Second step: warning text with explicit Confirm/Cancel
+        ui.vertical(|ui| {
+            ui.colored_label(
+                egui::Color32::from_rgb(146, 64, 14),
+                egui::RichText::new(warning).size(11.0),
+            );
+            ui.horizontal(|ui| {
+                if ui.button(format!("Confirm: {}", label)).clicked() {
+                    confirmed = true;
+                    armed = false;
+                }
+                if ui.button("Cancel").clicked() {
+                    armed = false;
+                }
             });
         });
-}
\ No newline at end of file
+    }
+
+    ui.data_mut(|d| d.insert_temp(id, armed));
+    confirmed
+}
+
+/// Renders the Apply button of a drive setting: disabled while the draft equals the current value,
+/// otherwise a `confirm_button` that shows what applying it will do.
+///
+/// # Arguments
+/// * `ui` - The egui UI context to render into
+/// * `id_source` - Unique identifier for this button's confirmation state
+/// * `changed` - Whether the draft differs from the drive's current setting
+/// * `warning` - Explanation shown before the user confirms
+///
+/// # Returns
+/// True on the frame the user clicks Confirm.
+pub fn confirm_apply_button(ui: &mut egui::Ui, id_source: impl std::hash::Hash, changed: bool, warning: &str) -> bool {
+    if !changed {
+        ui.add_enabled(false, egui::Button::new("Apply"));
+        return false;
+    }
+    confirm_button(ui, id_source, "Apply", warning)
+}
+
+/// Draws a line chart of temperature samples with min/max and time span labels.
+///
+/// # Arguments
+/// * `ui` - The egui UI context to render into
+/// * `samples` - (Unix seconds, °C) pairs, oldest first
+/// * `height` - Chart height in pixels
+///
+/// # Returns
+/// Where the chart was drawn.
+pub fn temperature_graph(ui: &mut egui::Ui, samples: &[(u64, f32)], height: f32) -> egui::Rect {
+    line_graph(ui, samples, height, |v| format!("{:.0}°C", v))
+}
+
+/// Draws a line chart of timestamped samples with min/max and time span labels.
+///
+/// # Arguments
+/// * `ui` - The egui UI context to render into
+/// * `samples` - (Unix seconds, value) pairs, oldest first
+/// * `height` - Chart height in pixels
+/// * `label` - Formats a value for the min/max axis labels
+///
+/// # Returns
+/// Where the chart was drawn.
+pub fn line_graph(ui: &mut egui::Ui, samples: &[(u64, f32)], height: f32, label: impl Fn(f32) -> String) -> egui::Rect {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width(), height), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    let gray = egui::Color32::from_gray(120);
+    painter.rect_filled(rect, 6.0, egui::Color32::from_gray(248));
+
+    if samples.len() < 2 {
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            "Collecting samples…",
+            egui::FontId::proportional(11.0),
+            gray,
+        );
+        return rect;
+    }
+
+    // Leave room for axis labels on the left and bottom
+    let plot = egui::Rect::from_min_max(rect.min + egui::vec2(40.0, 8.0), rect.max - egui::vec2(8.0, 18.0));
+    let (t0, t1) = (samples[0].0, samples[samples.len() - 1].0.max(samples[0].0 + 1));
+    let lo = samples.iter().map(|s| s.1).fold(f32::MAX, f32::min).floor() - 1.0;
+    let hi = samples.iter().map(|s| s.1).fold(f32::MIN, f32::max).ceil() + 1.0;
+
+    // Horizontal guide lines at the top and bottom of the value range
+    for (value, y) in [(hi, plot.top()), (lo, plot.bottom())] {
+        painter.line_segment(
+            [egui::pos2(plot.left(), y), egui::pos2(plot.right(), y)],
+            egui::Stroke::new(1.0, egui::Color32::from_gray(220)),
+        );
+        painter.text(
+            egui::pos2(plot.left() - 6.0, y),
+            egui::Align2::RIGHT_CENTER,
+            label(value),
+            egui::FontId::proportional(10.0),
+            gray,
+        );
+    }
+
+    // Time span label ("-8.0 h" … "now"), in days for long histories
+    let span_h = (t1 - t0) as f32 / 3600.0;
+    let span = if span_h >= 48.0 {
+        format!("-{:.0} days", span_h / 24.0)
+    } else if span_h >= 1.0 {
+        format!("-{:.1} h", span_h)
+    } else {
+        format!("-{:.0} min", span_h * 60.0)
+    };
+    let font = egui::FontId::proportional(10.0);
+    painter.text(plot.left_bottom() + egui::vec2(0.0, 4.0), egui::Align2::LEFT_TOP, span, font.clone(), gray);
+    painter.text(plot.right_bottom() + egui::vec2(0.0, 4.0), egui::Align2::RIGHT_TOP, "now", font, gray);
+
+    // Value line
+    let points: Vec<egui::Pos2> = samples
+        .iter()
+        .map(|(t, v)| {
+            let x = plot.left() + plot.width() * (t - t0) as f32 / (t1 - t0) as f32;
+            let y = plot.bottom() - plot.height() * (v - lo) / (hi - lo);
+            egui::pos2(x, y)
+        })
+        .collect();
+    painter.add(egui::Shape::line(points, egui::Stroke::new(1.5, egui::Color32::from_rgb(59, 130, 246))));
+    rect
+}
+
+/// Draws a right-aligned "Save chart" button below a chart.
+///
+/// # Arguments
+/// * `ui` - The egui UI context to render into
+/// * `chart` - Where the chart was drawn
+/// * `name` - What the chart shows, used in the file name (e.g., "nvme0n1-temperature")
+/// * `request` - Receives the chart's rectangle and name when clicked
+pub fn save_chart_button(ui: &mut egui::Ui, chart: egui::Rect, name: &str, request: &mut Option<(egui::Rect, String)>) {
+    ui.horizontal(|ui| {
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            let button = egui::Button::new(egui::RichText::new("💾 Save chart").size(11.0));
+            if ui.add(button).on_hover_text("Save this chart as a PNG image in your home directory").clicked() {
+                *request = Some((chart, name.to_string()));
+            }
+        });
+    });
+}
+
+/// Draws events as colored dots on a horizontal time axis, labeled with the first and last dates.
+/// Hovering near a dot shows its label.
+///
+/// # Arguments
+/// * `ui` - The egui UI context to render into
+/// * `events` - (Unix seconds, color, hover label) per event, in any order
+/// * `date` - Formats a Unix time for the axis ends (e.g., "2024-03-15")
+pub fn event_timeline(ui: &mut egui::Ui, events: &[(u64, egui::Color32, String)], date: impl Fn(u64) -> String) {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(ui.available_width(), 40.0), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    let gray = egui::Color32::from_gray(120);
+    painter.rect_filled(rect, 6.0, egui::Color32::from_gray(248));
+    let (Some(t0), Some(t1)) = (events.iter().map(|e| e.0).min(), events.iter().map(|e| e.0).max()) else {
+        return;
+    };
+    let t1 = t1.max(t0 + 1);
+
+    // Axis line across the middle of the upper half, dates below it
+    let axis_y = rect.top() + 14.0;
+    let (left, right) = (rect.left() + 12.0, rect.right() - 12.0);
+    painter.line_segment([egui::pos2(left, axis_y), egui::pos2(right, axis_y)], egui::Stroke::new(1.0, egui::Color32::from_gray(200)));
+    let font = egui::FontId::proportional(10.0);
+    painter.text(egui::pos2(left, rect.bottom() - 4.0), egui::Align2::LEFT_BOTTOM, date(t0), font.clone(), gray);
+    painter.text(egui::pos2(right, rect.bottom() - 4.0), egui::Align2::RIGHT_BOTTOM, date(t1), font, gray);
+
+    let x_of = |t: u64| left + (right - left) * (t - t0) as f32 / (t1 - t0) as f32;
+    for (t, color, _) in events {
+        painter.circle_filled(egui::pos2(x_of(*t), axis_y), 4.0, *color);
+    }
+
+    // Label of the event closest to the pointer, within a few pixels
+    let hovered = response.hover_pos().and_then(|pos| {
+        events
+            .iter()
+            .map(|e| ((x_of(e.0) - pos.x).abs(), e))
+            .filter(|(distance, _)| *distance <= 6.0)
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, e)| e.2.clone())
+    });
+    if let Some(label) = hovered {
+        response.on_hover_text(label);
+    }
+}
@@ -0,0 +1,141 @@
+// Environment details for the About dialog: what is needed to triage "no drives show up" reports
+
+// C-locale commands and the device list shared with the scanner
+use crate::gui::disk_scanner::{c_locale_command, detected_devices};
+// Flatpak detection, where smartctl runs on the host instead
+use crate::sandbox::in_flatpak;
+// Effective user id for the root check
+use nix::unistd::geteuid;
+// Locating smartctl on PATH
+use std::path::{Path, PathBuf};
+// Kernel version
+use sysinfo::System;
+
+/// Snapshot of the environment the app runs in.
+#[derive(Clone, Debug)]
+pub struct Diagnostics {
+    /// Version of this app
+    pub version: &'static str,
+    /// Where smartctl was found on PATH, None if it is not installed (or runs on the host from a sandbox)
+    pub smartctl_path: Option<PathBuf>,
+    /// smartctl release (e.g., "7.3 2022-02-28 r5338"), None if it could not be run
+    pub smartctl_version: Option<String>,
+    /// Running kernel version (e.g., "6.5.0-14-generic")
+    pub kernel: Option<String>,
+    /// Whether the app runs with root privileges, which smartctl needs
+    pub is_root: bool,
+    /// Whole NVMe and SATA drives found in /dev, probed or not
+    pub detected: Vec<String>,
+    /// Whether the app runs inside a Flatpak sandbox and reaches the host tools through flatpak-spawn
+    pub flatpak: bool,
+}
+
+impl Diagnostics {
+    /// Gathers the environment details; runs `smartctl --version` once.
+    pub fn collect() -> Self {
+        let smartctl_version = c_locale_command("smartctl")
+            .arg("--version")
+            .output()
+            .ok()
+            .and_then(|o| parse_smartctl_version(&String::from_utf8_lossy(&o.stdout)));
+        let flatpak = in_flatpak();
+        // The sandbox's own PATH says nothing about the host's smartctl
+        let smartctl_path = match flatpak {
+            true => None,
+            false => std::env::var("PATH").ok().and_then(|path| find_program("smartctl", &path)),
+        };
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            smartctl_path,
+            smartctl_version,
+            kernel: System::kernel_version(),
+            is_root: geteuid().is_root(),
+            detected: detected_devices().unwrap_or_default(),
+            flatpak,
+        }
+    }
+
+    /// Lists the details as (label, value) rows.
+    ///
+    /// # Arguments
+    /// * `probed` - Devices that returned data on the last scan, None when a saved report is shown instead
+    ///
+    /// # Returns
+    /// smartctl, kernel, root and drive count rows, a sandbox row inside Flatpak,
+    /// plus the detected devices that could not be probed.
+    pub fn rows(&self, probed: Option<&[&str]>) -> Vec<(&'static str, String)> {
+        let smartctl = match (&self.smartctl_version, &self.smartctl_path) {
+            (Some(version), Some(path)) => format!("{} ({})", version, path.display()),
+            (None, Some(path)) => format!("found at {} but could not be run", path.display()),
+            (Some(version), None) => version.clone(),
+            (None, None) if self.flatpak => "not found on the host (or flatpak-spawn is not allowed)".to_string(),
+            (None, None) => "not found on PATH".to_string(),
+        };
+        let mut rows = vec![
+            ("smartctl", smartctl),
+            ("Kernel", self.kernel.clone().unwrap_or("unknown".into())),
+            ("Running as root", if self.is_root { "yes" } else { "no (SMART data needs root; run with sudo)" }.to_string()),
+        ];
+        if self.flatpak {
+            rows.push((
+                "Sandbox",
+                "Flatpak; drive tools run on the host through flatpak-spawn --host \
+                 (needs --device=all and --talk-name=org.freedesktop.Flatpak)"
+                    .to_string(),
+            ));
+        }
+        match probed {
+            Some(probed) => {
+                rows.push(("Drives", format!("{} detected, {} probed", self.detected.len(), probed.len())));
+                let missing: Vec<&str> =
+                    self.detected.iter().map(String::as_str).filter(|dev| !probed.contains(dev)).collect();
+                if !missing.is_empty() {
+                    rows.push(("Not probed", missing.join(", ")));
+                }
+            }
+            None => rows.push(("Drives", format!("{} detected (a saved report is open)", self.detected.len()))),
+        }
+        rows
+    }
+
+    /// Formats the details as plain text for pasting into a bug report.
+    ///
+    /// # Arguments
+    /// * `probed` - Devices that returned data on the last scan, None when a saved report is shown instead
+    pub fn summary(&self, probed: Option<&[&str]>) -> String {
+        let mut text = format!("SSD Health Checker {}", self.version);
+        for (label, value) in self.rows(probed) {
+            text.push_str(&format!("\n{}: {}", label, value));
+        }
+        text
+    }
+}
+
+/// Extracts the release from `smartctl --version` output.
+/// The first line looks like "smartctl 7.3 2022-02-28 r5338 [x86_64-linux-6.5.0] (local build)".
+///
+/// # Arguments
+/// * `output` - Standard output of `smartctl --version`
+///
+/// # Returns
+/// The version, date and revision (e.g., "7.3 2022-02-28 r5338"), or None if the output is not recognized.
+pub fn parse_smartctl_version(output: &str) -> Option<String> {
+    let rest = output.lines().next()?.strip_prefix("smartctl ")?;
+    let version = rest.split(" [").next()?.trim();
+    (!version.is_empty()).then(|| version.to_string())
+}
+
+/// Looks up an executable in a PATH-style list of directories.
+///
+/// # Arguments
+/// * `program` - Executable name (e.g., "smartctl")
+/// * `path` - Colon-separated directories, as in `$PATH`
+///
+/// # Returns
+/// The first existing file named `program`, or None.
+pub fn find_program(program: &str, path: &str) -> Option<PathBuf> {
+    path.split(':')
+        .filter(|dir| !dir.is_empty())
+        .map(|dir| Path::new(dir).join(program))
+        .find(|candidate| candidate.is_file())
+}
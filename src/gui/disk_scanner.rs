@@ -1,13 +1,222 @@
 // Disk discovery and SMART data collection using smartctl
 
 // Import data models for disk information
-use crate::models::{AttributeStatus, DiskInfo, PartitionInfo, SmartAttribute};
+use crate::models::advisories;
+use crate::models::{
+    ApstStatus, AtaSecurity, AttributeStatus, CapacityLimits, DiskInfo, EnduranceGroup, ErcSettings, EventCategory, FirmwareSlots, LinkPower,
+    NamespaceUsage, NvmeController, OperationProgress, PartitionInfo, PersistentEvent, QueueInfo, SedStatus, SmartAttribute,
+};
+// Identify Controller output is cached per drive key (model + serial)
+use crate::gui::history::drive_key;
+// Host tools run through flatpak-spawn inside a Flatpak sandbox
+use crate::sandbox::host_command;
+// Lazily initialised statics for the compiled patterns
+use once_cell::sync::Lazy;
 // Regex for parsing smartctl output
 use regex::Regex;
+// Mount options lookup by mount point
+use std::collections::HashMap;
+// Program names passed to Command
+use std::ffi::OsStr;
+// Roots of the device, sysfs and proc trees
+use std::path::PathBuf;
+// Identify Controller cache shared by scans on any thread
+use std::sync::Mutex;
 // Command execution for calling smartctl
 use std::process::Command;
 // Disk and partition enumeration
 use sysinfo::Disks;
+// Inode counts of mounted filesystems
+use nix::sys::statvfs::statvfs;
+
+/// `nvme id-ctrl` output by sysfs root and drive key. Identify Controller data does not change while a drive
+/// is attached, so it is read once per drive instead of on every refresh.
+static ID_CTRL_CACHE: Lazy<Mutex<HashMap<String, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Results of the NVMe probes that do not change between refreshes, keyed like `ID_CTRL_CACHE`.
+/// Automatic refreshes reuse them; `forget_static_probes` drops them before a manual rescan.
+static NVME_PROBE_CACHE: Lazy<Mutex<HashMap<String, NvmeStaticProbes>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// What the static NVMe probes found for one drive
+#[derive(Clone, Default)]
+struct NvmeStaticProbes {
+    /// Firmware slot log
+    firmware_slots: Option<FirmwareSlots>,
+    /// APST feature and the kernel's latency limit
+    apst: Option<ApstStatus>,
+    /// Endurance group logs
+    endurance_groups: Vec<EnduranceGroup>,
+    /// Size and use of each namespace
+    namespace_usage: Vec<NamespaceUsage>,
+    /// Self-encryption status
+    sed: Option<SedStatus>,
+    /// Sanitize in progress; while one runs the entry is not cached, so its progress keeps moving
+    sanitize: Option<OperationProgress>,
+}
+
+// Patterns are compiled once on first use instead of on every refresh
+
+// smartctl: identity section
+static MODEL_NUMBER_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"Model Number:\s+(.+)").unwrap());
+static DEVICE_MODEL_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"Device Model:\s+(.+)").unwrap());
+static SERIAL_NUMBER_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"Serial Number:\s+(.+)").unwrap());
+static FIRMWARE_VERSION_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"Firmware Version:\s+(.+)").unwrap());
+static WWN_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"LU WWN Device Id:\s+([0-9a-fA-F])\s+([0-9a-fA-F]+)\s+([0-9a-fA-F]+)").unwrap());
+static EUI64_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"IEEE EUI-64:\s+([0-9a-fA-F]+)\s+([0-9a-fA-F]+)").unwrap());
+static CAPACITY_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:Total NVM Capacity|Namespace 1 Size/Capacity|User Capacity):\s+([\d,.' ]*\d)\s+(?:bytes\s+)?\[.*?(\d+(?:[.,]\d+)?)\s+(GB|TB)").unwrap());
+static ROTATION_RATE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"Rotation Rate:\s+(\d+)\s+rpm").unwrap());
+static IEEE_OUI_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"IEEE OUI Identifier:\s+(0x[0-9a-fA-F]+)").unwrap());
+static CONTROLLER_ID_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"Controller ID:\s+(\d+)").unwrap());
+static NVME_VERSION_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"NVMe Version:\s+(\S+)").unwrap());
+static LOG_PAGE_ATTRIBUTES_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"Log Page Attributes \((0x[0-9a-fA-F]+)\)").unwrap());
+static FIRMWARE_UPDATES_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"Firmware Updates \((0x[0-9a-fA-F]+)\)").unwrap());
+static NAMESPACE_COUNT_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"Number of Namespaces:\s+(\d+)").unwrap());
+static STANDBY_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"Device is in (STANDBY|SLEEP) mode").unwrap());
+
+// smartctl: health data and attribute table
+static PERCENTAGE_USED_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"Percentage Used:\s+(\d+)%").unwrap());
+static NVME_TEMP_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"Temperature:\s+(\d+)\s+Celsius").unwrap());
+static DATA_UNITS_WRITTEN_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"Data Units Written:\s+([\d,.' ]*\d)").unwrap());
+static DATA_UNITS_READ_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"Data Units Read:\s+([\d,.' ]*\d)").unwrap());
+static POWER_CYCLES_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"Power Cycles:\s+([\d,.' ]*\d)").unwrap());
+static POWER_ON_HOURS_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"Power On Hours:\s+([\d,.' ]*\d)").unwrap());
+static UNSAFE_SHUTDOWNS_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"Unsafe Shutdowns:\s+([\d,.' ]*\d)").unwrap());
+static CONTROLLER_BUSY_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"Controller Busy Time:\s+([\d,.' ]*\d)").unwrap());
+static SCTERC_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"SCT Error Recovery Control:\s*\n\s*Read:\s+(\d+|Disabled).*\n\s*Write:\s+(\d+|Disabled)").unwrap());
+static SMART_SUPPORT_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"SMART support is:\s+(Enabled|Disabled)").unwrap());
+static HEALTH_RESULT_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"self-assessment test result:\s+(PASSED|FAILED)").unwrap());
+static MEDIA_ERRORS_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"Media and Data Integrity Errors:\s+([\d,.' ]*\d)").unwrap());
+static ATA_SELF_TEST_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^#\s*1\s+.+?\s{2,}(.+?)\s{2,}\d+%").unwrap());
+static NVME_SELF_TEST_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^\s*0\s+(?:Short|Extended|Vendor specific)\s+(.+?)\s{2,}\d").unwrap());
+static ATTRIBUTE_ROW_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*(\d+)\s+(\S.*?)\s+(0x[0-9a-f]+)\s+(\d+)\s+(\d+)\s+(\d+)\s+(\S+)\s+\S+\s+\S+\s+(.+)$").unwrap());
+static ATTRIBUTE_RAW_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^\s*\d+\s+(\S+)\s+0x[0-9a-f]+(?:\s+\S+){6}\s+(\d+)").unwrap());
+
+// smartctl -l scttemp: history table
+static SCT_INTERVAL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"Temperature Logging Interval:\s+(\d+)\s+minute").unwrap());
+static SCT_ROW_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\s*\d+\s+\d{4}-\d{2}-\d{2} \d{2}:\d{2}\s+(\d+|\?)").unwrap());
+static SCT_SKIP_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*\.\.\.\s+\.\.\(\s*(\d+) skipped\)").unwrap());
+
+// hdparm
+static DRIVE_STATE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"drive state is:\s+(.+)").unwrap());
+static WRITE_CACHING_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"write-caching\s*=\s*(\d)").unwrap());
+static MAX_SECTORS_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"max sectors\s*=\s*(\d+)/(\d+)").unwrap());
+static DCO_REAL_MAX_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"Real max sectors:\s*(\d+)").unwrap());
+static APM_LEVEL_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"APM_level\s*=\s*(\d+|off)").unwrap());
+static AAM_LEVEL_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"acoustic\s*=\s*(\d+)").unwrap());
+
+// nvme-cli
+static FEATURE_VALUE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"Current value:\s*(0x[0-9a-fA-F]+)").unwrap());
+static FPI_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^fpi\s*:\s*(\S+)").unwrap());
+static OACS_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^oacs\s*:\s*(\S+)").unwrap());
+static SUBNQN_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^subnqn[ \t]*:[ \t]*(\S+)").unwrap());
+static VWC_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^vwc[ \t]*:[ \t]*(\S+)").unwrap());
+static HMPRE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^hmpre[ \t]*:[ \t]*(\S+)").unwrap());
+static APSTA_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^apsta[ \t]*:[ \t]*(\S+)").unwrap());
+static APST_ENTRY_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\(ITPT\)\s*:\s*(\d+)\s*ms\s*\n.*\(ITPS\)\s*:\s*(\d+)").unwrap());
+static AFI_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^afi[ \t]*:[ \t]*(\S+)").unwrap());
+static FRS_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^frs(\d)[ \t]*:[ \t]*(0x[0-9a-fA-F]+)[ \t]*\(([^)]*)\)").unwrap());
+static CTRATT_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^ctratt[ \t]*:[ \t]*(\S+)").unwrap());
+static ENDGIDMAX_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^endgidmax[ \t]*:[ \t]*(\S+)").unwrap());
+static LOG_FIELD_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^(\w+)[ \t]*:[ \t]*(0x[0-9a-fA-F]+|\d+)").unwrap());
+static LIST_NS_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^\[\s*\d+\]:(0x[0-9a-fA-F]+)").unwrap());
+static LBADS_IN_USE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"lbads:(\d+).*\(in use\)").unwrap());
+static HSIZE_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\(HSIZE\)\s*:\s*(\S+)").unwrap());
+static NAMESPACE_SUFFIX_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"n\d+$").unwrap());
+static SSTAT_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\(SSTAT\)\s*:\s*(\S+)").unwrap());
+static SPROG_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\(SPROG\)\s*:\s*(\S+)").unwrap());
+static SCDW10_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\(SCDW10\)\s*:\s*(\S+)").unwrap());
+static BLOCK_ERASE_TIME_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"Estimated Time For Block Erase\s*:\s*(\d+)").unwrap());
+static OVERWRITE_TIME_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"Estimated Time For Overwrite\s*:\s*(\d+)").unwrap());
+static CRYPTO_ERASE_TIME_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"Estimated Time For Crypto Erase\s*:\s*(\d+)").unwrap());
+
+// sedutil-cli --query
+static SED_SSC_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^\s*(Opal SSC [\d.]+|Opalite|Pyrite(?: SSC)?(?: [\d.]+)?|Ruby|Enterprise) function").unwrap());
+static SED_FLAG_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\b(\w+)\s*=\s*([YN])\b").unwrap());
+
+// lsblk -P
+static LSBLK_PAIR_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"([A-Z:-]+)="([^"]*)""#).unwrap());
+
+/// Creates a Command that runs a host tool with the C locale, through `flatpak-spawn --host` inside Flatpak.
+/// Localized output (translated labels, decimal commas) would otherwise break the regex parsers.
+///
+/// # Arguments
+/// * `program` - Executable name (e.g., "smartctl") or path
+pub fn c_locale_command(program: impl AsRef<OsStr>) -> Command {
+    host_command(program, &[("LC_ALL", "C")])
+}
+
+/// Where the scanner finds devices, their sysfs attributes and the tools it runs.
+/// The host's trees by default; integration tests point it at a fake tree with stub executables.
+#[derive(Clone, Debug)]
+pub struct SystemRoot {
+    /// Device directory (normally /dev); drives keep their "/dev/..." paths whatever the root
+    pub dev: PathBuf,
+    /// sysfs mount (normally /sys)
+    pub sys: PathBuf,
+    /// procfs mount (normally /proc)
+    pub proc: PathBuf,
+    /// Directory holding smartctl, nvme, hdparm, lsblk and sedutil-cli; None looks them up on PATH.
+    /// Tools missing from it are treated like tools that are not installed.
+    pub bin: Option<PathBuf>,
+    /// Leave drives that runtime power management has suspended unprobed, like drives in standby,
+    /// since reading SMART data would resume them (battery saver)
+    pub skip_suspended: bool,
+}
+
+impl SystemRoot {
+    /// The running system: /dev, /sys, /proc and the tools on PATH.
+    pub fn host() -> Self {
+        Self { dev: "/dev".into(), sys: "/sys".into(), proc: "/proc".into(), bin: None, skip_suspended: false }
+    }
+
+    /// A fake system laid out under one directory as `dev/`, `sys/`, `proc/` and `bin/`.
+    ///
+    /// # Arguments
+    /// * `dir` - Directory holding the trees
+    pub fn under(dir: impl Into<PathBuf>) -> Self {
+        let dir = dir.into();
+        Self {
+            dev: dir.join("dev"),
+            sys: dir.join("sys"),
+            proc: dir.join("proc"),
+            bin: Some(dir.join("bin")),
+            skip_suspended: false,
+        }
+    }
+
+    /// Creates a C-locale Command for one of the tools the scanner runs (see `c_locale_command`).
+    ///
+    /// # Arguments
+    /// * `program` - Executable name (e.g., "smartctl")
+    pub fn command(&self, program: &str) -> Command {
+        match &self.bin {
+            // Stub tools live next to the fake tree, so they run directly even inside a sandbox
+            Some(bin) => {
+                let mut cmd = Command::new(bin.join(program));
+                cmd.env("LC_ALL", "C");
+                cmd
+            }
+            None => c_locale_command(program),
+        }
+    }
+}
 
 /// Scans /dev for NVMe and SATA/HDD drives and collects SMART data.
 /// Returns a vector of DiskInfo structures sorted by device path.
@@ -15,37 +224,30 @@ use sysinfo::Disks;
 /// # Errors
 /// Returns an error string if /dev cannot be read or if no drives are found.
 pub fn scan_disks() -> Result<Vec<DiskInfo>, String> {
-    use std::fs;
-    let mut out = Vec::new();
+    scan_disks_with_progress(|_| {})
+}
 
-    // Read entries from /dev directory
-    let dev_entries = fs::read_dir("/dev").map_err(|e| format!("failed to read /dev: {}", e))?;
-    
-    for entry in dev_entries {
-        if let Ok(e) = entry {
-            let name = e.file_name().into_string().unwrap_or_default();
-
-            // Detect NVMe drives (nvme0n1, nvme1n1, etc.)
-            // Filter out partitions which contain 'p' (nvme0n1p1, nvme0n1p2)
-            if name.starts_with("nvme") && !name.contains('p') {
-                let dev_path = format!("/dev/{}", name);
-                if let Ok(mut di) = probe_smart(&dev_path, "NVMe") {
-                    get_partitions(&name, &mut di);
-                    out.push(di);
-                }
-            }
+/// Same as `scan_disks`, reporting each device path before it is probed
+/// (probing a drive can take seconds, e.g., while an HDD spins up).
+///
+/// # Arguments
+/// * `progress` - Called with the device path (e.g., "/dev/sdb") about to be probed
+pub fn scan_disks_with_progress(progress: impl FnMut(&str)) -> Result<Vec<DiskInfo>, String> {
+    scan_disks_from(&SystemRoot::host(), progress)
+}
 
-            // Detect SATA drives (sda, sdb, sdc, etc.)
-            // Only 3-character names to avoid partitions like sda1
-            if name.starts_with("sd") && name.len() == 3 {
-                let dev_path = format!("/dev/{}", name);
-                // Check if it's an SSD or HDD by reading rotational flag
-                let kind = if is_ssd(&name) { "SATA" } else { "HDD" };
-                if let Ok(mut di) = probe_smart(&dev_path, kind) {
-                    get_partitions(&name, &mut di);
-                    out.push(di);
-                }
-            }
+/// Same as `scan_disks_with_progress`, reading devices and running tools under `root`.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `progress` - Called with the device path (e.g., "/dev/sdb") about to be probed
+pub fn scan_disks_from(root: &SystemRoot, mut progress: impl FnMut(&str)) -> Result<Vec<DiskInfo>, String> {
+    let mut out = Vec::new();
+
+    for dev in detected_devices_from(root)? {
+        progress(&dev);
+        if let Ok(di) = probe_device(root, dev.trim_start_matches("/dev/")) {
+            out.push(di);
         }
     }
 
@@ -54,21 +256,250 @@ pub fn scan_disks() -> Result<Vec<DiskInfo>, String> {
     Ok(out)
 }
 
+/// Lists the whole NVMe and SATA drives in /dev, including ones that cannot be probed.
+///
+/// # Returns
+/// Device paths (e.g., "/dev/sda"), or an error if /dev cannot be read.
+pub fn detected_devices() -> Result<Vec<String>, String> {
+    detected_devices_from(&SystemRoot::host())
+}
+
+/// Lists the whole NVMe and SATA drives in the device directory of `root`.
+///
+/// # Arguments
+/// * `root` - Device and sysfs locations
+///
+/// # Returns
+/// Device paths (e.g., "/dev/sda"), or an error if the device directory cannot be read.
+pub fn detected_devices_from(root: &SystemRoot) -> Result<Vec<String>, String> {
+    let dev_entries =
+        std::fs::read_dir(&root.dev).map_err(|e| format!("failed to read {}: {}", root.dev.display(), e))?;
+    let mut devices: Vec<String> = dev_entries
+        .flatten()
+        .map(|e| e.file_name().into_string().unwrap_or_default())
+        .filter(|name| drive_kind(root, name).is_some())
+        .map(|name| format!("/dev/{}", name))
+        .collect();
+    devices.sort();
+    Ok(devices)
+}
+
+/// Probes a single drive, e.g., to rescan one device without waking every disk in the system.
+///
+/// # Arguments
+/// * `dev` - Device path (e.g., "/dev/sdb")
+///
+/// # Returns
+/// The drive's information, or an error if the path is not a whole NVMe/SATA drive or probing failed.
+pub fn scan_disk(dev: &str) -> Result<DiskInfo, String> {
+    probe_device(&SystemRoot::host(), dev.trim_start_matches("/dev/"))
+}
+
+/// Which kind of drive a /dev entry is.
+///
+/// # Returns
+/// "NVMe", "SATA" or "HDD" for whole drives, None for partitions and other devices.
+fn drive_kind(root: &SystemRoot, name: &str) -> Option<&'static str> {
+    // NVMe drives (nvme0n1); partitions contain 'p' (nvme0n1p1)
+    if name.starts_with("nvme") && !name.contains('p') {
+        return Some("NVMe");
+    }
+    // SATA drives (sda, sdb); only 3-character names to avoid partitions like sda1
+    if name.starts_with("sd") && name.len() == 3 {
+        // Check if it's an SSD or HDD by reading rotational flag
+        return Some(if is_ssd(root, name) { "SATA" } else { "HDD" });
+    }
+    None
+}
+
+/// Probes one drive's SMART data, partitions, queue settings and by-id link.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `name` - Device name without "/dev/" (e.g., "nvme0n1", "sda")
+fn probe_device(root: &SystemRoot, name: &str) -> Result<DiskInfo, String> {
+    let kind = drive_kind(root, name).ok_or_else(|| format!("/dev/{} is not a whole NVMe or SATA drive", name))?;
+    let mut di = match network_transport(root, name) {
+        Some(transport) => probe_network(root, name, kind, transport),
+        None => probe_smart(root, &format!("/dev/{}", name), kind)?,
+    };
+    di.removable = is_removable_media(root, name, &di);
+    get_partitions(root, name, &mut di);
+    di.queue = get_queue_info(root, name);
+    di.link_power = get_link_power(root, name);
+    di.by_id = find_by_id(root, name);
+    Ok(di)
+}
+
+/// Tells whether a drive is served over the network, from the transport of its NVMe controller
+/// or the iSCSI session its SCSI device hangs off.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `dev_name` - Device name (e.g., "nvme1n1", "sdc")
+///
+/// # Returns
+/// The fabric (e.g., "NVMe/TCP", "iSCSI"), or None for local drives.
+fn network_transport(root: &SystemRoot, dev_name: &str) -> Option<String> {
+    let device = root.sys.join(format!("block/{}/device", dev_name));
+    if dev_name.starts_with("nvme") {
+        // The namespace's device is its controller, or with native multipath the subsystem holding the controllers
+        let transport = std::fs::read_to_string(device.join("transport")).ok().or_else(|| {
+            std::fs::read_dir(&device)
+                .ok()?
+                .flatten()
+                .filter(|e| e.file_name().to_string_lossy().starts_with("nvme"))
+                .find_map(|e| std::fs::read_to_string(e.path().join("transport")).ok())
+        })?;
+        return nvme_transport_name(transport.trim());
+    }
+    let resolved = std::fs::canonicalize(device).ok()?;
+    is_iscsi_device(&resolved).then(|| "iSCSI".to_string())
+}
+
+/// Names an NVMe controller transport as read from sysfs.
+///
+/// # Arguments
+/// * `transport` - Contents of `/sys/class/nvme/<ctrl>/transport` (e.g., "pcie", "tcp", "rdma")
+///
+/// # Returns
+/// The fabric (e.g., "NVMe/TCP"), or None for local PCIe controllers.
+pub fn nvme_transport_name(transport: &str) -> Option<String> {
+    match transport {
+        "pcie" | "" => None,
+        "loop" => Some("NVMe loop".to_string()),
+        other => Some(format!("NVMe/{}", other.to_uppercase())),
+    }
+}
+
+/// Tells whether a resolved SCSI device path belongs to an iSCSI session.
+///
+/// # Arguments
+/// * `device` - Resolved `/sys/block/<dev>/device` link
+///   (e.g., "/sys/devices/platform/host3/session1/target3:0:0/3:0:0:0")
+pub fn is_iscsi_device(device: &std::path::Path) -> bool {
+    device.components().filter_map(|c| c.as_os_str().to_str()).any(|c| {
+        c.strip_prefix("session").is_some_and(|n| !n.is_empty() && n.chars().all(|ch| ch.is_ascii_digit()))
+    })
+}
+
+/// Tells whether a drive is a card reader or flash stick rather than a disk.
+/// The kernel's removable flag catches card readers and most sticks; sticks that clear it are caught by
+/// sitting on USB without passing any SMART data through (USB SSDs behind a SAT bridge do pass it).
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `dev_name` - Device name (e.g., "sdb")
+/// * `di` - The drive as probed so far
+fn is_removable_media(root: &SystemRoot, dev_name: &str, di: &DiskInfo) -> bool {
+    let flagged =
+        std::fs::read_to_string(root.sys.join(format!("block/{}/removable", dev_name))).is_ok_and(|v| v.trim() == "1");
+    if flagged {
+        return true;
+    }
+    // A sleeping placeholder has no SMART data to judge by
+    !di.cached
+        && !di.has_smart_data()
+        && std::fs::canonicalize(root.sys.join(format!("block/{}/device", dev_name))).is_ok_and(|device| is_usb_device(&device))
+}
+
+/// Tells whether a resolved block device path goes through a USB controller.
+///
+/// # Arguments
+/// * `device` - Resolved `/sys/block/<dev>/device` link
+///   (e.g., "/sys/devices/pci0000:00/0000:00:14.0/usb2/2-1/2-1:1.0/host4/target4:0:0/4:0:0:0")
+pub fn is_usb_device(device: &std::path::Path) -> bool {
+    device.components().filter_map(|c| c.as_os_str().to_str()).any(|c| {
+        c.strip_prefix("usb").is_some_and(|n| !n.is_empty() && n.chars().all(|ch| ch.is_ascii_digit()))
+    })
+}
+
+/// Describes a network-attached drive from sysfs alone.
+/// Its SMART data (if the target passes any through) describes the remote storage, so smartctl,
+/// hdparm and nvme-cli are not run; identity and capacity come from the kernel.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `dev_name` - Device name (e.g., "nvme1n1", "sdc")
+/// * `hint_kind` - Type hint from `drive_kind`
+/// * `transport` - Fabric the drive is reached over
+fn probe_network(root: &SystemRoot, dev_name: &str, hint_kind: &str, transport: String) -> DiskInfo {
+    let mut di = DiskInfo::empty(format!("/dev/{}", dev_name));
+    di.kind = hint_kind.to_string();
+    let device = root.sys.join(format!("block/{}/device", dev_name));
+    let read = |path: PathBuf| std::fs::read_to_string(path).ok().map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+
+    if hint_kind == "NVMe" {
+        di.protocol = Some("NVMe".to_string());
+        // Identity attributes live on the controller; with multipath, on the subsystem
+        di.model = read(device.join("model"));
+        di.serial = read(device.join("serial"));
+        di.firmware = read(device.join("firmware_rev"));
+    } else {
+        di.protocol = Some("SCSI".to_string());
+        let model = [read(device.join("vendor")), read(device.join("model"))].into_iter().flatten().collect::<Vec<_>>().join(" ");
+        di.model = (!model.is_empty()).then_some(model);
+        di.firmware = read(device.join("rev"));
+    }
+    di.wwn = read(root.sys.join(format!("block/{}/wwid", dev_name))).or_else(|| read(device.join("wwid")));
+
+    // The size attribute counts 512-byte sectors whatever the logical block size
+    let sectors = read(root.sys.join(format!("block/{}/size", dev_name))).and_then(|s| s.parse::<u64>().ok());
+    if let Some(bytes) = sectors.filter(|&s| s > 0).map(|s| s * 512) {
+        di.capacity = Some(bytes as f64);
+        di.capacity_str = Some(format_capacity(bytes));
+    }
+    di.network_transport = Some(transport);
+    di
+}
+
+/// Formats a capacity the way smartctl does in brackets: three significant digits in GB or TB.
+///
+/// # Arguments
+/// * `bytes` - Capacity in bytes
+///
+/// # Returns
+/// e.g., "500 GB", "1.00 TB", "32.0 GB".
+pub fn format_capacity(bytes: u64) -> String {
+    let (value, unit) = if bytes >= 1_000_000_000_000 { (bytes as f64 / 1e12, "TB") } else { (bytes as f64 / 1e9, "GB") };
+    let decimals = if value >= 100.0 { 0 } else if value >= 10.0 { 1 } else { 2 };
+    format!("{:.*} {}", decimals, value, unit)
+}
+
 /// Populates partition information for a given drive.
 /// Uses sysinfo to enumerate mounted partitions and collect usage statistics.
 ///
 /// # Arguments
+/// * `root` - Device, sysfs and tool locations
 /// * `dev_name` - Base device name (e.g., "nvme0n1", "sda")
 /// * `di` - DiskInfo structure to populate with partition data
-fn get_partitions(dev_name: &str, di: &mut DiskInfo) {
+fn get_partitions(root: &SystemRoot, dev_name: &str, di: &mut DiskInfo) {
     // Refresh the list of mounted disks
     let disks = Disks::new_with_refreshed_list();
+    // Boundary every partition on this drive should be aligned to
+    let alignment = alignment_boundary(root, dev_name, di.kind == "HDD");
+    // GPT names, UUIDs and type GUIDs for every partition on this drive
+    let table = lsblk_partitions(root, dev_name);
+    di.unallocated_bytes = unallocated_bytes(&table);
+    // Mount points of the drive and everything stacked on it (LVM volumes, LUKS mappings)
+    let mounts = root.command("lsblk")
+        .args(["-P", "-o", "NAME,MOUNTPOINT,PARTTYPE", &format!("/dev/{}", dev_name)])
+        .output()
+        .map(|o| parse_lsblk_pairs(&String::from_utf8_lossy(&o.stdout)))
+        .unwrap_or_default();
+    (di.hosts_root, di.hosts_esp) = system_roles(&mounts);
+    // Mount options keyed by mount point
+    let options = std::fs::read_to_string(root.proc.join("self/mountinfo"))
+        .map(|text| parse_mountinfo(&text))
+        .unwrap_or_default();
 
     for disk in disks.iter() {
         let disk_name = disk.name().to_string_lossy();
-        
-        // Match partitions belonging to this device
-        if disk_name.contains(dev_name) {
+        let mount_point = disk.mount_point().display().to_string();
+
+        // Match partitions belonging to this device; sysinfo always lists the host's mounts,
+        // so only the ones in the mount table of `root` count
+        if disk_name.contains(dev_name) && options.contains_key(&mount_point) {
             // Calculate space metrics in gigabytes
             let total = disk.total_space() as f64 / 1_000_000_000.0;
             let available = disk.available_space() as f64 / 1_000_000_000.0;
@@ -79,8 +510,28 @@ fn get_partitions(dev_name: &str, di: &mut DiskInfo) {
                 0.0
             };
 
+            // sysinfo reports "/dev/sda1"; sysfs uses the bare kernel name
+            let device = disk_name.trim_start_matches("/dev/").to_string();
+            let start_bytes = std::fs::read_to_string(root.sys.join(format!("block/{}/{}/start", dev_name, device)))
+                .ok()
+                .and_then(|s| s.trim().parse::<u64>().ok())
+                .map(|sectors| sectors * 512);
+            // Filesystems that allocate inodes dynamically (btrfs) report zero
+            let inodes = statvfs(disk.mount_point()).ok().filter(|s| s.files() > 0);
+            let gpt = table.iter().find(|row| row.get("NAME") == Some(&device));
+            let field = |key: &str| gpt.and_then(|row| row.get(key)).filter(|v| !v.is_empty()).cloned();
             di.partitions.push(PartitionInfo {
-                mount_point: disk.mount_point().display().to_string(),
+                fs_label: field("LABEL"),
+                part_label: field("PARTLABEL"),
+                part_uuid: field("PARTUUID"),
+                part_type: field("PARTTYPE"),
+                inodes_total: inodes.as_ref().map(|s| s.files()),
+                inodes_used: inodes.as_ref().map(|s| s.files().saturating_sub(s.files_free())),
+                start_bytes,
+                alignment_bytes: alignment,
+                device,
+                mount_options: options.get(&mount_point).cloned().unwrap_or_default(),
+                mount_point,
                 fs_type: disk.file_system().to_string_lossy().into_owned(),
                 total_gb: total,
                 used_gb: used,
@@ -91,16 +542,192 @@ fn get_partitions(dev_name: &str, di: &mut DiskInfo) {
     }
 }
 
+/// Lists partition table details and filesystem labels for a drive using `lsblk -P`.
+/// Failures are silently ignored and yield an empty list.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `dev_name` - Base device name (e.g., "nvme0n1", "sda")
+fn lsblk_partitions(root: &SystemRoot, dev_name: &str) -> Vec<HashMap<String, String>> {
+    root.command("lsblk")
+        .args(["-P", "-b", "-o", "NAME,TYPE,SIZE,LABEL,PARTLABEL,PARTUUID,PARTTYPE", &format!("/dev/{}", dev_name)])
+        .output()
+        .map(|o| parse_lsblk_pairs(&String::from_utf8_lossy(&o.stdout)))
+        .unwrap_or_default()
+}
+
+/// Computes space on a drive that is not covered by any partition.
+/// Small gaps from the partition table and 1 MiB alignment are not reported.
+///
+/// # Arguments
+/// * `table` - Parsed `lsblk -P -b` rows including TYPE and SIZE columns
+///
+/// # Returns
+/// Unallocated bytes, or None if the drive is not partitioned or the gap is negligible.
+pub fn unallocated_bytes(table: &[HashMap<String, String>]) -> Option<u64> {
+    let size = |row: &HashMap<String, String>| row.get("SIZE").and_then(|s| s.parse::<u64>().ok());
+    let disk = table.iter().find(|r| r.get("TYPE").map(String::as_str) == Some("disk"))?;
+    let parts: Vec<&HashMap<String, String>> =
+        table.iter().filter(|r| r.get("TYPE").map(String::as_str) == Some("part")).collect();
+
+    // Whole-disk filesystems have no partition table to leave gaps in
+    if parts.is_empty() {
+        return None;
+    }
+
+    let used: u64 = parts.iter().filter_map(|r| size(r)).sum();
+    let free = size(disk)?.saturating_sub(used);
+
+    // Anything below 16 MiB is partition table overhead and alignment padding
+    if free >= 16 * 1024 * 1024 {
+        Some(free)
+    } else {
+        None
+    }
+}
+
+/// Tells whether a drive holds the running system: the root filesystem, or the mounted EFI system partition.
+///
+/// # Arguments
+/// * `rows` - Parsed `lsblk -P -o NAME,MOUNTPOINT,PARTTYPE` rows of the drive and its children
+///
+/// # Returns
+/// (hosts root, hosts the mounted ESP)
+pub fn system_roles(rows: &[HashMap<String, String>]) -> (bool, bool) {
+    let mounted = |row: &HashMap<String, String>| row.get("MOUNTPOINT").is_some_and(|m| !m.is_empty());
+    let root = rows.iter().any(|row| row.get("MOUNTPOINT").is_some_and(|m| m == "/"));
+    let esp = rows
+        .iter()
+        .any(|row| mounted(row) && row.get("PARTTYPE").and_then(|t| partition_type_name(t)) == Some("EFI System"));
+    (root, esp)
+}
+
+/// Parses `lsblk -P` output, where every line is a list of KEY="value" pairs.
+///
+/// # Arguments
+/// * `text` - lsblk output (e.g., `NAME="sda1" PARTLABEL="EFI system partition"`)
+///
+/// # Returns
+/// One map per output line, keyed by column name.
+pub fn parse_lsblk_pairs(text: &str) -> Vec<HashMap<String, String>> {
+    text.lines()
+        .map(|line| {
+            LSBLK_PAIR_RE
+                .captures_iter(line)
+                // lsblk escapes unsafe characters as \xNN; a space is the common case
+                .map(|c| (c[1].to_string(), c[2].replace("\\x20", " ")))
+                .collect::<HashMap<_, _>>()
+        })
+        .filter(|row| !row.is_empty())
+        .collect()
+}
+
+/// Returns a readable name for common GPT partition type GUIDs and MBR type codes.
+///
+/// # Arguments
+/// * `part_type` - Type GUID or MBR code as reported by lsblk
+///
+/// # Returns
+/// The type name, or None for unrecognised types.
+pub fn partition_type_name(part_type: &str) -> Option<&'static str> {
+    Some(match part_type.to_lowercase().as_str() {
+        "c12a7328-f81f-11d2-ba4b-00a0c93ec93b" | "0xef" => "EFI System",
+        "21686148-6449-6e6f-744e-656564454649" => "BIOS boot",
+        "0fc63daf-8483-4772-8e79-3d69d8477de4" | "0x83" => "Linux filesystem",
+        "4f68bce3-e8cd-4db1-96e7-fbcaf984b709" => "Linux root (x86-64)",
+        "933ac7e1-2eb4-4f13-b844-0e14e2aef915" => "Linux home",
+        "0657fd6d-a4ab-43c4-84e5-0933c84b4f4f" | "0x82" => "Linux swap",
+        "e6d6d379-f507-44c2-a23c-238f2a3df928" | "0x8e" => "Linux LVM",
+        "a19d880f-05fc-4d3b-a006-743f0f84911e" | "0xfd" => "Linux RAID",
+        "ca7d7ccb-63ed-4c53-861c-1742536059cc" => "Linux LUKS",
+        "bc13c2ff-59e6-4262-a352-b275fd6f7172" => "Linux extended boot",
+        "ebd0a0a2-b9e5-4433-87c0-68b6b72699c7" | "0x07" => "Microsoft basic data",
+        "e3c9e316-0b5c-4db8-817d-f92df00215ae" => "Microsoft reserved",
+        "de94bba4-06d1-4d40-a16a-bfd50179d6ac" => "Windows recovery",
+        "48465300-0000-11aa-aa11-00306543ecac" => "Apple HFS+",
+        "7c3457ef-0000-11aa-aa11-00306543ecac" => "Apple APFS",
+        "0x0c" | "0x0b" => "FAT32",
+        _ => return None,
+    })
+}
+
+/// Determines the boundary partitions on a drive should start on.
+/// HDDs only need physical sector alignment; SSDs should align to the erase block,
+/// which the kernel does not expose, so the conventional 1 MiB boundary is used.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `dev_name` - Base device name (e.g., "nvme0n1", "sda")
+/// * `rotational` - True for spinning drives
+///
+/// # Returns
+/// Alignment in bytes, or None if the drive's block sizes cannot be read.
+fn alignment_boundary(root: &SystemRoot, dev_name: &str, rotational: bool) -> Option<u64> {
+    let read = |file: &str| {
+        std::fs::read_to_string(root.sys.join(format!("block/{}/queue/{}", dev_name, file)))
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+    };
+    let physical = read("physical_block_size")?;
+    let optimal_io = read("optimal_io_size").unwrap_or(0);
+
+    let boundary = physical.max(optimal_io);
+    Some(if rotational { boundary } else { boundary.max(1024 * 1024) })
+}
+
+/// Parses `/proc/self/mountinfo` into mount options per mount point.
+/// Per-mount options (ro, noatime) and superblock options (discard, errors=)
+/// are merged because both affect how the filesystem treats the drive.
+///
+/// # Arguments
+/// * `text` - Contents of /proc/self/mountinfo
+///
+/// # Returns
+/// A map from mount point to its de-duplicated option list.
+pub fn parse_mountinfo(text: &str) -> HashMap<String, Vec<String>> {
+    let mut out = HashMap::new();
+    for line in text.lines() {
+        // Format: "ID PARENT MAJ:MIN ROOT MOUNTPOINT OPTIONS [OPTIONAL...] - FSTYPE SOURCE SUPEROPTIONS"
+        let Some((left, right)) = line.split_once(" - ") else {
+            continue;
+        };
+        let fields: Vec<&str> = left.split_whitespace().collect();
+        if fields.len() < 6 {
+            continue;
+        }
+
+        // Mount points escape whitespace as octal (e.g., "\040" for a space)
+        let mount_point = fields[4]
+            .replace("\\040", " ")
+            .replace("\\011", "\t")
+            .replace("\\012", "\n")
+            .replace("\\134", "\\");
+
+        let mut options: Vec<String> = Vec::new();
+        let super_options = right.split_whitespace().nth(2).unwrap_or("");
+        for opt in fields[5].split(',').chain(super_options.split(',')) {
+            if !opt.is_empty() && !options.iter().any(|o| o == opt) {
+                options.push(opt.to_string());
+            }
+        }
+
+        // Later entries over-mount earlier ones, so the last line wins
+        out.insert(mount_point, options);
+    }
+    out
+}
+
 /// Determines if a drive is an SSD by checking the rotational flag.
 /// SSDs have rotational=0, HDDs have rotational=1.
 ///
 /// # Arguments
+/// * `root` - Device, sysfs and tool locations
 /// * `dev_name` - Device name (e.g., "sda")
 ///
 /// # Returns
 /// True if the device is an SSD, false if it's an HDD or the flag cannot be read.
-fn is_ssd(dev_name: &str) -> bool {
-    let path = format!("/sys/block/{}/queue/rotational", dev_name);
+fn is_ssd(root: &SystemRoot, dev_name: &str) -> bool {
+    let path = root.sys.join(format!("block/{}/queue/rotational", dev_name));
     if let Ok(s) = std::fs::read_to_string(path) {
         s.trim() == "0"
     } else {
@@ -108,34 +735,241 @@ fn is_ssd(dev_name: &str) -> bool {
     }
 }
 
+/// Finds the most descriptive `/dev/disk/by-id/` symlink pointing at a device.
+/// Model/serial based names (ata-..., nvme-...) are preferred over bare WWN/EUI names.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `dev_name` - Device name (e.g., "sda", "nvme0n1")
+///
+/// # Returns
+/// The full symlink path, or None if udev has not created one.
+fn find_by_id(root: &SystemRoot, dev_name: &str) -> Option<String> {
+    let entries = std::fs::read_dir(root.dev.join("disk/by-id")).ok()?;
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter(|e| {
+            std::fs::read_link(e.path())
+                .ok()
+                .and_then(|t| t.file_name().map(|n| n == dev_name))
+                .unwrap_or(false)
+        })
+        .filter_map(|e| e.file_name().into_string().ok())
+        .collect();
+
+    // Sort so that identifier-only names come last, then alphabetically for stability
+    names.sort_by_key(|n| (n.starts_with("wwn-") || n.contains("eui.") || n.ends_with("_1"), n.clone()));
+    names.first().map(|n| format!("/dev/disk/by-id/{}", n))
+}
+
+/// Reads the link power management policy of the SATA port a drive hangs off.
+/// Only AHCI hosts have the policy file, so NVMe and USB drives yield None.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `dev_name` - Device name (e.g., "sda")
+fn get_link_power(root: &SystemRoot, dev_name: &str) -> Option<LinkPower> {
+    let device = std::fs::canonicalize(root.sys.join(format!("block/{}/device", dev_name))).ok()?;
+    let host = scsi_host_name(&device)?;
+    let policy =
+        std::fs::read_to_string(root.sys.join(format!("class/scsi_host/{}/link_power_management_policy", host))).ok()?;
+    Some(LinkPower { host, policy: policy.trim().to_string() })
+}
+
+/// Finds the SCSI host in the resolved sysfs path of a SCSI device.
+///
+/// # Arguments
+/// * `device` - Resolved `/sys/block/<dev>/device` link
+///   (e.g., "/sys/devices/pci0000:00/0000:00:17.0/ata3/host2/target2:0:0/2:0:0:0")
+///
+/// # Returns
+/// The host name (e.g., "host2"), or None if the path has no host component.
+pub fn scsi_host_name(device: &std::path::Path) -> Option<String> {
+    device
+        .components()
+        .filter_map(|c| c.as_os_str().to_str())
+        .find(|c| c.strip_prefix("host").is_some_and(|n| !n.is_empty() && n.chars().all(|ch| ch.is_ascii_digit())))
+        .map(str::to_string)
+}
+
+/// Reads I/O scheduler and queue settings from sysfs.
+/// Sysfs reads never wake a sleeping drive, so this runs for every device.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `dev_name` - Device name (e.g., "sda", "nvme0n1")
+///
+/// # Returns
+/// None if the device has no queue directory in sysfs.
+fn get_queue_info(root: &SystemRoot, dev_name: &str) -> Option<QueueInfo> {
+    use std::fs;
+    let base = root.sys.join("block").join(dev_name);
+    let read_u32 = |path: PathBuf| fs::read_to_string(path).ok().and_then(|s| s.trim().parse::<u32>().ok());
+
+    let scheduler = fs::read_to_string(base.join("queue/scheduler")).ok()?;
+    let (active, available) = parse_scheduler(&scheduler);
+
+    Some(QueueInfo {
+        scheduler: active,
+        available_schedulers: available,
+        nr_requests: read_u32(base.join("queue/nr_requests")),
+        // Only SCSI-backed devices (SATA/SAS/USB) expose a device queue depth
+        queue_depth: read_u32(base.join("device/queue_depth")),
+        // Each entry under mq/ is one hardware submission queue
+        hw_queues: fs::read_dir(base.join("mq")).ok().map(|d| d.flatten().count()),
+    })
+}
+
+/// Parses the contents of `/sys/block/<dev>/queue/scheduler`.
+/// The active scheduler is the one wrapped in square brackets.
+///
+/// # Arguments
+/// * `s` - File contents (e.g., "[mq-deadline] kyber bfq none")
+///
+/// # Returns
+/// The active scheduler (if any) and the list of all available schedulers.
+pub fn parse_scheduler(s: &str) -> (Option<String>, Vec<String>) {
+    let mut active = None;
+    let mut available = Vec::new();
+    for token in s.split_whitespace() {
+        let name = token.trim_start_matches('[').trim_end_matches(']');
+        if token.starts_with('[') {
+            active = Some(name.to_string());
+        }
+        available.push(name.to_string());
+    }
+    (active, available)
+}
+
 /// Executes smartctl to retrieve SMART data for a specific drive.
-/// Parses the output to extract model, serial, temperature, health, and usage metrics.
+/// Parses the output and runs additional device-specific probes (NVMe operations, HPA/DCO).
 ///
 /// # Arguments
+/// * `root` - Device, sysfs and tool locations
 /// * `dev` - Device path (e.g., "/dev/nvme0n1")
 /// * `hint_kind` - Type hint ("NVMe", "SATA", or "HDD")
 ///
 /// # Returns
 /// A populated DiskInfo structure on success, or an error string on failure.
-fn probe_smart(dev: &str, hint_kind: &str) -> Result<DiskInfo, String> {
-    // Execute smartctl with all attributes flag
-    let output = Command::new("smartctl")
-        .args(["-a", dev])
+fn probe_smart(root: &SystemRoot, dev: &str, hint_kind: &str) -> Result<DiskInfo, String> {
+    let is_ata = hint_kind != "NVMe";
+
+    // A runtime-suspended drive would be resumed by any command, so in battery saver it is left alone
+    if root.skip_suspended && is_runtime_suspended(root, dev.trim_start_matches("/dev/")) {
+        return Ok(sleeping_placeholder(dev, hint_kind, Some("suspended".to_string())));
+    }
+
+    // Check the ATA power state first so sleeping drives are not spun up
+    let power_state = if is_ata { ata_power_state(root, dev) } else { None };
+    if power_state.as_deref().is_some_and(is_sleep_state) {
+        return Ok(sleeping_placeholder(dev, hint_kind, power_state));
+    }
+
+    // Execute smartctl with all attributes flag (plus the SCT ERC timeouts for ATA)
+    // `-n standby` makes smartctl bail out instead of waking an ATA drive (in case hdparm is missing)
+    let args: &[&str] = if is_ata { &["-n", "standby", "-a", "-l", "scterc", dev] } else { &["-a", dev] };
+    let output = root.command("smartctl")
+        .args(args)
         .output()
         .map_err(|e| format!("failed to run smartctl on {}: {}", dev, e))?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
+    if let Some(cap) = STANDBY_RE.captures(&stdout) {
+        return Ok(sleeping_placeholder(dev, hint_kind, Some(cap[1].to_lowercase())));
+    }
+
+    let mut di = parse_smartctl_output(dev, hint_kind, &stdout);
+    di.power_state = power_state;
+
+    if hint_kind == "NVMe" {
+        di.power_state = nvme_power_state(root, dev);
+        di.write_cache = nvme_write_cache(root, dev);
+        let id_ctrl = nvme_id_ctrl(root, dev, &di);
+        // smartctl does not print the subsystem NQN, VWC or HMB sizes; they come from nvme-cli's Identify Controller dump
+        if let Some(controller) = di.nvme_controller.as_mut() {
+            if let Some(id_ctrl) = &id_ctrl {
+                parse_id_ctrl(id_ctrl, controller);
+            }
+            // Only DRAM-less drives ask for a host memory buffer
+            if controller.hmb_preferred_bytes.is_some_and(|bytes| bytes > 0) {
+                controller.hmb_allocated_bytes = nvme_hmb_allocation(root, dev);
+            }
+        }
+        let probes = nvme_static_probes(root, dev, &di, id_ctrl.as_deref());
+        if let Some(controller) = di.nvme_controller.as_mut() {
+            controller.firmware_slots = probes.firmware_slots;
+            controller.apst = probes.apst;
+            controller.endurance_groups = probes.endurance_groups;
+            controller.namespace_usage = probes.namespace_usage;
+        }
+        di.sed = probes.sed;
+        // Check for a running sanitize or format operation
+        di.operation = probes.sanitize;
+        if di.operation.is_none() {
+            probe_format_progress(root, dev, &mut di);
+        }
+    } else {
+        // Check for capacity hidden by HPA/DCO (ATA only)
+        probe_capacity_limits(root, dev, &mut di);
+        // Read APM/AAM levels and write cache state (ATA only)
+        probe_power_management(root, dev, &mut di);
+        di.write_cache = ata_write_cache(root, dev);
+        // Read the security feature set state (ATA only)
+        di.security = root.command("hdparm")
+            .args(["-I", dev])
+            .output()
+            .ok()
+            .and_then(|o| parse_hdparm_security(&String::from_utf8_lossy(&o.stdout)));
+        di.sed = probe_sed(root, dev, hint_kind, None);
+    }
+
+    Ok(di)
+}
+
+/// Parses `smartctl -a` output into a DiskInfo structure.
+/// Extracts model, serial, temperature, health, usage metrics, and the SMART attribute table.
+/// Performs no I/O, so it can be exercised directly against captured output.
+///
+/// # Arguments
+/// * `dev` - Device path (e.g., "/dev/nvme0n1")
+/// * `hint_kind` - Type hint ("NVMe", "SATA", or "HDD")
+/// * `stdout` - The full smartctl output text
+pub fn parse_smartctl_output(dev: &str, hint_kind: &str, stdout: &str) -> DiskInfo {
     let mut di = DiskInfo::empty(dev.to_string());
     di.kind = hint_kind.to_string();
 
-    // Helper function to create regex patterns
-    let re = |pat: &str| Regex::new(pat).unwrap();
-
     // Extract basic drive information
-    extract_into(&stdout, r"Model Number:\s+(.+)", &mut di.model);
-    extract_into(&stdout, r"Device Model:\s+(.+)", &mut di.model);
-    extract_into(&stdout, r"Serial Number:\s+(.+)", &mut di.serial);
-    extract_into(&stdout, r"Firmware Version:\s+(.+)", &mut di.firmware);
+    extract_into(stdout, &MODEL_NUMBER_RE, &mut di.model);
+    extract_into(stdout, &DEVICE_MODEL_RE, &mut di.model);
+    extract_into(stdout, &SERIAL_NUMBER_RE, &mut di.serial);
+    extract_into(stdout, &FIRMWARE_VERSION_RE, &mut di.firmware);
+
+    // World Wide Name for ATA drives ("5 000cca 2731a2b3c"), EUI-64 for NVMe namespaces
+    if let Some(cap) = WWN_RE.captures(stdout) {
+        di.wwn = Some(format!("0x{}{}{}", &cap[1], &cap[2], &cap[3]).to_lowercase());
+    } else if let Some(cap) = EUI64_RE.captures(stdout) {
+        di.wwn = Some(format!("eui.{}{}", &cap[1], &cap[2]).to_lowercase());
+    }
+
+    // NVMe Identify Controller fields ("Controller ID:  8224", "NVMe Version:  1.4", ...)
+    let controller = NvmeController {
+        controller_id: CONTROLLER_ID_RE.captures(stdout).and_then(|c| c[1].parse().ok()),
+        ieee_oui: IEEE_OUI_RE.captures(stdout).map(|c| c[1].to_lowercase()),
+        nvme_version: NVME_VERSION_RE.captures(stdout).map(|c| c[1].to_string()),
+        namespaces: NAMESPACE_COUNT_RE.captures(stdout).and_then(|c| c[1].parse().ok()),
+        log_page_attributes: LOG_PAGE_ATTRIBUTES_RE
+            .captures(stdout)
+            .and_then(|c| parse_nvme_int(&c[1]))
+            .and_then(|v| u32::try_from(v).ok()),
+        firmware_updates: FIRMWARE_UPDATES_RE
+            .captures(stdout)
+            .and_then(|c| parse_nvme_int(&c[1]))
+            .and_then(|v| u8::try_from(v).ok()),
+        ..NvmeController::default()
+    };
+    if controller != NvmeController::default() {
+        di.nvme_controller = Some(controller);
+    }
 
     // Set protocol based on drive type
     di.protocol = Some(if hint_kind == "NVMe" {
@@ -153,127 +987,940 @@ fn probe_smart(dev: &str, hint_kind: &str) -> Result<DiskInfo, String> {
 
     // Parse capacity from various possible formats
     if let Some(cap) =
-        re(r"(?:Total NVM Capacity|Namespace 1 Size/Capacity|User Capacity):\s+([\d,]+)\s+\[.*?(\d+(?:\.\d+)?)\s+(GB|TB)")
-            .captures(&stdout)
+        CAPACITY_RE
+            .captures(stdout)
     {
-        if let Ok(bytes) = cap[1].replace(",", "").parse::<f64>() {
-            di.capacity = Some(bytes);
-            di.capacity_str = Some(format!("{} {}", &cap[2], &cap[3]));
+        if let Some(bytes) = parse_grouped_int(&cap[1]) {
+            di.capacity = Some(bytes as f64);
+            // Normalize a decimal comma ("1,00 TB") to a decimal point
+            di.capacity_str = Some(format!("{} {}", cap[2].replace(',', "."), &cap[3]));
         }
     }
 
+    // SCT Error Recovery Control ("Read:     70 (7.0 seconds)" or "Read: Disabled")
+    if let Some(cap) = SCTERC_RE
+        .captures(stdout)
+    {
+        di.erc = Some(ErcSettings {
+            read_ds: cap[1].parse::<u16>().ok(),
+            write_ds: cap[2].parse::<u16>().ok(),
+        });
+    }
+
     // Parse health percentage (NVMe reports "Percentage Used", convert to health)
-    if let Some(cap) = re(r"Percentage Used:\s+(\d+)%").captures(&stdout) {
+    if let Some(cap) = PERCENTAGE_USED_RE.captures(stdout) {
         if let Ok(used) = cap[1].parse::<u8>() {
             di.health_percent = Some(100u8.saturating_sub(used));
         }
     }
 
     // Parse temperature from NVMe output
-    if let Some(cap) = re(r"Temperature:\s+(\d+)\s+Celsius").captures(&stdout) {
+    if let Some(cap) = NVME_TEMP_RE.captures(stdout) {
         if let Ok(t) = cap[1].parse::<i32>() {
             di.temp_c = Some(t);
         }
     } 
-    // Parse temperature from SATA SMART attributes
-    else if let Some(cap) = re(r"Temperature_Celsius.*?(\d+)(?:\s+\(|$)").captures(&stdout) {
-        if let Ok(t) = cap[1].parse::<i32>() {
-            di.temp_c = Some(t);
-        }
+    // Parse temperature from SATA SMART attributes (falling back to the airflow sensor)
+    else if let Some(t) = attribute_raw(stdout, "Temperature_Celsius")
+        .or_else(|| attribute_raw(stdout, "Airflow_Temperature_Cel"))
+    {
+        di.temp_c = Some(t as i32);
     }
 
     // Parse data written for NVMe drives (in 512KB units)
-    if let Some(cap) = re(r"Data Units Written:\s+([\d,]+)").captures(&stdout) {
-        if let Ok(units) = cap[1].replace(",", "").parse::<f64>() {
+    if let Some(cap) = DATA_UNITS_WRITTEN_RE.captures(stdout) {
+        if let Some(units) = parse_grouped_int(&cap[1]).map(|u| u as f64) {
             di.data_written_tb = Some(nvme_units_to_tb(units));
         }
     }
     
     // Parse data read for NVMe drives (in 512KB units)
-    if let Some(cap) = re(r"Data Units Read:\s+([\d,]+)").captures(&stdout) {
-        if let Ok(units) = cap[1].replace(",", "").parse::<f64>() {
+    if let Some(cap) = DATA_UNITS_READ_RE.captures(stdout) {
+        if let Some(units) = parse_grouped_int(&cap[1]).map(|u| u as f64) {
             di.data_read_tb = Some(nvme_units_to_tb(units));
         }
     }
 
     // Parse data written for SATA drives (in LBAs)
-    if let Some(cap) = re(r"Total_LBAs_Written\s+\S+\s+\S+\s+\S+\s+([\d,]+)").captures(&stdout) {
-        if let Ok(lbas) = cap[1].replace(",", "").parse::<f64>() {
-            di.data_written_tb = Some(lbas_to_tb(lbas));
-        }
+    if let Some(lbas) = attribute_raw(stdout, "Total_LBAs_Written") {
+        di.data_written_tb = Some(lbas_to_tb(lbas as f64));
     }
     
     // Parse data read for SATA drives (in LBAs)
-    if let Some(cap) = re(r"Total_LBAs_Read\s+\S+\s+\S+\s+\S+\s+([\d,]+)").captures(&stdout) {
-        if let Ok(lbas) = cap[1].replace(",", "").parse::<f64>() {
-            di.data_read_tb = Some(lbas_to_tb(lbas));
-        }
+    if let Some(lbas) = attribute_raw(stdout, "Total_LBAs_Read") {
+        di.data_read_tb = Some(lbas_to_tb(lbas as f64));
     }
 
     // Parse power cycles from NVMe or SATA output
-    if let Some(cap) = re(r"Power Cycles:\s+([\d,]+)").captures(&stdout) {
-        if let Ok(v) = cap[1].replace(",", "").parse::<u64>() {
-            di.power_cycles = Some(v);
-        }
-    } else if let Some(cap) = re(r"Power_Cycle_Count.*?(\d+)").captures(&stdout) {
-        if let Ok(v) = cap[1].parse::<u64>() {
+    if let Some(cap) = POWER_CYCLES_RE.captures(stdout) {
+        if let Some(v) = parse_grouped_int(&cap[1]) {
             di.power_cycles = Some(v);
         }
+    } else if let Some(v) = attribute_raw(stdout, "Power_Cycle_Count") {
+        di.power_cycles = Some(v);
     }
 
     // Parse power on hours from NVMe or SATA output
-    if let Some(cap) = re(r"Power On Hours:\s+([\d,]+)").captures(&stdout) {
-        if let Ok(v) = cap[1].replace(",", "").parse::<u64>() {
-            di.power_on_hours = Some(v);
-        }
-    } else if let Some(cap) = re(r"Power_On_Hours.*?(\d+)").captures(&stdout) {
-        if let Ok(v) = cap[1].parse::<u64>() {
+    if let Some(cap) = POWER_ON_HOURS_RE.captures(stdout) {
+        if let Some(v) = parse_grouped_int(&cap[1]) {
             di.power_on_hours = Some(v);
         }
+    } else if let Some(v) = attribute_raw(stdout, "Power_On_Hours") {
+        di.power_on_hours = Some(v);
     }
 
     // Parse unsafe shutdown count (NVMe specific)
-    if let Some(cap) = re(r"Unsafe Shutdowns:\s+([\d,]+)").captures(&stdout) {
-        if let Ok(v) = cap[1].replace(",", "").parse::<u64>() {
+    if let Some(cap) = UNSAFE_SHUTDOWNS_RE.captures(stdout) {
+        if let Some(v) = parse_grouped_int(&cap[1]) {
             di.unsafe_shutdowns = Some(v);
         }
     }
 
-    // Parse rotation speed for HDDs (SSDs will not have this)
-    if let Some(cap) = re(r"Rotation Rate:\s+(\d+)\s+rpm").captures(&stdout) {
-        if let Ok(rpm) = cap[1].parse::<u64>() {
-            di.rotation_rpm = Some(rpm);
-        }
+    // SMART switched off in the drive's settings ("SMART support is: Disabled")
+    if let Some(cap) = SMART_SUPPORT_RE.captures(stdout) {
+        di.smart_enabled = Some(&cap[1] == "Enabled");
     }
 
-    // Parse detailed SMART attributes table
-    parse_smart_attributes(&stdout, &mut di);
+    // Overall-health self-assessment ("PASSED" or "FAILED!")
+    if let Some(cap) = HEALTH_RESULT_RE.captures(stdout) {
+        di.smart_passed = Some(&cap[1] == "PASSED");
+    }
 
-    Ok(di)
+    // Media and data integrity errors (NVMe specific)
+    if let Some(cap) = MEDIA_ERRORS_RE.captures(stdout) {
+        di.media_errors = parse_grouped_int(&cap[1]);
+    }
+
+    // Minutes the controller was busy with I/O (NVMe specific)
+    if let Some(cap) = CONTROLLER_BUSY_RE.captures(stdout) {
+        di.controller_busy_minutes = parse_grouped_int(&cap[1]);
+    }
+
+    // Most recent self-test: "# 1" in the ATA log, entry "0" in the NVMe log
+    if let Some(cap) = ATA_SELF_TEST_RE
+        .captures(stdout)
+        .or_else(|| NVME_SELF_TEST_RE.captures(stdout))
+    {
+        di.last_self_test = Some(cap[1].trim().to_string());
+    }
+
+    // Write amplification from host vs NAND write counters (SATA SSD vendor attributes)
+    di.write_amplification = write_amplification(stdout);
+
+    // Parse rotation speed for HDDs (SSDs will not have this)
+    if let Some(cap) = ROTATION_RATE_RE.captures(stdout) {
+        if let Ok(rpm) = cap[1].parse::<u64>() {
+            di.rotation_rpm = Some(rpm);
+        }
+    }
+
+    // Parse detailed SMART attributes table
+    parse_smart_attributes(stdout, &mut di);
+
+    // Match the model/firmware against the bundled advisory knowledge base
+    if let Some(model) = &di.model {
+        di.advisories = advisories::lookup(model, di.firmware.as_deref());
+    }
+
+    di
+}
+
+/// Reads the ATA power state using `hdparm -C`, which does not wake the drive.
+/// Returns None if hdparm is unavailable or the state cannot be parsed.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `dev` - Device path (e.g., "/dev/sda")
+///
+/// # Returns
+/// The reported state (e.g., "active/idle", "standby", "sleeping").
+fn ata_power_state(root: &SystemRoot, dev: &str) -> Option<String> {
+    let output = root.command("hdparm").args(["-C", dev]).output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // Format: " drive state is:  standby"
+    DRIVE_STATE_RE
+        .captures(&stdout)
+        .map(|c| c[1].trim().to_string())
+}
+
+/// Reads the current NVMe power state via the Power Management feature (FID 0x02).
+/// Returns None if nvme-cli is unavailable.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `dev` - Device path (e.g., "/dev/nvme0n1")
+///
+/// # Returns
+/// The power state as "PS<n>", where PS0 is full performance.
+fn nvme_power_state(root: &SystemRoot, dev: &str) -> Option<String> {
+    let output = root.command("nvme")
+        .args(["get-feature", dev, "-f", "0x02"])
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // Format: "get-feature:0x02 (Power Management), Current value:0x00000003"
+    let cap = FEATURE_VALUE_RE.captures(&stdout)?;
+    parse_nvme_int(&cap[1]).map(|v| format!("PS{}", v & 0x1f))
+}
+
+/// Reads whether the NVMe volatile write cache is enabled (feature 0x06, bit 0).
+/// Returns None if nvme-cli is unavailable or the drive has no volatile write cache.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `dev` - Device path (e.g., "/dev/nvme0n1")
+fn nvme_write_cache(root: &SystemRoot, dev: &str) -> Option<bool> {
+    let output = root.command("nvme")
+        .args(["get-feature", dev, "-f", "0x06"])
+        .output()
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // Format: "get-feature:0x06 (Volatile Write Cache), Current value:0x00000001"
+    let cap = FEATURE_VALUE_RE.captures(&stdout)?;
+    parse_nvme_int(&cap[1]).map(|v| v & 0x1 == 1)
+}
+
+/// Reads whether the ATA write cache is enabled using `hdparm -W`.
+/// Returns None if hdparm is unavailable or the drive does not report it.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `dev` - Device path (e.g., "/dev/sda")
+fn ata_write_cache(root: &SystemRoot, dev: &str) -> Option<bool> {
+    let output = root.command("hdparm").args(["-W", dev]).output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // Format: " write-caching =  1 (on)"
+    let cap = WRITE_CACHING_RE.captures(&stdout)?;
+    Some(&cap[1] == "1")
+}
+
+/// Reads the drive's internal SCT temperature history (`smartctl -l scttemp`).
+/// Only meaningful for ATA drives that are awake; NVMe has no equivalent log.
+///
+/// # Arguments
+/// * `dev` - Device path (e.g., "/dev/sda")
+/// * `now` - Current time in Unix seconds, assigned to the newest sample
+///
+/// # Returns
+/// Samples as (Unix seconds, °C), oldest first; empty if the log is unavailable.
+pub fn probe_sct_temp_history(dev: &str, now: u64) -> Vec<(u64, f32)> {
+    match c_locale_command("smartctl").args(["-l", "scttemp", dev]).output() {
+        Ok(output) => parse_sct_temp_history(&String::from_utf8_lossy(&output.stdout), now),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Parses the history table printed by `smartctl -l scttemp`.
+/// Rows are listed oldest first; "(N skipped)" rows repeat the previous temperature
+/// and "?" marks slots the drive has not filled yet.
+///
+/// # Arguments
+/// * `stdout` - smartctl output
+/// * `now` - Current time in Unix seconds, assigned to the newest sample
+///
+/// # Returns
+/// Samples as (Unix seconds, °C), oldest first.
+pub fn parse_sct_temp_history(stdout: &str, now: u64) -> Vec<(u64, f32)> {
+    // Logging interval in minutes (e.g., "Temperature Logging Interval:        1 minute")
+    let interval = SCT_INTERVAL_RE
+        .captures(stdout)
+        .and_then(|c| c[1].parse::<u64>().ok())
+        .unwrap_or(1)
+        .max(1);
+
+
+    // One slot per logging interval; None for unfilled slots
+    let mut slots: Vec<Option<f32>> = Vec::new();
+    for line in stdout.lines() {
+        if let Some(cap) = SCT_ROW_RE.captures(line) {
+            slots.push(cap[1].parse::<f32>().ok());
+        } else if let Some(cap) = SCT_SKIP_RE.captures(line) {
+            let repeat = cap[1].parse::<usize>().unwrap_or(0);
+            let last = slots.last().copied().flatten();
+            slots.extend(std::iter::repeat_n(last, repeat));
+        }
+    }
+
+    // The last slot is the current reading; step backwards one interval per slot
+    let count = slots.len() as u64;
+    slots
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, t)| {
+            let age = (count - 1 - i as u64) * interval * 60;
+            t.map(|t| (now.saturating_sub(age), t))
+        })
+        .collect()
+}
+
+/// Reads a drive's Identify Controller dump with `nvme id-ctrl`, or takes it from the cache if the same drive
+/// was read before.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `dev` - Device path (e.g., "/dev/nvme0n1")
+/// * `di` - The drive as parsed from smartctl, whose model and serial key the cache
+///
+/// # Returns
+/// The nvme id-ctrl output, or None if nvme-cli is missing or failed (failures are not cached).
+fn nvme_id_ctrl(root: &SystemRoot, dev: &str, di: &DiskInfo) -> Option<String> {
+    let key = drive_key(di).map(|key| format!("{}:{}", root.sys.display(), key));
+    if let Some(cached) = key.as_ref().and_then(|key| ID_CTRL_CACHE.lock().ok()?.get(key).cloned()) {
+        return Some(cached);
+    }
+    let output = root.command("nvme").args(["id-ctrl", dev]).output().ok().filter(|o| o.status.success())?;
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    if let (Some(key), Ok(mut cache)) = (key, ID_CTRL_CACHE.lock()) {
+        cache.insert(key, stdout.clone());
+    }
+    Some(stdout)
+}
+
+/// Runs the NVMe probes whose answers only change when the drive is reconfigured: the firmware slot log,
+/// APST, endurance groups and namespaces, self-encryption and the sanitize log. The results are taken from
+/// the cache if the same drive was probed since the last manual rescan.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `dev` - Device path (e.g., "/dev/nvme0n1")
+/// * `di` - The drive with its Identify Controller fields filled in, whose model and serial key the cache
+/// * `id_ctrl` - `nvme id-ctrl` output, None if it could not be read (the results are not cached then)
+fn nvme_static_probes(root: &SystemRoot, dev: &str, di: &DiskInfo, id_ctrl: Option<&str>) -> NvmeStaticProbes {
+    let key = drive_key(di).map(|key| format!("{}:{}", root.sys.display(), key));
+    if let Some(cached) = key.as_ref().and_then(|key| NVME_PROBE_CACHE.lock().ok()?.get(key).cloned()) {
+        return cached;
+    }
+
+    let mut probes = NvmeStaticProbes {
+        sed: probe_sed(root, dev, "NVMe", id_ctrl),
+        // Sanitize status log reports progress and per-method time estimates
+        sanitize: root.command("nvme")
+            .args(["sanitize-log", dev])
+            .output()
+            .ok()
+            .and_then(|o| parse_sanitize_log(&String::from_utf8_lossy(&o.stdout))),
+        ..Default::default()
+    };
+    if let Some(controller) = &di.nvme_controller {
+        probes.firmware_slots = root.command("nvme")
+            .args(["fw-log", dev])
+            .output()
+            .ok()
+            .and_then(|o| parse_fw_log(&String::from_utf8_lossy(&o.stdout)));
+        if controller.apst_supported != Some(false) {
+            probes.apst = nvme_apst(root, dev);
+        }
+        // Enterprise drives can split their media into separately wearing endurance groups
+        if controller.supports_endurance_groups() {
+            (probes.endurance_groups, probes.namespace_usage) = probe_endurance_groups(root, dev, controller);
+        }
+    }
+
+    if let (Some(key), true, true, Ok(mut cache)) =
+        (key, id_ctrl.is_some(), probes.sanitize.is_none(), NVME_PROBE_CACHE.lock())
+    {
+        cache.insert(key, probes.clone());
+    }
+    probes
+}
+
+/// Drops cached Identify Controller dumps and static NVMe probe results so the next scan reads them again.
+///
+/// # Arguments
+/// * `drive` - Key of the drive to forget (see `drive_key`), or None to forget every drive
+pub fn forget_static_probes(drive: Option<&str>) {
+    let suffix = drive.map(|key| format!(":{}", key));
+    let keep = |key: &String| suffix.as_ref().is_some_and(|suffix| !key.ends_with(suffix.as_str()));
+    if let Ok(mut cache) = ID_CTRL_CACHE.lock() {
+        cache.retain(|key, _| keep(key));
+    }
+    if let Ok(mut cache) = NVME_PROBE_CACHE.lock() {
+        cache.retain(|key, _| keep(key));
+    }
+}
+
+/// Detects TCG Opal/SED support using `sedutil-cli --query`.
+/// For NVMe drives where sedutil-cli is missing or fails, falls back to the Security Send/Receive
+/// bit of the Identify Controller OACS field.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `dev` - Device path (e.g., "/dev/sda", "/dev/nvme0n1")
+/// * `hint_kind` - Type hint ("NVMe", "SATA", or "HDD")
+/// * `id_ctrl` - `nvme id-ctrl` output of an NVMe drive, None if it could not be read
+fn probe_sed(root: &SystemRoot, dev: &str, hint_kind: &str, id_ctrl: Option<&str>) -> Option<SedStatus> {
+    // sedutil-cli addresses NVMe drives by controller (/dev/nvme0), not namespace
+    let target = if hint_kind == "NVMe" {
+        NAMESPACE_SUFFIX_RE.replace(dev, "").into_owned()
+    } else {
+        dev.to_string()
+    };
+    // Inside a Flatpak, flatpak-spawn starts even when sedutil-cli is missing, so only a successful run counts
+    if let Some(output) = root.command("sedutil-cli").args(["--query", &target]).output().ok().filter(|o| o.status.success()) {
+        return parse_sedutil_query(&String::from_utf8_lossy(&output.stdout));
+    }
+
+    if hint_kind != "NVMe" {
+        return None;
+    }
+    let cap = OACS_RE.captures(id_ctrl?)?;
+    // OACS bit 0: Security Send/Receive supported, a prerequisite for Opal
+    if parse_nvme_int(&cap[1])? & 0x1 == 1 {
+        Some(SedStatus::default())
+    } else {
+        None
+    }
+}
+
+/// Fills in the Identify Controller fields smartctl does not print from `nvme id-ctrl` output:
+/// the subsystem NQN, whether a volatile write cache is present, and the preferred Host Memory Buffer size.
+///
+/// # Arguments
+/// * `stdout` - nvme id-ctrl output
+/// * `controller` - Controller details to update; fields missing from the output are left unchanged
+pub fn parse_id_ctrl(stdout: &str, controller: &mut NvmeController) {
+    // Format: "subnqn    : nqn.2018-01.com.wdc:nguid:E8238FA6BF53-0001-001B448B4A1B2C3D"
+    if let Some(cap) = SUBNQN_RE.captures(stdout) {
+        controller.subsystem_nqn = Some(cap[1].to_string());
+    }
+    // Format: "vwc       : 0x7"; bit 0 = volatile write cache present
+    if let Some(vwc) = VWC_RE.captures(stdout).and_then(|c| parse_nvme_int(&c[1])) {
+        controller.volatile_write_cache = Some(vwc & 0x1 == 1);
+    }
+    // Format: "apsta     : 0x1"; bit 0 = APST supported
+    if let Some(apsta) = APSTA_RE.captures(stdout).and_then(|c| parse_nvme_int(&c[1])) {
+        controller.apst_supported = Some(apsta & 0x1 == 1);
+    }
+    // Format: "hmpre     : 16384", in 4 KiB units
+    if let Some(pages) = HMPRE_RE.captures(stdout).and_then(|c| parse_nvme_int(&c[1])) {
+        controller.hmb_preferred_bytes = Some(pages * 4096);
+    }
+    // Format: "ctratt    : 0x10"; bit 4 = endurance groups
+    if let Some(ctratt) = CTRATT_RE.captures(stdout).and_then(|c| parse_nvme_int(&c[1])) {
+        controller.controller_attributes = u32::try_from(ctratt).ok();
+    }
+    // Format: "endgidmax : 1"
+    if let Some(max) = ENDGIDMAX_RE.captures(stdout).and_then(|c| parse_nvme_int(&c[1])) {
+        controller.endurance_group_max = u16::try_from(max).ok();
+    }
+}
+
+/// Reads the endurance group logs and the size and use of each namespace.
+/// Groups beyond the 16th and namespaces beyond the 64th are not read, to keep scans quick.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `dev` - Device path (e.g., "/dev/nvme0n1")
+/// * `controller` - Controller details, for the number of endurance groups
+///
+/// # Returns
+/// The endurance groups and the namespaces; the namespaces are empty if nvme-cli could not list them.
+fn probe_endurance_groups(root: &SystemRoot, dev: &str, controller: &NvmeController) -> (Vec<EnduranceGroup>, Vec<NamespaceUsage>) {
+    let max = controller.endurance_group_max.unwrap_or(1).clamp(1, 16);
+    let groups = (1..=max)
+        .filter_map(|id| {
+            let output = root.command("nvme").args(["endurance-log", dev, &format!("--group-id={}", id)]).output().ok()?;
+            parse_endurance_log(&String::from_utf8_lossy(&output.stdout), id)
+        })
+        .collect();
+
+    let Ok(output) = root.command("nvme").args(["list-ns", dev]).output() else {
+        return (groups, Vec::new());
+    };
+    let namespaces = LIST_NS_RE
+        .captures_iter(&String::from_utf8_lossy(&output.stdout))
+        .filter_map(|c| u32::try_from(parse_nvme_int(&c[1])?).ok())
+        .take(64)
+        .filter_map(|nsid| {
+            let output = root.command("nvme").args(["id-ns", dev, &format!("--namespace-id={}", nsid)]).output().ok()?;
+            parse_id_ns(&String::from_utf8_lossy(&output.stdout), nsid)
+        })
+        .collect();
+    (groups, namespaces)
+}
+
+/// Parses the Endurance Group Information log as printed by `nvme endurance-log`.
+///
+/// # Arguments
+/// * `stdout` - nvme endurance-log output
+/// * `id` - Endurance group the log was read for
+///
+/// # Returns
+/// The group's wear and capacity, or None if the drive rejected the log page.
+pub fn parse_endurance_log(stdout: &str, id: u16) -> Option<EnduranceGroup> {
+    // Format: "percent_used            : 3%"; data and media units are thousands of 512-byte blocks,
+    // capacities are in bytes
+    let fields: HashMap<&str, u64> = LOG_FIELD_RE
+        .captures_iter(stdout)
+        .filter_map(|c| Some((c.get(1)?.as_str(), parse_nvme_int(&c[2])?)))
+        .collect();
+    let percent = |key: &str| fields.get(key).map(|&v| v.min(255) as u8);
+    let units = |key: &str| fields.get(key).map(|&v| v.saturating_mul(512_000));
+    let capacity = |key: &str| fields.get(key).copied().filter(|&v| v > 0);
+    if !fields.contains_key("percent_used") {
+        return None;
+    }
+    Some(EnduranceGroup {
+        id,
+        percent_used: percent("percent_used"),
+        available_spare: percent("avl_spare"),
+        host_bytes_written: units("data_units_written"),
+        media_bytes_written: units("media_units_written"),
+        total_bytes: capacity("total_end_grp_cap"),
+        unallocated_bytes: capacity("total_end_grp_cap").and(fields.get("unalloc_end_grp_cap").copied()),
+    })
+}
+
+/// Parses the size, use and endurance group of a namespace as printed by `nvme id-ns`.
+///
+/// # Arguments
+/// * `stdout` - nvme id-ns output
+/// * `nsid` - Namespace the output was read for
+///
+/// # Returns
+/// The namespace's usage, or None if the size or the LBA format in use is missing.
+pub fn parse_id_ns(stdout: &str, nsid: u32) -> Option<NamespaceUsage> {
+    let fields: HashMap<&str, u64> = LOG_FIELD_RE
+        .captures_iter(stdout)
+        .filter_map(|c| Some((c.get(1)?.as_str(), parse_nvme_int(&c[2])?)))
+        .collect();
+    // Format: "lbaf  0 : ms:0   lbads:9  rp:0x2 (in use)"; sizes are counted in blocks of 2^lbads bytes
+    let block = 1u64 << LBADS_IN_USE_RE.captures(stdout)?[1].parse::<u32>().ok().filter(|&b| b < 32)?;
+    Some(NamespaceUsage {
+        nsid,
+        // Format: "endgid  : 1"; 0 means the namespace is not in a reported group
+        endurance_group: fields.get("endgid").and_then(|&g| u16::try_from(g).ok()).filter(|&g| g > 0),
+        size_bytes: fields.get("nsze")?.saturating_mul(block),
+        used_bytes: fields.get("nuse").copied().unwrap_or(0).saturating_mul(block),
+    })
+}
+
+/// Reads the APST feature (0x0c) and the latency limit the kernel applies to it.
+/// Returns None if nvme-cli is unavailable or the drive rejects the feature.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `dev` - Device path (e.g., "/dev/nvme0n1")
+fn nvme_apst(root: &SystemRoot, dev: &str) -> Option<ApstStatus> {
+    let output = root.command("nvme")
+        .args(["get-feature", dev, "-f", "0x0c", "-H"])
+        .output()
+        .ok()?;
+    let mut apst = parse_apst_feature(&String::from_utf8_lossy(&output.stdout))?;
+
+    // The per-controller limit overrides the module default; "auto" and "any" are not numbers and fall through
+    let controller = NAMESPACE_SUFFIX_RE.replace(dev.trim_start_matches("/dev/"), "").into_owned();
+    apst.max_latency_us = [
+        root.sys.join(format!("class/nvme/{}/power/pm_qos_latency_tolerance_us", controller)),
+        root.sys.join("module/nvme_core/parameters/default_ps_max_latency_us"),
+    ]
+    .iter()
+    .find_map(|path| std::fs::read_to_string(path).ok()?.trim().parse().ok());
+    Some(apst)
+}
+
+/// Parses the APST feature as printed by `nvme get-feature -f 0x0c -H`.
+/// nvme-cli prints all 32 table entries; unused ones have an idle time of 0 and repeated
+/// transitions (one per operational state) are listed once.
+///
+/// # Arguments
+/// * `stdout` - nvme get-feature output
+///
+/// # Returns
+/// The APST state with `max_latency_us` unset, or None if the output is not recognized.
+pub fn parse_apst_feature(stdout: &str) -> Option<ApstStatus> {
+    // Format: "get-feature:0x0c (Autonomous Power State Transition), Current value:0x00000001"; bit 0 = enabled
+    let value = FEATURE_VALUE_RE.captures(stdout).and_then(|c| parse_nvme_int(&c[1]))?;
+
+    // Format: "	Idle Time Prior to Transition (ITPT): 86 ms" followed by "	Idle Transition Power State   (ITPS): 3"
+    let mut transitions: Vec<(u64, u8)> = APST_ENTRY_RE
+        .captures_iter(stdout)
+        .filter_map(|c| Some((c[1].parse().ok()?, c[2].parse().ok()?)))
+        .filter(|&(idle_ms, _)| idle_ms > 0)
+        .collect();
+    transitions.sort_unstable();
+    transitions.dedup();
+
+    Some(ApstStatus { enabled: value & 0x1 == 1, transitions, max_latency_us: None })
+}
+
+/// Parses the firmware slot log printed by `nvme fw-log`.
+///
+/// # Arguments
+/// * `stdout` - nvme fw-log output
+///
+/// # Returns
+/// The active slot, pending slot and stored revisions, or None if the output is not recognized.
+pub fn parse_fw_log(stdout: &str) -> Option<FirmwareSlots> {
+    // Format: "afi  : 0x1"; bits 2:0 = active slot, bits 6:4 = slot activated on the next reset
+    let afi = AFI_RE.captures(stdout).and_then(|c| parse_nvme_int(&c[1]))?;
+    let active = (afi & 0x7) as u8;
+    let next = ((afi >> 4) & 0x7) as u8;
+
+    // Format: "frs1 : 0x2020203144413142 (B1AD1   )"; an all-zero value is an empty slot
+    let slots = FRS_RE
+        .captures_iter(stdout)
+        .filter(|c| parse_nvme_int(&c[2]) != Some(0))
+        .filter_map(|c| Some((c[1].parse().ok()?, c[3].trim().to_string())))
+        .filter(|(_, revision): &(u8, String)| !revision.is_empty())
+        .collect();
+
+    Some(FirmwareSlots { active, next_reset: (next != 0 && next != active).then_some(next), slots })
+}
+
+/// Reads the NVMe persistent event log with `nvme persistent-event-log`.
+/// The log can be large, so this runs on demand rather than on every refresh.
+///
+/// # Arguments
+/// * `dev` - Device path (e.g., "/dev/nvme0n1")
+///
+/// # Returns
+/// The significant events, oldest first, or an error if nvme-cli failed.
+pub fn probe_persistent_event_log(dev: &str) -> Result<Vec<PersistentEvent>, String> {
+    // Action 1 establishes a reading context (a stable copy of the log) and reads it
+    let output = c_locale_command("nvme")
+        .args(["persistent-event-log", dev, "--action=1"])
+        .output()
+        .map_err(|e| format!("failed to run nvme: {}", e))?;
+    // Action 2 releases the context again so the drive resumes logging into it
+    let _ = c_locale_command("nvme").args(["persistent-event-log", dev, "--action=2"]).output();
+
+    if !output.status.success() {
+        return Err(format!("nvme persistent-event-log failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(parse_persistent_event_log(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parses the entries printed by `nvme persistent-event-log`.
+/// Each entry starts with "Event Type:" and carries an "Event Timestamp:" in milliseconds;
+/// entries that are not significant (SMART snapshots, timestamp changes, set features) are dropped.
+///
+/// # Arguments
+/// * `stdout` - nvme persistent-event-log output
+pub fn parse_persistent_event_log(stdout: &str) -> Vec<PersistentEvent> {
+    let mut events = Vec::new();
+    // Category of the entry being read; None while skipping an insignificant one
+    let mut current: Option<EventCategory> = None;
+    for line in stdout.lines().map(str::trim) {
+        if let Some(event_type) = line.strip_prefix("Event Type:") {
+            current = EventCategory::from_event_type(event_type);
+            if let Some(category) = current {
+                events.push(PersistentEvent { category, timestamp: None });
+            }
+        } else if let (Some(value), Some(_)) = (line.strip_prefix("Event Timestamp:"), current) {
+            // Bits 47:0 are milliseconds; a drive whose clock was never set counts from power-on instead,
+            // which shows up as a date before 2000
+            let ms = value.trim().parse::<u64>().unwrap_or(0) & 0xFFFF_FFFF_FFFF;
+            if let Some(event) = events.last_mut() {
+                event.timestamp = (ms >= 946_684_800_000).then_some(ms / 1000);
+            }
+        }
+    }
+    events
+}
+
+/// Reads how much host memory the driver has given a DRAM-less NVMe drive (feature 0x0d).
+/// Returns None if nvme-cli is unavailable.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `dev` - Device path (e.g., "/dev/nvme0n1")
+fn nvme_hmb_allocation(root: &SystemRoot, dev: &str) -> Option<u64> {
+    let output = root.command("nvme")
+        .args(["get-feature", dev, "-f", "0x0d", "-H"])
+        .output()
+        .ok()?;
+    parse_hmb_feature(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parses the Host Memory Buffer feature as printed by `nvme get-feature -f 0x0d -H`.
+///
+/// # Arguments
+/// * `stdout` - nvme get-feature output
+///
+/// # Returns
+/// The allocated size in bytes, Some(0) if the buffer is disabled, or None if the output is not recognized.
+pub fn parse_hmb_feature(stdout: &str) -> Option<u64> {
+    // Format: "get-feature:0x0d (Host Memory Buffer), Current value:0x00000001"; bit 0 = enabled
+    let value = FEATURE_VALUE_RE.captures(stdout).and_then(|c| parse_nvme_int(&c[1]))?;
+    if value & 0x1 == 0 {
+        return Some(0);
+    }
+    // Format: "	Host Memory Buffer Size (HSIZE): 16384", in 4 KiB memory pages
+    HSIZE_RE.captures(stdout).and_then(|c| parse_nvme_int(&c[1])).map(|pages| pages * 4096)
+}
+
+/// Parses `sedutil-cli --query` output.
+///
+/// # Arguments
+/// * `stdout` - sedutil-cli output
+///
+/// # Returns
+/// The SED status, or None if the drive is not a TCG drive.
+pub fn parse_sedutil_query(stdout: &str) -> Option<SedStatus> {
+    // Format: "    Locked = N, LockingEnabled = Y, LockingSupported = Y, MBRDone = N, MBREnabled = N, MediaEncrypt = Y"
+    let flags: HashMap<&str, bool> = SED_FLAG_RE
+        .captures_iter(stdout)
+        .map(|c| (c.get(1).unwrap().as_str(), &c[2] == "Y"))
+        .collect();
+    let flag = |name: &str| flags.get(name).copied().unwrap_or(false);
+    let ssc = SED_SSC_RE
+        .captures(stdout)
+        .map(|c| c[1].to_string());
+
+    // Not a TCG drive when neither a locking feature nor an SSC is reported
+    if ssc.is_none() && !stdout.contains("Locking function") {
+        return None;
+    }
+    Some(SedStatus {
+        ssc,
+        media_encrypt: flag("MediaEncrypt"),
+        locking_supported: flag("LockingSupported"),
+        locking_enabled: flag("LockingEnabled"),
+        locked: flag("Locked"),
+    })
+}
+
+/// Parses the "Security:" section of `hdparm -I` output.
+/// Each flag is printed on its own line, prefixed by "not" when it is clear.
+///
+/// # Arguments
+/// * `stdout` - hdparm -I output
+///
+/// # Returns
+/// The parsed security state, or None if the section is missing.
+pub fn parse_hdparm_security(stdout: &str) -> Option<AtaSecurity> {
+    let mut lines = stdout.lines().skip_while(|l| l.trim_end() != "Security:");
+    lines.next()?;
+
+    let mut sec = AtaSecurity::default();
+    // The section ends at the next line that is not indented
+    for line in lines.take_while(|l| l.starts_with('\t') || l.starts_with(' ')) {
+        let text = line.trim();
+        let negated = text.starts_with("not");
+        let flag = text.trim_start_matches("not").trim();
+        match flag {
+            "supported" => sec.supported = !negated,
+            "enabled" => sec.enabled = !negated,
+            "locked" => sec.locked = !negated,
+            "frozen" => sec.frozen = !negated,
+            "supported: enhanced erase" => sec.enhanced_erase = !negated,
+            _ if flag.contains("SECURITY ERASE UNIT") => sec.erase_time = Some(flag.to_string()),
+            _ => {}
+        }
+    }
+    Some(sec)
+}
+
+/// Returns true for power states in which reading SMART data would spin the drive up
+/// ("standby" and "sleeping" from hdparm, "standby" and "sleep" from smartctl).
+///
+/// # Arguments
+/// * `state` - Power state as reported by hdparm or smartctl
+pub fn is_sleep_state(state: &str) -> bool {
+    state.starts_with("standby") || state.starts_with("sleep")
+}
+
+/// Whether runtime power management has suspended a drive: its SCSI device (SATA, USB) or, for NVMe,
+/// the controller's PCI function.
+///
+/// # Arguments
+/// * `root` - Device and sysfs locations
+/// * `dev_name` - Device name (e.g., "sda", "nvme0n1")
+fn is_runtime_suspended(root: &SystemRoot, dev_name: &str) -> bool {
+    let device = root.sys.join("block").join(dev_name).join("device");
+    [device.join("power/runtime_status"), device.join("device/power/runtime_status")]
+        .iter()
+        .any(|path| std::fs::read_to_string(path).is_ok_and(|s| s.trim() == "suspended"))
+}
+
+/// Builds a DiskInfo for a drive that was skipped because it is asleep.
+/// Only identity fields derived from the type hint are filled in; the GUI merges in cached data.
+///
+/// # Arguments
+/// * `dev` - Device path (e.g., "/dev/sda")
+/// * `hint_kind` - Type hint ("SATA" or "HDD")
+/// * `power_state` - Detected power state
+fn sleeping_placeholder(dev: &str, hint_kind: &str, power_state: Option<String>) -> DiskInfo {
+    let mut di = parse_smartctl_output(dev, hint_kind, "");
+    di.power_state = power_state;
+    di.cached = true;
+    di
+}
+
+/// Detects a format operation in progress on an NVMe drive.
+/// Reads the namespace format progress indicator via nvme-cli; a running sanitize comes from `nvme_static_probes`.
+/// Failures (e.g., nvme-cli not installed) are silently ignored.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `dev` - Device path (e.g., "/dev/nvme0n1")
+/// * `di` - DiskInfo structure to populate with operation progress
+fn probe_format_progress(root: &SystemRoot, dev: &str, di: &mut DiskInfo) {
+    // Identify Namespace exposes the Format Progress Indicator (FPI)
+    if let Ok(output) = root.command("nvme").args(["id-ns", dev]).output() {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if let Some(cap) = FPI_RE.captures(&stdout) {
+            if let Some(fpi) = parse_nvme_int(&cap[1]) {
+                // Bit 7 = indicator supported, bits 6:0 = percentage remaining
+                let remaining = fpi & 0x7f;
+                if fpi & 0x80 != 0 && remaining > 0 {
+                    di.operation = Some(OperationProgress {
+                        kind: "Format".to_string(),
+                        percent: 100.0 - remaining as f32,
+                        eta_secs: None,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Parses `nvme sanitize-log` output into operation progress.
+/// Returns None unless the sanitize status reports an operation in progress.
+///
+/// # Arguments
+/// * `stdout` - The full nvme sanitize-log output text
+fn parse_sanitize_log(stdout: &str) -> Option<OperationProgress> {
+    let field = |re: &Regex| re.captures(stdout).and_then(|c| parse_nvme_int(&c[1]));
+
+    // SSTAT bits 2:0 == 2 means a sanitize operation is in progress
+    let status = field(&SSTAT_RE)?;
+    if status & 0x7 != 2 {
+        return None;
+    }
+
+    // SPROG is the fraction complete, numerator of a /65536 ratio
+    let progress = field(&SPROG_RE).unwrap_or(0) as f64 / 65536.0;
+
+    // SCDW10 bits 2:0 select the sanitize action, which determines the time estimate
+    let (kind, estimate) = match field(&SCDW10_RE).unwrap_or(0) & 0x7 {
+        2 => ("Sanitize (block erase)", field(&BLOCK_ERASE_TIME_RE)),
+        3 => ("Sanitize (overwrite)", field(&OVERWRITE_TIME_RE)),
+        4 => ("Sanitize (crypto erase)", field(&CRYPTO_ERASE_TIME_RE)),
+        _ => ("Sanitize", None),
+    };
+
+    // 0xFFFFFFFF means the drive does not report a time estimate
+    let eta_secs = estimate
+        .filter(|&secs| secs != 0xFFFF_FFFF)
+        .map(|secs| (secs as f64 * (1.0 - progress)).round() as u64);
+
+    Some(OperationProgress {
+        kind: kind.to_string(),
+        percent: (progress * 100.0) as f32,
+        eta_secs,
+    })
+}
+
+/// Reads HPA and DCO capacity limits for an ATA drive using hdparm.
+/// `hdparm -N` reports visible/native max sectors; `hdparm --dco-identify` reports the DCO real max.
+/// Failures (e.g., hdparm not installed) are silently ignored.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `dev` - Device path (e.g., "/dev/sda")
+/// * `di` - DiskInfo structure to populate with capacity limits
+fn probe_capacity_limits(root: &SystemRoot, dev: &str, di: &mut DiskInfo) {
+    let Ok(output) = root.command("hdparm").args(["-N", dev]).output() else {
+        return;
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // Format: " max sectors   = 1953523055/1953525168, HPA is enabled"
+    let Some(cap) = MAX_SECTORS_RE.captures(&stdout) else {
+        return;
+    };
+    let (Ok(visible), Ok(native)) = (cap[1].parse::<u64>(), cap[2].parse::<u64>()) else {
+        return;
+    };
+
+    // DCO identify is optional; many drives (and USB bridges) do not support it
+    let dco_sectors = root.command("hdparm")
+        .args(["--dco-identify", dev])
+        .output()
+        .ok()
+        .and_then(|o| {
+            let text = String::from_utf8_lossy(&o.stdout);
+            DCO_REAL_MAX_RE
+                .captures(&text)
+                .and_then(|c| c[1].parse::<u64>().ok())
+        });
+
+    di.capacity_limits = Some(CapacityLimits {
+        visible_sectors: visible,
+        native_sectors: native,
+        dco_sectors,
+    });
+}
+
+/// Reads the ATA Advanced Power Management and Automatic Acoustic Management levels with hdparm.
+/// Drives that do not support a feature report "not supported" and leave the field as None.
+///
+/// # Arguments
+/// * `root` - Device, sysfs and tool locations
+/// * `dev` - Device path (e.g., "/dev/sda")
+/// * `di` - DiskInfo structure to populate with APM/AAM levels
+fn probe_power_management(root: &SystemRoot, dev: &str, di: &mut DiskInfo) {
+    if let Ok(output) = root.command("hdparm").args(["-B", dev]).output() {
+        di.apm_level = parse_hdparm_apm(&String::from_utf8_lossy(&output.stdout));
+    }
+
+    // Format: " acoustic      = 254 (128=quiet ... 254=fast)"
+    if let Ok(output) = root.command("hdparm").args(["-M", dev]).output() {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if let Some(cap) = AAM_LEVEL_RE.captures(&stdout) {
+            di.aam_level = cap[1].parse::<u8>().ok();
+        }
+    }
+}
+
+/// Parses the APM level from `hdparm -B` output (" APM_level      = 128", or "= off" when APM is disabled).
+///
+/// # Arguments
+/// * `stdout` - hdparm -B output
+///
+/// # Returns
+/// The level, 255 for "off", or None if the drive does not support APM.
+pub fn parse_hdparm_apm(stdout: &str) -> Option<u8> {
+    let cap = APM_LEVEL_RE.captures(stdout)?;
+    match &cap[1] {
+        "off" => Some(255),
+        level => level.parse().ok(),
+    }
+}
+
+/// Parses an integer as printed by nvme-cli, which uses either decimal or 0x-prefixed hex.
+///
+/// # Arguments
+/// * `s` - Number text (e.g., "65535", "0x101")
+fn parse_nvme_int(s: &str) -> Option<u64> {
+    match s.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => s.parse::<u64>().ok(),
+    }
 }
 
 /// Parses the SMART attributes table from smartctl output.
-/// Extracts attribute ID, name, current/worst/threshold values, and computes status.
+/// Extracts attribute ID, name, current/worst/threshold values and type, and computes status.
 ///
 /// # Arguments
 /// * `stdout` - The full smartctl output text
 /// * `di` - DiskInfo structure to populate with attributes
 fn parse_smart_attributes(stdout: &str, di: &mut DiskInfo) {
-    // Regex to match SMART attribute lines
-    // Format: ID NAME FLAGS VALUE WORST THRESH TYPE UPDATED WHEN_FAILED RAW_VALUE
-    let attr_re = Regex::new(
-        r"^\s*(\d+)\s+(\S.*?)\s+(0x[0-9a-f]+)\s+(\d+)\s+(\d+)\s+(\d+)\s+\S+\s+\S+\s+\S+\s+(.+)$",
-    )
-    .unwrap();
-
     for line in stdout.lines() {
-        if let Some(cap) = attr_re.captures(line) {
+        if let Some(cap) = ATTRIBUTE_ROW_RE.captures(line) {
             let id = cap[1].to_string();
             let name = cap[2].trim().to_string();
             let current = cap[4].to_string();
             let worst = cap[5].to_string();
             let threshold = cap[6].to_string();
-            let raw_value = cap[7].trim().to_string();
+            let pre_fail = &cap[7] == "Pre-fail";
+            let raw_value = cap[8].trim().to_string();
 
             let current_val = current.parse::<u32>().unwrap_or(0);
             let threshold_val = threshold.parse::<u32>().unwrap_or(0);
@@ -295,19 +1942,82 @@ fn parse_smart_attributes(stdout: &str, di: &mut DiskInfo) {
                 threshold,
                 raw_value,
                 status,
+                pre_fail,
             });
         }
     }
 }
 
+/// Estimates the write amplification factor from vendor attributes that count host and NAND writes.
+/// NVMe drives only expose NAND writes in vendor-specific logs, so they are not covered.
+///
+/// # Arguments
+/// * `stdout` - The full smartctl output text
+///
+/// # Returns
+/// NAND writes divided by host writes, or None if the drive lacks either counter.
+pub fn write_amplification(stdout: &str) -> Option<f64> {
+    let ratio = |nand: u64, host: u64| (host > 0).then(|| nand as f64 / host as f64);
+
+    // Crucial/Micron: pages programmed for the host plus pages programmed by the FTL itself
+    if let (Some(host), Some(ftl)) = (
+        attribute_raw(stdout, "Host_Program_Page_Count"),
+        attribute_raw(stdout, "FTL_Program_Page_Count"),
+    ) {
+        return ratio(host + ftl, host);
+    }
+
+    // Phison (Kingston and others) and SanDisk: both counters in GiB
+    for (nand, host) in [("Flash_Writes_GiB", "Lifetime_Writes_GiB"), ("Total_NAND_Writes_GiB", "Total_Writes_GiB")] {
+        if let (Some(nand), Some(host)) = (attribute_raw(stdout, nand), attribute_raw(stdout, host)) {
+            return ratio(nand, host);
+        }
+    }
+
+    // Intel: NAND writes in GiB, host writes in 32 MiB units
+    if let (Some(nand), Some(host)) = (
+        attribute_raw(stdout, "NAND_Writes_1GiB"),
+        attribute_raw(stdout, "Host_Writes_32MiB"),
+    ) {
+        return ratio(nand * 32, host);
+    }
+
+    None
+}
+
+/// Reads the leading integer of a SMART attribute's raw value, looked up by attribute name.
+/// Trailing raw-value decorations like "34 (Min/Max 19/55)" or "1234h+05m" are ignored.
+///
+/// # Arguments
+/// * `stdout` - The full smartctl output text
+/// * `name` - Attribute name as printed by smartctl (e.g., "Power_On_Hours")
+fn attribute_raw(stdout: &str, name: &str) -> Option<u64> {
+    ATTRIBUTE_RAW_RE
+        .captures_iter(stdout)
+        .find(|c| &c[1] == name)
+        .and_then(|c| c[2].parse::<u64>().ok())
+}
+
+/// Parses an integer that may contain locale-specific digit grouping.
+/// Accepts "1,234,567" (en), "1.234.567" (de), "1'234'567" (ch), and "1 234 567" (fr).
+///
+/// # Arguments
+/// * `s` - Number text with optional group separators
+fn parse_grouped_int(s: &str) -> Option<u64> {
+    let digits: String = s
+        .chars()
+        .filter(|c| !matches!(c, ',' | '.' | '\'' | ' ' | '\u{a0}' | '\u{202f}'))
+        .collect();
+    digits.parse::<u64>().ok()
+}
+
 /// Helper function to extract a value using regex and store it in an Option<String>.
 ///
 /// # Arguments
 /// * `src` - Source text to search
-/// * `pat` - Regex pattern with one capture group
+/// * `re` - Compiled pattern with one capture group
 /// * `out` - Output Option<String> to populate
-fn extract_into(src: &str, pat: &str, out: &mut Option<String>) {
-    let re = Regex::new(pat).unwrap();
+fn extract_into(src: &str, re: &Regex, out: &mut Option<String>) {
     if let Some(c) = re.captures(src) {
         *out = Some(c[1].trim().to_string());
     }
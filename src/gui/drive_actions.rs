@@ -0,0 +1,139 @@
+// State-changing drive operations triggered from the UI (spin down, etc.)
+
+// C-locale command builder shared with the scanner
+use crate::gui::disk_scanner::c_locale_command;
+// The scheduler is written through the host inside a Flatpak
+use crate::sandbox::in_flatpak;
+// Telemetry dump location
+use crate::gui::settings::home_dir;
+use std::path::PathBuf;
+
+/// A drive operation requested from the UI.
+/// Collected while rendering and executed once the frame's panels are drawn.
+#[derive(Clone, Debug)]
+pub enum DriveAction {
+    /// Put an ATA drive into standby immediately (`hdparm -y`)
+    SpinDown(String),
+    /// Set the Advanced Power Management level (`hdparm -B`)
+    SetApm(String, u8),
+    /// Set the Automatic Acoustic Management level (`hdparm -M`)
+    SetAam(String, u8),
+    /// Enable or disable the volatile write cache (`hdparm -W` or NVMe feature 0x06)
+    SetWriteCache(String, bool),
+    /// Switch the block I/O scheduler via sysfs (not persistent across reboots)
+    SetScheduler(String, String),
+    /// Set SCT Error Recovery Control read/write timeouts in deciseconds, 0 disables (`smartctl -l scterc`)
+    SetErc(String, u16, u16),
+    /// Switch on SMART for a drive that has it disabled (`smartctl -s on`)
+    EnableSmart(String),
+    /// Start a SMART self-test ("short", "long" or "conveyance") in the background (`smartctl -t`)
+    StartSelfTest(String, String),
+    /// Save the NVMe host-initiated telemetry log to a file (`nvme telemetry-log`)
+    CaptureTelemetry(String, PathBuf),
+    /// Activate the firmware stored in an NVMe slot, immediately if true or on the next reset (`nvme fw-commit`)
+    ActivateFirmware(String, u8, bool),
+}
+
+impl DriveAction {
+    /// Executes the action by running the matching external command.
+    ///
+    /// # Returns
+    /// A human-readable success message, or an error string on failure.
+    pub fn run(&self) -> Result<String, String> {
+        match self {
+            DriveAction::SpinDown(dev) => {
+                run_checked("hdparm", &["-y", dev])?;
+                Ok(format!("{} spun down", dev))
+            }
+            DriveAction::SetApm(dev, level) => {
+                run_checked("hdparm", &["-B", &level.to_string(), dev])?;
+                Ok(format!("APM level of {} set to {}", dev, level))
+            }
+            DriveAction::SetAam(dev, level) => {
+                run_checked("hdparm", &["-M", &level.to_string(), dev])?;
+                Ok(format!("AAM level of {} set to {}", dev, level))
+            }
+            DriveAction::SetWriteCache(dev, enabled) => {
+                let value = if *enabled { "1" } else { "0" };
+                if dev.starts_with("/dev/nvme") {
+                    run_checked("nvme", &["set-feature", dev, "-f", "0x06", "-v", value])?;
+                } else {
+                    run_checked("hdparm", &[&format!("-W{}", value), dev])?;
+                }
+                let state = if *enabled { "enabled" } else { "disabled" };
+                Ok(format!("Write cache {} on {}", state, dev))
+            }
+            DriveAction::SetScheduler(dev, scheduler) => {
+                let name = dev.trim_start_matches("/dev/");
+                let path = format!("/sys/block/{}/queue/scheduler", name);
+                if in_flatpak() {
+                    // The sandbox's /sys is read-only, so the host's shell writes it; the values are passed as arguments
+                    run_checked("sh", &["-c", "echo \"$1\" > \"$2\"", "sh", scheduler, &path])?;
+                } else {
+                    std::fs::write(&path, scheduler).map_err(|e| format!("failed to write {}: {}", path, e))?;
+                }
+                Ok(format!("I/O scheduler of {} set to {}", dev, scheduler))
+            }
+            DriveAction::SetErc(dev, read, write) => {
+                run_checked("smartctl", &["-l", &format!("scterc,{},{}", read, write), dev])?;
+                Ok(format!("Error recovery timeouts of {} set to {:.1} s / {:.1} s", dev, *read as f32 / 10.0, *write as f32 / 10.0))
+            }
+            DriveAction::EnableSmart(dev) => {
+                run_checked("smartctl", &["-s", "on", dev])?;
+                Ok(format!("SMART enabled on {}", dev))
+            }
+            DriveAction::StartSelfTest(dev, kind) => {
+                run_checked("smartctl", &["-t", kind, dev])?;
+                Ok(format!("{} self-test started on {}", kind, dev))
+            }
+            DriveAction::CaptureTelemetry(dev, path) => {
+                // Data area 3 is the largest; vendors ask for all of it
+                let output = format!("--output-file={}", path.display());
+                run_checked("nvme", &["telemetry-log", dev, &output, "--host-generate=1", "--data-area=3"])?;
+                Ok(format!("Telemetry log of {} saved to {}", dev, path.display()))
+            }
+            DriveAction::ActivateFirmware(dev, slot, immediate) => {
+                // Commit action 3 switches right away, action 2 at the next controller reset
+                let action = if *immediate { "3" } else { "2" };
+                run_checked("nvme", &["fw-commit", dev, &format!("--slot={}", slot), &format!("--action={}", action)])?;
+                Ok(if *immediate {
+                    format!("Firmware slot {} activated on {}", slot, dev)
+                } else {
+                    format!("Firmware slot {} of {} becomes active on the next reset", slot, dev)
+                })
+            }
+        }
+    }
+}
+
+/// Runs an external command and turns a non-zero exit status into an error.
+///
+/// # Arguments
+/// * `program` - Executable name (e.g., "hdparm")
+/// * `args` - Command-line arguments
+///
+/// # Returns
+/// The command's standard output on success.
+fn run_checked(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = c_locale_command(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("failed to run {}: {}", program, e))?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(format!("{} failed: {}", program, stderr.trim()))
+    }
+}
+
+/// Default location for a telemetry dump: `~/nvme-telemetry-<device>-<unix time>.bin`
+///
+/// # Arguments
+/// * `dev` - Device path (e.g., "/dev/nvme0n1")
+/// * `now` - Current Unix time in seconds
+pub fn default_telemetry_path(dev: &str, now: u64) -> PathBuf {
+    let dir = home_dir();
+    dir.join(format!("nvme-telemetry-{}-{}.bin", dev.trim_start_matches("/dev/"), now))
+}
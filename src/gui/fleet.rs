@@ -0,0 +1,170 @@
+// Fleet view: drive reports fetched from other machines running `ssd_info_cli serve`
+
+// Reports returned by the REST API
+use crate::gui::report::Report;
+// Location of the host list
+use crate::gui::settings::config_dir;
+// HTTP client
+use crate::http;
+// JSON parsing of responses
+use crate::json::Json;
+// Host list file location
+use std::path::PathBuf;
+// Channel delivering fetch results to the UI thread
+use std::sync::mpsc::{channel, Receiver, Sender};
+// Fetch timestamps
+use std::time::Instant;
+
+/// Port `ssd_info_cli serve` listens on by default
+const DEFAULT_PORT: u16 = 8190;
+
+/// One remote machine in the fleet.
+pub struct FleetHost {
+    /// Address as entered: "host", "host:port" or a full http:// URL
+    pub address: String,
+    /// Latest fetch result, None until the first fetch finishes
+    pub report: Option<Result<Report, String>>,
+    /// When the latest fetch finished
+    pub fetched_at: Option<Instant>,
+    /// Whether a fetch is running
+    pub pending: bool,
+}
+
+/// The configured remote hosts and their latest reports.
+pub struct Fleet {
+    /// Hosts in the order they were added
+    pub hosts: Vec<FleetHost>,
+    /// File the host list is saved to, None to keep it in memory only
+    path: Option<PathBuf>,
+    /// Sender cloned into fetch threads
+    tx: Sender<(String, Result<Report, String>)>,
+    /// Receives (address, result) from fetch threads
+    rx: Receiver<(String, Result<Report, String>)>,
+}
+
+impl Fleet {
+    /// Loads the host list from `fleet.conf` in the config directory (one address per line, `#` comments).
+    pub fn load_default() -> Self {
+        let path = config_dir().map(|dir| dir.join("fleet.conf"));
+        let text = path.as_ref().and_then(|p| std::fs::read_to_string(p).ok()).unwrap_or_default();
+        let mut fleet = Self::new(parse_hosts(&text));
+        fleet.path = path;
+        fleet
+    }
+
+    /// Creates a fleet that is not backed by a file.
+    ///
+    /// # Arguments
+    /// * `addresses` - Host addresses
+    pub fn new(addresses: Vec<String>) -> Self {
+        let (tx, rx) = channel();
+        let hosts = addresses
+            .into_iter()
+            .map(|address| FleetHost { address, report: None, fetched_at: None, pending: false })
+            .collect();
+        Self { hosts, path: None, tx, rx }
+    }
+
+    /// Adds a host and saves the list. Duplicates are ignored.
+    ///
+    /// # Returns
+    /// Ok(()) on success, Err with a message if the list could not be saved.
+    pub fn add(&mut self, address: &str) -> Result<(), String> {
+        let address = address.trim();
+        if address.is_empty() || self.hosts.iter().any(|h| h.address == address) {
+            return Ok(());
+        }
+        self.hosts.push(FleetHost { address: address.to_string(), report: None, fetched_at: None, pending: false });
+        self.save()
+    }
+
+    /// Removes the host at `index` and saves the list.
+    pub fn remove(&mut self, index: usize) -> Result<(), String> {
+        if index < self.hosts.len() {
+            self.hosts.remove(index);
+        }
+        self.save()
+    }
+
+    /// Starts fetching every host that is not already being fetched, one thread per host.
+    pub fn refresh(&mut self) {
+        for host in self.hosts.iter_mut().filter(|h| !h.pending) {
+            host.pending = true;
+            let (address, tx) = (host.address.clone(), self.tx.clone());
+            std::thread::spawn(move || {
+                let result = fetch(&address);
+                let _ = tx.send((address, result));
+            });
+        }
+    }
+
+    /// Stores results of finished fetches.
+    ///
+    /// # Returns
+    /// True while fetches are still running.
+    pub fn poll(&mut self) -> bool {
+        while let Ok((address, result)) = self.rx.try_recv() {
+            // The host may have been removed while its fetch was running
+            if let Some(host) = self.hosts.iter_mut().find(|h| h.address == address) {
+                host.report = Some(result);
+                host.fetched_at = Some(Instant::now());
+                host.pending = false;
+            }
+        }
+        self.hosts.iter().any(|h| h.pending)
+    }
+
+    /// Writes the host list back to its file.
+    fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        let mut text = String::from("# Hosts running `ssd_info_cli serve`, one per line\n");
+        for host in &self.hosts {
+            text.push_str(&host.address);
+            text.push('\n');
+        }
+        std::fs::write(path, text).map_err(|e| format!("Failed to save {}: {}", path.display(), e))
+    }
+}
+
+/// Parses a host list: one address per line, blank lines and `#` comments ignored.
+pub fn parse_hosts(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// URL of a host's `/drives` endpoint.
+///
+/// # Arguments
+/// * `address` - "nas", "nas:9000" or "http://nas:9000" (an explicit path is kept as is)
+pub fn drives_url(address: &str) -> String {
+    let address = address.trim().trim_end_matches('/');
+    match address.strip_prefix("http://") {
+        Some(rest) if rest.contains('/') => address.to_string(),
+        Some(_) => format!("{}/drives", address),
+        None if address.contains(':') => format!("http://{}/drives", address),
+        None => format!("http://{}:{}/drives", address, DEFAULT_PORT),
+    }
+}
+
+/// Fetches a host's current report.
+///
+/// # Arguments
+/// * `address` - Host address as accepted by `drives_url`
+pub fn fetch(address: &str) -> Result<Report, String> {
+    let (status, body) = http::request("GET", &drives_url(address), &[("Accept", "application/json")], "")?;
+    let json = Json::parse(&body);
+    if status != 200 {
+        // Our server explains errors in {"error": ...}; anything else only has the status
+        let error = json.ok().and_then(|j| j.get("error").and_then(Json::as_str).map(str::to_string));
+        return Err(format!("HTTP {}{}", status, error.map(|e| format!(": {}", e)).unwrap_or_default()));
+    }
+    Report::from_json(&json?)
+}
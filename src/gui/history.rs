@@ -0,0 +1,373 @@
+// Persistent per-drive history of SMART counters, used for long-term rates and trends
+
+// Import data models for disk information
+use crate::models::DiskInfo;
+// Appending samples to history files
+use std::io::Write;
+// History file locations
+use std::path::PathBuf;
+
+/// Minimum time between two stored samples of the same drive (one per hour)
+const MIN_SAMPLE_INTERVAL_SECS: u64 = 3600;
+
+/// Window for the "recent" write rate, compared against the lifetime average
+const RECENT_WINDOW_SECS: u64 = 7 * 86_400;
+
+/// Minimum span of history before rates are reported, so a few minutes of data do not extrapolate wildly
+const MIN_RATE_SPAN_SECS: u64 = 86_400;
+
+/// One stored reading of a drive's counters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistorySample {
+    /// Unix time of the reading in seconds
+    pub timestamp: u64,
+    /// Total data written in TB
+    pub data_written_tb: Option<f64>,
+    /// Total data read in TB
+    pub data_read_tb: Option<f64>,
+    /// Power-on hours
+    pub power_on_hours: Option<u64>,
+    /// Health percentage (100 - Percentage Used on NVMe)
+    pub health_percent: Option<u8>,
+    /// Temperature in Celsius
+    pub temp_c: Option<i32>,
+    /// Current_Pending_Sector raw count (ATA only)
+    pub pending_sectors: Option<u64>,
+    /// Controller Busy Time in minutes (NVMe only)
+    pub controller_busy_minutes: Option<u64>,
+}
+
+impl HistorySample {
+    /// Takes a sample of a drive's current counters.
+    ///
+    /// # Arguments
+    /// * `di` - Drive to sample
+    /// * `now` - Current Unix time in seconds
+    pub fn from_disk(di: &DiskInfo, now: u64) -> Self {
+        Self {
+            timestamp: now,
+            data_written_tb: di.data_written_tb,
+            data_read_tb: di.data_read_tb,
+            power_on_hours: di.power_on_hours,
+            health_percent: di.health_percent,
+            temp_c: di.temp_c,
+            pending_sectors: di.critical_sectors().and_then(|s| s.pending),
+            controller_busy_minutes: di.controller_busy_minutes,
+        }
+    }
+}
+
+/// Write and wear rates derived from a drive's history.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UsageRates {
+    /// Average TB written per day over the whole history
+    pub written_tb_per_day: f64,
+    /// Average TB written per day over the last week, None if the week is not covered yet
+    pub recent_written_tb_per_day: Option<f64>,
+    /// Health percentage points lost per 30 days, None if the drive does not report health
+    pub wear_percent_per_month: Option<f64>,
+    /// Minutes of Controller Busy Time added per day, None if the drive does not report it
+    pub busy_minutes_per_day: Option<f64>,
+}
+
+/// An estimated date by which a drive should be replaced.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplacementEstimate {
+    /// Estimated date as days since 1970-01-01
+    pub day: i64,
+    /// How the date was derived, one line per input
+    pub explanation: Vec<String>,
+}
+
+/// Approximate host traffic between two readings of a drive's read/written counters.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TrafficRate {
+    /// Megabytes read per second, None if the drive does not count reads
+    pub read_mb_per_sec: Option<f64>,
+    /// Megabytes written per second, None if the drive does not count writes
+    pub write_mb_per_sec: Option<f64>,
+}
+
+/// Directory of per-drive history files, one CSV file per serial number.
+pub struct HistoryStore {
+    /// Directory holding the history files
+    dir: PathBuf,
+}
+
+impl HistoryStore {
+    /// Opens the store in `$XDG_DATA_HOME/ssd_info_cli/history` (or `~/.local/share/...`).
+    /// Returns None if neither variable is set.
+    pub fn open_default() -> Option<Self> {
+        Some(Self::new(data_dir()?.join("history")))
+    }
+
+    /// Creates a store rooted at an explicit directory (created on first write).
+    ///
+    /// # Arguments
+    /// * `dir` - Directory holding the history files
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Loads all samples of a drive, oldest first.
+    /// A missing or unreadable file yields an empty history.
+    ///
+    /// # Arguments
+    /// * `key` - Drive key from `drive_key`
+    pub fn load(&self, key: &str) -> Vec<HistorySample> {
+        std::fs::read_to_string(self.path(key))
+            .map(|text| parse_history(&text))
+            .unwrap_or_default()
+    }
+
+    /// Appends a sample unless the previous one is less than an hour old.
+    ///
+    /// # Arguments
+    /// * `key` - Drive key from `drive_key`
+    /// * `samples` - The drive's loaded history; the new sample is pushed onto it when stored
+    /// * `sample` - Reading to store
+    ///
+    /// # Returns
+    /// Ok(true) if the sample was written, Ok(false) if it was skipped.
+    pub fn record(&self, key: &str, samples: &mut Vec<HistorySample>, sample: HistorySample) -> std::io::Result<bool> {
+        if samples
+            .last()
+            .is_some_and(|last| sample.timestamp < last.timestamp + MIN_SAMPLE_INTERVAL_SECS)
+        {
+            return Ok(false);
+        }
+
+        std::fs::create_dir_all(&self.dir)?;
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.path(key))?;
+        writeln!(file, "{}", format_sample(&sample))?;
+        samples.push(sample);
+        Ok(true)
+    }
+
+    /// Path of a drive's history file.
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.csv", key))
+    }
+}
+
+/// This program's data directory: `$XDG_DATA_HOME/ssd_info_cli` (or `~/.local/share/ssd_info_cli`).
+/// Returns None if neither variable is set.
+pub fn data_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".local/share")))
+        .map(|base| base.join("ssd_info_cli"))
+}
+
+/// Derives the file name key for a drive from its model and serial number.
+/// Drives without a serial (USB bridges, failed probes) have no history.
+///
+/// # Arguments
+/// * `di` - Drive to key
+///
+/// # Returns
+/// A filesystem-safe key such as "Samsung_SSD_870_EVO_1TB-S6PTNX0R123456A".
+pub fn drive_key(di: &DiskInfo) -> Option<String> {
+    let serial = di.serial.as_deref()?.trim();
+    if serial.is_empty() {
+        return None;
+    }
+    let raw = match di.model.as_deref() {
+        Some(model) => format!("{}-{}", model.trim(), serial),
+        None => serial.to_string(),
+    };
+    Some(
+        raw.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '.' { c } else { '_' })
+            .collect(),
+    )
+}
+
+/// Parses history file contents.
+/// Format per line: "timestamp,written_tb,read_tb,power_on_hours,health_percent,temp_c,pending_sectors,busy_minutes",
+/// with empty fields for unknown values; lines written by older versions lack the last one or two fields.
+/// Malformed lines are skipped.
+///
+/// # Arguments
+/// * `text` - Contents of a history file
+pub fn parse_history(text: &str) -> Vec<HistorySample> {
+    text.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.trim().split(',').collect();
+            if !(6..=8).contains(&fields.len()) {
+                return None;
+            }
+            Some(HistorySample {
+                timestamp: fields[0].parse().ok()?,
+                data_written_tb: fields[1].parse().ok(),
+                data_read_tb: fields[2].parse().ok(),
+                power_on_hours: fields[3].parse().ok(),
+                health_percent: fields[4].parse().ok(),
+                temp_c: fields[5].parse().ok(),
+                pending_sectors: fields.get(6).and_then(|f| f.parse().ok()),
+                controller_busy_minutes: fields.get(7).and_then(|f| f.parse().ok()),
+            })
+        })
+        .collect()
+}
+
+/// Formats a sample as one history file line (see `parse_history`).
+fn format_sample(sample: &HistorySample) -> String {
+    fn field<T: ToString>(v: Option<T>) -> String {
+        v.map(|v| v.to_string()).unwrap_or_default()
+    }
+    format!(
+        "{},{},{},{},{},{},{},{}",
+        sample.timestamp,
+        field(sample.data_written_tb),
+        field(sample.data_read_tb),
+        field(sample.power_on_hours),
+        field(sample.health_percent),
+        field(sample.temp_c),
+        field(sample.pending_sectors),
+        field(sample.controller_busy_minutes)
+    )
+}
+
+/// Computes average write and wear rates from a drive's history.
+///
+/// # Arguments
+/// * `samples` - History, oldest first
+///
+/// # Returns
+/// None until the history spans at least a day with data written on both ends.
+pub fn usage_rates(samples: &[HistorySample]) -> Option<UsageRates> {
+    let written: Vec<(u64, f64)> = samples
+        .iter()
+        .filter_map(|s| s.data_written_tb.map(|tb| (s.timestamp, tb)))
+        .collect();
+    let (first, last) = (written.first()?, written.last()?);
+    let span = last.0.saturating_sub(first.0);
+    if span < MIN_RATE_SPAN_SECS {
+        return None;
+    }
+    let per_day = |from: &(u64, f64), to: &(u64, f64)| (to.1 - from.1).max(0.0) * 86_400.0 / (to.0 - from.0) as f64;
+
+    // Recent rate from the oldest sample inside the window, if the history reaches back that far
+    let window_start = last.0.saturating_sub(RECENT_WINDOW_SECS);
+    let recent_written_tb_per_day = if first.0 <= window_start {
+        written
+            .iter()
+            .find(|s| s.0 >= window_start)
+            .filter(|s| s.0 < last.0)
+            .map(|s| per_day(s, last))
+    } else {
+        None
+    };
+
+    // Health points lost between the first and last samples that report health
+    let health: Vec<(u64, u8)> = samples
+        .iter()
+        .filter_map(|s| s.health_percent.map(|h| (s.timestamp, h)))
+        .collect();
+    let wear_percent_per_month = match (health.first(), health.last()) {
+        (Some(a), Some(b)) if b.0 - a.0 >= MIN_RATE_SPAN_SECS => {
+            Some(a.1.saturating_sub(b.1) as f64 * 30.0 * 86_400.0 / (b.0 - a.0) as f64)
+        }
+        _ => None,
+    };
+
+    // Busy minutes added between the first and last samples that report them
+    let busy: Vec<(u64, u64)> = samples
+        .iter()
+        .filter_map(|s| s.controller_busy_minutes.map(|m| (s.timestamp, m)))
+        .collect();
+    let busy_minutes_per_day = match (busy.first(), busy.last()) {
+        (Some(a), Some(b)) if b.0 - a.0 >= MIN_RATE_SPAN_SECS => {
+            Some(b.1.saturating_sub(a.1) as f64 * 86_400.0 / (b.0 - a.0) as f64)
+        }
+        _ => None,
+    };
+
+    Some(UsageRates {
+        written_tb_per_day: per_day(first, last),
+        recent_written_tb_per_day,
+        wear_percent_per_month,
+        busy_minutes_per_day,
+    })
+}
+
+/// Computes host traffic from two readings of the total read/written counters.
+/// The counters have coarse units (512,000 bytes on NVMe, one LBA or more on SATA), so this is an
+/// activity indicator rather than a precise throughput.
+///
+/// # Arguments
+/// * `previous` - Earlier reading
+/// * `current` - Later reading
+/// * `elapsed_secs` - Time between the readings
+///
+/// # Returns
+/// Rates in MB/s; a side is None if either reading lacks it or the counter went backwards.
+pub fn traffic_rate(previous: &HistorySample, current: &HistorySample, elapsed_secs: f64) -> TrafficRate {
+    let rate = |before: Option<f64>, after: Option<f64>| {
+        let (before, after) = (before?, after?);
+        (elapsed_secs > 0.0 && after >= before).then(|| (after - before) * 1e6 / elapsed_secs)
+    };
+    TrafficRate {
+        read_mb_per_sec: rate(previous.data_read_tb, current.data_read_tb),
+        write_mb_per_sec: rate(previous.data_written_tb, current.data_written_tb),
+    }
+}
+
+/// Estimates when a drive should be replaced from its history: health falling at the current wear rate,
+/// writes reaching the endurance rating, and growing pending sectors. The earliest of these wins.
+///
+/// # Arguments
+/// * `samples` - History, oldest first
+/// * `rated_tb` - Endurance rating in TB written, if known
+/// * `today` - Today as days since 1970-01-01
+///
+/// # Returns
+/// None if no input gives a date (too little history, or nothing wearing out).
+pub fn replacement_estimate(samples: &[HistorySample], rated_tb: Option<f64>, today: i64) -> Option<ReplacementEstimate> {
+    let mut candidates: Vec<(f64, String)> = Vec::new();
+    let rates = usage_rates(samples);
+
+    let health = samples.iter().rev().find_map(|s| s.health_percent);
+    if let (Some(health), Some(wear)) = (health, rates.and_then(|r| r.wear_percent_per_month)) {
+        if wear > 0.0 {
+            let days = health as f64 / wear * 30.0;
+            candidates.push((days, format!("Health {}% falling {:.2}% per month reaches 0% in {:.0} days", health, wear, days)));
+        }
+    }
+
+    let written = samples.iter().rev().find_map(|s| s.data_written_tb);
+    if let (Some(rated), Some(written), Some(rates)) = (rated_tb, written, rates) {
+        if rates.written_tb_per_day > 0.0 {
+            let days = (rated - written).max(0.0) / rates.written_tb_per_day;
+            candidates.push((
+                days,
+                format!(
+                    "{:.1} TB of the {:.0} TBW rating left at {:.2} TB/day lasts {:.0} days",
+                    (rated - written).max(0.0),
+                    rated,
+                    rates.written_tb_per_day,
+                    days
+                ),
+            ));
+        }
+    }
+
+    // Growing pending sectors mean the surface is failing now, whatever the wear says
+    let pending: Vec<u64> = samples.iter().filter_map(|s| s.pending_sectors).collect();
+    if let (Some(first), Some(last)) = (pending.first(), pending.last()) {
+        if last > first {
+            candidates.push((0.0, format!("Pending sectors grew from {} to {}: replace now", first, last)));
+        }
+    }
+
+    let earliest = candidates.iter().map(|(days, _)| *days).fold(f64::INFINITY, f64::min);
+    earliest.is_finite().then(|| ReplacementEstimate {
+        day: today + earliest.round() as i64,
+        explanation: candidates.into_iter().map(|(_, line)| line).collect(),
+    })
+}
@@ -1,11 +1,45 @@
 // GUI module organization and public exports
 
+// Alert rules and notification channels
+pub mod alerts;
+// Start-on-login desktop entry
+pub mod autostart;
+// Read-only drive benchmark and its history
+pub mod benchmark;
 // Main application state and UI logic
 mod app;
+// Saving charts as PNG images
+pub mod chart_export;
 // Reusable UI components (stat cards, etc.)
 mod components;
+// Environment details for the About window
+pub mod diagnostics;
 // Disk scanning and SMART data collection
-mod disk_scanner;
+pub mod disk_scanner;
+// State-changing drive operations (spin down, etc.)
+pub mod drive_actions;
+// Reports fetched from other machines for the fleet view
+pub mod fleet;
+// Persistent per-drive history of SMART counters
+pub mod history;
+// Ctrl+K command palette matching
+pub mod palette;
+// JSON report export and import
+pub mod report;
+// Daily or weekly automatic report exports with rotation
+pub mod scheduled_export;
+// Chronological log of scans, errors, changes and alerts this session
+pub mod session_log;
+// Per-drive user settings file
+pub mod settings;
+// Background read-only surface scan
+pub mod surface_scan;
+// CPU/GPU temperatures, fans and swap devices
+pub mod system_sensors;
+// System tray icon for the window hidden on close
+pub mod tray;
+// Background "What's using space?" analysis
+pub mod usage_analyzer;
 
 // Export AppState for use in main.rs
 pub use app::AppState;
@@ -0,0 +1,70 @@
+// Ctrl+K command palette: typed query state and fuzzy matching of the entries
+
+/// Query and highlighted row of the open palette.
+#[derive(Clone, Debug, Default)]
+pub struct PaletteState {
+    /// Text typed so far
+    pub query: String,
+    /// Index into the ranked entries of the row Enter runs
+    pub highlighted: usize,
+}
+
+/// Scores `text` against `query` as a case-insensitive subsequence match, like most editors' "go to" boxes.
+/// Consecutive matches and matches at the start of a word score higher.
+///
+/// # Arguments
+/// * `query` - Typed text; spaces are ignored
+/// * `text` - Entry label
+///
+/// # Returns
+/// Higher is better; None if `text` does not contain every query character in order.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let query: Vec<char> = query.to_lowercase().chars().filter(|c| !c.is_whitespace()).collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let Some(&first) = query.first() else {
+        return Some(0);
+    };
+    // Matching greedily from the first occurrence can miss a better run later on ("sda" in "switch … /dev/sda"),
+    // so every occurrence of the first character is tried as the start
+    (0..text.len()).filter(|&start| text[start] == first).filter_map(|start| score_from(&query, &text, start)).max()
+}
+
+/// Scores a greedy match of `query` in `text` whose first character is at `start`.
+fn score_from(query: &[char], text: &[char], start: usize) -> Option<i32> {
+    let mut score = 0;
+    let mut pos = 0;
+    let mut previous: Option<usize> = None;
+    for (n, &q) in query.iter().enumerate() {
+        let found = if n == 0 { start } else { pos + text[pos..].iter().position(|&c| c == q)? };
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 8;
+        }
+        // Skipped characters cost a little, so tighter matches win among equals
+        score -= (found - pos).min(10) as i32 / 2;
+        previous = Some(found);
+        pos = found + 1;
+    }
+    Some(score)
+}
+
+/// Orders entry labels by how well they match the query, dropping the ones that do not match.
+///
+/// # Arguments
+/// * `query` - Typed text; an empty query keeps every entry in its original order
+/// * `labels` - Entry labels in their default order
+///
+/// # Returns
+/// Indices into `labels`, best match first; ties keep their original order.
+pub fn rank<'a>(query: &str, labels: impl IntoIterator<Item = &'a str>) -> Vec<usize> {
+    let mut scored: Vec<(usize, i32)> = labels
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, label)| fuzzy_score(query, label).map(|score| (i, score)))
+        .collect();
+    scored.sort_by_key(|&(i, score)| (-score, i));
+    scored.into_iter().map(|(i, _)| i).collect()
+}
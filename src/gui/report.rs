@@ -0,0 +1,509 @@
+// JSON drive reports: exported from the GUI and opened again as read-only snapshots
+
+// JSON value, parser and writer
+use crate::json::Json;
+// Import data models for disk information
+use crate::models::{
+    advisories, ApstStatus, AttributeStatus, DiskInfo, EnduranceGroup, ErcSettings, FirmwareSlots, LinkPower, NamespaceUsage, NvmeController,
+    PartitionInfo, SmartAttribute,
+};
+// Report file paths
+use crate::gui::settings::home_dir;
+use std::path::{Path, PathBuf};
+
+/// Value of the "format" field identifying a report file
+const REPORT_FORMAT: &str = "ssd_info_cli report";
+
+/// Report format version, bumped when fields change meaning
+const REPORT_VERSION: u64 = 1;
+
+/// A snapshot of every drive on one machine.
+#[derive(Clone, Debug)]
+pub struct Report {
+    /// Host name of the machine the report was taken on
+    pub host: String,
+    /// Unix time the report was taken, in seconds
+    pub generated_at: u64,
+    /// Drives as they were at that time
+    pub drives: Vec<DiskInfo>,
+}
+
+impl Report {
+    /// Converts the report to its JSON document.
+    pub fn to_json(&self) -> Json {
+        Json::Object(vec![
+            ("format".into(), REPORT_FORMAT.into()),
+            ("version".into(), REPORT_VERSION.into()),
+            ("host".into(), self.host.as_str().into()),
+            ("generated_at".into(), self.generated_at.into()),
+            ("drives".into(), Json::Array(self.drives.iter().map(disk_to_json).collect())),
+        ])
+    }
+
+    /// Reads a report from its JSON document.
+    ///
+    /// # Returns
+    /// The report, or an error if the document is not a report of a supported version.
+    pub fn from_json(json: &Json) -> Result<Self, String> {
+        if json.get("format").and_then(Json::as_str) != Some(REPORT_FORMAT) {
+            return Err("Not an ssd_info_cli report".to_string());
+        }
+        let version = json.get("version").and_then(Json::as_u64).unwrap_or(0);
+        if version > REPORT_VERSION {
+            return Err(format!("Report version {} is newer than this program supports", version));
+        }
+        let drives = json
+            .get("drives")
+            .and_then(Json::as_array)
+            .ok_or("Report has no drive list")?
+            .iter()
+            .map(disk_from_json)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            host: json.get("host").and_then(Json::as_str).unwrap_or("unknown host").to_string(),
+            generated_at: json.get("generated_at").and_then(Json::as_u64).unwrap_or(0),
+            drives,
+        })
+    }
+
+    /// Loads a report file.
+    ///
+    /// # Arguments
+    /// * `path` - Path of the JSON report
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Self::from_json(&Json::parse(&text)?)
+    }
+
+    /// Writes the report as indented JSON.
+    ///
+    /// # Arguments
+    /// * `path` - Destination file
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        std::fs::write(path, self.to_json().to_pretty() + "\n")
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+/// Default location for a new report: `~/ssd-report-<host>-<unix time>.json`
+///
+/// # Arguments
+/// * `host` - Host name the report is taken on
+/// * `now` - Current Unix time in seconds
+pub fn default_report_path(host: &str, now: u64) -> PathBuf {
+    let dir = home_dir();
+    dir.join(format!("ssd-report-{}-{}.json", host, now))
+}
+
+/// Serializes one drive as it appears in the report's "drives" array.
+pub fn disk_to_json(di: &DiskInfo) -> Json {
+    let str_field = |v: &Option<String>| Json::from(v.clone());
+    Json::Object(vec![
+        ("dev".into(), di.dev.as_str().into()),
+        ("kind".into(), di.kind.as_str().into()),
+        ("model".into(), str_field(&di.model)),
+        ("serial".into(), str_field(&di.serial)),
+        ("firmware".into(), str_field(&di.firmware)),
+        ("wwn".into(), str_field(&di.wwn)),
+        ("by_id".into(), str_field(&di.by_id)),
+        ("network_transport".into(), str_field(&di.network_transport)),
+        ("removable".into(), di.removable.into()),
+        ("hosts_root".into(), di.hosts_root.into()),
+        ("hosts_esp".into(), di.hosts_esp.into()),
+        ("capacity_bytes".into(), di.capacity.into()),
+        ("capacity".into(), str_field(&di.capacity_str)),
+        ("protocol".into(), str_field(&di.protocol)),
+        ("device_type".into(), str_field(&di.device_type)),
+        ("health_percent".into(), di.health_percent.into()),
+        ("smart_enabled".into(), di.smart_enabled.into()),
+        ("smart_passed".into(), di.smart_passed.into()),
+        ("temperature_c".into(), di.temp_c.into()),
+        ("data_written_tb".into(), di.data_written_tb.into()),
+        ("data_read_tb".into(), di.data_read_tb.into()),
+        ("power_on_hours".into(), di.power_on_hours.into()),
+        ("power_cycles".into(), di.power_cycles.into()),
+        ("unsafe_shutdowns".into(), di.unsafe_shutdowns.into()),
+        ("media_errors".into(), di.media_errors.into()),
+        ("controller_busy_minutes".into(), di.controller_busy_minutes.into()),
+        ("rotation_rpm".into(), di.rotation_rpm.into()),
+        ("last_self_test".into(), str_field(&di.last_self_test)),
+        ("write_amplification".into(), di.write_amplification.into()),
+        ("power_state".into(), str_field(&di.power_state)),
+        ("write_cache".into(), di.write_cache.into()),
+        (
+            "link_power".into(),
+            di.link_power
+                .as_ref()
+                .map(|lp| {
+                    Json::Object(vec![("host".into(), lp.host.as_str().into()), ("policy".into(), lp.policy.as_str().into())])
+                })
+                .into(),
+        ),
+        ("apm_level".into(), di.apm_level.into()),
+        ("aam_level".into(), di.aam_level.into()),
+        (
+            "erc".into(),
+            di.erc
+                .map(|erc| {
+                    Json::Object(vec![
+                        ("read_ds".into(), erc.read_ds.map(u64::from).into()),
+                        ("write_ds".into(), erc.write_ds.map(u64::from).into()),
+                    ])
+                })
+                .into(),
+        ),
+        (
+            "nvme_controller".into(),
+            di.nvme_controller
+                .as_ref()
+                .map(|c| {
+                    Json::Object(vec![
+                        ("controller_id".into(), c.controller_id.map(u64::from).into()),
+                        ("ieee_oui".into(), str_field(&c.ieee_oui)),
+                        ("nvme_version".into(), str_field(&c.nvme_version)),
+                        ("namespaces".into(), c.namespaces.map(u64::from).into()),
+                        ("subsystem_nqn".into(), str_field(&c.subsystem_nqn)),
+                        ("volatile_write_cache".into(), c.volatile_write_cache.into()),
+                        ("hmb_preferred_bytes".into(), c.hmb_preferred_bytes.into()),
+                        ("hmb_allocated_bytes".into(), c.hmb_allocated_bytes.into()),
+                        ("log_page_attributes".into(), c.log_page_attributes.map(u64::from).into()),
+                        ("firmware_updates".into(), c.firmware_updates.map(u64::from).into()),
+                        (
+                            "firmware_slots".into(),
+                            c.firmware_slots
+                                .as_ref()
+                                .map(|fw| {
+                                    Json::Object(vec![
+                                        ("active".into(), u64::from(fw.active).into()),
+                                        ("next_reset".into(), fw.next_reset.map(u64::from).into()),
+                                        (
+                                            "slots".into(),
+                                            Json::Array(
+                                                fw.slots
+                                                    .iter()
+                                                    .map(|(slot, revision)| {
+                                                        Json::Object(vec![
+                                                            ("slot".into(), u64::from(*slot).into()),
+                                                            ("revision".into(), revision.as_str().into()),
+                                                        ])
+                                                    })
+                                                    .collect(),
+                                            ),
+                                        ),
+                                    ])
+                                })
+                                .into(),
+                        ),
+                        ("apst_supported".into(), c.apst_supported.into()),
+                        (
+                            "apst".into(),
+                            c.apst
+                                .as_ref()
+                                .map(|apst| {
+                                    Json::Object(vec![
+                                        ("enabled".into(), apst.enabled.into()),
+                                        (
+                                            "transitions".into(),
+                                            Json::Array(
+                                                apst.transitions
+                                                    .iter()
+                                                    .map(|&(idle_ms, state)| {
+                                                        Json::Object(vec![
+                                                            ("idle_ms".into(), idle_ms.into()),
+                                                            ("power_state".into(), u64::from(state).into()),
+                                                        ])
+                                                    })
+                                                    .collect(),
+                                            ),
+                                        ),
+                                        ("max_latency_us".into(), apst.max_latency_us.into()),
+                                    ])
+                                })
+                                .into(),
+                        ),
+                        ("controller_attributes".into(), c.controller_attributes.map(u64::from).into()),
+                        ("endurance_group_max".into(), c.endurance_group_max.map(u64::from).into()),
+                        (
+                            "endurance_groups".into(),
+                            Json::Array(
+                                c.endurance_groups
+                                    .iter()
+                                    .map(|g| {
+                                        Json::Object(vec![
+                                            ("id".into(), u64::from(g.id).into()),
+                                            ("percent_used".into(), g.percent_used.map(u64::from).into()),
+                                            ("available_spare".into(), g.available_spare.map(u64::from).into()),
+                                            ("host_bytes_written".into(), g.host_bytes_written.into()),
+                                            ("media_bytes_written".into(), g.media_bytes_written.into()),
+                                            ("total_bytes".into(), g.total_bytes.into()),
+                                            ("unallocated_bytes".into(), g.unallocated_bytes.into()),
+                                        ])
+                                    })
+                                    .collect(),
+                            ),
+                        ),
+                        (
+                            "namespace_usage".into(),
+                            Json::Array(
+                                c.namespace_usage
+                                    .iter()
+                                    .map(|ns| {
+                                        Json::Object(vec![
+                                            ("nsid".into(), u64::from(ns.nsid).into()),
+                                            ("endurance_group".into(), ns.endurance_group.map(u64::from).into()),
+                                            ("size_bytes".into(), ns.size_bytes.into()),
+                                            ("used_bytes".into(), ns.used_bytes.into()),
+                                        ])
+                                    })
+                                    .collect(),
+                            ),
+                        ),
+                    ])
+                })
+                .into(),
+        ),
+        ("unallocated_bytes".into(), di.unallocated_bytes.into()),
+        (
+            "smart_attributes".into(),
+            Json::Array(
+                di.smart_attributes
+                    .iter()
+                    .map(|a| {
+                        Json::Object(vec![
+                            ("id".into(), a.id.as_str().into()),
+                            ("name".into(), a.name.as_str().into()),
+                            ("current".into(), a.current.as_str().into()),
+                            ("worst".into(), a.worst.as_str().into()),
+                            ("threshold".into(), a.threshold.as_str().into()),
+                            // Raw values stay strings: they can exceed 2^53 or carry text ("36 (Min/Max 0/52)")
+                            ("raw".into(), a.raw_value.as_str().into()),
+                            ("status".into(), status_name(&a.status).into()),
+                            ("pre_fail".into(), a.pre_fail.into()),
+                        ])
+                    })
+                    .collect(),
+            ),
+        ),
+        ("partitions".into(), Json::Array(di.partitions.iter().map(partition_to_json).collect())),
+    ])
+}
+
+/// Serializes one partition.
+fn partition_to_json(p: &PartitionInfo) -> Json {
+    let str_field = |v: &Option<String>| Json::from(v.clone());
+    Json::Object(vec![
+        ("device".into(), p.device.as_str().into()),
+        ("mount_point".into(), p.mount_point.as_str().into()),
+        ("fs_type".into(), p.fs_type.as_str().into()),
+        ("label".into(), str_field(&p.fs_label)),
+        ("part_label".into(), str_field(&p.part_label)),
+        ("part_uuid".into(), str_field(&p.part_uuid)),
+        ("part_type".into(), str_field(&p.part_type)),
+        ("total_gb".into(), p.total_gb.into()),
+        ("used_gb".into(), p.used_gb.into()),
+        ("free_gb".into(), p.free_gb.into()),
+        ("used_percent".into(), p.used_percent.into()),
+        (
+            "mount_options".into(),
+            Json::Array(p.mount_options.iter().map(|o| o.as_str().into()).collect()),
+        ),
+        ("start_bytes".into(), p.start_bytes.into()),
+        ("alignment_bytes".into(), p.alignment_bytes.into()),
+        ("inodes_total".into(), p.inodes_total.into()),
+        ("inodes_used".into(), p.inodes_used.into()),
+    ])
+}
+
+/// Deserializes one drive. Missing fields are left unknown; only "dev" is required.
+fn disk_from_json(json: &Json) -> Result<DiskInfo, String> {
+    let text = |key: &str| json.get(key).and_then(Json::as_str).map(str::to_string);
+    let int = |key: &str| json.get(key).and_then(Json::as_u64);
+    let float = |key: &str| json.get(key).and_then(Json::as_f64);
+
+    let mut di = DiskInfo::empty(text("dev").ok_or("Drive entry without a device path")?);
+    di.kind = text("kind").unwrap_or(di.kind);
+    di.model = text("model");
+    di.serial = text("serial");
+    di.firmware = text("firmware");
+    di.wwn = text("wwn");
+    di.by_id = text("by_id");
+    di.network_transport = text("network_transport");
+    di.removable = json.get("removable").and_then(Json::as_bool).unwrap_or(false);
+    di.hosts_root = json.get("hosts_root").and_then(Json::as_bool).unwrap_or(false);
+    di.hosts_esp = json.get("hosts_esp").and_then(Json::as_bool).unwrap_or(false);
+    di.capacity = float("capacity_bytes");
+    di.capacity_str = text("capacity");
+    di.protocol = text("protocol");
+    di.device_type = text("device_type");
+    di.health_percent = int("health_percent").map(|v| v.min(100) as u8);
+    di.smart_enabled = json.get("smart_enabled").and_then(Json::as_bool);
+    di.smart_passed = json.get("smart_passed").and_then(Json::as_bool);
+    di.temp_c = float("temperature_c").map(|t| t as i32);
+    di.data_written_tb = float("data_written_tb");
+    di.data_read_tb = float("data_read_tb");
+    di.power_on_hours = int("power_on_hours");
+    di.power_cycles = int("power_cycles");
+    di.unsafe_shutdowns = int("unsafe_shutdowns");
+    di.media_errors = int("media_errors");
+    di.controller_busy_minutes = int("controller_busy_minutes");
+    di.rotation_rpm = int("rotation_rpm");
+    di.last_self_test = text("last_self_test");
+    di.write_amplification = float("write_amplification");
+    di.power_state = text("power_state");
+    di.write_cache = json.get("write_cache").and_then(Json::as_bool);
+    di.link_power = json.get("link_power").and_then(|lp| {
+        Some(LinkPower {
+            host: lp.get("host").and_then(Json::as_str)?.to_string(),
+            policy: lp.get("policy").and_then(Json::as_str)?.to_string(),
+        })
+    });
+    di.apm_level = int("apm_level").and_then(|v| u8::try_from(v).ok());
+    di.aam_level = int("aam_level").and_then(|v| u8::try_from(v).ok());
+    di.erc = json.get("erc").filter(|e| **e != Json::Null).map(|erc| ErcSettings {
+        read_ds: erc.get("read_ds").and_then(Json::as_u64).and_then(|v| u16::try_from(v).ok()),
+        write_ds: erc.get("write_ds").and_then(Json::as_u64).and_then(|v| u16::try_from(v).ok()),
+    });
+    di.nvme_controller = json.get("nvme_controller").filter(|c| **c != Json::Null).map(|c| {
+        let text = |key: &str| c.get(key).and_then(Json::as_str).map(str::to_string);
+        NvmeController {
+            controller_id: c.get("controller_id").and_then(Json::as_u64).and_then(|v| u16::try_from(v).ok()),
+            ieee_oui: text("ieee_oui"),
+            nvme_version: text("nvme_version"),
+            namespaces: c.get("namespaces").and_then(Json::as_u64).and_then(|v| u32::try_from(v).ok()),
+            subsystem_nqn: text("subsystem_nqn"),
+            volatile_write_cache: c.get("volatile_write_cache").and_then(Json::as_bool),
+            hmb_preferred_bytes: c.get("hmb_preferred_bytes").and_then(Json::as_u64),
+            hmb_allocated_bytes: c.get("hmb_allocated_bytes").and_then(Json::as_u64),
+            log_page_attributes: c.get("log_page_attributes").and_then(Json::as_u64).and_then(|v| u32::try_from(v).ok()),
+            firmware_updates: c.get("firmware_updates").and_then(Json::as_u64).and_then(|v| u8::try_from(v).ok()),
+            firmware_slots: c.get("firmware_slots").filter(|f| **f != Json::Null).map(|fw| {
+                let slot = |v: Option<&Json>| v.and_then(Json::as_u64).and_then(|v| u8::try_from(v).ok());
+                FirmwareSlots {
+                    active: slot(fw.get("active")).unwrap_or(0),
+                    next_reset: slot(fw.get("next_reset")),
+                    slots: fw
+                        .get("slots")
+                        .and_then(Json::as_array)
+                        .unwrap_or_default()
+                        .iter()
+                        .filter_map(|s| Some((slot(s.get("slot"))?, s.get("revision").and_then(Json::as_str)?.to_string())))
+                        .collect(),
+                }
+            }),
+            apst_supported: c.get("apst_supported").and_then(Json::as_bool),
+            apst: c.get("apst").filter(|a| **a != Json::Null).map(|apst| ApstStatus {
+                enabled: apst.get("enabled").and_then(Json::as_bool).unwrap_or(false),
+                transitions: apst
+                    .get("transitions")
+                    .and_then(Json::as_array)
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|t| {
+                        let idle_ms = t.get("idle_ms").and_then(Json::as_u64)?;
+                        let state = t.get("power_state").and_then(Json::as_u64).and_then(|v| u8::try_from(v).ok())?;
+                        Some((idle_ms, state))
+                    })
+                    .collect(),
+                max_latency_us: apst.get("max_latency_us").and_then(Json::as_u64),
+            }),
+            controller_attributes: c.get("controller_attributes").and_then(Json::as_u64).and_then(|v| u32::try_from(v).ok()),
+            endurance_group_max: c.get("endurance_group_max").and_then(Json::as_u64).and_then(|v| u16::try_from(v).ok()),
+            endurance_groups: c
+                .get("endurance_groups")
+                .and_then(Json::as_array)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|g| {
+                    let percent = |key: &str| g.get(key).and_then(Json::as_u64).and_then(|v| u8::try_from(v).ok());
+                    Some(EnduranceGroup {
+                        id: g.get("id").and_then(Json::as_u64).and_then(|v| u16::try_from(v).ok())?,
+                        percent_used: percent("percent_used"),
+                        available_spare: percent("available_spare"),
+                        host_bytes_written: g.get("host_bytes_written").and_then(Json::as_u64),
+                        media_bytes_written: g.get("media_bytes_written").and_then(Json::as_u64),
+                        total_bytes: g.get("total_bytes").and_then(Json::as_u64),
+                        unallocated_bytes: g.get("unallocated_bytes").and_then(Json::as_u64),
+                    })
+                })
+                .collect(),
+            namespace_usage: c
+                .get("namespace_usage")
+                .and_then(Json::as_array)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|ns| {
+                    Some(NamespaceUsage {
+                        nsid: ns.get("nsid").and_then(Json::as_u64).and_then(|v| u32::try_from(v).ok())?,
+                        endurance_group: ns.get("endurance_group").and_then(Json::as_u64).and_then(|v| u16::try_from(v).ok()),
+                        size_bytes: ns.get("size_bytes").and_then(Json::as_u64)?,
+                        used_bytes: ns.get("used_bytes").and_then(Json::as_u64).unwrap_or(0),
+                    })
+                })
+                .collect(),
+        }
+    });
+    di.unallocated_bytes = int("unallocated_bytes");
+
+    for attr in json.get("smart_attributes").and_then(Json::as_array).unwrap_or_default() {
+        let field = |key: &str| attr.get(key).and_then(Json::as_str).unwrap_or_default().to_string();
+        di.smart_attributes.push(SmartAttribute {
+            id: field("id"),
+            name: field("name"),
+            current: field("current"),
+            worst: field("worst"),
+            threshold: field("threshold"),
+            raw_value: field("raw"),
+            status: match field("status").as_str() {
+                "critical" => AttributeStatus::Critical,
+                "warning" => AttributeStatus::Warning,
+                _ => AttributeStatus::Good,
+            },
+            pre_fail: attr.get("pre_fail").and_then(Json::as_bool).unwrap_or(false),
+        });
+    }
+
+    for part in json.get("partitions").and_then(Json::as_array).unwrap_or_default() {
+        let text = |key: &str| part.get(key).and_then(Json::as_str).map(str::to_string);
+        let float = |key: &str| part.get(key).and_then(Json::as_f64).unwrap_or(0.0);
+        di.partitions.push(PartitionInfo {
+            mount_point: text("mount_point").unwrap_or_default(),
+            fs_type: text("fs_type").unwrap_or_default(),
+            total_gb: float("total_gb"),
+            used_gb: float("used_gb"),
+            free_gb: float("free_gb"),
+            used_percent: float("used_percent"),
+            mount_options: part
+                .get("mount_options")
+                .and_then(Json::as_array)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|o| o.as_str().map(str::to_string))
+                .collect(),
+            device: text("device").unwrap_or_default(),
+            start_bytes: part.get("start_bytes").and_then(Json::as_u64),
+            alignment_bytes: part.get("alignment_bytes").and_then(Json::as_u64),
+            fs_label: text("label"),
+            part_label: text("part_label"),
+            part_uuid: text("part_uuid"),
+            part_type: text("part_type"),
+            inodes_total: part.get("inodes_total").and_then(Json::as_u64),
+            inodes_used: part.get("inodes_used").and_then(Json::as_u64),
+        });
+    }
+
+    // Advisories come from this program's knowledge base, which may be newer than the report
+    if let Some(model) = &di.model {
+        di.advisories = advisories::lookup(model, di.firmware.as_deref());
+    }
+    Ok(di)
+}
+
+/// Name of an attribute status as stored in reports.
+fn status_name(status: &AttributeStatus) -> &'static str {
+    match status {
+        AttributeStatus::Good => "good",
+        AttributeStatus::Warning => "warning",
+        AttributeStatus::Critical => "critical",
+    }
+}
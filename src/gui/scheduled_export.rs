@@ -0,0 +1,223 @@
+// Automatic daily or weekly report exports into a directory of the user's choice, oldest files rotated out,
+// so there is an audit trail of every drive even if nobody presses the export button
+
+// Exported report and its CSV form
+use crate::gui::report::Report;
+use crate::metrics::csv_document;
+// Export directory and files
+use crate::gui::settings::home_dir;
+use std::path::{Path, PathBuf};
+
+/// Exports kept in the directory when the settings do not say otherwise (a month of daily exports)
+pub const DEFAULT_KEEP: usize = 30;
+
+/// File name prefix of scheduled exports; manual exports ("ssd-report-<host>-...") are never rotated
+const FILE_PREFIX: &str = "ssd-report-auto-";
+
+/// How often a scheduled export is written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportInterval {
+    /// Once a day
+    #[default]
+    Daily,
+    /// Once a week
+    Weekly,
+}
+
+impl ExportInterval {
+    /// Every interval, in the order the settings menu lists them.
+    pub const ALL: [ExportInterval; 2] = [ExportInterval::Daily, ExportInterval::Weekly];
+
+    /// Lower-case name as used in the config file.
+    pub fn name(self) -> &'static str {
+        match self {
+            ExportInterval::Daily => "daily",
+            ExportInterval::Weekly => "weekly",
+        }
+    }
+
+    /// Length of the interval in seconds.
+    pub fn seconds(self) -> u64 {
+        match self {
+            ExportInterval::Daily => 86_400,
+            ExportInterval::Weekly => 7 * 86_400,
+        }
+    }
+}
+
+/// Which files a scheduled export writes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// The full JSON report, as the export button writes it
+    #[default]
+    Json,
+    /// One CSV row per drive (columns as in `monitor --csv`)
+    Csv,
+    /// Both files
+    Both,
+}
+
+impl ExportFormat {
+    /// Every format, in the order the settings menu lists them.
+    pub const ALL: [ExportFormat; 3] = [ExportFormat::Json, ExportFormat::Csv, ExportFormat::Both];
+
+    /// Lower-case name as used in the config file.
+    pub fn name(self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Both => "both",
+        }
+    }
+
+    /// File extensions written for this format.
+    pub fn extensions(self) -> &'static [&'static str] {
+        match self {
+            ExportFormat::Json => &["json"],
+            ExportFormat::Csv => &["csv"],
+            ExportFormat::Both => &["json", "csv"],
+        }
+    }
+}
+
+/// Settings of the scheduled export: the `[export]` section.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExportSchedule {
+    /// How often an export is written
+    pub interval: ExportInterval,
+    /// Which files each export writes
+    pub format: ExportFormat,
+    /// Directory the exports go to; created if missing
+    pub dir: PathBuf,
+    /// Number of exports kept; older ones are deleted after each export
+    pub keep: usize,
+}
+
+impl Default for ExportSchedule {
+    fn default() -> Self {
+        Self { interval: ExportInterval::default(), format: ExportFormat::default(), dir: default_export_dir(), keep: DEFAULT_KEEP }
+    }
+}
+
+impl ExportSchedule {
+    /// Applies an `[export]` entry. Unknown names and malformed values are ignored.
+    ///
+    /// # Arguments
+    /// * `name` - "interval", "format", "dir" or "keep"
+    /// * `value` - "daily"/"weekly", "json"/"csv"/"both", a directory, or a number of exports
+    pub fn parse_entry(&mut self, name: &str, value: &str) {
+        let value = value.trim();
+        match name.trim() {
+            "interval" => {
+                if let Some(interval) = ExportInterval::ALL.into_iter().find(|i| i.name() == value) {
+                    self.interval = interval;
+                }
+            }
+            "format" => {
+                if let Some(format) = ExportFormat::ALL.into_iter().find(|f| f.name() == value) {
+                    self.format = format;
+                }
+            }
+            "dir" if !value.is_empty() => self.dir = PathBuf::from(value),
+            "keep" => {
+                if let Some(keep) = value.parse().ok().filter(|k| *k > 0) {
+                    self.keep = keep;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Formats the schedule as `[export]` lines (see `parse_entry`).
+    pub fn to_lines(&self) -> Vec<String> {
+        vec![
+            format!("interval = {}", self.interval.name()),
+            format!("format = {}", self.format.name()),
+            format!("dir = {}", self.dir.display()),
+            format!("keep = {}", self.keep),
+        ]
+    }
+
+    /// Unix time of the newest scheduled export in the directory, None if there is none (or no directory).
+    pub fn last_export(&self) -> Option<u64> {
+        exported_files(&self.dir).into_iter().map(|(time, _)| time).max()
+    }
+
+    /// Whether an export is due.
+    ///
+    /// # Arguments
+    /// * `last` - Unix time of the last export (see `last_export`), None if there was none
+    /// * `now` - Current Unix time in seconds
+    pub fn is_due(&self, last: Option<u64>, now: u64) -> bool {
+        last.is_none_or(|last| now >= last + self.interval.seconds())
+    }
+
+    /// Writes an export and deletes the exports beyond `keep`.
+    ///
+    /// # Arguments
+    /// * `report` - Drives to export; its `generated_at` time names the files
+    ///
+    /// # Returns
+    /// The files written, or an error message if the directory or a file could not be written.
+    pub fn export(&self, report: &Report) -> Result<Vec<PathBuf>, String> {
+        std::fs::create_dir_all(&self.dir).map_err(|e| format!("Failed to create {}: {}", self.dir.display(), e))?;
+        let mut written = Vec::new();
+        for ext in self.format.extensions() {
+            let path = self.dir.join(format!("{}{}.{}", FILE_PREFIX, report.generated_at, ext));
+            match *ext {
+                "json" => report.save(&path)?,
+                _ => std::fs::write(&path, csv_document(&report.drives, &report.host, report.generated_at))
+                    .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?,
+            }
+            written.push(path);
+        }
+        rotate_exports(&self.dir, self.keep)?;
+        Ok(written)
+    }
+}
+
+/// Default export directory: `~/ssd-reports`.
+pub fn default_export_dir() -> PathBuf {
+    home_dir().join("ssd-reports")
+}
+
+/// Deletes all but the newest scheduled exports in a directory. The JSON and CSV file of one export
+/// count as one; other files are left alone.
+///
+/// # Arguments
+/// * `dir` - Export directory
+/// * `keep` - Number of exports to keep (at least the newest one is always kept)
+///
+/// # Returns
+/// The number of files deleted, or an error message naming the file that could not be deleted.
+pub fn rotate_exports(dir: &Path, keep: usize) -> Result<usize, String> {
+    let files = exported_files(dir);
+    let mut times: Vec<u64> = files.iter().map(|(time, _)| *time).collect();
+    times.sort_unstable_by(|a, b| b.cmp(a));
+    times.dedup();
+    let Some(&oldest_kept) = times.get(keep.max(1) - 1) else {
+        return Ok(0);
+    };
+    let mut deleted = 0;
+    for (_, path) in files.iter().filter(|(time, _)| *time < oldest_kept) {
+        std::fs::remove_file(path).map_err(|e| format!("Failed to delete {}: {}", path.display(), e))?;
+        deleted += 1;
+    }
+    Ok(deleted)
+}
+
+/// Scheduled exports in a directory as (Unix time, path), in no particular order.
+fn exported_files(dir: &Path) -> Vec<(u64, PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let (time, ext) = name.to_str()?.strip_prefix(FILE_PREFIX)?.split_once('.')?;
+            matches!(ext, "json" | "csv").then_some(())?;
+            Some((time.parse().ok()?, entry.path()))
+        })
+        .collect()
+}
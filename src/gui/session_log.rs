@@ -0,0 +1,169 @@
+// Chronological log of this session: scans, errors, SMART attribute changes and alerts, for reconstructing
+// what happened during an unattended run such as an overnight stress test
+
+// Dates in log lines
+use crate::gui::settings::{format_date, home_dir};
+// Drives compared between scans
+use crate::models::DiskInfo;
+// Exported log location
+use std::path::{Path, PathBuf};
+
+/// Oldest events are dropped beyond this many (about three days of scans at the 5 s refresh)
+const MAX_SESSION_EVENTS: usize = 50_000;
+
+/// SMART attributes whose raw value counts errors or damaged sectors; a change in any of them is logged.
+/// Other raw values (hours, LBAs written, temperature) move on every scan and only their normalized value is followed.
+const ERROR_ATTRIBUTES: &[&str] = &["5", "10", "184", "187", "188", "196", "197", "198", "199"];
+
+/// What a session event is about.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventKind {
+    /// A scan of all drives or a single rescan, and the scheduled export written after one
+    Scan,
+    /// A failed scan or drive action
+    Error,
+    /// A SMART value that changed between scans, or a drive that appeared or disappeared
+    Change,
+    /// An alert rule that fired
+    Alert,
+}
+
+impl EventKind {
+    /// Every kind, in the order the log page lists its filters.
+    pub const ALL: [EventKind; 4] = [EventKind::Scan, EventKind::Error, EventKind::Change, EventKind::Alert];
+
+    /// Upper-case tag written in front of each log line.
+    pub fn label(self) -> &'static str {
+        match self {
+            EventKind::Scan => "SCAN",
+            EventKind::Error => "ERROR",
+            EventKind::Change => "CHANGE",
+            EventKind::Alert => "ALERT",
+        }
+    }
+}
+
+/// One entry of the session log.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SessionEvent {
+    /// Unix time in seconds
+    pub timestamp: u64,
+    /// What the event is about
+    pub kind: EventKind,
+    /// Human-readable description
+    pub message: String,
+}
+
+/// Events of the running session, oldest first. Kept in memory only; `save` exports them.
+#[derive(Default)]
+pub struct SessionLog {
+    /// Logged events, oldest first
+    pub entries: Vec<SessionEvent>,
+}
+
+impl SessionLog {
+    /// Appends an event, dropping the oldest ones beyond `MAX_SESSION_EVENTS`.
+    ///
+    /// # Arguments
+    /// * `timestamp` - Unix time in seconds
+    /// * `kind` - What the event is about
+    /// * `message` - Human-readable description
+    pub fn record(&mut self, timestamp: u64, kind: EventKind, message: impl Into<String>) {
+        self.entries.push(SessionEvent { timestamp, kind, message: message.into() });
+        if self.entries.len() > MAX_SESSION_EVENTS {
+            self.entries.drain(..self.entries.len() - MAX_SESSION_EVENTS);
+        }
+    }
+
+    /// Formats the log as text, one event per line: "2024-03-15 14:05:09  CHANGE  message".
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("# SSD Health Checker session log (times in UTC)\n");
+        for event in &self.entries {
+            text.push_str(&format_event(event));
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Writes the log to a text file.
+    ///
+    /// # Arguments
+    /// * `path` - Destination file
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        std::fs::write(path, self.to_text()).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+/// Formats one event as a log line (see `SessionLog::to_text`).
+pub fn format_event(event: &SessionEvent) -> String {
+    let secs = event.timestamp as i64;
+    let time = secs.rem_euclid(86_400);
+    format!(
+        "{} {:02}:{:02}:{:02}  {:<6}  {}",
+        format_date(secs.div_euclid(86_400)),
+        time / 3600,
+        time % 3600 / 60,
+        time % 60,
+        event.kind.label(),
+        event.message.replace(['\n', '\r'], " ")
+    )
+}
+
+/// Default location for an exported log: `~/ssd-session-<unix time>.log`
+///
+/// # Arguments
+/// * `now` - Current Unix time in seconds
+pub fn default_log_path(now: u64) -> PathBuf {
+    let dir = home_dir();
+    dir.join(format!("ssd-session-{}.log", now))
+}
+
+/// Describes what changed on a drive between two scans: the SMART self-assessment, health, NVMe media errors,
+/// attribute status, normalized values, and the raw value of error-counting attributes.
+///
+/// # Arguments
+/// * `previous` - The drive as read by the previous scan
+/// * `current` - The drive as read now
+///
+/// # Returns
+/// One line per change (e.g., "/dev/sda: Reallocated_Sector_Ct raw 0 → 8"); empty if nothing of note changed.
+pub fn drive_changes(previous: &DiskInfo, current: &DiskInfo) -> Vec<String> {
+    let mut changes = Vec::new();
+    let dev = &current.dev;
+    let show = |v: Option<String>| v.unwrap_or_else(|| "?".to_string());
+
+    if previous.smart_passed != current.smart_passed {
+        let verdict = |p: Option<bool>| show(p.map(|p| if p { "PASSED".to_string() } else { "FAILED".to_string() }));
+        changes.push(format!(
+            "{}: SMART self-assessment {} → {}",
+            dev,
+            verdict(previous.smart_passed),
+            verdict(current.smart_passed)
+        ));
+    }
+    if previous.health_percent != current.health_percent {
+        let percent = |h: Option<u8>| show(h.map(|h| format!("{}%", h)));
+        changes.push(format!("{}: health {} → {}", dev, percent(previous.health_percent), percent(current.health_percent)));
+    }
+    if previous.media_errors != current.media_errors {
+        let count = |n: Option<u64>| show(n.map(|n| n.to_string()));
+        changes.push(format!("{}: media errors {} → {}", dev, count(previous.media_errors), count(current.media_errors)));
+    }
+
+    for attr in &current.smart_attributes {
+        let Some(before) = previous.attribute(&attr.id) else {
+            continue;
+        };
+        if before.status != attr.status {
+            changes.push(format!("{}: {} ({}) {:?} → {:?}", dev, attr.name, attr.id, before.status, attr.status));
+        }
+        // Temperature attributes report 100 minus the temperature as their normalized value
+        if before.current != attr.current && !matches!(attr.id.as_str(), "190" | "194") {
+            changes.push(format!("{}: {} ({}) value {} → {}", dev, attr.name, attr.id, before.current.trim(), attr.current.trim()));
+        }
+        if ERROR_ATTRIBUTES.contains(&attr.id.as_str()) && before.raw_numeric() != attr.raw_numeric() {
+            changes.push(format!("{}: {} ({}) raw {} → {}", dev, attr.name, attr.id, before.raw_value, attr.raw_value));
+        }
+    }
+    changes
+}
@@ -0,0 +1,536 @@
+// User settings persisted in a small INI-style file: per-drive values (endurance rating, warranty, temperature alarm),
+// alert rules and notification channels
+
+// Alert rules and notification channels
+use crate::gui::alerts::{AlertRule, NotificationChannel, NotificationSchedule, DEFAULT_ALARM_SOUND};
+// Per-drive settings are keyed by model + serial
+use crate::gui::history::drive_key;
+// Automatic report exports
+use crate::gui::scheduled_export::ExportSchedule;
+// Drives looked up in the hidden list, health score weights
+use crate::models::{DiskInfo, HealthWeights};
+// Settings ordered by drive key so the file stays stable between saves
+use std::collections::BTreeMap;
+// Settings file location
+use std::path::PathBuf;
+
+/// Rated endurance as printed on the drive's datasheet.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EnduranceRating {
+    /// Total terabytes written over the warranty period
+    Tbw(f64),
+    /// Drive writes per day over the warranty period
+    Dwpd(f64),
+}
+
+impl EnduranceRating {
+    /// Converts the rating to total terabytes written.
+    /// DWPD ratings are quoted over the warranty period, so the drive's capacity and warranty length are needed.
+    ///
+    /// # Arguments
+    /// * `capacity_bytes` - Drive capacity in bytes
+    /// * `warranty_years` - Length of the warranty the rating applies to
+    ///
+    /// # Returns
+    /// The rating in TB, or None for a DWPD rating on a drive of unknown capacity.
+    pub fn tbw(&self, capacity_bytes: Option<f64>, warranty_years: f64) -> Option<f64> {
+        match *self {
+            EnduranceRating::Tbw(tb) => Some(tb),
+            EnduranceRating::Dwpd(dwpd) => capacity_bytes.map(|c| dwpd * c / 1e12 * 365.0 * warranty_years),
+        }
+    }
+}
+
+/// Settings the user entered for one drive.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DriveSettings {
+    /// Rated endurance, None if not entered
+    pub endurance: Option<EnduranceRating>,
+    /// Purchase date as days since 1970-01-01
+    pub purchased: Option<i64>,
+    /// Warranty length in years, counted from the purchase date
+    pub warranty_years: Option<u32>,
+    /// Temperature in °C at which the drive alerts; replaces the `temp_c` alert rules for this drive
+    pub temp_alarm: Option<i32>,
+}
+
+impl DriveSettings {
+    /// Day the warranty ends, as days since 1970-01-01.
+    ///
+    /// # Returns
+    /// None unless both the purchase date and the warranty length are set.
+    pub fn warranty_end(&self) -> Option<i64> {
+        let (y, m, d) = civil_from_days(self.purchased?);
+        let years = self.warranty_years? as i64;
+        // Anniversary of a 29 February purchase falls on 28 February in non-leap years
+        let day = if m == 2 && d == 29 && days_from_civil(y + years, 3, 1) - days_from_civil(y + years, 2, 1) == 28 {
+            28
+        } else {
+            d
+        };
+        Some(days_from_civil(y + years, m, day))
+    }
+}
+
+/// When the GUI switches to battery-saver polling: slower refresh, no GPU polling and suspended drives left alone.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PowerSaver {
+    /// While the machine runs on battery
+    #[default]
+    Auto,
+    /// All the time
+    Always,
+    /// Never
+    Never,
+}
+
+impl PowerSaver {
+    /// Every mode, in the order the settings menu lists them.
+    pub const ALL: [PowerSaver; 3] = [PowerSaver::Auto, PowerSaver::Always, PowerSaver::Never];
+
+    /// Lower-case name as used in the config file.
+    pub fn name(self) -> &'static str {
+        match self {
+            PowerSaver::Auto => "auto",
+            PowerSaver::Always => "always",
+            PowerSaver::Never => "never",
+        }
+    }
+
+    /// Whether battery-saver polling applies.
+    ///
+    /// # Arguments
+    /// * `on_battery` - Whether the machine currently runs on battery
+    pub fn is_active(self, on_battery: bool) -> bool {
+        match self {
+            PowerSaver::Auto => on_battery,
+            PowerSaver::Always => true,
+            PowerSaver::Never => false,
+        }
+    }
+}
+
+/// All settings: per-drive values keyed by `history::drive_key` (model + serial), plus the
+/// `[alerts]` and `[notifications]` sections.
+#[derive(Clone, Debug, Default)]
+pub struct Settings {
+    /// Settings per drive key
+    pub drives: BTreeMap<String, DriveSettings>,
+    /// Alert rules as (name, text) in file order; kept as written so invalid rules survive a save
+    pub alerts: Vec<(String, String)>,
+    /// Notification channels, None if the file has no `[notifications]` section
+    pub notifications: Option<Vec<NotificationChannel>>,
+    /// Quiet hours and rate limit from the `[notifications]` section
+    pub schedule: NotificationSchedule,
+    /// Malformed `quiet_hours` and `rate_limit` entries, one message each; they are dropped on the next save
+    pub schedule_errors: Vec<String>,
+    /// Sound file of the audible alarm for failing drives, None if the alarm is off
+    pub alarm: Option<String>,
+    /// Visible columns per table ("partitions", "smart") from the `[columns]` section; tables not listed
+    /// show their default columns
+    pub columns: BTreeMap<String, Vec<String>>,
+    /// Closing the GUI window minimizes it and keeps monitoring (`minimize_on_close` in `[window]`)
+    pub minimize_on_close: bool,
+    /// When battery-saver polling applies (`saver` in `[power]`)
+    pub power_saver: PowerSaver,
+    /// Automatic report exports (`[export]`), None if they are off
+    pub export: Option<ExportSchedule>,
+    /// Serial numbers of drives hidden by the user (`serials` in `[hidden]`); they are kept out of
+    /// alerts and exports and listed in a collapsed sidebar section
+    pub hidden: Vec<String>,
+    /// Points each signal takes off the health score (`[health]`); unlisted weights keep their defaults
+    pub health: HealthWeights,
+    /// File the settings were loaded from and are saved to, None to keep them in memory only
+    path: Option<PathBuf>,
+}
+
+impl Settings {
+    /// Loads `$XDG_CONFIG_HOME/ssd_info_cli/drives.conf` (or `~/.config/...`).
+    /// A missing or unreadable file yields empty settings.
+    pub fn load_default() -> Self {
+        let path = config_dir().map(|dir| dir.join("drives.conf"));
+
+        let mut settings = path
+            .as_ref()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .map(|text| Self::parse(&text))
+            .unwrap_or_default();
+        settings.path = path;
+        settings
+    }
+
+    /// Parses settings text.
+    /// Format: a `[drive key]` header followed by `name = value` lines (`tbw`, `dwpd`, `purchased`, `warranty_years`,
+    /// `temp_alarm`); `#` starts a comment.
+    /// `[alerts]` holds `name = rule` lines and `[notifications]` the channels (see `NotificationChannel::parse`)
+    /// and schedule (see `NotificationSchedule::parse_entry`); `alarm = true|false|<sound file>` there
+    /// switches the audible alarm. `[columns]` holds `table = column, column, ...` lines, and `[window]` holds
+    /// `minimize_on_close = true|false`, and `[power]` holds `saver = auto|always|never`. Entries in `[export]` switch on
+    /// scheduled report exports (see `ExportSchedule::parse_entry`). `[hidden]` holds `serials = serial, serial, ...`. `[health]` holds
+    /// `name = points` lines for the health score weights (see `HealthWeights::entries`).
+    /// Unknown names and malformed values are ignored; malformed schedule entries are listed in `schedule_errors`.
+    ///
+    /// # Arguments
+    /// * `text` - Contents of a settings file
+    pub fn parse(text: &str) -> Self {
+        let mut settings = Self::default();
+        let mut current: Option<String> = None;
+
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(key) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                current = Some(key.trim().to_string());
+                continue;
+            }
+            let (Some(key), Some((name, value))) = (&current, line.split_once('=')) else {
+                continue;
+            };
+            match key.as_str() {
+                "alerts" => {
+                    settings.alerts.push((name.trim().to_string(), value.trim().to_string()));
+                    continue;
+                }
+                "columns" => {
+                    let columns = value.split(',').map(str::trim).filter(|c| !c.is_empty()).map(str::to_string).collect();
+                    settings.columns.insert(name.trim().to_string(), columns);
+                    continue;
+                }
+                "hidden" => {
+                    if name.trim() == "serials" {
+                        settings.hidden =
+                            value.split(',').map(str::trim).filter(|s| !s.is_empty()).map(str::to_string).collect();
+                    }
+                    continue;
+                }
+                "health" => {
+                    if let Ok(points) = value.trim().parse() {
+                        settings.health.set(name.trim(), points);
+                    }
+                    continue;
+                }
+                "window" => {
+                    if name.trim() == "minimize_on_close" {
+                        settings.minimize_on_close = value.trim() == "true";
+                    }
+                    continue;
+                }
+                "power" => {
+                    if name.trim() == "saver" {
+                        if let Some(mode) = PowerSaver::ALL.into_iter().find(|m| m.name() == value.trim()) {
+                            settings.power_saver = mode;
+                        }
+                    }
+                    continue;
+                }
+                "export" => {
+                    settings.export.get_or_insert_with(ExportSchedule::default).parse_entry(name, value);
+                    continue;
+                }
+                "notifications" => {
+                    // Schedule entries alone do not turn off the default desktop notifications
+                    match settings.schedule.parse_entry(name, value) {
+                        Ok(true) => continue,
+                        Ok(false) => {}
+                        Err(e) => {
+                            settings.schedule_errors.push(e);
+                            continue;
+                        }
+                    }
+                    if name.trim() == "alarm" {
+                        settings.alarm = match value.trim() {
+                            "true" => Some(DEFAULT_ALARM_SOUND.to_string()),
+                            "false" | "" => None,
+                            sound => Some(sound.to_string()),
+                        };
+                        continue;
+                    }
+                    // Present but empty (e.g., only "desktop = false") means notifications are off
+                    let channels = settings.notifications.get_or_insert_with(Vec::new);
+                    channels.extend(NotificationChannel::parse(name, value));
+                    continue;
+                }
+                _ => {}
+            }
+            let drive = settings.drives.entry(key.clone()).or_default();
+            let value = value.trim();
+            match name.trim() {
+                "tbw" => drive.endurance = value.parse().ok().map(EnduranceRating::Tbw),
+                "dwpd" => drive.endurance = value.parse().ok().map(EnduranceRating::Dwpd),
+                "purchased" => drive.purchased = parse_date(value),
+                "warranty_years" => drive.warranty_years = value.parse().ok(),
+                "temp_alarm" => drive.temp_alarm = value.parse().ok(),
+                _ => {}
+            }
+        }
+        settings
+    }
+
+    /// Formats the settings as file contents (see `parse`). Drives without any setting are omitted.
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("# ssd_info_cli settings\n");
+        if !self.alerts.is_empty() {
+            text.push_str("\n[alerts]\n");
+            for (name, rule) in &self.alerts {
+                text.push_str(&format!("{} = {}\n", name, rule));
+            }
+        }
+        let schedule = self.schedule.to_lines();
+        if self.notifications.is_some() || !schedule.is_empty() || self.alarm.is_some() {
+            text.push_str("\n[notifications]\n");
+        }
+        if let Some(channels) = &self.notifications {
+            if !channels.contains(&NotificationChannel::Desktop) {
+                text.push_str("desktop = false\n");
+            }
+            for channel in channels {
+                text.push_str(&format!("{}\n", channel.to_line()));
+            }
+        }
+        for line in schedule {
+            text.push_str(&format!("{}\n", line));
+        }
+        match self.alarm.as_deref() {
+            Some(DEFAULT_ALARM_SOUND) => text.push_str("alarm = true\n"),
+            Some(sound) => text.push_str(&format!("alarm = {}\n", sound)),
+            None => {}
+        }
+        if !self.columns.is_empty() {
+            text.push_str("\n[columns]\n");
+            for (table, columns) in &self.columns {
+                text.push_str(&format!("{} = {}\n", table, columns.join(", ")));
+            }
+        }
+        if self.minimize_on_close {
+            text.push_str("\n[window]\nminimize_on_close = true\n");
+        }
+        if self.power_saver != PowerSaver::default() {
+            text.push_str(&format!("\n[power]\nsaver = {}\n", self.power_saver.name()));
+        }
+        if let Some(export) = &self.export {
+            text.push_str("\n[export]\n");
+            for line in export.to_lines() {
+                text.push_str(&format!("{}\n", line));
+            }
+        }
+        if !self.hidden.is_empty() {
+            text.push_str(&format!("\n[hidden]\nserials = {}\n", self.hidden.join(", ")));
+        }
+        // Only the weights that differ from the defaults, so later changes to the defaults still apply
+        let defaults = HealthWeights::default().entries();
+        let changed: Vec<(&str, f64)> =
+            self.health.entries().into_iter().zip(defaults).filter(|(w, d)| w.1 != d.1).map(|(w, _)| w).collect();
+        if !changed.is_empty() {
+            text.push_str("\n[health]\n");
+            for (name, points) in changed {
+                text.push_str(&format!("{} = {}\n", name, points));
+            }
+        }
+        for (key, drive) in &self.drives {
+            if *drive == DriveSettings::default() {
+                continue;
+            }
+            text.push_str(&format!("\n[{}]\n", key));
+            match drive.endurance {
+                Some(EnduranceRating::Tbw(tb)) => text.push_str(&format!("tbw = {}\n", tb)),
+                Some(EnduranceRating::Dwpd(dwpd)) => text.push_str(&format!("dwpd = {}\n", dwpd)),
+                None => {}
+            }
+            if let Some(days) = drive.purchased {
+                text.push_str(&format!("purchased = {}\n", format_date(days)));
+            }
+            if let Some(years) = drive.warranty_years {
+                text.push_str(&format!("warranty_years = {}\n", years));
+            }
+            if let Some(limit) = drive.temp_alarm {
+                text.push_str(&format!("temp_alarm = {}\n", limit));
+            }
+        }
+        text
+    }
+
+    /// Writes the settings back to the file they were loaded from.
+    ///
+    /// # Returns
+    /// Ok(()) on success (or when the settings have no file), Err with a message otherwise.
+    pub fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        std::fs::write(path, self.to_text()).map_err(|e| format!("Failed to save {}: {}", path.display(), e))
+    }
+
+    /// Whether the user hid this drive. Drives without a serial number cannot be hidden.
+    ///
+    /// # Arguments
+    /// * `di` - Drive to look up
+    pub fn is_hidden(&self, di: &DiskInfo) -> bool {
+        di.serial.as_ref().is_some_and(|serial| self.hidden.contains(serial))
+    }
+
+    /// Hides or unhides a drive by serial number.
+    ///
+    /// # Arguments
+    /// * `serial` - The drive's serial number
+    /// * `hidden` - True to hide it
+    pub fn set_hidden(&mut self, serial: &str, hidden: bool) {
+        self.hidden.retain(|s| s != serial);
+        if hidden {
+            self.hidden.push(serial.to_string());
+        }
+    }
+
+    /// Parses the `[alerts]` section.
+    ///
+    /// # Returns
+    /// Each rule in file order, or an error message naming the rule if it is invalid.
+    pub fn alert_rules(&self) -> Vec<Result<AlertRule, String>> {
+        self.alerts
+            .iter()
+            .map(|(name, text)| AlertRule::parse(name, text).map_err(|e| format!("{}: {}", name, e)))
+            .collect()
+    }
+
+    /// Channels alerts are sent to: the `[notifications]` section, or desktop notifications without one.
+    pub fn channels(&self) -> Vec<NotificationChannel> {
+        self.notifications.clone().unwrap_or_else(|| vec![NotificationChannel::Desktop])
+    }
+
+    /// File the settings are saved to.
+    pub fn path(&self) -> Option<&std::path::Path> {
+        self.path.as_deref()
+    }
+
+    /// Whether a table column is shown.
+    ///
+    /// # Arguments
+    /// * `table` - Table name ("partitions", "smart")
+    /// * `column` - Column header
+    /// * `default` - Visibility when the table has no `[columns]` entry
+    pub fn column_visible(&self, table: &str, column: &str, default: bool) -> bool {
+        match self.columns.get(table) {
+            Some(visible) => visible.iter().any(|c| c == column),
+            None => default,
+        }
+    }
+
+    /// Shows or hides a table column, writing out the table's full column list.
+    ///
+    /// # Arguments
+    /// * `table` - Table name ("partitions", "smart")
+    /// * `all` - Every column of the table as (header, shown by default), in display order
+    /// * `column` - Column to change
+    /// * `visible` - Whether the column is shown
+    pub fn set_column_visible(&mut self, table: &str, all: &[(&str, bool)], column: &str, visible: bool) {
+        let columns = all
+            .iter()
+            .filter(|(name, default)| if *name == column { visible } else { self.column_visible(table, name, *default) })
+            .map(|(name, _)| name.to_string())
+            .collect();
+        self.columns.insert(table.to_string(), columns);
+    }
+
+    /// Settings of one drive, or the defaults if none were entered.
+    ///
+    /// # Arguments
+    /// * `key` - Drive key from `history::drive_key`
+    pub fn drive(&self, key: &str) -> DriveSettings {
+        self.drives.get(key).cloned().unwrap_or_default()
+    }
+
+    /// Temperature alarm the user set for a drive.
+    ///
+    /// # Arguments
+    /// * `di` - Drive to look up
+    ///
+    /// # Returns
+    /// The limit in °C, or None if the drive has none (or no serial to key it by).
+    pub fn temp_alarm(&self, di: &DiskInfo) -> Option<i32> {
+        drive_key(di).and_then(|key| self.drives.get(&key)).and_then(|drive| drive.temp_alarm)
+    }
+}
+
+/// The user's home directory from HOME. Files saved "to ~" land in the current directory if HOME is not set.
+pub fn home_dir() -> PathBuf {
+    std::env::var_os("HOME").map(PathBuf::from).unwrap_or_default()
+}
+
+/// This program's configuration directory: `$XDG_CONFIG_HOME/ssd_info_cli` (or `~/.config/ssd_info_cli`).
+/// Returns None if neither variable is set.
+pub fn config_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))
+        .map(|base| base.join("ssd_info_cli"))
+}
+
+/// Parses an ISO date ("2024-03-15").
+///
+/// # Arguments
+/// * `s` - Date text in YYYY-MM-DD form
+///
+/// # Returns
+/// Days since 1970-01-01, or None if the text is not a valid date.
+pub fn parse_date(s: &str) -> Option<i64> {
+    let mut parts = s.trim().splitn(3, '-');
+    let y: i64 = parts.next()?.parse().ok()?;
+    let m: u32 = parts.next()?.parse().ok()?;
+    let d: u32 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&m) || d == 0 {
+        return None;
+    }
+    let days = days_from_civil(y, m, d);
+    // Reject days past the end of the month (e.g., 2023-02-30)
+    (civil_from_days(days) == (y, m, d)).then_some(days)
+}
+
+/// Formats days since 1970-01-01 as an ISO date ("2024-03-15").
+pub fn format_date(days: i64) -> String {
+    let (y, m, d) = civil_from_days(days);
+    format!("{:04}-{:02}-{:02}", y, m, d)
+}
+
+/// Formats a Unix timestamp as "2024-03-15 14:05 UTC".
+pub fn format_timestamp(secs: u64) -> String {
+    let secs = secs as i64;
+    let time = secs.rem_euclid(86_400);
+    format!("{} {:02}:{:02} UTC", format_date(secs.div_euclid(86_400)), time / 3600, time % 3600 / 60)
+}
+
+/// Today's date as days since 1970-01-01 (UTC).
+pub fn today() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| (d.as_secs() / 86_400) as i64)
+        .unwrap_or(0)
+}
+
+/// Converts a Gregorian date to days since 1970-01-01 (Howard Hinnant's algorithm).
+fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (m as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Converts days since 1970-01-01 to a Gregorian (year, month, day).
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    (if m <= 2 { yoe + era * 400 + 1 } else { yoe + era * 400 }, m, d)
+}
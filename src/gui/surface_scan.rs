@@ -0,0 +1,222 @@
+// Background read-only surface scan of a whole drive (like `badblocks` in its default read-only mode)
+
+// Device files and errors from failed reads
+use std::fs::{File, OpenOptions};
+use std::io;
+// Positioned reads and O_DIRECT on the device file
+use std::os::unix::fs::{FileExt, OpenOptionsExt};
+// Shared progress counter and cancellation flag between UI and worker thread
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+// Channel delivering unreadable sectors and completion to the UI thread
+use std::sync::mpsc::{channel, Receiver};
+// Arc for sharing atomics with the worker thread
+use std::sync::Arc;
+// Scan duration for the speed readout
+use std::time::{Duration, Instant};
+// O_DIRECT flag so reads hit the medium instead of the page cache
+use nix::fcntl::OFlag;
+
+/// Bytes read per request while the drive reads cleanly
+pub const CHUNK_BYTES: usize = 1 << 20;
+
+/// Alignment O_DIRECT requires for the read buffer
+const BUFFER_ALIGN: usize = 4096;
+
+/// Progress sent from the worker thread.
+enum SurfaceMessage {
+    /// A logical sector that could not be read
+    Unreadable(u64),
+    /// The scan ended (finished or stopped)
+    Done,
+}
+
+/// A running or finished surface scan owned by the UI.
+pub struct SurfaceScan {
+    /// Device being scanned (e.g., "/dev/sda")
+    pub dev: String,
+    /// Size of the device in bytes
+    pub total_bytes: u64,
+    /// Logical sector size; unreadable LBAs are counted in these units
+    pub sector_bytes: u64,
+    /// Bytes read so far, updated by the worker thread
+    pub bytes_read: Arc<AtomicU64>,
+    /// Unreadable LBAs found so far, in ascending order
+    pub unreadable: Vec<u64>,
+    /// True once the scan has ended
+    pub finished: bool,
+    /// Set to ask the worker thread to stop early
+    cancel: Arc<AtomicBool>,
+    /// Receives unreadable sectors and completion
+    rx: Receiver<SurfaceMessage>,
+    /// When the scan started
+    started: Instant,
+    /// How long the scan ran, fixed once it has ended
+    duration: Option<Duration>,
+}
+
+impl SurfaceScan {
+    /// Opens the device read-only and starts reading it in a background thread.
+    /// Nothing is ever written to the drive.
+    ///
+    /// # Arguments
+    /// * `dev` - Whole-drive device path (e.g., "/dev/sda")
+    ///
+    /// # Returns
+    /// The running scan, or an error if the device cannot be opened (usually missing root).
+    pub fn start(dev: &str) -> Result<Self, String> {
+        let name = dev.trim_start_matches("/dev/");
+        let read_sys = |path: &str| {
+            std::fs::read_to_string(format!("/sys/block/{}/{}", name, path))
+                .ok()
+                .and_then(|s| s.trim().parse::<u64>().ok())
+        };
+        // The size file counts 512-byte units regardless of the logical sector size
+        let total_bytes = read_sys("size").map(|s| s * 512).ok_or_else(|| format!("Cannot read the size of {}", dev))?;
+        let sector_bytes = read_sys("queue/logical_block_size").filter(|&s| s > 0).unwrap_or(512);
+        let file = open_direct(dev).map_err(|e| format!("Cannot open {}: {} (surface scans need root)", dev, e))?;
+
+        let bytes_read = Arc::new(AtomicU64::new(0));
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = channel();
+
+        let (counter, stop) = (bytes_read.clone(), cancel.clone());
+        std::thread::spawn(move || {
+            let mut storage = Vec::new();
+            let buf = aligned_buffer(&mut storage, CHUNK_BYTES);
+            let bad_tx = tx.clone();
+            scan(
+                |buf, offset| file.read_exact_at(buf, offset),
+                total_bytes,
+                sector_bytes,
+                buf,
+                &counter,
+                &stop,
+                // The receiver is gone if the UI dropped the scan; the worker stops on the cancel flag then
+                |lba| {
+                    let _ = bad_tx.send(SurfaceMessage::Unreadable(lba));
+                },
+            );
+            let _ = tx.send(SurfaceMessage::Done);
+        });
+
+        Ok(Self {
+            dev: dev.to_string(),
+            total_bytes,
+            sector_bytes,
+            bytes_read,
+            unreadable: Vec::new(),
+            finished: false,
+            cancel,
+            rx,
+            started: Instant::now(),
+            duration: None,
+        })
+    }
+
+    /// Picks up unreadable sectors and completion sent by the worker thread.
+    ///
+    /// # Returns
+    /// True once the scan has ended.
+    pub fn poll(&mut self) -> bool {
+        while let Ok(message) = self.rx.try_recv() {
+            match message {
+                SurfaceMessage::Unreadable(lba) => self.unreadable.push(lba),
+                SurfaceMessage::Done => {
+                    self.finished = true;
+                    self.duration = Some(self.started.elapsed());
+                }
+            }
+        }
+        self.finished
+    }
+
+    /// Asks the worker thread to stop after the current read.
+    pub fn stop(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the user stopped the scan before it covered the whole drive.
+    pub fn stopped(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    /// Fraction of the drive read so far, from 0.0 to 1.0.
+    pub fn progress(&self) -> f32 {
+        if self.total_bytes == 0 {
+            return 1.0;
+        }
+        self.bytes_read.load(Ordering::Relaxed) as f32 / self.total_bytes as f32
+    }
+
+    /// Average read speed since the scan started, in MB/s.
+    pub fn mb_per_sec(&self) -> f64 {
+        let secs = self.duration.unwrap_or_else(|| self.started.elapsed()).as_secs_f64();
+        if secs <= 0.0 {
+            return 0.0;
+        }
+        self.bytes_read.load(Ordering::Relaxed) as f64 / 1_000_000.0 / secs
+    }
+}
+
+impl Drop for SurfaceScan {
+    /// Stops the worker thread when the scan is discarded.
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Opens a device for reading, bypassing the page cache where the kernel allows it.
+/// Cached data would hide sectors that can no longer be read from the medium.
+pub(crate) fn open_direct(dev: &str) -> io::Result<File> {
+    open_direct_only(dev).or_else(|_| File::open(dev))
+}
+
+/// Opens a device for reading with O_DIRECT, failing instead of falling back to buffered reads.
+pub(crate) fn open_direct_only(dev: &str) -> io::Result<File> {
+    OpenOptions::new().read(true).custom_flags(OFlag::O_DIRECT.bits()).open(dev)
+}
+
+/// Returns a `len`-byte window of `storage` aligned for O_DIRECT reads, growing `storage` as needed.
+pub(crate) fn aligned_buffer(storage: &mut Vec<u8>, len: usize) -> &mut [u8] {
+    // Over-allocate so an aligned window exists inside the Vec
+    storage.resize(len + BUFFER_ALIGN, 0);
+    let start = storage.as_ptr().align_offset(BUFFER_ALIGN).min(BUFFER_ALIGN);
+    &mut storage[start..start + len]
+}
+
+/// Reads `total_bytes` in chunks, retrying a failed chunk one sector at a time to find the unreadable sectors.
+///
+/// # Arguments
+/// * `read_at` - Fills the buffer from the given byte offset; an error marks the range unreadable
+/// * `total_bytes` - Bytes to read from offset 0
+/// * `sector_bytes` - Logical sector size; must divide the buffer length
+/// * `buf` - Read buffer; its length is the chunk size
+/// * `progress` - Incremented with every byte covered, readable or not
+/// * `cancel` - Checked between chunks; the scan returns early when set
+/// * `on_unreadable` - Called with the LBA of each sector that failed to read
+pub fn scan(
+    mut read_at: impl FnMut(&mut [u8], u64) -> io::Result<()>,
+    total_bytes: u64,
+    sector_bytes: u64,
+    buf: &mut [u8],
+    progress: &AtomicU64,
+    cancel: &AtomicBool,
+    mut on_unreadable: impl FnMut(u64),
+) {
+    let mut offset = 0;
+    while offset < total_bytes && !cancel.load(Ordering::Relaxed) {
+        let len = (buf.len() as u64).min(total_bytes - offset) as usize;
+        if read_at(&mut buf[..len], offset).is_err() {
+            let mut sector = offset;
+            while sector < offset + len as u64 {
+                let n = sector_bytes.min(offset + len as u64 - sector) as usize;
+                if read_at(&mut buf[..n], sector).is_err() {
+                    on_unreadable(sector / sector_bytes);
+                }
+                sector += n as u64;
+            }
+        }
+        offset += len as u64;
+        progress.fetch_add(len as u64, Ordering::Relaxed);
+    }
+}
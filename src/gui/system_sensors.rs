@@ -0,0 +1,395 @@
+// System vitals: CPU/GPU temperatures, fan speeds (hwmon, nvidia-smi), laptop batteries (power_supply)
+// and swap devices (/proc/swaps)
+
+// C-locale command builder shared with the scanner
+use crate::gui::disk_scanner::c_locale_command;
+// Path handling for the sysfs root
+use std::path::Path;
+
+/// hwmon driver names that report CPU package or core temperatures
+const CPU_DRIVERS: &[&str] = &["coretemp", "k10temp", "zenpower", "cpu_thermal", "soc_thermal"];
+
+/// Reads the average CPU temperature from `/sys/class/hwmon`.
+/// Works without lm-sensors installed and is unaffected by the locale.
+///
+/// # Returns
+/// Average temperature in Celsius, or None if no CPU sensor is found.
+pub fn cpu_temperature() -> Option<f32> {
+    cpu_temperature_from(Path::new("/sys/class/hwmon"))
+}
+
+/// Reads the average CPU temperature from an hwmon class directory.
+/// Package, core, Tctl and Tdie sensors are averaged; drivers without
+/// labels (e.g., ARM `cpu_thermal`) contribute all of their inputs.
+///
+/// # Arguments
+/// * `hwmon_root` - Directory containing hwmon* entries (normally /sys/class/hwmon)
+///
+/// # Returns
+/// Average temperature in Celsius, or None if no CPU sensor is found.
+pub fn cpu_temperature_from(hwmon_root: &Path) -> Option<f32> {
+    let temps: Vec<f32> = cpu_inputs(hwmon_root)
+        .into_iter()
+        .filter(|(label, _, _)| {
+            // Labels are optional; unlabeled inputs on a CPU driver are CPU temperatures
+            let label = label.to_lowercase();
+            label.is_empty()
+                || label.contains("tctl")
+                || label.contains("tdie")
+                || label.contains("package")
+                || label.contains("core")
+        })
+        .map(|(_, _, temp)| temp)
+        .collect();
+
+    // Compute average of all found temperature values
+    if temps.is_empty() {
+        None
+    } else {
+        Some(temps.iter().sum::<f32>() / temps.len() as f32)
+    }
+}
+
+/// Lists every CPU temperature sensor, so one hot core is not hidden by the average.
+///
+/// # Returns
+/// (sensor label, °C) pairs; empty if no CPU sensor is found.
+pub fn cpu_sensors() -> Vec<(String, f32)> {
+    cpu_sensors_from(Path::new("/sys/class/hwmon"))
+}
+
+/// Lists every temperature input of the CPU drivers in an hwmon class directory, including the ones the
+/// average leaves out (e.g., AMD's per-die Tccd sensors).
+/// Inputs are labeled with their hwmon label when present, otherwise "<driver> tempN".
+///
+/// # Arguments
+/// * `hwmon_root` - Directory containing hwmon* entries (normally /sys/class/hwmon)
+///
+/// # Returns
+/// (sensor label, °C) pairs in hwmon and input order.
+pub fn cpu_sensors_from(hwmon_root: &Path) -> Vec<(String, f32)> {
+    cpu_inputs(hwmon_root)
+        .into_iter()
+        .map(|(label, fallback, temp)| (if label.is_empty() { fallback } else { label }, temp))
+        .collect()
+}
+
+/// Reads the temperature inputs of the CPU drivers in an hwmon class directory.
+///
+/// # Returns
+/// (hwmon label or "", "<driver> tempN", °C) per input, sorted by hwmon entry and input number.
+fn cpu_inputs(hwmon_root: &Path) -> Vec<(String, String, f32)> {
+    let Ok(entries) = std::fs::read_dir(hwmon_root) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<_> = entries.flatten().map(|e| e.path()).collect();
+    dirs.sort();
+
+    let mut inputs = Vec::new();
+    for dir in dirs {
+        let name = std::fs::read_to_string(dir.join("name")).unwrap_or_default();
+        let driver = name.trim();
+        if !CPU_DRIVERS.contains(&driver) {
+            continue;
+        }
+
+        let mut indices: Vec<u32> = std::fs::read_dir(&dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|file| {
+                let file_name = file.file_name().to_string_lossy().into_owned();
+                file_name.strip_prefix("temp")?.strip_suffix("_input")?.parse().ok()
+            })
+            .collect();
+        indices.sort_unstable();
+
+        for index in indices {
+            // Values are in millidegrees Celsius
+            let Some(milli) = std::fs::read_to_string(dir.join(format!("temp{}_input", index)))
+                .ok()
+                .and_then(|v| v.trim().parse::<i64>().ok())
+            else {
+                continue;
+            };
+            let label = std::fs::read_to_string(dir.join(format!("temp{}_label", index)))
+                .map(|l| l.trim().to_string())
+                .unwrap_or_default();
+            inputs.push((label, format!("{} temp{}", driver, index), milli as f32 / 1000.0));
+        }
+    }
+    inputs
+}
+
+/// Reads every fan speed reported by hwmon.
+///
+/// # Returns
+/// (fan label, RPM) pairs; empty if no fan sensor is exposed.
+pub fn fan_speeds() -> Vec<(String, u32)> {
+    fan_speeds_from(Path::new("/sys/class/hwmon"))
+}
+
+/// Reads fan speeds from an hwmon class directory.
+/// Fans are labeled with their hwmon label when present, otherwise "<chip> fanN".
+///
+/// # Arguments
+/// * `hwmon_root` - Directory containing hwmon* entries (normally /sys/class/hwmon)
+///
+/// # Returns
+/// (fan label, RPM) pairs sorted by label.
+pub fn fan_speeds_from(hwmon_root: &Path) -> Vec<(String, u32)> {
+    let mut fans = Vec::new();
+    let Ok(entries) = std::fs::read_dir(hwmon_root) else {
+        return fans;
+    };
+
+    for dir in entries.flatten().map(|e| e.path()) {
+        let chip = std::fs::read_to_string(dir.join("name")).unwrap_or_default().trim().to_string();
+        for file in std::fs::read_dir(&dir).into_iter().flatten().flatten() {
+            let file_name = file.file_name().to_string_lossy().into_owned();
+            let Some(index) = file_name.strip_prefix("fan").and_then(|f| f.strip_suffix("_input")) else {
+                continue;
+            };
+            let Some(rpm) = std::fs::read_to_string(file.path())
+                .ok()
+                .and_then(|v| v.trim().parse::<u32>().ok())
+            else {
+                continue;
+            };
+            let label = std::fs::read_to_string(dir.join(format!("fan{}_label", index)))
+                .map(|l| l.trim().to_string())
+                .ok()
+                .filter(|l| !l.is_empty())
+                .unwrap_or_else(|| format!("{} fan{}", chip, index));
+            fans.push((label, rpm));
+        }
+    }
+
+    fans.sort();
+    fans
+}
+
+/// Lists every GPU with its current temperature.
+/// NVIDIA GPUs come from nvidia-smi; AMD GPUs from the amdgpu hwmon driver.
+///
+/// # Returns
+/// (GPU name, °C) pairs in discovery order; empty if no GPU sensor is found.
+pub fn gpu_temperatures() -> Vec<(String, f32)> {
+    let mut gpus = Vec::new();
+
+    if let Ok(output) = c_locale_command("nvidia-smi")
+        .args(["--query-gpu=name,temperature.gpu", "--format=csv,noheader,nounits"])
+        .output()
+    {
+        gpus.extend(parse_nvidia_smi_gpus(&String::from_utf8_lossy(&output.stdout)));
+    }
+
+    // amdgpu exposes the edge temperature as temp1_input
+    if let Ok(entries) = std::fs::read_dir("/sys/class/hwmon") {
+        for dir in entries.flatten().map(|e| e.path()) {
+            if std::fs::read_to_string(dir.join("name")).is_ok_and(|n| n.trim() == "amdgpu") {
+                if let Some(milli) = std::fs::read_to_string(dir.join("temp1_input"))
+                    .ok()
+                    .and_then(|v| v.trim().parse::<i64>().ok())
+                {
+                    // Identify the card by its PCI address (e.g., "0000:03:00.0")
+                    let slot = std::fs::read_link(dir.join("device"))
+                        .ok()
+                        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+                        .unwrap_or_default();
+                    gpus.push((format!("AMD GPU {}", slot).trim_end().to_string(), milli as f32 / 1000.0));
+                }
+            }
+        }
+    }
+
+    gpus
+}
+
+/// Parses `nvidia-smi --query-gpu=name,temperature.gpu --format=csv,noheader,nounits` output.
+///
+/// # Arguments
+/// * `text` - One "name, temperature" line per GPU
+///
+/// # Returns
+/// (GPU name, °C) pairs; lines with an unreadable temperature are skipped.
+pub fn parse_nvidia_smi_gpus(text: &str) -> Vec<(String, f32)> {
+    text.lines()
+        .filter_map(|line| {
+            // GPU names never contain commas, so the last field is the temperature
+            let (name, temp) = line.rsplit_once(',')?;
+            let temp = temp.trim().replace(',', ".").parse::<f32>().ok()?;
+            Some((name.trim().to_string(), temp))
+        })
+        .collect()
+}
+
+/// A laptop battery as reported by the kernel's power_supply class.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Battery {
+    /// power_supply entry name (e.g., "BAT0")
+    pub name: String,
+    /// Manufacturer and model (e.g., "SMP 5B10W13975"), None if the firmware reports neither
+    pub model: Option<String>,
+    /// "Charging", "Discharging", "Full", "Not charging" or "Unknown"
+    pub status: Option<String>,
+    /// Charge in percent of the current full capacity
+    pub charge_percent: Option<u8>,
+    /// Capacity when fully charged today, in `capacity_unit`
+    pub full_capacity: Option<f64>,
+    /// Capacity the battery was designed for, in `capacity_unit`
+    pub design_capacity: Option<f64>,
+    /// "Wh" for batteries that report energy, "mAh" for the ones that report charge
+    pub capacity_unit: &'static str,
+    /// Charge/discharge cycles, None if the firmware does not count them (many report 0)
+    pub cycle_count: Option<u32>,
+}
+
+impl Battery {
+    /// Full capacity as a share of the design capacity, the usual measure of battery wear.
+    ///
+    /// # Returns
+    /// Percent (can exceed 100 on a new battery), None without both capacities.
+    pub fn health_percent(&self) -> Option<f64> {
+        let design = self.design_capacity.filter(|&d| d > 0.0)?;
+        Some(self.full_capacity? / design * 100.0)
+    }
+}
+
+/// Reads every laptop battery from `/sys/class/power_supply`.
+///
+/// # Returns
+/// Batteries sorted by name; empty on desktops.
+pub fn batteries() -> Vec<Battery> {
+    batteries_from(Path::new("/sys/class/power_supply"))
+}
+
+/// Reads the system batteries from a power_supply class directory.
+/// Mains adapters, USB ports and peripheral batteries (wireless mice, headsets) are skipped.
+///
+/// # Arguments
+/// * `power_supply_root` - Directory containing the supply entries (normally /sys/class/power_supply)
+///
+/// # Returns
+/// Batteries sorted by name.
+pub fn batteries_from(power_supply_root: &Path) -> Vec<Battery> {
+    let Ok(entries) = std::fs::read_dir(power_supply_root) else {
+        return Vec::new();
+    };
+
+    let mut out: Vec<Battery> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let dir = entry.path();
+            let read = |file: &str| {
+                std::fs::read_to_string(dir.join(file)).ok().map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+            };
+            let number = |file: &str| read(file).and_then(|v| v.parse::<u64>().ok());
+            if read("type").as_deref() != Some("Battery") || read("scope").as_deref() == Some("Device") {
+                return None;
+            }
+
+            // Values are in µWh (energy_*) or µAh (charge_*), depending on the firmware
+            let (full, design, capacity_unit) = match number("energy_full_design") {
+                Some(design) => (number("energy_full"), Some(design), "Wh"),
+                None => (number("charge_full"), number("charge_full_design"), "mAh"),
+            };
+            let scale = if capacity_unit == "Wh" { 1_000_000.0 } else { 1_000.0 };
+            let model = [read("manufacturer"), read("model_name")].into_iter().flatten().collect::<Vec<_>>().join(" ");
+            Some(Battery {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                model: (!model.is_empty()).then_some(model),
+                status: read("status"),
+                charge_percent: number("capacity").and_then(|c| u8::try_from(c).ok()),
+                full_capacity: full.map(|v| v as f64 / scale),
+                design_capacity: design.map(|v| v as f64 / scale),
+                capacity_unit,
+                cycle_count: number("cycle_count").filter(|&c| c > 0).and_then(|c| u32::try_from(c).ok()),
+            })
+        })
+        .collect();
+    out.sort_by(|a, b| a.name.cmp(&b.name));
+    out
+}
+
+/// Tells whether the machine runs on battery, from `/sys/class/power_supply`.
+pub fn on_battery() -> bool {
+    on_battery_from(Path::new("/sys/class/power_supply"))
+}
+
+/// Tells whether the machine runs on battery: no mains or USB charger is online and a system battery is
+/// discharging. Desktops without a battery never run on battery.
+///
+/// # Arguments
+/// * `power_supply_root` - Directory containing the supply entries (normally /sys/class/power_supply)
+pub fn on_battery_from(power_supply_root: &Path) -> bool {
+    let Ok(entries) = std::fs::read_dir(power_supply_root) else {
+        return false;
+    };
+    let mut discharging = false;
+    for entry in entries.flatten() {
+        let read = |file: &str| std::fs::read_to_string(entry.path().join(file)).map(|v| v.trim().to_string()).unwrap_or_default();
+        match read("type").as_str() {
+            "Mains" | "USB" if read("online") == "1" => return false,
+            "Battery" if read("scope") != "Device" && read("status") == "Discharging" => discharging = true,
+            _ => {}
+        }
+    }
+    discharging
+}
+
+/// Lists active swap devices and files from `/proc/swaps`.
+///
+/// # Returns
+/// Swap paths (e.g., "/dev/nvme0n1p3", "/swapfile"); empty if swap is off.
+pub fn swap_devices() -> Vec<String> {
+    std::fs::read_to_string("/proc/swaps")
+        .map(|text| parse_proc_swaps(&text))
+        .unwrap_or_default()
+}
+
+/// Drives that hold swap: the parent drive of each swap partition, or the drive itself for whole-drive swap.
+///
+/// # Arguments
+/// * `swaps` - Swap paths from `swap_devices`
+///
+/// # Returns
+/// Kernel drive names (e.g., "nvme0n1"); swap files and mapped devices are left out.
+pub fn swap_drives(swaps: &[String]) -> Vec<String> {
+    swap_drives_from(Path::new("/sys/class/block"), swaps)
+}
+
+/// Resolves swap devices to drives using a specific `/sys/class/block` directory (for testing).
+/// Partitions are found by their `partition` file; their sysfs directory sits inside the parent drive's.
+///
+/// # Arguments
+/// * `class_block` - Directory with one entry per block device (usually /sys/class/block)
+/// * `swaps` - Swap paths from `swap_devices`
+pub fn swap_drives_from(class_block: &Path, swaps: &[String]) -> Vec<String> {
+    swaps
+        .iter()
+        .filter_map(|path| {
+            let name = path.strip_prefix("/dev/")?;
+            let entry = class_block.join(name);
+            if entry.join("partition").exists() {
+                let resolved = std::fs::canonicalize(&entry).ok()?;
+                Some(resolved.parent()?.file_name()?.to_string_lossy().into_owned())
+            } else {
+                entry.exists().then(|| name.to_string())
+            }
+        })
+        .collect()
+}
+
+/// Parses the contents of `/proc/swaps`, skipping the header line.
+///
+/// # Arguments
+/// * `text` - File contents ("Filename Type Size Used Priority" table)
+pub fn parse_proc_swaps(text: &str) -> Vec<String> {
+    text.lines()
+        .skip(1)
+        .filter_map(|line| line.split_whitespace().next())
+        // Paths with spaces are escaped as \040
+        .map(|path| path.replace("\\040", " "))
+        .collect()
+}
@@ -0,0 +1,122 @@
+// System tray icon (StatusNotifierItem over D-Bus) that brings back the window hidden by "Keep monitoring when closed"
+
+// Session bus connection and the exported item
+use zbus::blocking::connection::Builder;
+use zbus::blocking::Connection;
+
+/// Object path of the item, as StatusNotifierWatcher implementations expect it
+const ITEM_PATH: &str = "/StatusNotifierItem";
+
+/// Tray icon registered with the desktop's StatusNotifierWatcher. The icon disappears when this is dropped.
+pub struct Tray {
+    /// Session bus connection serving the item; it answers clicks on its own thread
+    _connection: Connection,
+}
+
+impl Tray {
+    /// Registers a tray icon that shows the window again when clicked.
+    ///
+    /// # Arguments
+    /// * `ctx` - egui context of the window to show
+    ///
+    /// # Returns
+    /// The tray, or an error message if there is no session bus or the desktop has no tray
+    /// (e.g. GNOME without the AppIndicator extension).
+    pub fn start(ctx: egui::Context) -> Result<Self, String> {
+        let name = format!("org.kde.StatusNotifierItem-{}-1", std::process::id());
+        let connection = Builder::session()
+            .and_then(|b| b.name(name.as_str()))
+            .and_then(|b| b.serve_at(ITEM_PATH, StatusNotifierItem { ctx }))
+            .and_then(|b| b.build())
+            .map_err(|e| format!("No D-Bus session for the tray icon: {}", e))?;
+        connection
+            .call_method(
+                Some("org.kde.StatusNotifierWatcher"),
+                "/StatusNotifierWatcher",
+                Some("org.kde.StatusNotifierWatcher"),
+                "RegisterStatusNotifierItem",
+                &name,
+            )
+            .map_err(|e| format!("The desktop has no system tray: {}", e))?;
+        Ok(Self { _connection: connection })
+    }
+}
+
+/// Hides the window to the tray. It is also minimized, since some platforms (Wayland) cannot hide windows.
+///
+/// # Arguments
+/// * `ctx` - egui context of the window
+pub fn hide_window(ctx: &egui::Context) {
+    ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(true));
+    ctx.send_viewport_cmd(egui::ViewportCommand::Visible(false));
+}
+
+/// Shows, restores and focuses the window again.
+///
+/// # Arguments
+/// * `ctx` - egui context of the window
+pub fn show_window(ctx: &egui::Context) {
+    ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+    ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+    ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+    ctx.request_repaint();
+}
+
+/// The `org.kde.StatusNotifierItem` interface: a fixed icon and title; any click shows the window.
+struct StatusNotifierItem {
+    /// egui context of the window to show
+    ctx: egui::Context,
+}
+
+#[zbus::interface(name = "org.kde.StatusNotifierItem")]
+impl StatusNotifierItem {
+    /// Left click
+    fn activate(&self, _x: i32, _y: i32) {
+        show_window(&self.ctx);
+    }
+
+    /// Middle click
+    fn secondary_activate(&self, _x: i32, _y: i32) {
+        show_window(&self.ctx);
+    }
+
+    /// Right click; there is no menu, so it shows the window too
+    fn context_menu(&self, _x: i32, _y: i32) {
+        show_window(&self.ctx);
+    }
+
+    /// Mouse wheel over the icon (ignored)
+    fn scroll(&self, _delta: i32, _orientation: &str) {}
+
+    #[zbus(property)]
+    fn category(&self) -> String {
+        "Hardware".into()
+    }
+
+    #[zbus(property)]
+    fn id(&self) -> String {
+        "ssd_info_cli".into()
+    }
+
+    #[zbus(property)]
+    fn title(&self) -> String {
+        "SSD Health Checker".into()
+    }
+
+    #[zbus(property)]
+    fn status(&self) -> String {
+        "Active".into()
+    }
+
+    /// Freedesktop icon theme name
+    #[zbus(property)]
+    fn icon_name(&self) -> String {
+        "drive-harddisk".into()
+    }
+
+    /// False: clicks activate the item rather than asking for a menu
+    #[zbus(property)]
+    fn item_is_menu(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,204 @@
+// Background disk usage analysis ("What's using space?") for a mounted partition
+
+// Unix metadata for allocated blocks and device IDs
+use std::os::unix::fs::MetadataExt;
+// Filesystem paths
+use std::path::{Path, PathBuf};
+// Shared progress counter and cancellation flag between UI and worker thread
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+// Channel delivering the finished report to the UI thread
+use std::sync::mpsc::{channel, Receiver};
+// Arc for sharing atomics with the worker thread
+use std::sync::Arc;
+
+/// Number of largest files kept in a report
+const LARGEST_FILES: usize = 20;
+
+/// Space used by one direct child of the scanned directory.
+#[derive(Clone, Debug)]
+pub struct UsageEntry {
+    /// Full path of the file or directory
+    pub path: PathBuf,
+    /// Allocated bytes, including everything below a directory
+    pub bytes: u64,
+    /// True for directories
+    pub is_dir: bool,
+}
+
+/// Result of walking a mount point.
+#[derive(Clone, Debug, Default)]
+pub struct UsageReport {
+    /// Direct children of the root, largest first
+    pub entries: Vec<UsageEntry>,
+    /// Largest individual files anywhere below the root, largest first
+    pub largest_files: Vec<(PathBuf, u64)>,
+    /// Total allocated bytes below the root
+    pub total_bytes: u64,
+    /// Number of entries that could not be read (permissions, races)
+    pub errors: u64,
+}
+
+/// A running or finished usage scan owned by the UI.
+pub struct UsageAnalysis {
+    /// Directory being analyzed (the partition's mount point)
+    pub root: PathBuf,
+    /// Files visited so far, updated by the worker thread
+    pub files_scanned: Arc<AtomicU64>,
+    /// Set to ask the worker thread to stop early
+    cancel: Arc<AtomicBool>,
+    /// Receives the report once the walk finishes
+    rx: Receiver<UsageReport>,
+    /// Finished report, None while the scan is running
+    pub report: Option<UsageReport>,
+}
+
+impl UsageAnalysis {
+    /// Starts walking `root` in a background thread.
+    /// The walk stays on the root's filesystem and does not follow symlinks.
+    ///
+    /// # Arguments
+    /// * `root` - Mount point to analyze (e.g., "/home")
+    pub fn start(root: &str) -> Self {
+        let root = PathBuf::from(root);
+        let files_scanned = Arc::new(AtomicU64::new(0));
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = channel();
+
+        let (thread_root, counter, stop) = (root.clone(), files_scanned.clone(), cancel.clone());
+        std::thread::spawn(move || {
+            let report = analyze(&thread_root, &counter, &stop);
+            // The receiver is gone if the user closed the window; nothing to do then
+            let _ = tx.send(report);
+        });
+
+        Self { root, files_scanned, cancel, rx, report: None }
+    }
+
+    /// Picks up the finished report if the worker thread has sent it.
+    ///
+    /// # Returns
+    /// True once the report is available.
+    pub fn poll(&mut self) -> bool {
+        if self.report.is_none() {
+            if let Ok(report) = self.rx.try_recv() {
+                self.report = Some(report);
+            }
+        }
+        self.report.is_some()
+    }
+}
+
+impl Drop for UsageAnalysis {
+    /// Stops the worker thread when the analysis window is closed.
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Walks a directory tree and sums allocated space per direct child.
+///
+/// # Arguments
+/// * `root` - Directory to analyze
+/// * `counter` - Incremented for every file visited
+/// * `cancel` - Checked between entries; the walk returns early when set
+///
+/// # Returns
+/// The usage report (partial if cancelled).
+pub fn analyze(root: &Path, counter: &AtomicU64, cancel: &AtomicBool) -> UsageReport {
+    let mut report = UsageReport::default();
+    let Ok(root_meta) = std::fs::symlink_metadata(root) else {
+        report.errors += 1;
+        return report;
+    };
+    let device = root_meta.dev();
+
+    let Ok(children) = std::fs::read_dir(root) else {
+        report.errors += 1;
+        return report;
+    };
+
+    for child in children.flatten() {
+        if cancel.load(Ordering::Relaxed) {
+            break;
+        }
+        let path = child.path();
+        let Ok(meta) = std::fs::symlink_metadata(&path) else {
+            report.errors += 1;
+            continue;
+        };
+
+        // Directories on other filesystems (nested mounts) are not part of this partition
+        let bytes = if meta.is_dir() {
+            if meta.dev() != device {
+                continue;
+            }
+            directory_size(&path, device, counter, cancel, &mut report)
+        } else {
+            let bytes = allocated(&meta);
+            record_file(&mut report, &path, bytes, counter);
+            bytes
+        };
+
+        report.total_bytes += bytes;
+        report.entries.push(UsageEntry { path, bytes, is_dir: meta.is_dir() });
+    }
+
+    report.entries.sort_by_key(|e| std::cmp::Reverse(e.bytes));
+    report
+}
+
+/// Sums allocated space below a directory with an explicit stack (no recursion depth limit).
+///
+/// # Arguments
+/// * `dir` - Directory to sum
+/// * `device` - Filesystem device ID the walk is restricted to
+/// * `counter` - Incremented for every file visited
+/// * `cancel` - Checked between entries
+/// * `report` - Receives largest files and error counts
+fn directory_size(dir: &Path, device: u64, counter: &AtomicU64, cancel: &AtomicBool, report: &mut UsageReport) -> u64 {
+    let mut total = 0;
+    let mut stack = vec![dir.to_path_buf()];
+
+    while let Some(current) = stack.pop() {
+        if cancel.load(Ordering::Relaxed) {
+            break;
+        }
+        let Ok(entries) = std::fs::read_dir(&current) else {
+            report.errors += 1;
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(meta) = std::fs::symlink_metadata(&path) else {
+                report.errors += 1;
+                continue;
+            };
+            if meta.is_dir() {
+                if meta.dev() == device {
+                    stack.push(path);
+                }
+            } else {
+                let bytes = allocated(&meta);
+                total += bytes;
+                record_file(report, &path, bytes, counter);
+            }
+        }
+    }
+    total
+}
+
+/// Returns the space a file actually occupies on disk (sparse files count only written blocks).
+fn allocated(meta: &std::fs::Metadata) -> u64 {
+    meta.blocks() * 512
+}
+
+/// Counts a visited file and keeps it if it is among the largest seen so far.
+fn record_file(report: &mut UsageReport, path: &Path, bytes: u64, counter: &AtomicU64) {
+    counter.fetch_add(1, Ordering::Relaxed);
+    let largest = &mut report.largest_files;
+    if largest.len() < LARGEST_FILES || largest.last().is_some_and(|l| bytes > l.1) {
+        let pos = largest.partition_point(|l| l.1 >= bytes);
+        largest.insert(pos, (path.to_path_buf(), bytes));
+        largest.truncate(LARGEST_FILES);
+    }
+}
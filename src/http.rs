@@ -0,0 +1,79 @@
+// Minimal plain-HTTP/1.1 client and TCP helpers shared by the metric sinks and the fleet view
+
+// Writing requests and reading responses
+use std::io::{Read, Write};
+// Outgoing connections
+use std::net::{TcpStream, ToSocketAddrs};
+// Network timeouts
+use std::time::Duration;
+
+/// Connect/read/write timeout, so a dead endpoint cannot stall the caller for long
+const NETWORK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Largest response read, so a misbehaving server cannot exhaust memory (reports of a few dozen drives are well below)
+const MAX_RESPONSE_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Opens a TCP connection with read and write timeouts set.
+///
+/// # Arguments
+/// * `address` - "host:port"
+///
+/// # Returns
+/// The connected stream, or an error message naming the address.
+pub fn connect(address: &str) -> Result<TcpStream, String> {
+    let fail = |e: std::io::Error| format!("{}: {}", address, e);
+    let addr = address.to_socket_addrs().map_err(fail)?.next().ok_or(format!("{}: no address", address))?;
+    let stream = TcpStream::connect_timeout(&addr, NETWORK_TIMEOUT).map_err(fail)?;
+    stream.set_read_timeout(Some(NETWORK_TIMEOUT)).map_err(fail)?;
+    stream.set_write_timeout(Some(NETWORK_TIMEOUT)).map_err(fail)?;
+    Ok(stream)
+}
+
+/// Sends one request and reads the whole response (the connection is closed after it).
+/// Only `http://` URLs are supported; chunked response bodies are not decoded. Responses larger than
+/// `MAX_RESPONSE_BYTES` are rejected.
+///
+/// # Arguments
+/// * `method` - HTTP method, e.g. "GET"
+/// * `url` - `http://host[:port]/path?query`
+/// * `headers` - Extra request headers as (name, value)
+/// * `body` - Request body, empty for none
+///
+/// # Returns
+/// The status code and response body.
+pub fn request(method: &str, url: &str, headers: &[(&str, &str)], body: &str) -> Result<(u16, String), String> {
+    let rest = url.strip_prefix("http://").ok_or(format!("Not an http:// URL: {}", url))?;
+    let (authority, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+    let address = if authority.contains(':') { authority.to_string() } else { format!("{}:80", authority) };
+    let mut stream = connect(&address)?;
+
+    let mut request = format!("{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n", method, path, authority);
+    for (name, value) in headers {
+        request.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    if !body.is_empty() || method == "POST" {
+        request.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    request.push_str("\r\n");
+    request.push_str(body);
+    stream.write_all(request.as_bytes()).map_err(|e| format!("{}: {}", authority, e))?;
+
+    let mut response = Vec::new();
+    // Servers may reset the connection after answering; whatever arrived before that is enough
+    let _ = (&mut stream).take(MAX_RESPONSE_BYTES + 1).read_to_end(&mut response);
+    if response.len() as u64 > MAX_RESPONSE_BYTES {
+        return Err(format!("{}: response larger than {} MiB", authority, MAX_RESPONSE_BYTES / (1024 * 1024)));
+    }
+    let response = String::from_utf8_lossy(&response);
+    let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+    let status = head
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse().ok())
+        .ok_or(format!("{}: no HTTP response", authority))?;
+    Ok((status, body.to_string()))
+}
@@ -0,0 +1,22 @@
+// Library crate shared by the GUI binary and the integration tests
+
+// Command-line subcommands (scan, report, test, monitor, check)
+pub mod cli;
+// GUI components, application state, and disk scanning
+pub mod gui;
+// Minimal plain-HTTP client for metric sinks and the fleet view
+pub mod http;
+// Minimal JSON support for exported reports
+pub mod json;
+// Thousands separators following the user's locale
+pub mod locale;
+// Metric sinks (InfluxDB, Zabbix, CSV) fed by the monitor command
+pub mod metrics;
+// Running host tools from inside a Flatpak sandbox
+pub mod sandbox;
+// Local REST API serving the latest scan as JSON
+pub mod server;
+// Data models for disk information and SMART attributes
+pub mod models;
+// Terminal interface for machines without a display
+pub mod tui;
@@ -0,0 +1,87 @@
+// Thousands separators that follow the user's locale, for counts shown to people (not for JSON or CSV)
+
+// Separator looked up once from the environment
+use once_cell::sync::Lazy;
+
+/// Separator of the locale the program was started in.
+/// smartctl and the other tools run with LC_ALL=C, so this reads the environment before any of that.
+static SEPARATOR: Lazy<&'static str> = Lazy::new(|| {
+    let locale = ["LC_ALL", "LC_NUMERIC", "LANG"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+        .unwrap_or_default();
+    separator_for_locale(&locale)
+});
+
+/// Picks the thousands separator of a POSIX locale name.
+/// Covers the common European conventions; other locales, C and POSIX group with a comma like English.
+///
+/// # Arguments
+/// * `locale` - Locale name as in `$LANG` (e.g., "de_DE.UTF-8", "fr_FR@euro", "C")
+///
+/// # Returns
+/// "," (English, Asian locales), "." (German, Dutch, Italian, Spanish...), a no-break space (French,
+/// Nordic and Slavic locales) or "’" (Swiss).
+pub fn separator_for_locale(locale: &str) -> &'static str {
+    let name = locale.split(['.', '@']).next().unwrap_or_default();
+    let (language, territory) = name.split_once('_').unwrap_or((name, ""));
+    match (language, territory) {
+        (_, "CH") => "’",
+        ("pt", "PT") => "\u{a0}",
+        ("de" | "nl" | "it" | "es" | "pt" | "da" | "id" | "tr" | "el" | "ro" | "sl" | "hr" | "sr" | "vi", _) => ".",
+        ("fr" | "ru" | "uk" | "be" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "nn" | "no" | "hu" | "bg" | "et" | "lv"
+        | "lt", _) => "\u{a0}",
+        _ => ",",
+    }
+}
+
+/// Writes a number with a separator between each group of three digits.
+///
+/// # Arguments
+/// * `n` - Number to format
+/// * `separator` - Separator between groups (see `separator_for_locale`)
+///
+/// # Returns
+/// The grouped number (e.g., "1,234,567").
+pub fn format_grouped(n: u64, separator: &str) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() * 2);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push_str(separator);
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Writes a number grouped with the separator of the user's locale.
+///
+/// # Arguments
+/// * `n` - Number to format (e.g., power-on hours or an LBA)
+pub fn group_digits(n: u64) -> String {
+    format_grouped(n, &SEPARATOR)
+}
+
+/// Groups the leading number of a SMART raw value, keeping the text after it.
+/// Values below 10000 and values that do not start with a plain decimal number (hex, "12h+34m") are left alone.
+///
+/// # Arguments
+/// * `raw` - Raw value as printed by smartctl (e.g., "123456789" or "34 (Min/Max 19/55)")
+/// * `separator` - Separator between groups
+pub fn group_raw_value(raw: &str, separator: &str) -> String {
+    let end = raw.find(|c: char| !c.is_ascii_digit()).unwrap_or(raw.len());
+    let plain = raw[end..].chars().next().is_none_or(|c| c == ' ');
+    match raw[..end].parse::<u64>() {
+        Ok(n) if plain && n >= 10_000 => format!("{}{}", format_grouped(n, separator), &raw[end..]),
+        _ => raw.to_string(),
+    }
+}
+
+/// Groups the leading number of a SMART raw value with the separator of the user's locale.
+///
+/// # Arguments
+/// * `raw` - Raw value as printed by smartctl
+pub fn group_raw_digits(raw: &str) -> String {
+    group_raw_value(raw, &SEPARATOR)
+}
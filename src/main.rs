@@ -1,8 +1,20 @@
-mod gui;
-mod models;
+use ssd_info_cli::cli::{self, Command};
+use ssd_info_cli::gui;
 
-/// Initializes the eframe window with fixed dimensions and launches the GUI.
+/// Runs a CLI subcommand if one was given, otherwise initializes the eframe window
+/// with fixed dimensions and launches the GUI.
 fn main() -> eframe::Result<()> {
+    // Scriptable subcommands (scan, report, test, monitor) bypass the GUI entirely
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let minimized = match cli::parse_args(&args) {
+        Ok(Command::Gui { minimized }) => minimized,
+        Ok(command) => std::process::exit(cli::run(command)),
+        Err(e) => {
+            eprintln!("Error: {}\n\n{}", e, cli::USAGE);
+            std::process::exit(cli::usage_exit_code(&args));
+        }
+    };
+
     // Configure window options with fixed size of 1200x675 pixels
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
@@ -16,6 +28,6 @@ fn main() -> eframe::Result<()> {
     eframe::run_native(
         "SSD Health Checker",
         options,
-        Box::new(|cc| Ok(Box::new(gui::AppState::new(cc)))),
+        Box::new(move |cc| Ok(Box::new(gui::AppState::new(cc, minimized)))),
     )
 }
\ No newline at end of file
@@ -0,0 +1,463 @@
+// Metric sinks fed by the monitor loop: InfluxDB line protocol (file or HTTP), the Zabbix sender protocol
+// and CSV files
+
+// Readable timestamps in CSV rows
+use crate::gui::settings::format_timestamp;
+// HTTP write endpoints and Zabbix connections
+use crate::http;
+// Zabbix sender payloads are JSON
+use crate::json::Json;
+// Import data models for disk information
+use crate::models::{AttributeStatus, DiskInfo};
+// Appending to metric files and writing HTTP requests
+use std::io::{Read, Write};
+// Metric file paths
+use std::path::PathBuf;
+
+/// Measurement name used for every line
+const INFLUX_MEASUREMENT: &str = "ssd_info";
+
+/// Header row of CSV metric files
+const CSV_HEADER: &str = "time,unix_time,host,device,model,serial,health_percent,temperature_c,data_written_tb,\
+data_read_tb,power_on_hours,power_cycles,unsafe_shutdowns,media_errors,smart_passed";
+
+/// Default port of the Zabbix server/proxy trapper
+const ZABBIX_PORT: u16 = 10051;
+
+/// Name of the generated Zabbix template, also used in its trigger expressions
+const ZABBIX_TEMPLATE: &str = "SSD Info by Zabbix trapper";
+
+/// Per-drive Zabbix items: key prefix, display name, value type, units.
+/// Keys are sent as `ssd.<prefix>[<device>]`, e.g. `ssd.health[nvme0n1]`.
+const ZABBIX_ITEMS: &[(&str, &str, &str, &str)] = &[
+    ("health", "Health", "UNSIGNED", "%"),
+    ("temperature", "Temperature", "FLOAT", "°C"),
+    ("data_written", "Data written", "FLOAT", "TB"),
+    ("data_read", "Data read", "FLOAT", "TB"),
+    ("power_on_hours", "Power on hours", "UNSIGNED", "h"),
+    ("power_cycles", "Power cycles", "UNSIGNED", ""),
+    ("unsafe_shutdowns", "Unsafe shutdowns", "UNSIGNED", ""),
+    ("media_errors", "Media errors", "UNSIGNED", ""),
+    ("smart_passed", "SMART self-assessment passed", "UNSIGNED", ""),
+    ("critical_attributes", "Critical SMART attributes", "UNSIGNED", ""),
+];
+
+/// Where a sink delivers its payload.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MetricsTarget {
+    /// Append to a local file
+    File(PathBuf),
+    /// POST to a plain HTTP URL
+    Http(String),
+}
+
+impl MetricsTarget {
+    /// Interprets a command-line target: `http://...` is an endpoint, anything else a file path.
+    ///
+    /// # Returns
+    /// The target, or an error for HTTPS URLs (TLS is not supported; use a local Telegraf listener).
+    pub fn parse(s: &str) -> Result<Self, String> {
+        if s.starts_with("https://") {
+            Err("HTTPS endpoints are not supported, send to a local http:// listener (e.g., Telegraf)".to_string())
+        } else if s.starts_with("http://") {
+            Ok(MetricsTarget::Http(s.to_string()))
+        } else {
+            Ok(MetricsTarget::File(PathBuf::from(s)))
+        }
+    }
+}
+
+/// A destination the monitor loop writes every scan to.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Sink {
+    /// InfluxDB line protocol
+    Influx {
+        /// File or write endpoint (e.g., http://localhost:8086/api/v2/write?org=home&bucket=disks&precision=s)
+        target: MetricsTarget,
+        /// API token sent as "Authorization: Token ...", None for unauthenticated endpoints
+        token: Option<String>,
+    },
+    /// Zabbix sender protocol to a server or proxy trapper port
+    Zabbix {
+        /// Server address, "host" or "host:port" (default port 10051)
+        server: String,
+        /// Host name as configured in Zabbix, None to use this machine's host name
+        host: Option<String>,
+    },
+    /// One CSV row per drive appended to a file, with a header when the file is new
+    Csv(PathBuf),
+}
+
+impl Sink {
+    /// Delivers one scan to the sink.
+    ///
+    /// # Arguments
+    /// * `drives` - Scanned drives
+    /// * `host` - Host name to tag the metrics with
+    /// * `now` - Unix time of the scan in seconds
+    ///
+    /// # Returns
+    /// Ok(()) on success, Err with a message otherwise.
+    pub fn send(&self, drives: &[DiskInfo], host: &str, now: u64) -> Result<(), String> {
+        match self {
+            Sink::Influx { target, token } => {
+                let lines = influx_lines(drives, host, now);
+                if lines.is_empty() {
+                    return Ok(());
+                }
+                match target {
+                    MetricsTarget::File(path) => append_file(path, &lines),
+                    MetricsTarget::Http(url) => {
+                        let auth = token.as_ref().map(|t| format!("Token {}", t));
+                        let mut headers = vec![("Content-Type", "text/plain; charset=utf-8")];
+                        headers.extend(auth.as_deref().map(|a| ("Authorization", a)));
+                        match http::request("POST", url, &headers, &lines)? {
+                            (200..=299, _) => Ok(()),
+                            (status, body) => Err(format!("{} answered {}: {}", url, status, body.trim())),
+                        }
+                    }
+                }
+            }
+            Sink::Zabbix { server, host: zabbix_host } => {
+                let payload = zabbix_payload(drives, zabbix_host.as_deref().unwrap_or(host), now);
+                zabbix_send(server, &payload).map(|_| ())
+            }
+            Sink::Csv(path) => {
+                let is_new = std::fs::metadata(path).map(|m| m.len() == 0).unwrap_or(true);
+                let mut text = if is_new { format!("{}\n", CSV_HEADER) } else { String::new() };
+                text.push_str(&csv_rows(drives, host, now));
+                append_file(path, &text)
+            }
+        }
+    }
+}
+
+/// Formats drives as InfluxDB line protocol, one line per drive.
+/// Tags: host, device, model, serial, type. Integer fields carry the `i` suffix; the timestamp is in seconds
+/// (write with `precision=s`). Drives without any readable value (e.g., sleeping) are skipped.
+///
+/// # Arguments
+/// * `drives` - Scanned drives
+/// * `host` - Host name tag
+/// * `now` - Unix time of the scan in seconds
+pub fn influx_lines(drives: &[DiskInfo], host: &str, now: u64) -> String {
+    let mut out = String::new();
+    for di in drives {
+        let mut tags = vec![("host", host.to_string()), ("device", di.dev.clone())];
+        if let Some(model) = &di.model {
+            tags.push(("model", model.clone()));
+        }
+        if let Some(serial) = &di.serial {
+            tags.push(("serial", serial.clone()));
+        }
+        tags.push(("type", di.device_type.clone().unwrap_or_else(|| di.kind.clone())));
+
+        let mut fields: Vec<(&str, String)> = Vec::new();
+        let int = |v: u64| format!("{}i", v);
+        if let Some(h) = di.health_percent {
+            fields.push(("health_percent", int(h as u64)));
+        }
+        if let Some(t) = di.temp_c {
+            fields.push(("temperature_c", format!("{}i", t)));
+        }
+        if let Some(tb) = di.data_written_tb {
+            fields.push(("data_written_tb", tb.to_string()));
+        }
+        if let Some(tb) = di.data_read_tb {
+            fields.push(("data_read_tb", tb.to_string()));
+        }
+        let counters = [
+            ("power_on_hours", di.power_on_hours),
+            ("power_cycles", di.power_cycles),
+            ("unsafe_shutdowns", di.unsafe_shutdowns),
+            ("media_errors", di.media_errors),
+        ];
+        fields.extend(counters.iter().filter_map(|(name, v)| v.map(|v| (*name, int(v)))));
+        if let Some(waf) = di.write_amplification {
+            fields.push(("write_amplification", waf.to_string()));
+        }
+        if let Some(passed) = di.smart_passed {
+            fields.push(("smart_passed", passed.to_string()));
+        }
+        if !di.smart_attributes.is_empty() {
+            let count = |s: AttributeStatus| di.smart_attributes.iter().filter(|a| a.status == s).count() as u64;
+            fields.push(("critical_attributes", int(count(AttributeStatus::Critical))));
+            fields.push(("warning_attributes", int(count(AttributeStatus::Warning))));
+        }
+        if fields.is_empty() {
+            continue;
+        }
+
+        out.push_str(INFLUX_MEASUREMENT);
+        for (key, value) in tags.iter().filter(|(_, v)| !v.is_empty()) {
+            out.push_str(&format!(",{}={}", key, escape_tag(value)));
+        }
+        let fields: Vec<String> = fields.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        out.push_str(&format!(" {} {}\n", fields.join(","), now));
+    }
+    out
+}
+
+/// Formats a complete CSV file: the header row followed by one row per drive (see `csv_rows`).
+///
+/// # Arguments
+/// * `drives` - Scanned drives
+/// * `host` - Host name column
+/// * `now` - Unix time of the scan in seconds
+pub fn csv_document(drives: &[DiskInfo], host: &str, now: u64) -> String {
+    format!("{}\n{}", CSV_HEADER, csv_rows(drives, host, now))
+}
+
+/// Formats one CSV row per drive (columns as in the file header, empty for unknown values).
+///
+/// # Arguments
+/// * `drives` - Scanned drives
+/// * `host` - Host name column
+/// * `now` - Unix time of the scan in seconds
+pub fn csv_rows(drives: &[DiskInfo], host: &str, now: u64) -> String {
+    fn field<T: ToString>(v: Option<T>) -> String {
+        v.map(|v| v.to_string()).unwrap_or_default()
+    }
+    let mut out = String::new();
+    for di in drives {
+        let columns = [
+            format_timestamp(now),
+            now.to_string(),
+            csv_quote(host),
+            csv_quote(&di.dev),
+            csv_quote(di.model.as_deref().unwrap_or_default()),
+            csv_quote(di.serial.as_deref().unwrap_or_default()),
+            field(di.health_percent),
+            field(di.temp_c),
+            field(di.data_written_tb),
+            field(di.data_read_tb),
+            field(di.power_on_hours),
+            field(di.power_cycles),
+            field(di.unsafe_shutdowns),
+            field(di.media_errors),
+            field(di.smart_passed),
+        ];
+        out.push_str(&columns.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Quotes a CSV field if it contains a comma, quote or line break.
+fn csv_quote(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Builds the Zabbix sender request for one scan: the `ssd.discovery` low-level discovery value
+/// followed by one value per drive item (see `zabbix_template`).
+///
+/// # Arguments
+/// * `drives` - Scanned drives
+/// * `host` - Host name as configured in Zabbix
+/// * `now` - Unix time of the scan in seconds
+pub fn zabbix_payload(drives: &[DiskInfo], host: &str, now: u64) -> Json {
+    let value = |key: String, value: String| {
+        Json::Object(vec![
+            ("host".into(), host.into()),
+            ("key".into(), key.into()),
+            ("value".into(), value.into()),
+            ("clock".into(), now.into()),
+        ])
+    };
+
+    let discovery: Vec<Json> = drives
+        .iter()
+        .map(|di| {
+            Json::Object(vec![
+                ("{#DEVICE}".into(), di.dev.trim_start_matches("/dev/").into()),
+                ("{#MODEL}".into(), di.model.clone().unwrap_or_default().into()),
+                ("{#SERIAL}".into(), di.serial.clone().unwrap_or_default().into()),
+            ])
+        })
+        .collect();
+    let mut data = vec![value("ssd.discovery".into(), Json::Array(discovery).to_string())];
+
+    for di in drives {
+        let device = di.dev.trim_start_matches("/dev/");
+        let critical = di.smart_attributes.iter().filter(|a| a.status == AttributeStatus::Critical).count() as u64;
+        for (prefix, _, _, _) in ZABBIX_ITEMS {
+            let reading = match *prefix {
+                "health" => di.health_percent.map(|h| h.to_string()),
+                "temperature" => di.temp_c.map(|t| t.to_string()),
+                "data_written" => di.data_written_tb.map(|tb| tb.to_string()),
+                "data_read" => di.data_read_tb.map(|tb| tb.to_string()),
+                "power_on_hours" => di.power_on_hours.map(|v| v.to_string()),
+                "power_cycles" => di.power_cycles.map(|v| v.to_string()),
+                "unsafe_shutdowns" => di.unsafe_shutdowns.map(|v| v.to_string()),
+                "media_errors" => di.media_errors.map(|v| v.to_string()),
+                "smart_passed" => di.smart_passed.map(|p| u8::from(p).to_string()),
+                "critical_attributes" => (!di.smart_attributes.is_empty()).then(|| critical.to_string()),
+                _ => None,
+            };
+            if let Some(reading) = reading {
+                data.push(value(format!("ssd.{}[{}]", prefix, device), reading));
+            }
+        }
+    }
+
+    Json::Object(vec![
+        ("request".into(), "sender data".into()),
+        ("data".into(), Json::Array(data)),
+        ("clock".into(), now.into()),
+    ])
+}
+
+/// Sends a sender request to a Zabbix server or proxy.
+///
+/// # Arguments
+/// * `server` - "host" or "host:port"
+/// * `payload` - Request from `zabbix_payload`
+///
+/// # Returns
+/// The server's "info" text (e.g., "processed: 12; failed: 0; ..."), or an error.
+/// Values the server rejects are reported as an error so misconfigured hosts are noticed.
+pub fn zabbix_send(server: &str, payload: &Json) -> Result<String, String> {
+    let address = if server.contains(':') { server.to_string() } else { format!("{}:{}", server, ZABBIX_PORT) };
+    let fail = |e: std::io::Error| format!("{}: {}", address, e);
+    let mut stream = http::connect(&address)?;
+
+    // Header: "ZBXD", protocol flags 0x01, then the payload length as little-endian u64
+    let body = payload.to_string();
+    let mut packet = b"ZBXD\x01".to_vec();
+    packet.extend_from_slice(&(body.len() as u64).to_le_bytes());
+    packet.extend_from_slice(body.as_bytes());
+    stream.write_all(&packet).map_err(fail)?;
+
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).map_err(fail)?;
+    if response.len() < 13 || !response.starts_with(b"ZBXD") {
+        return Err(format!("{}: not a Zabbix response", address));
+    }
+    let reply = Json::parse(&String::from_utf8_lossy(&response[13..]))?;
+    let info = reply.get("info").and_then(Json::as_str).unwrap_or_default().to_string();
+    if reply.get("response").and_then(Json::as_str) != Some("success") {
+        return Err(format!("{} rejected the data: {}", address, info));
+    }
+    // "failed: N" counts values for unknown hosts/items, usually a missing template or host name mismatch
+    match info.split("failed: ").nth(1).and_then(|rest| rest.split(';').next()) {
+        Some(failed) if failed.trim() != "0" => Err(format!("{}: {}", address, info)),
+        _ => Ok(info),
+    }
+}
+
+/// Generates a Zabbix 6.0 template (YAML) with the discovery rule, item prototypes and triggers
+/// matching what `zabbix_payload` sends. Import it in Data collection → Templates → Import.
+pub fn zabbix_template() -> String {
+    let mut out = format!(
+        "\
+zabbix_export:
+  version: '6.0'
+  groups:
+    - uuid: {group}
+      name: Templates/Server hardware
+  templates:
+    - uuid: {template}
+      template: '{name}'
+      name: '{name}'
+      description: 'Drive health pushed by ssd_info_cli monitor --zabbix'
+      groups:
+        - name: Templates/Server hardware
+      discovery_rules:
+        - uuid: {discovery}
+          name: 'Drive discovery'
+          type: TRAP
+          key: ssd.discovery
+          lifetime: 7d
+          item_prototypes:
+",
+        group = uuid("group"),
+        template = uuid("template"),
+        discovery = uuid("discovery"),
+        name = ZABBIX_TEMPLATE
+    );
+
+    for (prefix, name, value_type, units) in ZABBIX_ITEMS {
+        let key = format!("ssd.{}[{{#DEVICE}}]", prefix);
+        out.push_str(&format!(
+            "            - uuid: {}
+              name: '{{#DEVICE}} ({{#MODEL}}): {}'
+              type: TRAP
+              key: '{}'
+              value_type: {}
+",
+            uuid(&key),
+            name,
+            key,
+            value_type
+        ));
+        if !units.is_empty() {
+            out.push_str(&format!("              units: '{}'\n", units));
+        }
+
+        let trigger = match *prefix {
+            "health" => Some(("<=10", "health at or below 10%", "HIGH")),
+            "temperature" => Some((">=65", "temperature at or above 65°C", "WARNING")),
+            "smart_passed" => Some(("=0", "SMART overall-health check failed", "DISASTER")),
+            "media_errors" => Some((">0", "media and data integrity errors", "HIGH")),
+            "critical_attributes" => Some((">0", "critical SMART attributes", "HIGH")),
+            _ => None,
+        };
+        if let Some((condition, description, priority)) = trigger {
+            out.push_str(&format!(
+                "              trigger_prototypes:
+                - uuid: {}
+                  expression: 'last(/{}/{}){}'
+                  name: '{{#DEVICE}}: {}'
+                  priority: {}
+",
+                uuid(&format!("trigger {}", key)),
+                ZABBIX_TEMPLATE,
+                key,
+                condition,
+                description,
+                priority
+            ));
+        }
+    }
+    out
+}
+
+/// Deterministic 32-hex-digit UUID for template objects, so regenerated templates import as updates.
+fn uuid(name: &str) -> String {
+    // Two FNV-1a passes with different offsets give 128 bits
+    let fnv = |offset: u64| {
+        name.bytes()
+            .fold(offset, |h, b| (h ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3))
+    };
+    let (high, low) = (fnv(0xcbf2_9ce4_8422_2325), fnv(0x8422_2325_cbf2_9ce4));
+    // Version 4 / variant bits as Zabbix expects
+    let high = (high & !0xf000) | 0x4000;
+    let low = (low & !(0xc << 60)) | (0x8 << 60);
+    format!("{:016x}{:016x}", high, low)
+}
+
+/// Escapes commas, equals signs and spaces in a tag value.
+fn escape_tag(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, ',' | '=' | ' ' | '\\') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Appends text to a file, creating it if needed.
+fn append_file(path: &std::path::Path, text: &str) -> Result<(), String> {
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut f| f.write_all(text.as_bytes()))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
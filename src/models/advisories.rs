@@ -0,0 +1,107 @@
+// Bundled knowledge base of model/firmware-specific drive advisories
+
+// Lazily initialised statics for the compiled patterns
+use once_cell::sync::Lazy;
+// Regex for matching model names
+use regex::Regex;
+
+/// A known issue affecting a specific drive model and (optionally) firmware revision.
+#[derive(Debug)]
+pub struct Advisory {
+    /// Case-insensitive regex matched against the drive model name
+    pub model_pattern: &'static str,
+    /// Affected firmware revisions (empty means every firmware is affected)
+    pub firmware: &'static [&'static str],
+    /// Short headline shown in the advisory banner
+    pub title: &'static str,
+    /// Explanation and recommended action
+    pub detail: &'static str,
+}
+
+/// Known problem drives. Keep entries conservative and actionable.
+static ADVISORIES: &[Advisory] = &[
+    Advisory {
+        model_pattern: r"Samsung SSD 980 PRO",
+        firmware: &["3B2QGXA7"],
+        title: "Firmware bug: rapid health degradation",
+        detail: "Firmware 3B2QGXA7 can rapidly consume Available Spare and put the drive into read-only mode. \
+                 Update to 5B2QGXA7 or newer with Samsung Magician or fwupd.",
+    },
+    Advisory {
+        model_pattern: r"Samsung SSD 990 PRO",
+        firmware: &["0B2QJXD7", "1B2QJXD7"],
+        title: "Firmware bug: abnormal health drop",
+        detail: "Early 990 PRO firmware reports fast-falling health percentage. \
+                 Update to 4B2QJXD7 or newer with Samsung Magician or fwupd.",
+    },
+    Advisory {
+        model_pattern: r"^M4-CT",
+        firmware: &["0001", "0002", "0009"],
+        title: "Firmware bug: 5184-hour power-on lockup",
+        detail: "Crucial m4 drives on this firmware stop responding after 5184 power-on hours. \
+                 Update to firmware 0309 or newer.",
+    },
+    Advisory {
+        model_pattern: r"INTEL SSDSA2CW",
+        firmware: &["4PC10302"],
+        title: "Known issue: 8MB capacity bug",
+        detail: "Intel 320 Series drives can shrink to 8MB after a power loss on firmware older than 4PC10362. \
+                 Make sure the drive runs 4PC10362 or newer and keep backups.",
+    },
+    Advisory {
+        model_pattern: r"WDC WD[2-6]0EFAX",
+        firmware: &[],
+        title: "SMR drive sold as NAS drive",
+        detail: "This WD Red model uses shingled magnetic recording (SMR). Expect very slow sustained writes \
+                 and long RAID/ZFS rebuilds; it may be dropped from arrays during resilvering.",
+    },
+    Advisory {
+        model_pattern: r"^ST(2000DM008|4000DM004|6000DM003|8000DM004)",
+        firmware: &[],
+        title: "SMR drive",
+        detail: "This Seagate BarraCuda model uses shingled magnetic recording (SMR). \
+                 Sustained random writes can slow down dramatically; avoid it for RAID or NAS use.",
+    },
+    Advisory {
+        model_pattern: r"^ST3000DM001",
+        firmware: &[],
+        title: "Model with high failure rate",
+        detail: "This model showed unusually high annualized failure rates in large fleet studies. \
+                 Keep current backups and watch reallocated/pending sector counts closely.",
+    },
+];
+
+/// Case-insensitive model patterns compiled once, in the same order as `ADVISORIES`.
+/// An invalid pattern compiles to None and never matches.
+static MODEL_PATTERNS: Lazy<Vec<Option<Regex>>> = Lazy::new(|| {
+    ADVISORIES
+        .iter()
+        .map(|a| Regex::new(&format!("(?i){}", a.model_pattern)).ok())
+        .collect()
+});
+
+/// Looks up advisories that apply to a drive.
+///
+/// # Arguments
+/// * `model` - Drive model name as reported by smartctl
+/// * `firmware` - Firmware revision, if known
+///
+/// # Returns
+/// All matching advisories, in knowledge base order.
+pub fn lookup(model: &str, firmware: Option<&str>) -> Vec<&'static Advisory> {
+    ADVISORIES
+        .iter()
+        .zip(MODEL_PATTERNS.iter())
+        .filter(|(a, pattern)| {
+            // Match the model pattern case-insensitively
+            let model_matches = pattern.as_ref().is_some_and(|re| re.is_match(model));
+
+            // Empty firmware list applies to every revision
+            let firmware_matches = a.firmware.is_empty()
+                || firmware.is_some_and(|fw| a.firmware.iter().any(|f| f.eq_ignore_ascii_case(fw)));
+
+            model_matches && firmware_matches
+        })
+        .map(|(a, _)| a)
+        .collect()
+}
@@ -1,30 +1,56 @@
 // Data models for disk information and SMART attributes
 
+// Bundled model/firmware advisory knowledge base
+pub mod advisories;
+
+// Advisory type stored on each drive
+use advisories::Advisory;
+
 /// Represents a single SMART attribute from disk diagnostics.
 /// Contains the attribute ID, name, values, and health status.
 #[derive(Clone, Debug)]
 pub struct SmartAttribute {
     /// Attribute identifier number
-    #[allow(dead_code)]
     pub id: String,
     /// Human-readable attribute name
-    #[allow(dead_code)]
     pub name: String,
     /// Current value of the attribute
-    #[allow(dead_code)]
     pub current: String,
     /// Worst value ever recorded for this attribute
-    #[allow(dead_code)]
     pub worst: String,
     /// Failure threshold for this attribute
-    #[allow(dead_code)]
     pub threshold: String,
     /// Raw value as reported by the drive
-    #[allow(dead_code)]
     pub raw_value: String,
     /// Health status based on threshold comparison
-    #[allow(dead_code)]
     pub status: AttributeStatus,
+    /// TYPE column: true for Pre-fail attributes, whose crossing a threshold predicts imminent failure,
+    /// false for Old_age (wear and usage) attributes
+    pub pre_fail: bool,
+}
+
+impl SmartAttribute {
+    /// Returns the leading integer of the raw value, if any.
+    /// Raw values often carry extra text (e.g., "34 (Min/Max 19/55)"), so only the first token is parsed.
+    pub fn raw_numeric(&self) -> Option<u64> {
+        self.raw_value
+            .split_whitespace()
+            .next()
+            .and_then(|tok| tok.parse::<u64>().ok())
+    }
+
+    /// Returns the normalized (current, worst, threshold) values.
+    /// None when any of them is not a number (e.g., "---" for attributes without a threshold).
+    pub fn normalized(&self) -> Option<(u16, u16, u16)> {
+        Some((self.current.trim().parse().ok()?, self.worst.trim().parse().ok()?, self.threshold.trim().parse().ok()?))
+    }
+
+    /// Points the attribute has left before it trips: its worst value minus the threshold, negative once crossed.
+    /// None for non-numeric values and for a threshold of 0, which never trips.
+    pub fn margin(&self) -> Option<i32> {
+        let (current, worst, threshold) = self.normalized()?;
+        (threshold > 0).then(|| i32::from(current.min(worst)) - i32::from(threshold))
+    }
 }
 
 /// Health status classification for SMART attributes.
@@ -55,8 +81,671 @@ pub struct PartitionInfo {
     pub free_gb: f64,
     /// Percentage of space currently used (0-100)
     pub used_percent: f64,
+    /// Active mount options (e.g., "rw", "noatime", "discard")
+    pub mount_options: Vec<String>,
+    /// Kernel device name of the partition (e.g., "sda1", "nvme0n1p2")
+    pub device: String,
+    /// Partition start offset in bytes, read from sysfs
+    pub start_bytes: Option<u64>,
+    /// Boundary the partition should start on (physical sector or erase block size)
+    pub alignment_bytes: Option<u64>,
+    /// Filesystem label (e.g., "EFI", "Data")
+    pub fs_label: Option<String>,
+    /// GPT partition name (PARTLABEL)
+    pub part_label: Option<String>,
+    /// GPT partition UUID (PARTUUID)
+    pub part_uuid: Option<String>,
+    /// Partition type GUID (GPT) or type code (MBR, e.g. "0x83")
+    pub part_type: Option<String>,
+    /// Number of inodes the filesystem has, None if it does not use a fixed inode table (e.g., btrfs)
+    pub inodes_total: Option<u64>,
+    /// Number of inodes in use
+    pub inodes_used: Option<u64>,
+}
+
+impl PartitionInfo {
+    /// Returns whether the partition start is a multiple of the alignment boundary.
+    /// None if either the offset or the boundary could not be read.
+    pub fn is_aligned(&self) -> Option<bool> {
+        match (self.start_bytes, self.alignment_bytes) {
+            (Some(start), Some(align)) if align > 0 => Some(start % align == 0),
+            _ => None,
+        }
+    }
+}
+
+/// Sidebar groups in display order (see `DiskInfo::bus_group`)
+pub const DRIVE_GROUPS: &[&str] = &["NVMe", "SATA SSD", "HDD", "USB", "Virtual"];
+
+/// Column the partition table is sorted by.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PartitionSort {
+    /// Mount point, alphabetically
+    MountPoint,
+    /// Total size
+    Size,
+    /// Used space
+    Used,
+    /// Free space as a percentage of the total
+    FreePercent,
+}
+
+impl PartitionSort {
+    /// Orders two partitions by this column, ascending.
+    pub fn compare(self, a: &PartitionInfo, b: &PartitionInfo) -> std::cmp::Ordering {
+        match self {
+            PartitionSort::MountPoint => a.mount_point.cmp(&b.mount_point),
+            PartitionSort::Size => a.total_gb.total_cmp(&b.total_gb),
+            PartitionSort::Used => a.used_gb.total_cmp(&b.used_gb),
+            // Free% is 100 - used%, so the fullest filesystem comes first when ascending
+            PartitionSort::FreePercent => b.used_percent.total_cmp(&a.used_percent),
+        }
+    }
+
+    /// The direction a column sorts in when first clicked: mount points A-Z, sizes largest first,
+    /// free space lowest first, so the interesting rows are at the top.
+    pub fn default_descending(self) -> bool {
+        matches!(self, PartitionSort::Size | PartitionSort::Used)
+    }
+}
+
+/// Returns the partitions in table order: as detected when `sort` is None, otherwise by the column.
+///
+/// # Arguments
+/// * `parts` - Partitions of a drive
+/// * `sort` - Column and whether the order is descending
+pub fn sorted_partitions(parts: &[PartitionInfo], sort: Option<(PartitionSort, bool)>) -> Vec<&PartitionInfo> {
+    let mut sorted: Vec<&PartitionInfo> = parts.iter().collect();
+    if let Some((column, descending)) = sort {
+        sorted.sort_by(|a, b| {
+            let order = column.compare(a, b);
+            if descending {
+                order.reverse()
+            } else {
+                order
+            }
+        });
+    }
+    sorted
+}
+
+/// How a drive's capacity is split, in bytes, for the storage overview.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CapacityUsage {
+    /// Size of the drive
+    pub total: u64,
+    /// Space used in its mounted filesystems
+    pub used: u64,
+    /// Space free in its mounted filesystems
+    pub free: u64,
+    /// Space not covered by any partition
+    pub unallocated: u64,
+}
+
+impl CapacityUsage {
+    /// Space that is partitioned but not in a mounted filesystem (swap, unmounted or encrypted partitions,
+    /// filesystem overhead).
+    pub fn other(&self) -> u64 {
+        self.total.saturating_sub(self.used + self.free + self.unallocated)
+    }
+
+    /// Sums the usage of several drives.
+    pub fn sum<'a>(usages: impl IntoIterator<Item = &'a CapacityUsage>) -> CapacityUsage {
+        usages.into_iter().fold(CapacityUsage::default(), |acc, u| CapacityUsage {
+            total: acc.total + u.total,
+            used: acc.used + u.used,
+            free: acc.free + u.free,
+            unallocated: acc.unallocated + u.unallocated,
+        })
+    }
+}
+
+/// Progress of a long-running sanitize or format operation on an NVMe drive.
+/// Powering off the drive while one of these is running can leave it unusable.
+#[derive(Clone, Debug)]
+pub struct OperationProgress {
+    /// Operation being performed (e.g., "Sanitize (block erase)", "Format")
+    pub kind: String,
+    /// Completion percentage (0-100)
+    pub percent: f32,
+    /// Estimated seconds remaining, if the drive reports a time estimate
+    pub eta_secs: Option<u64>,
+}
+
+/// Block layer queue settings read from `/sys/block/<dev>/queue/`.
+#[derive(Clone, Debug, Default)]
+pub struct QueueInfo {
+    /// Active I/O scheduler (e.g., "none", "mq-deadline", "bfq")
+    pub scheduler: Option<String>,
+    /// Schedulers the kernel offers for this device
+    pub available_schedulers: Vec<String>,
+    /// Maximum number of requests the block layer queues per hardware queue
+    pub nr_requests: Option<u32>,
+    /// Device command queue depth (SATA/SAS NCQ depth)
+    pub queue_depth: Option<u32>,
+    /// Number of blk-mq hardware queues (one per CPU on most NVMe drives)
+    pub hw_queues: Option<usize>,
+}
+
+/// SCT Error Recovery Control (TLER/CCTL) timeouts in tenths of a second.
+/// None means recovery is unlimited (ERC disabled), as on most desktop drives.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ErcSettings {
+    /// Read error recovery timeout in deciseconds
+    pub read_ds: Option<u16>,
+    /// Write error recovery timeout in deciseconds
+    pub write_ds: Option<u16>,
+}
+
+/// NVMe Identify Controller details, the fields `nvme id-ctrl` prints at the top.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NvmeController {
+    /// Controller ID (CNTLID), distinguishes controllers of a multi-port subsystem
+    pub controller_id: Option<u16>,
+    /// IEEE OUI of the controller vendor (e.g., "0x001b44")
+    pub ieee_oui: Option<String>,
+    /// NVMe specification version the controller implements (e.g., "1.4")
+    pub nvme_version: Option<String>,
+    /// Number of namespaces the controller supports (NN)
+    pub namespaces: Option<u32>,
+    /// NVMe Qualified Name of the subsystem, used by NVMe-oF and multipath (from nvme-cli)
+    pub subsystem_nqn: Option<String>,
+    /// Whether the controller has a volatile write cache at all (VWC bit 0, from nvme-cli)
+    pub volatile_write_cache: Option<bool>,
+    /// Host Memory Buffer size the drive asks for (HMPRE) in bytes; 0 for drives with their own DRAM
+    pub hmb_preferred_bytes: Option<u64>,
+    /// Host memory the driver has given the drive in bytes, 0 if the buffer is disabled
+    pub hmb_allocated_bytes: Option<u64>,
+    /// Whether the controller supports Autonomous Power State Transitions (APSTA, from nvme-cli)
+    pub apst_supported: Option<bool>,
+    /// APST configuration, None if unsupported or nvme-cli is unavailable
+    pub apst: Option<ApstStatus>,
+    /// Log Page Attributes (LPA) bit field, telling which optional log pages the controller has
+    pub log_page_attributes: Option<u32>,
+    /// Firmware Updates (FRMW) bit field: slot count, whether slot 1 is read-only and
+    /// whether a new image can be activated without a reset
+    pub firmware_updates: Option<u8>,
+    /// Firmware slot log, None if nvme-cli is unavailable
+    pub firmware_slots: Option<FirmwareSlots>,
+    /// Controller Attributes (CTRATT) bit field, telling which optional features the controller has (from nvme-cli)
+    pub controller_attributes: Option<u32>,
+    /// Highest endurance group ID (ENDGIDMAX, from nvme-cli)
+    pub endurance_group_max: Option<u16>,
+    /// Wear and capacity of each endurance group, empty unless the controller has them
+    pub endurance_groups: Vec<EnduranceGroup>,
+    /// Size and use of each active namespace, read alongside the endurance groups
+    pub namespace_usage: Vec<NamespaceUsage>,
+}
+
+/// Wear and capacity of one NVMe endurance group. Enterprise drives can divide their media into groups
+/// that wear separately, each holding one or more namespaces.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EnduranceGroup {
+    /// Endurance group ID (1-based)
+    pub id: u16,
+    /// Estimated share of the group's rated endurance used, can exceed 100
+    pub percent_used: Option<u8>,
+    /// Remaining spare capacity of the group in percent
+    pub available_spare: Option<u8>,
+    /// Bytes the host wrote to the group's namespaces
+    pub host_bytes_written: Option<u64>,
+    /// Bytes written to the group's media, including the controller's own writes (garbage collection)
+    pub media_bytes_written: Option<u64>,
+    /// Total capacity of the group in bytes, None if not reported
+    pub total_bytes: Option<u64>,
+    /// Capacity of the group not yet allocated to namespaces in bytes
+    pub unallocated_bytes: Option<u64>,
+}
+
+impl EnduranceGroup {
+    /// Media bytes per host byte written to the group.
+    /// Returns None until the group has seen host writes or when media writes are not reported.
+    pub fn write_amplification(&self) -> Option<f64> {
+        let host = self.host_bytes_written.filter(|&b| b > 0)?;
+        Some(self.media_bytes_written? as f64 / host as f64)
+    }
+}
+
+/// Size and use of one NVMe namespace, from Identify Namespace.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NamespaceUsage {
+    /// Namespace ID (1-based)
+    pub nsid: u32,
+    /// Endurance group the namespace belongs to, None if not reported
+    pub endurance_group: Option<u16>,
+    /// Namespace size (NSZE) in bytes
+    pub size_bytes: u64,
+    /// Bytes currently allocated (NUSE); equals the size unless the namespace is thin provisioned
+    /// or the drive tracks deallocated blocks
+    pub used_bytes: u64,
+}
+
+/// The NVMe firmware slot log: which revision each slot holds and which one runs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FirmwareSlots {
+    /// Slot the running firmware was loaded from (1-7)
+    pub active: u8,
+    /// Slot that becomes active on the next controller reset, None if nothing is pending
+    pub next_reset: Option<u8>,
+    /// Stored revisions as (slot, revision), empty slots left out
+    pub slots: Vec<(u8, String)>,
+}
+
+impl NvmeController {
+    /// Whether the controller can produce a host-initiated telemetry log (LPA bit 3),
+    /// the vendor-specific dump manufacturers ask for during RMA triage.
+    pub fn supports_telemetry(&self) -> bool {
+        self.log_page_attributes.is_some_and(|lpa| lpa & 0x8 != 0)
+    }
+
+    /// Whether the controller keeps a persistent event log (LPA bit 4), part of NVMe 1.4.
+    pub fn supports_persistent_event_log(&self) -> bool {
+        self.log_page_attributes.is_some_and(|lpa| lpa & 0x10 != 0)
+    }
+
+    /// Whether the controller divides its media into endurance groups (CTRATT bit 4).
+    pub fn supports_endurance_groups(&self) -> bool {
+        self.controller_attributes.is_some_and(|ctratt| ctratt & 0x10 != 0)
+    }
+
+    /// Namespaces that belong to an endurance group.
+    ///
+    /// # Arguments
+    /// * `id` - Endurance group ID
+    ///
+    /// # Returns
+    /// The group's namespaces; when the controller has a single group, namespaces that do not report one count as its own.
+    pub fn group_namespaces(&self, id: u16) -> Vec<&NamespaceUsage> {
+        let single = self.endurance_groups.len() == 1;
+        self.namespace_usage
+            .iter()
+            .filter(|ns| ns.endurance_group == Some(id) || (single && ns.endurance_group.is_none()))
+            .collect()
+    }
+
+    /// Whether firmware slot 1 is read-only (FRMW bit 0), holding the factory image.
+    pub fn slot1_read_only(&self) -> bool {
+        self.firmware_updates.is_some_and(|frmw| frmw & 0x1 != 0)
+    }
+
+    /// Whether a committed image can be activated without a reset (FRMW bit 4).
+    pub fn activates_without_reset(&self) -> bool {
+        self.firmware_updates.is_some_and(|frmw| frmw & 0x10 != 0)
+    }
+}
+
+/// Autonomous Power State Transition settings of an NVMe controller.
+/// With APST on, the drive drops to low-power states by itself after being idle; drives whose firmware
+/// mishandles the deepest states are a common cause of NVMe timeouts and "controller is down" resets.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ApstStatus {
+    /// Whether APST is enabled (feature 0x0c, bit 0)
+    pub enabled: bool,
+    /// Idle transitions as (idle time in ms, target power state), shortest idle time first
+    pub transitions: Vec<(u64, u8)>,
+    /// Entry + exit latency the kernel allows for a state in µs (pm_qos_latency_tolerance_us or
+    /// nvme_core.default_ps_max_latency_us); 0 means the kernel keeps APST off
+    pub max_latency_us: Option<u64>,
+}
+
+impl ApstStatus {
+    /// Describes the setting in one line (e.g., "enabled: PS3 after 86 ms idle, PS4 after 750 ms idle; latency limit 100000 µs").
+    pub fn summary(&self) -> String {
+        let mut text = if !self.enabled {
+            "disabled".to_string()
+        } else if self.transitions.is_empty() {
+            "enabled, no transitions configured".to_string()
+        } else {
+            let steps: Vec<String> =
+                self.transitions.iter().map(|(idle_ms, state)| format!("PS{} after {} ms idle", state, idle_ms)).collect();
+            format!("enabled: {}", steps.join(", "))
+        };
+        match self.max_latency_us {
+            Some(0) => text.push_str("; turned off by the kernel (latency limit 0)"),
+            Some(us) => text.push_str(&format!("; latency limit {} µs", us)),
+            None => {}
+        }
+        text
+    }
+}
+
+/// Significant entries of the NVMe persistent event log.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum EventCategory {
+    /// The composite temperature crossed a threshold
+    ThermalExcursion,
+    /// New firmware was committed to a slot
+    FirmwareCommit,
+    /// A sanitize operation was started
+    Sanitize,
+    /// A Format NVM was started
+    Format,
+    /// The controller was powered on or reset
+    PowerOnReset,
+    /// The subsystem reported a hardware error
+    HardwareError,
+}
+
+impl EventCategory {
+    /// Every category, in display order.
+    pub const ALL: [EventCategory; 6] = [
+        EventCategory::ThermalExcursion,
+        EventCategory::FirmwareCommit,
+        EventCategory::Sanitize,
+        EventCategory::Format,
+        EventCategory::PowerOnReset,
+        EventCategory::HardwareError,
+    ];
+
+    /// Classifies an event type as printed by nvme-cli (e.g., "Thermal Excursion Event(0xd)").
+    /// Completion events are left out so each operation counts once.
+    ///
+    /// # Arguments
+    /// * `event_type` - The "Event Type" value of a log entry
+    pub fn from_event_type(event_type: &str) -> Option<Self> {
+        let text = event_type.to_lowercase();
+        [
+            ("thermal excursion", EventCategory::ThermalExcursion),
+            ("firmware commit", EventCategory::FirmwareCommit),
+            ("sanitize start", EventCategory::Sanitize),
+            ("format nvm start", EventCategory::Format),
+            ("power-on or reset", EventCategory::PowerOnReset),
+            ("hardware error", EventCategory::HardwareError),
+        ]
+        .into_iter()
+        .find(|(name, _)| text.contains(name))
+        .map(|(_, category)| category)
+    }
+
+    /// Short name for counts and the timeline.
+    pub fn label(self) -> &'static str {
+        match self {
+            EventCategory::ThermalExcursion => "Thermal excursion",
+            EventCategory::FirmwareCommit => "Firmware commit",
+            EventCategory::Sanitize => "Sanitize",
+            EventCategory::Format => "Format",
+            EventCategory::PowerOnReset => "Power-on/reset",
+            EventCategory::HardwareError => "Hardware error",
+        }
+    }
+}
+
+/// One significant entry of the NVMe persistent event log.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PersistentEvent {
+    /// What happened
+    pub category: EventCategory,
+    /// When it happened in Unix seconds, None if the drive's clock was not set at the time
+    pub timestamp: Option<u64>,
+}
+
+/// Counts persistent log events per category.
+///
+/// # Arguments
+/// * `events` - Parsed log entries
+///
+/// # Returns
+/// (category, count) for every category that occurred, in display order.
+pub fn event_counts(events: &[PersistentEvent]) -> Vec<(EventCategory, usize)> {
+    EventCategory::ALL
+        .into_iter()
+        .map(|category| (category, events.iter().filter(|e| e.category == category).count()))
+        .filter(|&(_, count)| count > 0)
+        .collect()
+}
+
+/// SATA Aggressive Link Power Management policy of the AHCI port a drive is attached to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinkPower {
+    /// SCSI host of the port (e.g., "host2")
+    pub host: String,
+    /// Policy from link_power_management_policy (e.g., "med_power_with_dipm")
+    pub policy: String,
+}
+
+impl LinkPower {
+    /// Explains what the policy lets the link do when idle.
+    pub fn description(&self) -> &'static str {
+        match self.policy.as_str() {
+            "max_performance" => "Link power management off; the link stays active",
+            "medium_power" => "Host-initiated Partial state when idle",
+            "med_power_with_dipm" => "Partial state with device-initiated power management (the usual default)",
+            "min_power" => "Slumber and DevSleep; lowest power, but the most likely to cause link errors",
+            "keep_firmware" => "Left as configured by the firmware",
+            _ => "Unknown policy",
+        }
+    }
+
+    /// Whether the policy is the one most often behind link resets and CRC errors (min_power).
+    pub fn is_aggressive(&self) -> bool {
+        self.policy == "min_power"
+    }
+}
+
+/// ATA Security feature set state as reported by `hdparm -I`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AtaSecurity {
+    /// Drive implements the security feature set
+    pub supported: bool,
+    /// A user password is set
+    pub enabled: bool,
+    /// Drive is locked and refuses media access until unlocked
+    pub locked: bool,
+    /// Security commands are blocked until the next power cycle (usually done by the BIOS)
+    pub frozen: bool,
+    /// Enhanced security erase is supported
+    pub enhanced_erase: bool,
+    /// Estimated erase time line (e.g., "2min for SECURITY ERASE UNIT.")
+    pub erase_time: Option<String>,
+}
+
+/// TCG self-encrypting drive (SED) status.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SedStatus {
+    /// Security subsystem class (e.g., "Opal SSC 2.00", "Pyrite"), None if only
+    /// NVMe Security Send/Receive support is known
+    pub ssc: Option<String>,
+    /// Drive encrypts all data on the media
+    pub media_encrypt: bool,
+    /// Locking ranges can be configured
+    pub locking_supported: bool,
+    /// Locking has been enabled (an owner password is set)
+    pub locking_enabled: bool,
+    /// At least one locking range is currently locked
+    pub locked: bool,
 }
 
+/// Capacity limits reported by the ATA Host Protected Area (HPA) and
+/// Device Configuration Overlay (DCO), both of which can hide sectors from the OS.
+#[derive(Clone, Debug)]
+pub struct CapacityLimits {
+    /// Sectors currently visible to the operating system
+    pub visible_sectors: u64,
+    /// Native max sectors reported by the drive (HPA limit removed)
+    pub native_sectors: u64,
+    /// Real max sectors reported by DCO identify, if available
+    pub dco_sectors: Option<u64>,
+}
+
+impl CapacityLimits {
+    /// Returns the number of bytes hidden from the operating system.
+    /// Uses the larger of the HPA native and DCO real max sector counts.
+    pub fn hidden_bytes(&self) -> u64 {
+        let real = self.native_sectors.max(self.dco_sectors.unwrap_or(0));
+        real.saturating_sub(self.visible_sectors) * 512
+    }
+}
+
+/// Temperature extremes a SATA drive records in its temperature attribute's raw value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TemperatureRange {
+    /// Lowest temperature in °C, None when the drive reports 0 (many never record a minimum)
+    pub min: Option<i32>,
+    /// Highest temperature in °C
+    pub max: i32,
+    /// True when the extremes cover only the current power cycle (Airflow_Temperature_Cel on most drives)
+    /// rather than the drive's lifetime
+    pub since_power_on: bool,
+}
+
+impl TemperatureRange {
+    /// Formats the range for display (e.g., "Lifetime range: 19–55°C").
+    pub fn describe(&self) -> String {
+        let span = if self.since_power_on { "Range since power-on" } else { "Lifetime range" };
+        match self.min {
+            Some(min) => format!("{}: {}–{}°C", span, min, self.max),
+            None => format!("{}: up to {}°C", span, self.max),
+        }
+    }
+}
+
+/// Raw counts of the sector attributes that best predict HDD failure.
+/// None means the drive does not report that attribute.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CriticalSectors {
+    /// Reallocated_Sector_Ct (5): sectors remapped to spares after failing
+    pub reallocated: Option<u64>,
+    /// Current_Pending_Sector (197): unreadable sectors waiting to be remapped
+    pub pending: Option<u64>,
+    /// Offline_Uncorrectable (198): sectors the offline scan could not read
+    pub uncorrectable: Option<u64>,
+}
+
+impl CriticalSectors {
+    /// Whether any counter is above zero.
+    pub fn any_nonzero(&self) -> bool {
+        [self.reallocated, self.pending, self.uncorrectable].iter().any(|c| c.unwrap_or(0) > 0)
+    }
+}
+
+/// SATA SSD wear decoded from vendor attributes, the counterpart of NVMe "Percentage Used".
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SsdWear {
+    /// Share of the rated program/erase cycles used up, from the normalized value of a vendor wear attribute
+    pub used_percent: Option<u8>,
+    /// Name of the attribute `used_percent` came from (e.g., "Wear_Leveling_Count")
+    pub source: Option<String>,
+    /// Average erase count per block
+    pub avg_erase_count: Option<u64>,
+    /// Highest erase count of any block
+    pub max_erase_count: Option<u64>,
+}
+
+/// How many points each signal takes off the 100-point health score.
+/// The defaults make a drive without errors score its remaining wear, as NVMe drives report it,
+/// while a single pending sector costs as much as a tenth of the drive's endurance.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HealthWeights {
+    /// Points per percent of rated endurance used
+    pub wear: f64,
+    /// Points per pending sector (197): unreadable sectors waiting to be remapped
+    pub pending_sector: f64,
+    /// Points per offline uncorrectable sector (198)
+    pub uncorrectable_sector: f64,
+    /// Points per reallocated sector (5)
+    pub reallocated_sector: f64,
+    /// Points per NVMe media and data integrity error
+    pub media_error: f64,
+    /// Points per UDMA CRC error (199); these usually come from the cable, not the drive
+    pub crc_error: f64,
+    /// Points taken once when the drive fails its own SMART self-assessment
+    pub smart_failed: f64,
+}
+
+impl Default for HealthWeights {
+    fn default() -> Self {
+        Self {
+            wear: 1.0,
+            pending_sector: 10.0,
+            uncorrectable_sector: 10.0,
+            reallocated_sector: 1.0,
+            media_error: 5.0,
+            crc_error: 0.1,
+            smart_failed: 100.0,
+        }
+    }
+}
+
+impl HealthWeights {
+    /// The weights as (settings name, value) pairs, in display order.
+    pub fn entries(&self) -> [(&'static str, f64); 7] {
+        [
+            ("wear", self.wear),
+            ("pending_sector", self.pending_sector),
+            ("uncorrectable_sector", self.uncorrectable_sector),
+            ("reallocated_sector", self.reallocated_sector),
+            ("media_error", self.media_error),
+            ("crc_error", self.crc_error),
+            ("smart_failed", self.smart_failed),
+        ]
+    }
+
+    /// Sets a weight by its settings name.
+    ///
+    /// # Arguments
+    /// * `name` - Name as listed by `entries`
+    /// * `value` - Points; negative values are ignored
+    ///
+    /// # Returns
+    /// False if the name is unknown or the value negative.
+    pub fn set(&mut self, name: &str, value: f64) -> bool {
+        let field = match name {
+            "wear" => &mut self.wear,
+            "pending_sector" => &mut self.pending_sector,
+            "uncorrectable_sector" => &mut self.uncorrectable_sector,
+            "reallocated_sector" => &mut self.reallocated_sector,
+            "media_error" => &mut self.media_error,
+            "crc_error" => &mut self.crc_error,
+            "smart_failed" => &mut self.smart_failed,
+            _ => return false,
+        };
+        if !(value >= 0.0 && value.is_finite()) {
+            return false;
+        }
+        *field = value;
+        true
+    }
+}
+
+/// A drive's health score and the inputs that produced it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HealthScore {
+    /// Score from 0 (replace now) to 100 (new and error-free)
+    pub score: u8,
+    /// Every input the drive reports, largest deduction first; inputs that cost nothing are kept
+    /// so the breakdown shows what was checked
+    pub inputs: Vec<ScoreInput>,
+}
+
+/// One input of the health score.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScoreInput {
+    /// Name of the weight applied (see `HealthWeights::entries`)
+    pub weight: &'static str,
+    /// What was measured (e.g., "Pending sectors")
+    pub label: &'static str,
+    /// Measured value as shown (e.g., "12%", "0", "PASSED")
+    pub value: String,
+    /// Points taken off the score
+    pub points: f64,
+}
+
+/// Vendor attributes whose normalized value counts down from 100 as the NAND wears out, in order of preference
+const WEAR_ATTRIBUTES: &[&str] = &[
+    "Wear_Leveling_Count",
+    "Media_Wearout_Indicator",
+    "Percent_Lifetime_Remain",
+    "SSD_Life_Left",
+    "Remaining_Lifetime_Perc",
+    "Percent_Life_Remaining",
+];
+
+/// Vendor attributes holding the average erase count in their raw value
+const AVG_ERASE_ATTRIBUTES: &[&str] = &["Ave_Block-Erase_Count", "Average_Erase_Count", "Avg_Write/Erase_Count"];
+
+/// Vendor attributes holding the maximum erase count in their raw value
+const MAX_ERASE_ATTRIBUTES: &[&str] = &["Max_Erase_Count", "Maximum_Erase_Count"];
+
 /// Complete information about a disk drive.
 /// Aggregates device details, SMART data, temperature, and partition information.
 #[derive(Clone, Debug)]
@@ -99,6 +788,114 @@ pub struct DiskInfo {
     pub smart_attributes: Vec<SmartAttribute>,
     /// List of partitions on this drive
     pub partitions: Vec<PartitionInfo>,
+    /// Sanitize or format operation currently running on the drive
+    pub operation: Option<OperationProgress>,
+    /// HPA/DCO capacity limits (ATA only)
+    pub capacity_limits: Option<CapacityLimits>,
+    /// Known-issue advisories matching this model/firmware
+    pub advisories: Vec<&'static Advisory>,
+    /// Current power state: hdparm -C for ATA (e.g., "active/idle", "standby"),
+    /// or the NVMe power state number (e.g., "PS3")
+    pub power_state: Option<String>,
+    /// True when SMART data was not re-read because the drive is asleep
+    /// (the GUI keeps showing the last known values instead)
+    pub cached: bool,
+    /// Advanced Power Management level (1-254, 255 = disabled), ATA only
+    pub apm_level: Option<u8>,
+    /// Automatic Acoustic Management level (128 = quiet, 254 = fast), ATA only
+    pub aam_level: Option<u8>,
+    /// Whether the volatile write cache is enabled
+    pub write_cache: Option<bool>,
+    /// I/O scheduler and queue settings from sysfs
+    pub queue: Option<QueueInfo>,
+    /// SATA link power management policy of the drive's port, None for NVMe and USB drives
+    pub link_power: Option<LinkPower>,
+    /// Fabric a network-attached drive is reached over ("NVMe/TCP", "NVMe/RDMA", "NVMe/FC", "iSCSI"),
+    /// None for local drives
+    pub network_transport: Option<String>,
+    /// Whether the drive is a card reader or flash stick: the kernel flags its media as removable,
+    /// or it sits on USB and passes no SMART data through. Such media reports no or unreliable SMART.
+    pub removable: bool,
+    /// Stable `/dev/disk/by-id/` path that survives device letter changes
+    pub by_id: Option<String>,
+    /// World Wide Name (ATA/SCSI) or EUI-64 (NVMe) identifier
+    pub wwn: Option<String>,
+    /// Bytes not covered by any partition (after partition table overhead)
+    pub unallocated_bytes: Option<u64>,
+    /// Whether the root filesystem (/) lives on this drive, directly or through LVM/LUKS
+    pub hosts_root: bool,
+    /// Whether the mounted EFI system partition is on this drive
+    pub hosts_esp: bool,
+    /// SCT Error Recovery Control timeouts, None if the drive does not support SCT ERC
+    pub erc: Option<ErcSettings>,
+    /// NVMe Identify Controller details, None for ATA drives
+    pub nvme_controller: Option<NvmeController>,
+    /// ATA Security state, None for NVMe or if hdparm is unavailable
+    pub security: Option<AtaSecurity>,
+    /// TCG Opal/SED status, None if the drive is not self-encrypting or status is unknown
+    pub sed: Option<SedStatus>,
+    /// Whether SMART is switched on ("SMART support is: Enabled/Disabled");
+    /// a drive with SMART off reports no attributes until it is enabled
+    pub smart_enabled: Option<bool>,
+    /// SMART overall-health self-assessment (false = "FAILED!")
+    pub smart_passed: Option<bool>,
+    /// Status of the most recent self-test log entry (e.g., "Completed: read failure")
+    pub last_self_test: Option<String>,
+    /// NVMe "Media and Data Integrity Errors" count
+    pub media_errors: Option<u64>,
+    /// NVMe "Controller Busy Time": minutes the controller spent with I/O commands outstanding
+    pub controller_busy_minutes: Option<u64>,
+    /// Write amplification factor (NAND writes / host writes) from vendor SMART attributes
+    pub write_amplification: Option<f64>,
+}
+
+/// Masks an identifier such as a serial number, keeping its length and last two characters
+/// so drives remain distinguishable in a screenshot.
+///
+/// # Arguments
+/// * `value` - Identifier to mask (e.g., "S6PTNX0R123456A")
+///
+/// # Returns
+/// The masked identifier (e.g., "•••••••••••••6A").
+pub fn mask_identifier(value: &str) -> String {
+    let count = value.chars().count();
+    value
+        .chars()
+        .enumerate()
+        .map(|(i, c)| if i + 2 < count { '•' } else { c })
+        .collect()
+}
+
+/// Spells out an hour count in years, days and hours, keeping the two largest units.
+///
+/// # Arguments
+/// * `hours` - Hours to describe (e.g., power-on hours)
+///
+/// # Returns
+/// A phrase such as "2 years, 41 days", "41 days, 3 hours" or "5 hours"; "less than an hour" for 0.
+pub fn humanize_hours(hours: u64) -> String {
+    let unit = |n: u64, name: &str| format!("{} {}{}", n, name, if n == 1 { "" } else { "s" });
+    let (years, days, hours) = (hours / 8760, hours % 8760 / 24, hours % 24);
+    match (years, days) {
+        (0, 0) if hours == 0 => "less than an hour".to_string(),
+        (0, 0) => unit(hours, "hour"),
+        (0, _) if hours == 0 => unit(days, "day"),
+        (0, _) => format!("{}, {}", unit(days, "day"), unit(hours, "hour")),
+        (_, 0) => unit(years, "year"),
+        _ => format!("{}, {}", unit(years, "year"), unit(days, "day")),
+    }
+}
+
+/// Classifies a self-test log status as a failure.
+/// Covers ATA ("Completed: read failure", "Fatal or unknown error") and
+/// NVMe ("Completed: failed segments", "Fatal error") wording; aborts and interruptions are not failures.
+///
+/// # Arguments
+/// * `status` - Status column of a self-test log entry
+pub fn self_test_failed(status: &str) -> bool {
+    let status = status.to_lowercase();
+    (status.contains("fail") || status.contains("fatal") || status.contains("error"))
+        && !status.contains("without error")
 }
 
 impl DiskInfo {
@@ -125,6 +922,285 @@ impl DiskInfo {
             device_type: None,
             smart_attributes: vec![],
             partitions: vec![],
+            operation: None,
+            capacity_limits: None,
+            advisories: vec![],
+            power_state: None,
+            cached: false,
+            apm_level: None,
+            aam_level: None,
+            write_cache: None,
+            queue: None,
+            link_power: None,
+            network_transport: None,
+            removable: false,
+            by_id: None,
+            wwn: None,
+            unallocated_bytes: None,
+            hosts_root: false,
+            hosts_esp: false,
+            erc: None,
+            nvme_controller: None,
+            security: None,
+            sed: None,
+            smart_enabled: None,
+            smart_passed: None,
+            last_self_test: None,
+            media_errors: None,
+            controller_busy_minutes: None,
+            write_amplification: None,
         }
     }
+
+    /// Returns a copy with the serial number, WWN, by-id path and subsystem NQN masked,
+    /// for screenshots and reports shared in public (privacy mode).
+    pub fn masked(&self) -> DiskInfo {
+        let mut masked = self.clone();
+        masked.serial = self.serial.as_deref().map(mask_identifier);
+        masked.wwn = self.wwn.as_deref().map(mask_identifier);
+        // by-id names embed the serial ("ata-Model_SERIAL") or the WWN ("wwn-0x...")
+        masked.by_id = self.by_id.as_ref().map(|path| {
+            let mut path = path.clone();
+            for (id, replacement) in [(&self.serial, &masked.serial), (&self.wwn, &masked.wwn)] {
+                if let (Some(id), Some(replacement)) = (id, replacement) {
+                    path = path.replace(id.as_str(), replacement);
+                }
+            }
+            path
+        });
+        // Many vendors build the NQN from the serial ("nqn.2014.08.org.nvmexpress:144d144dS4EWNF0M123456...")
+        if let (Some(serial), Some(replacement)) = (&self.serial, &masked.serial) {
+            if let Some(nqn) = masked.nvme_controller.as_mut().and_then(|c| c.subsystem_nqn.as_mut()) {
+                *nqn = nqn.replace(serial.as_str(), replacement);
+            }
+        }
+        masked
+    }
+
+    /// Splits the drive's capacity into used, free, unallocated and other space.
+    /// A filesystem mounted at several places (e.g., btrfs subvolumes) is counted once.
+    ///
+    /// # Returns
+    /// None if the drive's capacity is unknown.
+    pub fn capacity_usage(&self) -> Option<CapacityUsage> {
+        let bytes = |gb: f64| (gb * 1_000_000_000.0).round() as u64;
+        let mut usage = CapacityUsage {
+            total: self.capacity.filter(|&c| c > 0.0)? as u64,
+            unallocated: self.unallocated_bytes.unwrap_or(0),
+            ..Default::default()
+        };
+        for part in self.distinct_partitions() {
+            usage.used += bytes(part.used_gb);
+            usage.free += bytes(part.free_gb);
+        }
+        Some(usage)
+    }
+
+    /// Mounted partitions with each filesystem listed once, at its first mount point.
+    pub fn distinct_partitions(&self) -> Vec<&PartitionInfo> {
+        let mut distinct: Vec<&PartitionInfo> = Vec::new();
+        for part in &self.partitions {
+            if part.device.is_empty() || !distinct.iter().any(|p| p.device == part.device) {
+                distinct.push(part);
+            }
+        }
+        distinct
+    }
+
+    /// Sidebar group of the drive, one of `DRIVE_GROUPS`.
+    /// USB enclosures are recognized by their by-id link and virtual disks by the hypervisor's model name,
+    /// since both show up as ordinary SATA devices otherwise.
+    pub fn bus_group(&self) -> &'static str {
+        let model = self.model.as_deref().unwrap_or_default().to_uppercase();
+        if self.by_id.as_deref().is_some_and(|p| p.starts_with("/dev/disk/by-id/usb-")) {
+            "USB"
+        } else if ["QEMU", "VBOX", "VIRTUAL", "VMWARE"].iter().any(|v| model.contains(v)) {
+            "Virtual"
+        } else if self.kind == "NVMe" {
+            "NVMe"
+        } else if self.kind == "HDD" {
+            "HDD"
+        } else {
+            "SATA SSD"
+        }
+    }
+
+    /// Lists problems that warrant attention without opening the drive's details:
+    /// a failed overall-health check, Critical attributes, a failed self-test or media errors.
+    ///
+    /// # Returns
+    /// One short description per problem; empty if nothing is wrong.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        if self.smart_passed == Some(false) {
+            problems.push("SMART overall-health check FAILED".to_string());
+        }
+
+        let critical = self
+            .smart_attributes
+            .iter()
+            .filter(|a| a.status == AttributeStatus::Critical)
+            .count();
+        if critical > 0 {
+            problems.push(format!("{} critical SMART attribute(s)", critical));
+        }
+
+        if let Some(status) = self.last_self_test.as_deref().filter(|s| self_test_failed(s)) {
+            problems.push(format!("Last self-test: {}", status));
+        }
+
+        if let Some(errors) = self.media_errors.filter(|&e| e > 0) {
+            problems.push(format!("{} media and data integrity error(s)", errors));
+        }
+
+        problems
+    }
+
+    /// Whether the drive is served over the network (NVMe over Fabrics, iSCSI).
+    /// Its media belongs to the remote target, so no local SMART data is expected.
+    pub fn is_network_attached(&self) -> bool {
+        self.network_transport.is_some()
+    }
+
+    /// Whether smartctl returned anything: a SMART state, health, temperature or attributes.
+    /// Card readers, flash sticks and network drives usually report none of these.
+    pub fn has_smart_data(&self) -> bool {
+        self.smart_enabled.is_some() || self.health_percent.is_some() || self.temp_c.is_some() || !self.smart_attributes.is_empty()
+    }
+
+    /// Reallocated, pending and offline uncorrectable sector counts.
+    ///
+    /// # Returns
+    /// None if the drive reports none of the three attributes (e.g., NVMe drives).
+    pub fn critical_sectors(&self) -> Option<CriticalSectors> {
+        let raw = |id: &str| self.attribute(id).and_then(|a| a.raw_numeric());
+        let sectors = CriticalSectors { reallocated: raw("5"), pending: raw("197"), uncorrectable: raw("198") };
+        (sectors != CriticalSectors::default()).then_some(sectors)
+    }
+
+    /// Share of the powered-on time the NVMe controller spent busy with I/O, i.e. how hard the drive
+    /// has been worked over its life independent of how much data moved.
+    ///
+    /// # Returns
+    /// A fraction (0.0-1.0), None without both Controller Busy Time and power-on hours.
+    pub fn busy_fraction(&self) -> Option<f64> {
+        let busy = self.controller_busy_minutes? as f64;
+        let powered_on = self.power_on_hours.filter(|&h| h > 0)? as f64 * 60.0;
+        Some((busy / powered_on).min(1.0))
+    }
+
+    /// Scores the drive from 100 down, taking off weighted points for wear and error counters.
+    ///
+    /// # Arguments
+    /// * `weights` - Points per unit of each signal
+    ///
+    /// # Returns
+    /// The score and its inputs, or None when the drive reports neither wear, error counters
+    /// nor a self-assessment (e.g., a USB bridge that hides SMART).
+    pub fn health_score(&self, weights: &HealthWeights) -> Option<HealthScore> {
+        let sectors = self.critical_sectors().unwrap_or_default();
+        // NVMe drives report the endurance used directly; SATA SSDs through a vendor wear attribute
+        let (wear_label, wear) = match self.health_percent {
+            Some(h) => ("Percentage used", Some(100u8.saturating_sub(h))),
+            None => ("Endurance used", self.ssd_wear().and_then(|w| w.used_percent)),
+        };
+        let count = |weight: &'static str, label: &'static str, count: Option<u64>, per: f64| {
+            count.map(|n| ScoreInput { weight, label, value: n.to_string(), points: n as f64 * per })
+        };
+        let mut inputs: Vec<ScoreInput> = [
+            wear.map(|used| ScoreInput {
+                weight: "wear",
+                label: wear_label,
+                value: format!("{}%", used),
+                points: f64::from(used) * weights.wear,
+            }),
+            count("pending_sector", "Pending sectors", sectors.pending, weights.pending_sector),
+            count("uncorrectable_sector", "Uncorrectable sectors", sectors.uncorrectable, weights.uncorrectable_sector),
+            count("reallocated_sector", "Reallocated sectors", sectors.reallocated, weights.reallocated_sector),
+            count("media_error", "Media errors", self.media_errors, weights.media_error),
+            count("crc_error", "CRC errors", self.udma_crc_errors(), weights.crc_error),
+            self.smart_passed.map(|passed| ScoreInput {
+                weight: "smart_failed",
+                label: "SMART self-assessment",
+                value: if passed { "PASSED" } else { "FAILED" }.to_string(),
+                points: if passed { 0.0 } else { weights.smart_failed },
+            }),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if inputs.is_empty() {
+            return None;
+        }
+
+        // Stable sort: inputs that cost nothing keep their order below the ones that did
+        inputs.sort_by(|a, b| b.points.total_cmp(&a.points));
+        let total: f64 = inputs.iter().map(|i| i.points).sum();
+        Some(HealthScore { score: (100.0 - total).clamp(0.0, 100.0).round() as u8, inputs })
+    }
+
+    /// Average time the drive stays powered on per power cycle, which tells a desktop that is switched off
+    /// every evening apart from an always-on server.
+    ///
+    /// # Returns
+    /// Hours per cycle, None without both power-on hours and a non-zero power cycle count.
+    pub fn hours_per_power_cycle(&self) -> Option<f64> {
+        let cycles = self.power_cycles.filter(|&c| c > 0)?;
+        Some(self.power_on_hours? as f64 / cycles as f64)
+    }
+
+    /// Raw UDMA_CRC_Error_Count (199): transfer errors between the drive and the controller.
+    /// These almost always come from the SATA cable or port, not the drive itself.
+    pub fn udma_crc_errors(&self) -> Option<u64> {
+        self.attribute("199").and_then(|a| a.raw_numeric())
+    }
+
+    /// Normalized Helium_Level (22) of helium-filled HDDs: 100 when sealed, falling as helium leaks out.
+    ///
+    /// # Returns
+    /// (value, failure threshold), or None for drives without the attribute.
+    pub fn helium_level(&self) -> Option<(u8, u8)> {
+        let attr = self.attribute("22")?;
+        Some((attr.current.trim().parse().ok()?, attr.threshold.trim().parse().unwrap_or(0)))
+    }
+
+    /// Decodes vendor SSD wear attributes. Attribute IDs differ between vendors, so they are matched by name.
+    ///
+    /// # Returns
+    /// None if the drive reports none of the known wear or erase count attributes.
+    pub fn ssd_wear(&self) -> Option<SsdWear> {
+        let by_name = |names: &[&str]| names.iter().find_map(|n| self.smart_attributes.iter().find(|a| a.name == *n));
+        let mut wear = SsdWear::default();
+        if let Some(attr) = by_name(WEAR_ATTRIBUTES) {
+            if let Ok(remaining) = attr.current.trim().parse::<u8>() {
+                wear.used_percent = Some(100u8.saturating_sub(remaining));
+                wear.source = Some(attr.name.clone());
+            }
+        }
+        wear.avg_erase_count = by_name(AVG_ERASE_ATTRIBUTES).and_then(|a| a.raw_numeric());
+        wear.max_erase_count = by_name(MAX_ERASE_ATTRIBUTES).and_then(|a| a.raw_numeric());
+        (wear != SsdWear::default()).then_some(wear)
+    }
+
+    /// Temperature extremes from the raw value of Temperature_Celsius (194), or Airflow_Temperature_Cel (190)
+    /// when 194 has none. smartctl prints them as "34 (Min/Max 19/55)".
+    ///
+    /// # Returns
+    /// None if neither attribute carries a Min/Max pair (e.g., NVMe drives).
+    pub fn temperature_range(&self) -> Option<TemperatureRange> {
+        [("194", false), ("190", true)].into_iter().find_map(|(id, since_power_on)| {
+            let raw = &self.attribute(id)?.raw_value;
+            // Format: "34 (Min/Max 19/55)", sometimes followed by an over-limit count ("19/55 #12)")
+            let pair = raw.split("Min/Max ").nth(1)?.split([' ', ')']).next()?;
+            let (min, max) = pair.split_once('/')?;
+            let (min, max) = (min.parse::<i32>().ok()?, max.parse::<i32>().ok()?);
+            Some(TemperatureRange { min: (min != 0).then_some(min), max, since_power_on })
+        })
+    }
+
+    /// Looks up a SMART attribute by its ID (e.g., "193" for Load_Cycle_Count).
+    pub fn attribute(&self, id: &str) -> Option<&SmartAttribute> {
+        self.smart_attributes.iter().find(|a| a.id == id)
+    }
 }
\ No newline at end of file
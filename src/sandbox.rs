@@ -0,0 +1,52 @@
+// Running host tools from inside a Flatpak sandbox, where smartctl, nvme and hdparm are not installed
+// and the drives can only be queried through the host
+
+// Sandbox detection runs once
+use once_cell::sync::Lazy;
+// Program names and paths
+use std::ffi::OsStr;
+// Sandbox marker file
+use std::path::Path;
+// Commands built for the host or the sandbox
+use std::process::Command;
+
+/// Whether this process runs inside Flatpak, which writes `/.flatpak-info` into every sandbox
+static IN_FLATPAK: Lazy<bool> = Lazy::new(|| Path::new("/.flatpak-info").exists());
+
+/// Whether the app runs inside a Flatpak sandbox.
+pub fn in_flatpak() -> bool {
+    *IN_FLATPAK
+}
+
+/// Creates a Command for a tool that has to run on the host: the tool itself outside a sandbox,
+/// `flatpak-spawn --host` inside Flatpak (see `flatpak_host_command`).
+///
+/// # Arguments
+/// * `program` - Executable name (e.g., "smartctl")
+/// * `env` - Environment variables the tool needs
+pub fn host_command(program: impl AsRef<OsStr>, env: &[(&str, &str)]) -> Command {
+    if in_flatpak() {
+        return flatpak_host_command(program, env);
+    }
+    let mut cmd = Command::new(program);
+    cmd.envs(env.iter().copied());
+    cmd
+}
+
+/// Wraps a tool in `flatpak-spawn --host` so it runs outside the sandbox.
+/// flatpak-spawn does not forward the caller's environment, so variables are passed as `--env=` options;
+/// arguments added to the returned Command go to the tool.
+/// Needs the `--talk-name=org.freedesktop.Flatpak` permission.
+///
+/// # Arguments
+/// * `program` - Executable name on the host (e.g., "smartctl")
+/// * `env` - Environment variables the tool needs
+pub fn flatpak_host_command(program: impl AsRef<OsStr>, env: &[(&str, &str)]) -> Command {
+    let mut cmd = Command::new("flatpak-spawn");
+    cmd.arg("--host");
+    for (name, value) in env {
+        cmd.arg(format!("--env={}={}", name, value));
+    }
+    cmd.arg(program);
+    cmd
+}
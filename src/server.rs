@@ -0,0 +1,223 @@
+// Local REST API: a small HTTP server exposing the latest scan as JSON
+
+// Disk scanning and JSON reports
+use crate::gui::disk_scanner::scan_disks;
+use crate::gui::report::{disk_to_json, Report};
+// JSON response bodies
+use crate::json::Json;
+// Reading requests and writing responses
+use std::io::{Read, Write};
+// Listening socket, and the address a connection arrived on for the Host check
+use std::net::{IpAddr, TcpListener, TcpStream};
+// Connections being answered
+use std::sync::atomic::{AtomicUsize, Ordering};
+// Latest scan shared between the scanner thread and the server
+use std::sync::{Arc, Mutex};
+// Scan interval, socket timeouts and the per-connection deadline
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Default listen address; loopback only, since responses include serial numbers
+pub const DEFAULT_LISTEN: &str = "127.0.0.1:8190";
+
+/// Largest request head accepted, bodies are never read
+const MAX_REQUEST_BYTES: usize = 8192;
+
+/// Time a client gets to send its whole request before the connection is dropped
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Connections answered at the same time; further ones are closed right away
+const MAX_CLIENTS: usize = 32;
+
+/// Latest scan result: the report, or the error of the last scan (also used before the first scan finishes).
+pub type SharedReport = Arc<Mutex<Result<Report, String>>>;
+
+/// Starts a background thread that rescans the drives every `interval`.
+///
+/// # Arguments
+/// * `host` - Host name stored in the reports
+/// * `interval` - Time between scans
+///
+/// # Returns
+/// The shared latest scan, updated in place by the thread.
+pub fn spawn_scanner(host: String, interval: Duration) -> SharedReport {
+    let latest: SharedReport = Arc::new(Mutex::new(Err("First scan still running".to_string())));
+    let shared = Arc::clone(&latest);
+    std::thread::spawn(move || loop {
+        let result = scan_disks().map(|drives| Report {
+            host: host.clone(),
+            generated_at: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+            drives,
+        });
+        // Keep serving the previous report if a rescan fails
+        if let Ok(mut slot) = shared.lock() {
+            if result.is_ok() || slot.is_err() {
+                *slot = result;
+            }
+        }
+        std::thread::sleep(interval);
+    });
+    latest
+}
+
+/// Answers requests on `listener` until the process exits, each connection on its own thread
+/// (at most `MAX_CLIENTS` at a time), so a slow client cannot hold up the others.
+///
+/// # Arguments
+/// * `listener` - Bound listening socket
+/// * `latest` - Scan results to serve
+pub fn serve(listener: TcpListener, latest: SharedReport) {
+    let active = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming().flatten() {
+        if active.fetch_add(1, Ordering::SeqCst) >= MAX_CLIENTS {
+            active.fetch_sub(1, Ordering::SeqCst);
+            continue;
+        }
+        let (latest, active) = (Arc::clone(&latest), Arc::clone(&active));
+        std::thread::spawn(move || {
+            // A misbehaving client only loses its own connection
+            let _ = handle_connection(stream, &latest);
+            active.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+}
+
+/// Reads one request and writes the response. The whole request has to arrive within `CLIENT_TIMEOUT`.
+fn handle_connection(mut stream: TcpStream, latest: &SharedReport) -> std::io::Result<()> {
+    let deadline = Instant::now() + CLIENT_TIMEOUT;
+    stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") && head.len() < MAX_REQUEST_BYTES {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(std::io::ErrorKind::TimedOut.into());
+        }
+        stream.set_read_timeout(Some(remaining))?;
+        let n = stream.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+    let head = String::from_utf8_lossy(&head);
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let (method, target) = (request_line.next().unwrap_or_default(), request_line.next().unwrap_or_default());
+    let host = head
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("host"))
+        .map(|(_, value)| value.trim());
+
+    let (status, body) = if !host_allowed(host, stream.local_addr()?.ip()) {
+        (403, error_body("Host not allowed; connect by IP address or localhost"))
+    } else {
+        match latest.lock() {
+            Ok(report) => route(method, target, report.as_ref()),
+            Err(_) => (500, error_body("Scanner state unavailable")),
+        }
+    };
+    let reason = match status {
+        200 => "OK",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    };
+    let body = body.to_pretty() + "\n";
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )
+}
+
+/// Maps a request to a status code and JSON body.
+///
+/// Endpoints:
+/// * `GET /drives` - the whole scan as a report document (same format as exported reports)
+/// * `GET /drives/{serial}` - one drive, looked up by serial number
+///
+/// # Arguments
+/// * `method` - HTTP method
+/// * `target` - Request target, query string allowed and ignored
+/// * `latest` - Latest scan result
+pub fn route(method: &str, target: &str, latest: Result<&Report, &String>) -> (u16, Json) {
+    let path = target.split('?').next().unwrap_or_default().trim_end_matches('/');
+    let serial = match path.strip_prefix("/drives") {
+        Some("") => None,
+        Some(rest) if rest.starts_with('/') => Some(percent_decode(&rest[1..])),
+        _ => return (404, error_body("Unknown endpoint, use /drives or /drives/{serial}")),
+    };
+    if method != "GET" {
+        return (405, error_body("Only GET is supported"));
+    }
+    let report = match latest {
+        Ok(report) => report,
+        Err(e) => return (503, error_body(e)),
+    };
+
+    match serial {
+        None => (200, report.to_json()),
+        Some(serial) => match report.drives.iter().find(|d| d.serial.as_deref() == Some(serial.as_str())) {
+            Some(di) => (200, disk_to_json(di)),
+            None => (404, error_body(&format!("No drive with serial {}", serial))),
+        },
+    }
+}
+
+/// Whether a request's Host header names this server directly: localhost or the address the connection
+/// arrived on. Other names are refused so a web page cannot read the drives through DNS rebinding.
+///
+/// # Arguments
+/// * `host` - Value of the Host header ("localhost:8190", "[::1]:8190"), None if there was none
+/// * `local` - Address of this end of the connection
+pub fn host_allowed(host: Option<&str>, local: IpAddr) -> bool {
+    let Some(host) = host else {
+        return false;
+    };
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or_default(),
+        None => host.rsplit_once(':').map_or(host, |(name, _)| name),
+    };
+    if name.eq_ignore_ascii_case("localhost") {
+        return true;
+    }
+    // An IPv4 client on a dual-stack socket shows up as ::ffff:a.b.c.d
+    let canonical = |ip: IpAddr| match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+        v4 => v4,
+    };
+    name.parse::<IpAddr>().is_ok_and(|ip| canonical(ip) == canonical(local))
+}
+
+/// Error response body: `{"error": "..."}`.
+fn error_body(message: &str) -> Json {
+    Json::Object(vec![("error".into(), message.into())])
+}
+
+/// Decodes %XX escapes in a path segment (serials may contain spaces or slashes).
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|h| u8::from_str_radix(std::str::from_utf8(h).ok()?, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                out.push(byte);
+                i += 3;
+            }
+            (b, _) => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
@@ -0,0 +1,270 @@
+// Terminal interface for SSH sessions: drive list on the left, the selected drive's report on the right
+
+// Scanner and text report shared with the CLI
+use crate::cli::{format_drive_report, status_word};
+use crate::gui::disk_scanner::scan_disks;
+// Keeps the selection on the same drive across rescans
+use crate::gui::history::drive_key;
+// Import data models for disk information
+use crate::models::DiskInfo;
+// Raw keyboard input without echo
+use nix::sys::termios::{cfmakeraw, tcgetattr, tcsetattr, SetArg, SpecialCharacterIndices, Termios};
+// Terminal size
+use nix::pty::Winsize;
+// Terminal output and key reads
+use std::io::{Read, Write};
+use std::os::fd::AsRawFd;
+// Refresh timing
+use std::time::{Duration, Instant};
+
+/// How often the drive list is rescanned, matching the GUI
+const REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Width of the drive list column, in characters
+const SIDEBAR_WIDTH: usize = 24;
+
+/// A key press the interface reacts to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Key {
+    /// Select the previous drive (↑ or k)
+    Up,
+    /// Select the next drive (↓ or j)
+    Down,
+    /// Scroll the report up (PgUp)
+    PageUp,
+    /// Scroll the report down (PgDn or space)
+    PageDown,
+    /// Rescan now (r)
+    Refresh,
+    /// Leave the interface (q or Ctrl-C)
+    Quit,
+}
+
+/// Decodes raw terminal input into keys; anything unrecognized is ignored.
+///
+/// # Arguments
+/// * `input` - Bytes read from the terminal in raw mode
+pub fn parse_keys(input: &[u8]) -> Vec<Key> {
+    let mut keys = Vec::new();
+    let mut i = 0;
+    while i < input.len() {
+        // Escape sequences for the arrow and page keys
+        let sequences: [(&[u8], Key); 4] = [
+            (b"\x1b[A", Key::Up),
+            (b"\x1b[B", Key::Down),
+            (b"\x1b[5~", Key::PageUp),
+            (b"\x1b[6~", Key::PageDown),
+        ];
+        if let Some((seq, key)) = sequences.iter().find(|(seq, _)| input[i..].starts_with(seq)) {
+            keys.push(*key);
+            i += seq.len();
+            continue;
+        }
+        match input[i] {
+            b'k' => keys.push(Key::Up),
+            b'j' => keys.push(Key::Down),
+            b' ' => keys.push(Key::PageDown),
+            b'r' => keys.push(Key::Refresh),
+            b'q' | 0x03 => keys.push(Key::Quit),
+            _ => {}
+        }
+        i += 1;
+    }
+    keys
+}
+
+/// Pads or cuts a string to exactly `width` characters.
+fn fit(text: &str, width: usize) -> String {
+    let mut out: String = text.chars().take(width).collect();
+    let len = out.chars().count();
+    out.extend(std::iter::repeat_n(' ', width - len));
+    out
+}
+
+/// Lays out one screen: a title line, the drive list beside the selected drive's report, and a key help line.
+///
+/// # Arguments
+/// * `drives` - Scanned drives
+/// * `selected` - Index of the selected drive
+/// * `scroll` - Report lines scrolled past
+/// * `width` - Terminal width in characters
+/// * `height` - Terminal height in lines
+/// * `status` - Text for the right end of the help line (data age, scan errors)
+///
+/// # Returns
+/// `height` lines; the selected drive is highlighted with reverse video and failing drives are red.
+pub fn render(drives: &[DiskInfo], selected: usize, scroll: usize, width: usize, height: usize, status: &str) -> Vec<String> {
+    let width = width.max(SIDEBAR_WIDTH + 20);
+    let height = height.max(4);
+    let detail_width = width - SIDEBAR_WIDTH - 3;
+    let body = height - 2;
+
+    // Two sidebar lines per drive: device and status, then health and temperature
+    let mut sidebar = Vec::new();
+    for (i, di) in drives.iter().enumerate() {
+        let status = status_word(di);
+        let first = fit(&format!(" {} {:>w$}", di.dev, status, w = SIDEBAR_WIDTH.saturating_sub(di.dev.len() + 2)), SIDEBAR_WIDTH);
+        let second = fit(
+            &format!(
+                "   {} {}",
+                di.health_percent.map(|h| format!("{}%", h)).unwrap_or("--".into()),
+                di.temp_c.map(|t| format!("{}°C", t)).unwrap_or("--".into())
+            ),
+            SIDEBAR_WIDTH,
+        );
+        let style = match (i == selected, status) {
+            (true, _) => "\x1b[7m",
+            (false, "FAILING") => "\x1b[31m",
+            (false, "WARNING") => "\x1b[33m",
+            _ => "",
+        };
+        for line in [first, second] {
+            sidebar.push(if style.is_empty() { line } else { format!("{}{}\x1b[0m", style, line) });
+        }
+    }
+    if drives.is_empty() {
+        sidebar.push(fit(" No drives found", SIDEBAR_WIDTH));
+    }
+
+    let report = drives.get(selected).map(format_drive_report).unwrap_or_default();
+    let detail: Vec<&str> = report.lines().skip(scroll).collect();
+
+    let mut lines = Vec::with_capacity(height);
+    lines.push(format!("\x1b[1m{}\x1b[0m", fit(&format!(" SSD Health Checker — {} drive(s)", drives.len()), width)));
+    for row in 0..body {
+        let left = sidebar.get(row).cloned().unwrap_or_else(|| fit("", SIDEBAR_WIDTH));
+        let right = fit(detail.get(row).copied().unwrap_or(""), detail_width);
+        lines.push(format!("{} │ {}", left, right));
+    }
+    let help = " ↑↓ select  PgUp/PgDn scroll  r refresh  q quit";
+    let gap = width.saturating_sub(help.chars().count() + status.chars().count() + 1);
+    lines.push(fit(&format!("{}{}{} ", help, " ".repeat(gap), status), width));
+    lines
+}
+
+/// Puts the terminal into raw mode on the alternate screen and restores it when dropped.
+struct RawTerminal {
+    /// Settings to restore on exit
+    original: Termios,
+}
+
+impl RawTerminal {
+    /// Switches stdin to raw mode with reads that time out after 0.2 s, so the loop can refresh.
+    fn enter() -> Result<Self, String> {
+        let stdin = std::io::stdin();
+        let original = tcgetattr(&stdin).map_err(|e| format!("The terminal interface needs a terminal: {}", e))?;
+        let mut raw = original.clone();
+        cfmakeraw(&mut raw);
+        raw.control_chars[SpecialCharacterIndices::VMIN as usize] = 0;
+        raw.control_chars[SpecialCharacterIndices::VTIME as usize] = 2;
+        tcsetattr(&stdin, SetArg::TCSANOW, &raw).map_err(|e| format!("Cannot set up the terminal: {}", e))?;
+        // Alternate screen, hidden cursor
+        print!("\x1b[?1049h\x1b[?25l");
+        Ok(Self { original })
+    }
+}
+
+impl Drop for RawTerminal {
+    /// Restores the terminal settings, cursor and main screen.
+    fn drop(&mut self) {
+        let _ = tcsetattr(std::io::stdin(), SetArg::TCSANOW, &self.original);
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = std::io::stdout().flush();
+    }
+}
+
+nix::ioctl_read_bad!(
+    /// Reads the terminal's window size (TIOCGWINSZ).
+    ///
+    /// # Safety
+    /// `data` must point to a writable `Winsize`.
+    window_size,
+    nix::libc::TIOCGWINSZ,
+    Winsize
+);
+
+/// Current terminal size as (columns, lines); 80x24 if unknown.
+fn terminal_size() -> (usize, usize) {
+    let mut size = Winsize { ws_row: 0, ws_col: 0, ws_xpixel: 0, ws_ypixel: 0 };
+    // SAFETY: `size` is a valid Winsize for the kernel to fill in
+    match unsafe { window_size(std::io::stdin().as_raw_fd(), &mut size) } {
+        Ok(_) if size.ws_col > 0 && size.ws_row > 0 => (size.ws_col as usize, size.ws_row as usize),
+        _ => (80, 24),
+    }
+}
+
+/// Identifies a drive across rescans: model and serial, or the device path for drives without a serial.
+fn drive_identity(di: &DiskInfo) -> String {
+    drive_key(di).unwrap_or_else(|| di.dev.clone())
+}
+
+/// Runs the terminal interface until the user quits.
+///
+/// # Returns
+/// The process exit code: 0 on a normal exit, 1 if the terminal cannot be set up.
+pub fn run() -> i32 {
+    let terminal = match RawTerminal::enter() {
+        Ok(terminal) => terminal,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return 1;
+        }
+    };
+
+    let mut drives = Vec::new();
+    let mut error = None;
+    let mut selected = 0;
+    let mut scroll = 0;
+    let mut last_scan: Option<Instant> = None;
+    let mut stdout = std::io::stdout();
+    let mut input = [0u8; 64];
+
+    loop {
+        // Rescan on schedule, keeping the same drive selected when it is still present
+        if last_scan.is_none_or(|t| t.elapsed() >= REFRESH_INTERVAL) {
+            let previous = drives.get(selected).map(drive_identity);
+            match scan_disks() {
+                Ok(list) => {
+                    drives = list;
+                    error = None;
+                }
+                Err(e) => error = Some(e),
+            }
+            selected = previous.and_then(|id| drives.iter().position(|d| drive_identity(d) == id)).unwrap_or(0);
+            last_scan = Some(Instant::now());
+        }
+
+        let (width, height) = terminal_size();
+        let status = match &error {
+            Some(e) => format!("Scan failed: {}", e),
+            None => format!("Updated {}s ago", last_scan.map(|t| t.elapsed().as_secs()).unwrap_or(0)),
+        };
+        let screen = render(&drives, selected, scroll, width, height, &status);
+        let _ = write!(stdout, "\x1b[H{}", screen.join("\r\n"));
+        let _ = stdout.flush();
+
+        let n = std::io::stdin().read(&mut input).unwrap_or(0);
+        for key in parse_keys(&input[..n]) {
+            match key {
+                Key::Up => {
+                    selected = selected.saturating_sub(1);
+                    scroll = 0;
+                }
+                Key::Down => {
+                    selected = (selected + 1).min(drives.len().saturating_sub(1));
+                    scroll = 0;
+                }
+                Key::PageUp => scroll = scroll.saturating_sub(height / 2),
+                Key::PageDown => {
+                    let lines = drives.get(selected).map(|d| format_drive_report(d).lines().count()).unwrap_or(0);
+                    scroll = (scroll + height / 2).min(lines.saturating_sub(1));
+                }
+                Key::Refresh => last_scan = None,
+                Key::Quit => {
+                    drop(terminal);
+                    return 0;
+                }
+            }
+        }
+    }
+}
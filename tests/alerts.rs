@@ -0,0 +1,236 @@
+// Tests for alert rule parsing, evaluation and notification channels.
+
+mod common;
+
+use common::parse_fixture;
+use ssd_info_cli::gui::alerts::{
+    alarm_rules, drive_rules, portal_notification, Alert, AlertEngine, AlertLog, AlertRule, NotificationChannel, NotificationSchedule, NotificationThrottle,
+    Severity, DEFAULT_ALARM_SOUND,
+};
+use ssd_info_cli::gui::history::drive_key;
+use ssd_info_cli::gui::settings::Settings;
+use ssd_info_cli::models::DiskInfo;
+use zbus::zvariant::Value;
+
+#[test]
+fn rule_parsing_and_validation() {
+    let rule = AlertRule::parse("hot", "temp_c >= 65").unwrap();
+    assert_eq!(rule.severity, Severity::Warning);
+    assert_eq!(rule.condition, "temp_c >= 65");
+    let rule = AlertRule::parse("pending", "critical: attribute(197).raw > 0").unwrap();
+    assert_eq!(rule.severity, Severity::Critical);
+
+    assert!(AlertRule::parse("x", "temp_c 65").unwrap_err().contains("comparison"));
+    assert!(AlertRule::parse("x", "temp > 65").unwrap_err().contains("unknown field 'temp'"));
+    assert!(AlertRule::parse("x", "temp_c > hot").unwrap_err().contains("not a number"));
+    assert!(AlertRule::parse("x", "attribute(abc).raw > 0").is_err());
+    assert!(AlertRule::parse("x", "attribute(5).rawest > 0").is_err());
+}
+
+#[test]
+fn rules_fire_once_per_condition() {
+    let failing = parse_fixture("/dev/sda", "hdd_toshiba_failing", "HDD");
+    let rules = vec![
+        AlertRule::parse("pending", "critical: attribute(197).raw > 0").unwrap(),
+        AlertRule::parse("cold", "temp_c < 0").unwrap(),
+    ];
+    assert!(rules[0].matches(&failing).is_some());
+    assert_eq!(rules[1].matches(&failing), None);
+
+    let mut engine = AlertEngine::default();
+    let fired = engine.check(&rules, [&failing], 100);
+    assert_eq!(fired.len(), 1);
+    assert_eq!(fired[0].rule, "pending");
+    assert!(fired[0].message.contains("pending — attribute(197).raw > 0"));
+
+    // Still matching: no repeat; cleared then matching again: fires again
+    assert!(engine.check(&rules, [&failing], 200).is_empty());
+    let mut fixed = failing.clone();
+    fixed.smart_attributes.retain(|a| a.id != "197");
+    assert!(engine.check(&rules, [&fixed], 300).is_empty());
+    assert_eq!(engine.check(&rules, [&failing], 400).len(), 1);
+}
+
+#[test]
+fn drive_temperature_alarm_replaces_global_temp_rules() {
+    let mut hdd = parse_fixture("/dev/sda", "hdd_toshiba_failing", "HDD");
+    hdd.temp_c = Some(58);
+    let mut nvme = parse_fixture("/dev/sda", "nvme_wd_sn850x", "NVMe");
+    nvme.dev = "/dev/nvme0n1".to_string();
+    nvme.temp_c = Some(70);
+
+    let mut settings = Settings::parse("[alerts]\nhot = temp_c > 65\npending = attribute(197).raw > 0\n");
+    settings.drives.entry(drive_key(&hdd).unwrap()).or_default().temp_alarm = Some(55);
+    settings.drives.entry(drive_key(&nvme).unwrap()).or_default().temp_alarm = Some(75);
+    assert_eq!(settings.temp_alarm(&hdd), Some(55));
+    assert_eq!(Settings::parse(&settings.to_text()).temp_alarm(&nvme), Some(75));
+
+    let rules: Vec<AlertRule> = settings.alert_rules().into_iter().flatten().collect();
+    let hdd_rules = drive_rules(&rules, settings.temp_alarm(&hdd));
+    let names: Vec<&str> = hdd_rules.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, ["pending", "temp_alarm"]);
+    assert_eq!(drive_rules(&rules, None), rules);
+
+    // 58°C trips the HDD's 55°C alarm; 70°C is under the NVMe's 75°C even though the global rule says 65
+    let mut engine = AlertEngine::default();
+    let checked = |hdd: &DiskInfo, nvme: &DiskInfo| {
+        vec![(hdd.clone(), drive_rules(&rules, settings.temp_alarm(hdd))), (nvme.clone(), drive_rules(&rules, settings.temp_alarm(nvme)))]
+    };
+    let drives = checked(&hdd, &nvme);
+    let fired = engine.check_each(drives.iter().map(|(d, r)| (d, r.clone())), 100);
+    let fired: Vec<(&str, &str)> = fired.iter().map(|a| (a.device.as_str(), a.rule.as_str())).collect();
+    assert_eq!(fired, [("/dev/sda", "pending"), ("/dev/sda", "temp_alarm")]);
+
+    nvme.temp_c = Some(76);
+    let drives = checked(&hdd, &nvme);
+    let fired = engine.check_each(drives.iter().map(|(d, r)| (d, r.clone())), 200);
+    assert_eq!(fired.len(), 1);
+    assert!(fired[0].message.contains("temp_alarm — temp_c >= 75 (now 76)"));
+}
+
+#[test]
+fn alert_settings_round_trip() {
+    let text = "[alerts]\nhot = temp_c > 65\nbroken = temp_c ~ 3\n\n[notifications]\ndesktop = false\ncommand = logger -t ssd\nwebhook = http://hooks.lan/ssd\n\n[Drive-A]\ntbw = 600\n";
+    let settings = Settings::parse(text);
+    assert_eq!(settings.alerts.len(), 2);
+    let rules = settings.alert_rules();
+    assert!(rules[0].is_ok());
+    assert!(rules[1].as_ref().unwrap_err().starts_with("broken: "));
+    assert_eq!(
+        settings.channels(),
+        vec![
+            NotificationChannel::Command("logger -t ssd".into()),
+            NotificationChannel::Webhook("http://hooks.lan/ssd".into())
+        ]
+    );
+    assert_eq!(settings.drives.len(), 1);
+
+    // Invalid rules are kept so a save from the GUI does not lose them
+    let reparsed = Settings::parse(&settings.to_text());
+    assert_eq!(reparsed.alerts, settings.alerts);
+    assert_eq!(reparsed.notifications, settings.notifications);
+
+    // Without a [notifications] section alerts go to the desktop; with only "desktop = false" nowhere
+    assert_eq!(Settings::parse("").channels(), vec![NotificationChannel::Desktop]);
+    let off = Settings::parse("[notifications]\ndesktop = false\n");
+    assert!(off.channels().is_empty());
+    assert!(Settings::parse(&off.to_text()).channels().is_empty());
+}
+
+#[test]
+fn command_channel_gets_alert_environment() {
+    let out = std::env::temp_dir().join(format!("ssd_info_alert_{}.txt", std::process::id()));
+    let channel = NotificationChannel::Command(format!(
+        "echo \"$SSD_ALERT_SEVERITY $SSD_ALERT_RULE $SSD_ALERT_DEVICE $SSD_ALERT_VALUE\" > {}",
+        out.display()
+    ));
+    let rule = AlertRule::parse("hot", "critical: temp_c > 30").unwrap();
+    let drive = parse_fixture("/dev/sda", "nvme_wd_sn850x", "NVMe");
+    channel.send(&rule.alert(&drive, 38.0, 1)).unwrap();
+    assert_eq!(std::fs::read_to_string(&out).unwrap(), "critical hot /dev/sda 38\n");
+    std::fs::remove_file(&out).unwrap();
+}
+
+#[test]
+fn alert_log_acknowledge_and_snooze() {
+    let path = std::env::temp_dir().join(format!("ssd_info_alerts_{}.log", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let rule = AlertRule::parse("hot", "temp_c > 30").unwrap();
+    let drive = parse_fixture("/dev/sda", "nvme_wd_sn850x", "NVMe");
+
+    let mut log = AlertLog::open(Some(path.clone()));
+    assert!(log.record(rule.alert(&drive, 38.0, 100)).unwrap());
+    assert_eq!(log.unacknowledged(), 1);
+    log.acknowledge(0).unwrap();
+    assert_eq!(log.unacknowledged(), 0);
+
+    // A snoozed rule/drive pair is logged already acknowledged and not notified until the snooze ends
+    assert!(log.record(rule.alert(&drive, 39.0, 200)).unwrap());
+    log.snooze(1, 1000).unwrap();
+    assert!(!log.record(rule.alert(&drive, 40.0, 500)).unwrap());
+    assert_eq!(log.unacknowledged(), 0);
+    assert!(log.record(rule.alert(&drive, 41.0, 1000)).unwrap());
+
+    // State survives a reload
+    let reloaded = AlertLog::open(Some(path.clone()));
+    assert_eq!(reloaded.entries, log.entries);
+    assert_eq!(reloaded.entries[1].snoozed_until, Some(1000));
+    assert_eq!(reloaded.entries[2].alert.value, 40.0);
+    assert_eq!(reloaded.unacknowledged(), 1);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn quiet_hours_and_rate_limit() {
+    let settings = Settings::parse("[notifications]\nquiet_hours = 22:00-07:30\nrate_limit = 6h\n");
+    let schedule = &settings.schedule;
+    assert_eq!(schedule.quiet_hours, Some((22 * 60, 7 * 60 + 30)));
+    assert_eq!(schedule.rate_limit_secs, 6 * 3600);
+    // Schedule entries alone keep the default desktop notifications, and survive a save
+    assert_eq!(settings.channels(), vec![NotificationChannel::Desktop]);
+    assert_eq!(Settings::parse(&settings.to_text()).schedule, *schedule);
+
+    assert!(schedule.is_quiet(23 * 60));
+    assert!(schedule.is_quiet(60));
+    assert!(!schedule.is_quiet(7 * 60 + 30));
+    assert!(!schedule.is_quiet(12 * 60));
+    assert!(NotificationSchedule { quiet_hours: Some((60, 120)), rate_limit_secs: 0 }.is_quiet(90));
+    assert!(settings.schedule_errors.is_empty());
+
+    // Malformed entries are reported and leave the defaults
+    let malformed = Settings::parse("[notifications]\nquiet_hours = 22-7\nrate_limit = 6 hours\n");
+    assert_eq!(malformed.schedule, NotificationSchedule::default());
+    assert_eq!(malformed.schedule_errors.len(), 2);
+    assert!(malformed.schedule_errors[0].starts_with("quiet_hours:"));
+    assert!(malformed.schedule_errors[1].starts_with("rate_limit:"));
+
+    let drive = parse_fixture("/dev/sda", "nvme_wd_sn850x", "NVMe");
+    let warning = AlertRule::parse("hot", "temp_c > 30").unwrap();
+    let critical = AlertRule::parse("very_hot", "critical: temp_c > 30").unwrap();
+    let mut throttle = NotificationThrottle::default();
+    // Quiet hours hold back warnings but not critical alerts
+    assert!(!throttle.allow(schedule, &warning.alert(&drive, 38.0, 0), 23 * 60));
+    assert!(throttle.allow(schedule, &critical.alert(&drive, 38.0, 0), 23 * 60));
+    // Repeats of the same condition wait out the rate limit
+    assert!(throttle.allow(schedule, &warning.alert(&drive, 38.0, 100), 12 * 60));
+    assert!(!throttle.allow(schedule, &warning.alert(&drive, 38.0, 3600), 12 * 60));
+    assert!(throttle.allow(schedule, &warning.alert(&drive, 38.0, 100 + 6 * 3600), 12 * 60));
+}
+
+#[test]
+fn alarm_sounds_for_failing_drives() {
+    let mut engine = AlertEngine::default();
+    let healthy = parse_fixture("/dev/sda", "nvme_wd_sn850x", "NVMe");
+    assert!(engine.check(&alarm_rules(), [&healthy], 0).is_empty());
+    let mut failing = parse_fixture("/dev/sda", "hdd_toshiba_failing", "HDD");
+    failing.dev = "/dev/sdb".into();
+    let fired = engine.check(&alarm_rules(), [&healthy, &failing], 0);
+    assert!(!fired.is_empty());
+    assert!(fired.iter().all(|a| a.device == "/dev/sdb" && a.severity == Severity::Critical));
+
+    let settings = Settings::parse("[notifications]\nalarm = true\n");
+    assert_eq!(settings.alarm.as_deref(), Some(DEFAULT_ALARM_SOUND));
+    assert_eq!(settings.channels(), vec![NotificationChannel::Desktop]);
+    assert_eq!(Settings::parse(&settings.to_text()).alarm, settings.alarm);
+    let custom = Settings::parse("[notifications]\nalarm = /opt/siren.wav\n");
+    assert_eq!(Settings::parse(&custom.to_text()).alarm.as_deref(), Some("/opt/siren.wav"));
+    assert_eq!(Settings::parse("[notifications]\nalarm = false\n").alarm, None);
+}
+
+#[test]
+fn portal_notification_carries_the_alert_text() {
+    let alert = Alert {
+        timestamp: 0,
+        rule: "smart_failed".into(),
+        severity: Severity::Critical,
+        device: "/dev/sda".into(),
+        drive_key: "Samsung_SSD-S4EV".into(),
+        value: 1.0,
+        message: "/dev/sda: it's \\failing\nreplace it".into(),
+    };
+    let (id, notification) = portal_notification(&alert);
+    assert_eq!(id, "ssd-alert-smart_failed-Samsung_SSD-S4EV");
+    assert_eq!(notification["title"], Value::from("Drive alert: smart_failed"));
+    assert_eq!(notification["body"], Value::from("/dev/sda: it's \\failing\nreplace it"));
+    assert_eq!(notification["priority"], Value::from("urgent"));
+}
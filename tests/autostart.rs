@@ -0,0 +1,28 @@
+// Tests for the start-on-login desktop entry.
+
+use ssd_info_cli::gui::autostart::{desktop_entry, Autostart};
+use std::path::Path;
+
+#[test]
+fn entry_starts_gui_minimized() {
+    let entry = desktop_entry(Path::new("/opt/ssd tools/ssd_info_cli"));
+    assert!(entry.starts_with("[Desktop Entry]\n"));
+    assert!(entry.contains("Type=Application\n"));
+    assert!(entry.contains("Exec=\"/opt/ssd tools/ssd_info_cli\" gui --minimized\n"));
+    assert!(desktop_entry(Path::new("/tmp/$x")).contains("Exec=\"/tmp/\\$x\""));
+}
+
+#[test]
+fn enable_and_disable_install_and_remove_the_entry() {
+    let dir = std::env::temp_dir().join(format!("ssd_info_autostart_{}", std::process::id()));
+    let autostart = Autostart::new(&dir);
+    assert!(!autostart.is_enabled());
+
+    autostart.set_enabled(true, Path::new("/usr/bin/ssd_info_cli")).unwrap();
+    assert!(autostart.is_enabled());
+    autostart.set_enabled(false, Path::new("/usr/bin/ssd_info_cli")).unwrap();
+    assert!(!autostart.is_enabled());
+    // Removing an entry that is not there is not an error
+    autostart.set_enabled(false, Path::new("/usr/bin/ssd_info_cli")).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,51 @@
+// Tests for stored benchmark history and the comparison against the first run.
+
+use ssd_info_cli::gui::benchmark::{change_since_first, parse_benchmarks, run_benchmark, BenchmarkResult, BenchmarkStore};
+
+fn result(timestamp: u64, seq: f64, iops: f64) -> BenchmarkResult {
+    BenchmarkResult { timestamp, seq_read_mb_per_sec: seq, random_read_iops: iops }
+}
+
+#[test]
+fn benchmark_store_round_trip() {
+    let dir = std::env::temp_dir().join(format!("ssd_info_benchmarks_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let store = BenchmarkStore::new(&dir);
+
+    assert!(store.load("drive").is_empty());
+    store.record("drive", &result(1_700_000_000, 3500.25, 18_000.4)).unwrap();
+    store.record("drive", &result(1_710_000_000, 2100.0, 17_500.0)).unwrap();
+    let loaded = store.load("drive");
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(loaded, [result(1_700_000_000, 3500.2, 18_000.0), result(1_710_000_000, 2100.0, 17_500.0)]);
+}
+
+#[test]
+fn parse_benchmarks_skips_malformed_lines() {
+    let text = "1700000000,520.5,9000\ngarbage\n1700086400,,9000\n1700172800,510,8800\n";
+    assert_eq!(parse_benchmarks(text), [result(1_700_000_000, 520.5, 9000.0), result(1_700_172_800, 510.0, 8800.0)]);
+}
+
+#[test]
+fn change_since_first_compares_latest_with_first_run() {
+    assert_eq!(change_since_first(&[result(1, 500.0, 10_000.0)]), None);
+
+    let runs = [result(1, 500.0, 10_000.0), result(2, 600.0, 9000.0), result(3, 400.0, 11_000.0)];
+    let (seq, random) = change_since_first(&runs).unwrap();
+    assert!((seq + 20.0).abs() < 1e-9);
+    assert!((random - 10.0).abs() < 1e-9);
+}
+
+#[test]
+fn benchmark_refuses_tiny_or_buffered_devices() {
+    let dir = std::env::temp_dir().join(format!("ssd_info_benchmark_tiny_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("tiny.img");
+    std::fs::write(&path, vec![0u8; 4096]).unwrap();
+    // Smaller than one sequential request; filesystems without O_DIRECT (tmpfs) refuse it before that
+    let err = run_benchmark(path.to_str().unwrap(), 1).unwrap_err();
+    assert!(err.contains("too small to benchmark") || err.contains("direct I/O"), "{}", err);
+    assert!(run_benchmark(dir.join("missing").to_str().unwrap(), 1).unwrap_err().contains("direct I/O"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,51 @@
+// Tests for cutting charts out of window screenshots and saving them as PNG.
+
+use eframe::egui::{pos2, Color32, ColorImage, Rect};
+use ssd_info_cli::gui::chart_export::{crop_chart, default_chart_path, save_png};
+
+#[test]
+fn chart_is_cut_out_at_the_display_scale() {
+    // 100×60 point window at 2× scale, with the chart's 20×10 point rectangle painted red
+    let mut screenshot = ColorImage::new([200, 120], Color32::WHITE);
+    for y in 40..60 {
+        for x in 60..100 {
+            screenshot.pixels[y * 200 + x] = Color32::RED;
+        }
+    }
+    let chart = Rect::from_min_max(pos2(30.0, 20.0), pos2(50.0, 30.0));
+    let cropped = crop_chart(&screenshot, chart, 2.0).unwrap();
+    assert_eq!(cropped.size, [40, 20]);
+    assert!(cropped.pixels.iter().all(|p| *p == Color32::RED));
+
+    // Partly scrolled out of view: only the visible part; fully out of view: nothing
+    let below = Rect::from_min_max(pos2(10.0, 50.0), pos2(90.0, 80.0));
+    assert_eq!(crop_chart(&screenshot, below, 2.0).unwrap().size, [160, 20]);
+    assert!(crop_chart(&screenshot, below.translate(eframe::egui::vec2(0.0, 100.0)), 2.0).is_none());
+}
+
+#[test]
+fn chart_is_saved_as_png() {
+    let dir = std::env::temp_dir().join(format!("ssd_info_chart_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("chart.png");
+
+    let mut chart = ColorImage::new([3, 2], Color32::WHITE);
+    chart.pixels[4] = Color32::from_rgb(59, 130, 246);
+    save_png(&chart, &path).unwrap();
+
+    let png = image::open(&path).unwrap().to_rgba8();
+    assert_eq!(png.dimensions(), (3, 2));
+    assert_eq!(png.get_pixel(1, 1).0, [59, 130, 246, 255]);
+    assert_eq!(png.get_pixel(0, 0).0, [255, 255, 255, 255]);
+
+    assert!(save_png(&chart, &dir.join("missing/chart.png")).unwrap_err().contains("missing/chart.png"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn chart_file_names_are_safe() {
+    let path = default_chart_path("nvme0n1-temperature", 1_700_000_000);
+    assert!(path.ends_with("ssd-chart-nvme0n1-temperature-1700000000.png"));
+    let path = default_chart_path("mapper/root vol", 1);
+    assert!(path.ends_with("ssd-chart-mapper_root_vol-1.png"));
+}
@@ -0,0 +1,137 @@
+// Tests for command-line parsing and the text output of the CLI subcommands.
+
+mod common;
+
+use common::parse_fixture;
+use ssd_info_cli::cli::{
+    check_status, format_drive_report, format_scan_table, format_status_line, parse_args, usage_exit_code, CheckThresholds,
+    Command,
+};
+use ssd_info_cli::metrics::{MetricsTarget, Sink};
+
+fn args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(str::to_string).collect()
+}
+
+#[test]
+fn parse_subcommands() {
+    assert_eq!(parse_args(&[]), Ok(Command::Gui { minimized: false }));
+    assert_eq!(parse_args(&args("gui --minimized")), Ok(Command::Gui { minimized: true }));
+    assert_eq!(parse_args(&args("scan --json")), Ok(Command::Scan { json: true }));
+    assert_eq!(
+        parse_args(&args("report --device /dev/nvme0n1")),
+        Ok(Command::Report { device: Some("/dev/nvme0n1".into()), json: false })
+    );
+    assert_eq!(
+        parse_args(&args("test --short --device=/dev/sda")),
+        Ok(Command::Test { device: Some("/dev/sda".into()), kind: "short".into() })
+    );
+    assert_eq!(
+        parse_args(&args("monitor --interval 5 --count 2")),
+        Ok(Command::Monitor { interval_secs: 5, count: Some(2), sinks: vec![] })
+    );
+    assert_eq!(
+        parse_args(&args("monitor")),
+        Ok(Command::Monitor { interval_secs: 60, count: None, sinks: vec![] })
+    );
+    assert_eq!(
+        parse_args(&args("monitor --influx http://localhost:8086/api/v2/write?bucket=disks --influx-token abc")),
+        Ok(Command::Monitor {
+            interval_secs: 60,
+            count: None,
+            sinks: vec![Sink::Influx {
+                target: MetricsTarget::Http("http://localhost:8086/api/v2/write?bucket=disks".into()),
+                token: Some("abc".into()),
+            }],
+        })
+    );
+    assert_eq!(
+        parse_args(&args("monitor --zabbix zabbix.lan --zabbix-host=web1")),
+        Ok(Command::Monitor {
+            interval_secs: 60,
+            count: None,
+            sinks: vec![Sink::Zabbix { server: "zabbix.lan".into(), host: Some("web1".into()) }],
+        })
+    );
+    assert_eq!(
+        parse_args(&args("monitor --csv drives.csv --count 1")),
+        Ok(Command::Monitor { interval_secs: 60, count: Some(1), sinks: vec![Sink::Csv("drives.csv".into())] })
+    );
+    assert_eq!(
+        parse_args(&args("serve --listen 0.0.0.0:9000")),
+        Ok(Command::Serve { listen: "0.0.0.0:9000".into(), interval_secs: 60 })
+    );
+    assert_eq!(parse_args(&args("zabbix-template")), Ok(Command::ZabbixTemplate));
+    assert_eq!(parse_args(&args("--tui")), Ok(Command::Tui));
+    assert_eq!(parse_args(&args("--help")), Ok(Command::Help));
+    assert_eq!(
+        parse_args(&args("check --temp-warn 50 --health-crit=5")),
+        Ok(Command::Check {
+            device: None,
+            thresholds: CheckThresholds { temp_warn: 50, health_crit: 5, ..CheckThresholds::default() },
+        })
+    );
+}
+
+#[test]
+fn parse_errors() {
+    assert!(parse_args(&args("frobnicate")).is_err());
+    assert!(parse_args(&args("scan --device /dev/sda")).is_err());
+    assert!(parse_args(&args("test")).is_err());
+    assert!(parse_args(&args("monitor --interval soon")).is_err());
+    assert!(parse_args(&args("report --device")).is_err());
+    assert!(parse_args(&args("report extra")).is_err());
+    assert!(parse_args(&args("monitor --influx https://influx.example.com/write")).is_err());
+    assert!(parse_args(&args("monitor --influx-token abc")).is_err());
+    assert!(parse_args(&args("monitor --zabbix-host web1")).is_err());
+    assert!(parse_args(&args("check --temp-warn 70")).is_err());
+    assert!(parse_args(&args("check --health-warn 5")).is_err());
+
+    // A typo in a plugin command line is UNKNOWN, not CRITICAL
+    assert_eq!(usage_exit_code(&args("check --tmp-warn 50")), 3);
+    assert_eq!(usage_exit_code(&args("frobnicate")), 2);
+}
+
+#[test]
+fn text_output() {
+    let failing = parse_fixture("/dev/sda", "hdd_toshiba_failing", "HDD");
+    let table = format_scan_table(std::slice::from_ref(&failing));
+    assert!(table.starts_with("DEVICE"));
+    assert!(table.lines().nth(1).unwrap().contains("TOSHIBA HDWD130"));
+    assert!(table.trim_end().ends_with("FAILING"));
+
+    let report = format_drive_report(&failing);
+    assert!(report.contains("SMART self-assessment: FAILED"));
+    assert!(report.contains("! Last self-test: Completed: read failure"));
+    assert!(report.contains("Reallocated_Sector_Ct"));
+
+    let line = format_status_line(&parse_fixture("/dev/sda", "nvme_wd_sn850x", "NVMe"));
+    assert_eq!(line, "/dev/sda health=100% temp=38C written=2.34TB status=OK");
+}
+
+#[test]
+fn nagios_check_levels_and_perfdata() {
+    let thresholds = CheckThresholds::default();
+    let healthy = parse_fixture("/dev/sda", "nvme_wd_sn850x", "NVMe");
+    let (code, line) = check_status(std::slice::from_ref(&healthy), &thresholds);
+    assert_eq!(code, 0);
+    assert_eq!(
+        line,
+        "SSD OK - 1 drive(s) healthy | 'sda_temp'=38;55;65 'sda_health'=100%;20:;10:;0;100 'sda_written'=2.339TB"
+    );
+
+    // 38°C crosses a lowered warning threshold
+    let warm = CheckThresholds { temp_warn: 35, ..thresholds };
+    assert_eq!(check_status(std::slice::from_ref(&healthy), &warm).0, 1);
+
+    let (code, line) = check_status(&[healthy, parse_fixture("/dev/sda", "hdd_toshiba_failing", "HDD")], &thresholds);
+    assert_eq!(code, 2);
+    assert!(line.starts_with("SSD CRITICAL - /dev/sda: SMART overall-health check FAILED"));
+
+    // No perfdata, no trailing separator
+    let mut bare = parse_fixture("/dev/sda", "nvme_wd_sn850x", "NVMe");
+    bare.temp_c = None;
+    bare.health_percent = None;
+    bare.data_written_tb = None;
+    assert_eq!(check_status(&[bare], &thresholds).1, "SSD OK - 1 drive(s) healthy");
+}
@@ -0,0 +1,17 @@
+// Helpers shared by the integration tests
+
+// smartctl parser used to turn fixtures into drives
+use ssd_info_cli::gui::disk_scanner::parse_smartctl_output;
+use ssd_info_cli::models::DiskInfo;
+
+/// Loads a captured smartctl output from tests/fixtures/smartctl and parses it.
+///
+/// # Arguments
+/// * `dev` - Device path the parsed drive gets (e.g. "/dev/sda")
+/// * `name` - Fixture file name without the .txt extension
+/// * `hint_kind` - Type hint passed to the parser ("NVMe", "SATA", or "HDD")
+pub fn parse_fixture(dev: &str, name: &str, hint_kind: &str) -> DiskInfo {
+    let path = format!("{}/tests/fixtures/smartctl/{}.txt", env!("CARGO_MANIFEST_DIR"), name);
+    let text = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("failed to read {}: {}", path, e));
+    parse_smartctl_output(dev, hint_kind, &text)
+}
@@ -0,0 +1,65 @@
+// Tests for the environment details shown in the About window.
+
+use ssd_info_cli::gui::diagnostics::{find_program, parse_smartctl_version, Diagnostics};
+
+#[test]
+fn smartctl_version_from_first_line() {
+    let output = "smartctl 7.3 2022-02-28 r5338 [x86_64-linux-6.5.0-14-generic] (local build)\n\
+                  Copyright (C) 2002-22, Bruce Allen, Christian Franke, www.smartmontools.org\n";
+    assert_eq!(parse_smartctl_version(output).as_deref(), Some("7.3 2022-02-28 r5338"));
+    assert_eq!(parse_smartctl_version("bash: smartctl: command not found"), None);
+    assert_eq!(parse_smartctl_version(""), None);
+}
+
+#[test]
+fn program_found_in_first_matching_path_entry() {
+    let root = std::env::temp_dir().join(format!("ssd_info_diag_{}", std::process::id()));
+    let (first, second) = (root.join("bin"), root.join("sbin"));
+    std::fs::create_dir_all(&first).unwrap();
+    std::fs::create_dir_all(&second).unwrap();
+    std::fs::write(second.join("smartctl"), "").unwrap();
+
+    let path = format!("{}::{}", first.display(), second.display());
+    assert_eq!(find_program("smartctl", &path), Some(second.join("smartctl")));
+    assert_eq!(find_program("nvme", &path), None);
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn summary_lists_unprobed_drives() {
+    let diagnostics = Diagnostics {
+        version: "1.0.0",
+        smartctl_path: None,
+        smartctl_version: None,
+        kernel: Some("6.5.0".into()),
+        is_root: false,
+        detected: vec!["/dev/nvme0n1".into(), "/dev/sda".into()],
+        flatpak: false,
+    };
+    let summary = diagnostics.summary(Some(&["/dev/nvme0n1"]));
+    assert!(summary.starts_with("SSD Health Checker 1.0.0\n"));
+    assert!(summary.contains("smartctl: not found on PATH"));
+    assert!(summary.contains("Running as root: no"));
+    assert!(summary.contains("Drives: 2 detected, 1 probed"));
+    assert!(summary.contains("Not probed: /dev/sda"));
+
+    let snapshot = diagnostics.summary(None);
+    assert!(!snapshot.contains("Not probed"));
+}
+
+#[test]
+fn flatpak_sandbox_is_reported() {
+    let diagnostics = Diagnostics {
+        version: "1.0.0",
+        smartctl_path: None,
+        smartctl_version: None,
+        kernel: None,
+        is_root: true,
+        detected: Vec::new(),
+        flatpak: true,
+    };
+    let summary = diagnostics.summary(Some(&[]));
+    assert!(summary.contains("smartctl: not found on the host"));
+    assert!(summary.contains("Sandbox: Flatpak"));
+    assert!(summary.contains("--talk-name=org.freedesktop.Flatpak"));
+}
@@ -0,0 +1,64 @@
+// Tests for the fleet view's host list and report fetching.
+
+mod common;
+
+use common::parse_fixture;
+use ssd_info_cli::gui::fleet::{drives_url, fetch, parse_hosts, Fleet};
+use ssd_info_cli::gui::report::Report;
+use ssd_info_cli::server::serve;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn host_list_and_urls() {
+    assert_eq!(parse_hosts("# fleet\nnas\n\n  web1:9000  \n"), vec!["nas", "web1:9000"]);
+    assert_eq!(drives_url("nas"), "http://nas:8190/drives");
+    assert_eq!(drives_url("web1:9000"), "http://web1:9000/drives");
+    assert_eq!(drives_url("http://web1:9000/"), "http://web1:9000/drives");
+    assert_eq!(drives_url("http://proxy/ssd/drives"), "http://proxy/ssd/drives");
+}
+
+#[test]
+fn fetches_reports_from_serve() {
+    let drive = parse_fixture("/dev/nvme0n1", "nvme_wd_sn850x", "NVMe");
+    let report = Report { host: "nas".into(), generated_at: 1_700_000_000, drives: vec![drive] };
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    std::thread::spawn(move || serve(listener, Arc::new(Mutex::new(Ok(report)))));
+
+    let fetched = fetch(&address).unwrap();
+    assert_eq!(fetched.host, "nas");
+    assert_eq!(fetched.drives[0].health_percent, Some(100));
+
+    // Unknown endpoints surface the server's error message
+    let err = fetch(&format!("http://{}/nope", address)).unwrap_err();
+    assert!(err.starts_with("HTTP 404: Unknown endpoint"), "{}", err);
+
+    // Background fetches land in the host list
+    let mut fleet = Fleet::new(vec![address]);
+    fleet.refresh();
+    while fleet.poll() {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    assert_eq!(fleet.hosts[0].report.as_ref().unwrap().as_ref().unwrap().host, "nas");
+}
+
+#[test]
+fn oversized_responses_are_rejected() {
+    use std::io::{Read, Write};
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    std::thread::spawn(move || {
+        let (mut conn, _) = listener.accept().unwrap();
+        let _ = conn.read(&mut [0u8; 1024]);
+        let _ = conn.write_all(b"HTTP/1.1 200 OK\r\n\r\n");
+        let chunk = vec![b'['; 1024 * 1024];
+        for _ in 0..9 {
+            if conn.write_all(&chunk).is_err() {
+                break;
+            }
+        }
+    });
+    let err = fetch(&address).unwrap_err();
+    assert!(err.contains("response larger than 8 MiB"), "{}", err);
+}
@@ -0,0 +1,134 @@
+// Tests for the per-drive history store and the rates derived from it.
+
+use ssd_info_cli::gui::history::{
+    drive_key, parse_history, replacement_estimate, traffic_rate, usage_rates, HistorySample, HistoryStore,
+};
+use ssd_info_cli::models::DiskInfo;
+
+const DAY: u64 = 86_400;
+
+fn sample(timestamp: u64, written: f64, health: u8) -> HistorySample {
+    HistorySample {
+        timestamp,
+        data_written_tb: Some(written),
+        data_read_tb: None,
+        power_on_hours: Some(timestamp / 3600),
+        health_percent: Some(health),
+        temp_c: Some(40),
+        pending_sectors: None,
+        controller_busy_minutes: None,
+    }
+}
+
+#[test]
+fn drive_key_is_filesystem_safe() {
+    let mut di = DiskInfo::empty("/dev/sda");
+    assert_eq!(drive_key(&di), None);
+    di.model = Some("Samsung SSD 870 EVO 1TB".into());
+    di.serial = Some("S6PT/NX0R1".into());
+    assert_eq!(drive_key(&di).as_deref(), Some("Samsung_SSD_870_EVO_1TB-S6PT_NX0R1"));
+}
+
+#[test]
+fn store_round_trips_and_throttles_to_hourly() {
+    let dir = std::env::temp_dir().join(format!("ssd_info_history_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let store = HistoryStore::new(&dir);
+
+    let mut samples = store.load("drive");
+    assert!(samples.is_empty());
+    assert!(store.record("drive", &mut samples, sample(1000, 1.5, 99)).unwrap());
+    // Less than an hour later: skipped
+    assert!(!store.record("drive", &mut samples, sample(2000, 1.6, 99)).unwrap());
+    let mut unknown = sample(1000 + 3600, 1.7, 98);
+    unknown.data_written_tb = None;
+    assert!(store.record("drive", &mut samples, unknown).unwrap());
+
+    let loaded = store.load("drive");
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(loaded, samples);
+    assert_eq!(loaded.len(), 2);
+    assert_eq!(loaded[1].data_written_tb, None);
+}
+
+#[test]
+fn parse_history_skips_malformed_lines() {
+    let samples = parse_history("100,1.0,,5,99,40\ngarbage\n200,1.1,0.5,,,\n");
+    assert_eq!(samples.len(), 2);
+    assert_eq!(samples[1].data_read_tb, Some(0.5));
+    assert_eq!(samples[1].power_on_hours, None);
+}
+
+#[test]
+fn usage_rates_need_a_day_and_flag_recent_surge() {
+    assert_eq!(usage_rates(&[sample(0, 1.0, 100), sample(DAY / 2, 1.1, 100)]), None);
+
+    // 0.1 TB/day for 30 days, then 1 TB/day for the last week
+    let mut samples: Vec<_> = (0..=30).map(|d| sample(d * DAY, 1.0 + d as f64 * 0.1, 100 - (d / 10) as u8)).collect();
+    samples.extend((1..=7).map(|d| sample((30 + d) * DAY, 4.0 + d as f64, 97)));
+
+    let rates = usage_rates(&samples).unwrap();
+    assert!((rates.written_tb_per_day - 10.0 / 37.0).abs() < 1e-9);
+    assert!((rates.recent_written_tb_per_day.unwrap() - 1.0).abs() < 1e-9);
+    assert!((rates.wear_percent_per_month.unwrap() - 3.0 * 30.0 / 37.0).abs() < 1e-9);
+}
+
+#[test]
+fn busy_time_is_stored_and_rated() {
+    // Lines from before busy time was recorded still parse
+    let samples = parse_history("0,1.0,,5,99,40,\n100,1.0,,5,99,40,,300\n");
+    assert_eq!(samples[0].controller_busy_minutes, None);
+    assert_eq!(samples[1].controller_busy_minutes, Some(300));
+
+    let busy = |timestamp: u64, minutes: u64| HistorySample { controller_busy_minutes: Some(minutes), ..sample(timestamp, 1.0, 100) };
+    let rates = usage_rates(&[busy(0, 300), busy(DAY, 330), busy(4 * DAY, 420)]).unwrap();
+    assert!((rates.busy_minutes_per_day.unwrap() - 30.0).abs() < 1e-9);
+    assert_eq!(usage_rates(&[sample(0, 1.0, 100), sample(DAY, 1.1, 100)]).unwrap().busy_minutes_per_day, None);
+}
+
+#[test]
+fn traffic_rate_between_refreshes() {
+    let reading = |read: Option<f64>, written: Option<f64>| HistorySample {
+        timestamp: 0,
+        data_written_tb: written,
+        data_read_tb: read,
+        power_on_hours: None,
+        health_percent: None,
+        temp_c: None,
+        pending_sectors: None,
+        controller_busy_minutes: None,
+    };
+    // 10 MB read and 2.56 MB (five NVMe data units) written in 5 s
+    let rate = traffic_rate(&reading(Some(1.0), Some(2.0)), &reading(Some(1.00001), Some(2.00000256)), 5.0);
+    assert!((rate.read_mb_per_sec.unwrap() - 2.0).abs() < 1e-6);
+    assert!((rate.write_mb_per_sec.unwrap() - 0.512).abs() < 1e-6);
+
+    // Unknown counters, a counter that went backwards and a zero interval give no rate
+    let rate = traffic_rate(&reading(None, Some(2.0)), &reading(Some(1.0), Some(1.5)), 5.0);
+    assert_eq!((rate.read_mb_per_sec, rate.write_mb_per_sec), (None, None));
+    assert_eq!(traffic_rate(&reading(Some(1.0), None), &reading(Some(1.0), None), 0.0).read_mb_per_sec, None);
+}
+
+#[test]
+fn replacement_estimate_takes_the_earliest_input() {
+    // 0.1 TB/day and 1% health per 10 days over 30 days
+    let mut samples: Vec<_> = (0..=30).map(|d| sample(d * DAY, 10.0 + d as f64 * 0.1, 90 - (d / 10) as u8)).collect();
+    assert_eq!(replacement_estimate(&samples[..1], Some(600.0), 20_000), None);
+
+    // Health 87% at 3% per 30 days: 870 days; 587 TB left at 0.1 TB/day: 5870 days
+    let estimate = replacement_estimate(&samples, Some(600.0), 20_000).unwrap();
+    assert_eq!(estimate.day, 20_870);
+    assert_eq!(estimate.explanation.len(), 2);
+    // A small rating runs out first
+    assert_eq!(replacement_estimate(&samples, Some(23.0), 20_000).unwrap().day, 20_000 + 100);
+
+    // Growing pending sectors mean now
+    samples[0].pending_sectors = Some(0);
+    samples[30].pending_sectors = Some(8);
+    let estimate = replacement_estimate(&samples, None, 20_000).unwrap();
+    assert_eq!(estimate.day, 20_000);
+    assert!(estimate.explanation.last().unwrap().contains("from 0 to 8"));
+
+    // Old six-field lines still parse
+    assert_eq!(parse_history("100,1.0,,5,99,40\n200,1.1,,6,99,41,3\n")[1].pending_sectors, Some(3));
+}
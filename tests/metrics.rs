@@ -0,0 +1,134 @@
+// Tests for the metric sinks written by the monitor command.
+
+mod common;
+
+use common::parse_fixture;
+use ssd_info_cli::json::Json;
+use ssd_info_cli::metrics::{csv_rows, influx_lines, zabbix_payload, zabbix_template, MetricsTarget, Sink};
+use ssd_info_cli::models::DiskInfo;
+use std::io::{Read, Write};
+
+#[test]
+fn influx_line_protocol() {
+    let nvme = parse_fixture("/dev/nvme0n1", "nvme_wd_sn850x", "NVMe");
+    let asleep = DiskInfo::empty("/dev/sdb");
+    let text = influx_lines(&[nvme, asleep], "my host", 1_700_000_000);
+
+    // Sleeping drives without readings produce no line
+    assert_eq!(text.lines().count(), 1);
+    let line = text.lines().next().unwrap();
+    assert!(line.starts_with("ssd_info,host=my\\ host,device=/dev/nvme0n1,model=WD_BLACK\\ SN850X"));
+    assert!(line.contains(" health_percent=100i,temperature_c=38i,data_written_tb=2.33875968,"));
+    assert!(line.contains(",smart_passed=true"));
+    assert!(line.ends_with(" 1700000000"));
+}
+
+#[test]
+fn influx_sinks_deliver_to_file_and_http() {
+    let drives = [parse_fixture("/dev/nvme0n1", "nvme_wd_sn850x", "NVMe")];
+
+    let path = std::env::temp_dir().join(format!("ssd_info_influx_{}.txt", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let sink = Sink::Influx { target: MetricsTarget::File(path.clone()), token: None };
+    sink.send(&drives, "h", 1).unwrap();
+    sink.send(&drives, "h", 2).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 2);
+    std::fs::remove_file(&path).unwrap();
+
+    // Minimal HTTP endpoint answering 204 like InfluxDB's write API
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/api/v2/write?bucket=disks&precision=s", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let (mut conn, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+        while !String::from_utf8_lossy(&request).contains(" 1\n") {
+            let n = conn.read(&mut buf).unwrap();
+            request.extend_from_slice(&buf[..n]);
+        }
+        conn.write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n").unwrap();
+        String::from_utf8(request).unwrap()
+    });
+    let sink = Sink::Influx { target: MetricsTarget::Http(url), token: Some("secret".into()) };
+    sink.send(&drives, "h", 1).unwrap();
+    let request = server.join().unwrap();
+    assert!(request.starts_with("POST /api/v2/write?bucket=disks&precision=s HTTP/1.1\r\n"));
+    assert!(request.contains("Authorization: Token secret\r\n"));
+    assert!(request.contains("\r\n\r\nssd_info,host=h,"));
+}
+
+#[test]
+fn zabbix_payload_and_template() {
+    let drives = [parse_fixture("/dev/nvme0n1", "nvme_wd_sn850x", "NVMe")];
+    let payload = zabbix_payload(&drives, "web1", 1_700_000_000);
+    assert_eq!(payload.get("request").and_then(Json::as_str), Some("sender data"));
+
+    let data = payload.get("data").and_then(Json::as_array).unwrap();
+    let value = |key: &str| {
+        data.iter()
+            .find(|d| d.get("key").and_then(Json::as_str) == Some(key))
+            .and_then(|d| d.get("value").and_then(Json::as_str))
+            .map(str::to_string)
+    };
+    assert!(data.iter().all(|d| d.get("host").and_then(Json::as_str) == Some("web1")));
+    let discovery = Json::parse(&value("ssd.discovery").unwrap()).unwrap();
+    assert_eq!(discovery.as_array().unwrap()[0].get("{#DEVICE}").and_then(Json::as_str), Some("nvme0n1"));
+    assert_eq!(value("ssd.health[nvme0n1]").as_deref(), Some("100"));
+    assert_eq!(value("ssd.temperature[nvme0n1]").as_deref(), Some("38"));
+    assert_eq!(value("ssd.smart_passed[nvme0n1]").as_deref(), Some("1"));
+
+    // Every key sent has a prototype in the template
+    let template = zabbix_template();
+    assert!(template.starts_with("zabbix_export:\n  version: '6.0'\n"));
+    for d in data {
+        let key = d.get("key").and_then(Json::as_str).unwrap().replace("[nvme0n1]", "[{#DEVICE}]");
+        assert!(template.contains(&format!("key: '{}'", key)) || key == "ssd.discovery", "{}", key);
+    }
+    assert!(template.contains("            - uuid: "));
+}
+
+#[test]
+fn zabbix_sink_speaks_sender_protocol() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let server = listener.local_addr().unwrap().to_string();
+    let handle = std::thread::spawn(move || {
+        let (mut conn, _) = listener.accept().unwrap();
+        let mut header = [0u8; 13];
+        conn.read_exact(&mut header).unwrap();
+        let len = u64::from_le_bytes(header[5..13].try_into().unwrap()) as usize;
+        let mut body = vec![0u8; len];
+        conn.read_exact(&mut body).unwrap();
+        let reply = br#"{"response":"success","info":"processed: 11; failed: 0; total: 11"}"#;
+        conn.write_all(b"ZBXD\x01").unwrap();
+        conn.write_all(&(reply.len() as u64).to_le_bytes()).unwrap();
+        conn.write_all(reply).unwrap();
+        (header[..5].to_vec(), String::from_utf8(body).unwrap())
+    });
+
+    let drives = [parse_fixture("/dev/nvme0n1", "nvme_wd_sn850x", "NVMe")];
+    Sink::Zabbix { server, host: None }.send(&drives, "web1", 1).unwrap();
+    let (magic, body) = handle.join().unwrap();
+    assert_eq!(magic, b"ZBXD\x01");
+    assert!(body.starts_with(r#"{"request":"sender data","data":[{"host":"web1","key":"ssd.discovery""#));
+}
+
+#[test]
+fn csv_sink_writes_header_once() {
+    let mut drive = parse_fixture("/dev/nvme0n1", "nvme_wd_sn850x", "NVMe");
+    drive.model = Some("Model, \"quoted\"".into());
+    assert_eq!(
+        csv_rows(std::slice::from_ref(&drive), "h", 86_400),
+        "1970-01-02 00:00 UTC,86400,h,/dev/nvme0n1,\"Model, \"\"quoted\"\"\",23124S801234,100,38,2.33875968,\
+1.769875968,1048,215,12,0,true\n"
+    );
+
+    let path = std::env::temp_dir().join(format!("ssd_info_metrics_{}.csv", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let sink = Sink::Csv(path.clone());
+    sink.send(std::slice::from_ref(&drive), "h", 1).unwrap();
+    sink.send(&[drive.clone(), drive], "h", 2).unwrap();
+    let text = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(text.lines().count(), 4);
+    assert!(text.starts_with("time,unix_time,host,device,"));
+}
@@ -0,0 +1,177 @@
+// Tests for model helpers that do not depend on smartctl output.
+
+mod common;
+
+use common::parse_fixture;
+use ssd_info_cli::locale::{format_grouped, group_raw_value, separator_for_locale};
+use ssd_info_cli::models::{humanize_hours, HealthWeights, sorted_partitions, AttributeStatus, CapacityUsage, DiskInfo, LinkPower, PartitionInfo, PartitionSort, SmartAttribute};
+
+fn partition(mount_point: &str, total_gb: f64, used_gb: f64) -> PartitionInfo {
+    PartitionInfo {
+        mount_point: mount_point.into(),
+        fs_type: "ext4".into(),
+        total_gb,
+        used_gb,
+        free_gb: total_gb - used_gb,
+        used_percent: used_gb / total_gb * 100.0,
+        mount_options: vec![],
+        device: String::new(),
+        start_bytes: None,
+        alignment_bytes: None,
+        fs_label: None,
+        part_label: None,
+        part_uuid: None,
+        part_type: None,
+        inodes_total: None,
+        inodes_used: None,
+    }
+}
+
+#[test]
+fn partitions_sort_by_column_and_direction() {
+    let parts = [partition("/home", 500.0, 100.0), partition("/", 50.0, 45.0), partition("/var", 100.0, 60.0)];
+    let order = |sort| sorted_partitions(&parts, sort).iter().map(|p| p.mount_point.as_str()).collect::<Vec<_>>();
+
+    assert_eq!(order(None), ["/home", "/", "/var"]);
+    assert_eq!(order(Some((PartitionSort::MountPoint, false))), ["/", "/home", "/var"]);
+    assert_eq!(order(Some((PartitionSort::Size, true))), ["/home", "/var", "/"]);
+    assert_eq!(order(Some((PartitionSort::Used, false))), ["/", "/var", "/home"]);
+    // Ascending free space puts the fullest filesystem first
+    assert_eq!(order(Some((PartitionSort::FreePercent, false))), ["/", "/var", "/home"]);
+    assert_eq!(order(Some((PartitionSort::FreePercent, true))), ["/home", "/var", "/"]);
+    assert!(PartitionSort::Size.default_descending() && !PartitionSort::FreePercent.default_descending());
+}
+
+#[test]
+fn drives_group_by_bus() {
+    assert_eq!(parse_fixture("/dev/sda", "nvme_wd_sn850x", "NVMe").bus_group(), "NVMe");
+    assert_eq!(parse_fixture("/dev/sda", "hdd_toshiba_failing", "HDD").bus_group(), "HDD");
+    assert_eq!(parse_fixture("/dev/sda", "sata_intel_320", "SATA").bus_group(), "SATA SSD");
+
+    let mut usb = parse_fixture("/dev/sda", "sata_intel_320", "SATA");
+    usb.by_id = Some("/dev/disk/by-id/usb-JMicron_Generic_0123456789-0:0".into());
+    assert_eq!(usb.bus_group(), "USB");
+    let mut vm = parse_fixture("/dev/sda", "sata_intel_320", "SATA");
+    vm.model = Some("QEMU HARDDISK".into());
+    assert_eq!(vm.bus_group(), "Virtual");
+}
+
+#[test]
+fn capacity_usage_counts_each_filesystem_once() {
+    let mut di = DiskInfo::empty("/dev/sda");
+    assert_eq!(di.capacity_usage(), None);
+
+    di.capacity = Some(1_000_000_000_000.0);
+    di.unallocated_bytes = Some(100_000_000_000);
+    let mut root = partition("/", 500.0, 200.0);
+    root.device = "sda2".into();
+    // Second mount of the same btrfs filesystem
+    let mut home = partition("/home", 500.0, 200.0);
+    home.device = "sda2".into();
+    let mut data = partition("/data", 300.0, 30.0);
+    data.device = "sda3".into();
+    di.partitions = vec![root, home, data];
+
+    let usage = di.capacity_usage().unwrap();
+    assert_eq!(usage.used, 230_000_000_000);
+    assert_eq!(usage.free, 570_000_000_000);
+    assert_eq!(usage.unallocated, 100_000_000_000);
+    assert_eq!(usage.other(), 100_000_000_000);
+
+    let total = CapacityUsage::sum([&usage, &usage]);
+    assert_eq!(total.total, 2_000_000_000_000);
+    assert_eq!(total.used, 460_000_000_000);
+}
+
+#[test]
+fn link_power_policy_flags_min_power() {
+    let link = |policy: &str| LinkPower { host: "host2".into(), policy: policy.into() };
+    assert!(link("min_power").is_aggressive());
+    assert!(!link("med_power_with_dipm").is_aggressive());
+    assert!(!link("max_performance").is_aggressive());
+    assert_eq!(link("something_new").description(), "Unknown policy");
+}
+
+#[test]
+fn smart_attribute_margin_uses_worst_value() {
+    let attr = |current: &str, worst: &str, threshold: &str| SmartAttribute {
+        id: "5".into(),
+        name: "Reallocated_Sector_Ct".into(),
+        current: current.into(),
+        worst: worst.into(),
+        threshold: threshold.into(),
+        raw_value: "0".into(),
+        status: AttributeStatus::Good,
+        pre_fail: true,
+    };
+    assert_eq!(attr("100", "095", "010").normalized(), Some((100, 95, 10)));
+    assert_eq!(attr("100", "095", "010").margin(), Some(85));
+    assert_eq!(attr("005", "005", "010").margin(), Some(-5));
+    // A zero threshold never trips, and "---" is not a value
+    assert_eq!(attr("253", "253", "000").margin(), None);
+    assert_eq!(attr("100", "100", "---").normalized(), None);
+}
+
+#[test]
+fn power_on_time_in_words() {
+    assert_eq!(humanize_hours(0), "less than an hour");
+    assert_eq!(humanize_hours(5), "5 hours");
+    assert_eq!(humanize_hours(24), "1 day");
+    assert_eq!(humanize_hours(41 * 24 + 3), "41 days, 3 hours");
+    assert_eq!(humanize_hours(8760), "1 year");
+    assert_eq!(humanize_hours(2 * 8760 + 41 * 24 + 7), "2 years, 41 days");
+
+    let di = parse_fixture("/dev/sda", "sata_crucial_mx500", "SATA");
+    // 9876 power-on hours over 1021 power cycles
+    assert!((di.hours_per_power_cycle().unwrap() - 9.673).abs() < 1e-3);
+}
+
+#[test]
+fn thousands_separators_follow_locale() {
+    assert_eq!(separator_for_locale("en_US.UTF-8"), ",");
+    assert_eq!(separator_for_locale("de_DE.UTF-8"), ".");
+    assert_eq!(separator_for_locale("de_CH.UTF-8"), "’");
+    assert_eq!(separator_for_locale("fr_FR@euro"), "\u{a0}");
+    assert_eq!(separator_for_locale("pt_BR.UTF-8"), ".");
+    assert_eq!(separator_for_locale("C"), ",");
+
+    assert_eq!(format_grouped(0, ","), "0");
+    assert_eq!(format_grouped(999, ","), "999");
+    assert_eq!(format_grouped(1000, "."), "1.000");
+    assert_eq!(format_grouped(1_234_567_890, ","), "1,234,567,890");
+
+    // Only plain decimal counts of five digits or more are grouped
+    assert_eq!(group_raw_value("123456789", ","), "123,456,789");
+    assert_eq!(group_raw_value("44531 (Min/Max 0/52)", "."), "44.531 (Min/Max 0/52)");
+    assert_eq!(group_raw_value("9876", ","), "9876");
+    assert_eq!(group_raw_value("12345h+05m+11.123s", ","), "12345h+05m+11.123s");
+    assert_eq!(group_raw_value("0x000500000000", ","), "0x000500000000");
+}
+
+#[test]
+fn health_score_deducts_weighted_points() {
+    let weights = HealthWeights::default();
+
+    // Wear only: 9% of the endurance used
+    let worn = parse_fixture("/dev/sda", "sata_crucial_mx500", "SATA").health_score(&weights).unwrap();
+    assert_eq!(worn.score, 91);
+    assert_eq!((worn.inputs[0].label, worn.inputs[0].value.as_str(), worn.inputs[0].points), ("Endurance used", "9%", 9.0));
+    // Inputs that cost nothing are still listed, so the breakdown shows what was checked
+    assert!(worn.inputs[1..].iter().all(|i| i.points == 0.0));
+    assert!(worn.inputs.iter().any(|i| i.label == "SMART self-assessment" && i.value == "PASSED"));
+
+    // An NVMe drive without wear or errors scores what it reports
+    let nvme = parse_fixture("/dev/sda", "nvme_wd_sn850x", "NVMe");
+    assert_eq!(nvme.health_score(&weights).unwrap().score, nvme.health_percent.unwrap());
+
+    // Pending sectors and a failed self-assessment floor the score; the largest deduction comes first
+    let failing = parse_fixture("/dev/sda", "hdd_toshiba_failing", "HDD").health_score(&weights).unwrap();
+    assert_eq!(failing.score, 0);
+    assert_eq!(failing.inputs[0].weight, "pending_sector");
+
+    // With every sector weight at 0, only the self-assessment counts
+    let lenient = HealthWeights { pending_sector: 0.0, uncorrectable_sector: 0.0, reallocated_sector: 0.0, smart_failed: 40.0, ..weights };
+    assert_eq!(parse_fixture("/dev/sda", "hdd_toshiba_failing", "HDD").health_score(&lenient).unwrap().score, 60);
+
+    assert_eq!(DiskInfo::empty("/dev/sdz").health_score(&weights), None);
+}
@@ -0,0 +1,30 @@
+// Tests for the command palette's fuzzy matching.
+
+use ssd_info_cli::gui::palette::{fuzzy_score, rank};
+
+#[test]
+fn subsequence_matches_only() {
+    assert!(fuzzy_score("rfsh", "Refresh all drives").is_some());
+    assert!(fuzzy_score("EXPORT", "📤 Export report").is_some());
+    assert_eq!(fuzzy_score("xyz", "Refresh all drives"), None);
+    // Order matters
+    assert_eq!(fuzzy_score("hser", "Refresh"), None);
+    assert_eq!(fuzzy_score("", "anything"), Some(0));
+}
+
+#[test]
+fn word_starts_and_runs_rank_first() {
+    let labels = [
+        "🔄 Refresh all drives",
+        "📤 Export report",
+        "💾 Switch to /dev/sda — Samsung SSD 870 EVO",
+        "💾 Switch to /dev/nvme0n1 — WD_BLACK SN850X",
+        "🧪 Start short self-test on /dev/sda",
+    ];
+    assert_eq!(rank("", labels), [0, 1, 2, 3, 4]);
+    assert_eq!(rank("nvme", labels)[0], 3);
+    assert_eq!(rank("short", labels), [4]);
+    // Both entries containing "sda" as a run beat the scattered match, and keep their original order
+    assert_eq!(rank("sda", labels)[..2], [2, 4]);
+    assert_eq!(rank("rep", labels)[0], 1);
+}
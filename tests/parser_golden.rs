@@ -0,0 +1,774 @@
+// Golden-file regression tests for the smartctl output parser.
+// Each fixture in tests/fixtures/smartctl is a captured `smartctl -a` output, one per drive and condition
+// (sct_temp_history.txt is `smartctl -l scttemp`); the `_de` files are the same drive printed with German digit grouping.
+// The smaller sysfs, mountinfo, lsblk, hdparm and sedutil parsers are covered at the end of the file.
+
+mod common;
+
+use common::parse_fixture;
+use ssd_info_cli::gui::disk_scanner::{
+    parse_hdparm_apm, parse_hdparm_security, parse_lsblk_pairs, parse_mountinfo, parse_scheduler,
+    parse_sct_temp_history, parse_sedutil_query, parse_apst_feature, parse_fw_log, parse_endurance_log, parse_hmb_feature, parse_id_ctrl, parse_id_ns, parse_persistent_event_log, partition_type_name, scsi_host_name,
+    format_capacity, is_iscsi_device, is_usb_device, nvme_transport_name, system_roles, unallocated_bytes,
+};
+use ssd_info_cli::models::advisories::lookup;
+use ssd_info_cli::models::{event_counts, AttributeStatus, DiskInfo, EventCategory, NamespaceUsage};
+
+/// Asserts a terabyte value to within rounding of the displayed precision.
+fn assert_tb(actual: Option<f64>, expected: f64) {
+    let actual = actual.expect("expected a TB value");
+    assert!(
+        (actual - expected).abs() < 0.001,
+        "expected {} TB, got {} TB",
+        expected,
+        actual
+    );
+}
+
+/// Looks up a parsed SMART attribute by ID.
+fn attr<'a>(di: &'a DiskInfo, id: &str) -> &'a ssd_info_cli::models::SmartAttribute {
+    di.smart_attributes
+        .iter()
+        .find(|a| a.id == id)
+        .unwrap_or_else(|| panic!("attribute {} not parsed", id))
+}
+
+#[test]
+fn nvme_samsung_980_pro() {
+    let di = parse_fixture("/dev/test", "nvme_samsung_980_pro", "NVMe");
+    assert_eq!(di.model.as_deref(), Some("Samsung SSD 980 PRO 1TB"));
+    assert_eq!(di.serial.as_deref(), Some("S5GXNF0R123456A"));
+    assert_eq!(di.firmware.as_deref(), Some("3B2QGXA7"));
+    assert_eq!(di.wwn.as_deref(), Some("eui.002538b21150a1b2"));
+    assert_eq!(di.capacity, Some(1_000_204_886_016.0));
+    assert_eq!(di.capacity_str.as_deref(), Some("1.00 TB"));
+    assert_eq!(di.health_percent, Some(97));
+    assert_eq!(di.temp_c, Some(41));
+    assert_tb(di.data_written_tb, 6.321);
+    assert_tb(di.data_read_tb, 12.863);
+    assert_eq!(di.power_cycles, Some(1532));
+    assert_eq!(di.power_on_hours, Some(4321));
+    assert_eq!(di.unsafe_shutdowns, Some(87));
+    assert_eq!(di.rotation_rpm, None);
+    assert_eq!(di.protocol.as_deref(), Some("NVMe"));
+    assert_eq!(di.device_type.as_deref(), Some("SSD"));
+    assert!(di.smart_attributes.is_empty());
+    // 3B2QGXA7 is the firmware with the known health degradation bug
+    assert_eq!(di.advisories.len(), 1);
+}
+
+#[test]
+fn nvme_wd_sn850x() {
+    let di = parse_fixture("/dev/test", "nvme_wd_sn850x", "NVMe");
+    assert_eq!(di.model.as_deref(), Some("WD_BLACK SN850X 2000GB"));
+    assert_eq!(di.firmware.as_deref(), Some("620361WD"));
+    assert_eq!(di.capacity_str.as_deref(), Some("2.00 TB"));
+    assert_eq!(di.health_percent, Some(100));
+    assert_eq!(di.temp_c, Some(38));
+    assert_tb(di.data_written_tb, 2.339);
+    assert_tb(di.data_read_tb, 1.770);
+    assert_eq!(di.power_cycles, Some(215));
+    assert_eq!(di.power_on_hours, Some(1048));
+    assert_eq!(di.unsafe_shutdowns, Some(12));
+    // Self-test log rows must not be mistaken for SMART attributes
+    assert!(di.smart_attributes.is_empty());
+    assert_eq!(di.critical_sectors(), None);
+    assert_eq!(di.helium_level(), None);
+    assert!(di.advisories.is_empty());
+    assert_eq!(di.smart_passed, Some(true));
+    assert_eq!(di.media_errors, Some(0));
+    assert_eq!(di.last_self_test.as_deref(), Some("Completed without error"));
+    assert!(di.problems().is_empty());
+    let controller = di.nvme_controller.clone().unwrap();
+    assert_eq!(controller.controller_id, Some(8224));
+    assert_eq!(controller.ieee_oui.as_deref(), Some("0x001b44"));
+    assert_eq!(controller.nvme_version.as_deref(), Some("1.4"));
+    assert_eq!(controller.namespaces, Some(1));
+    assert_eq!(controller.log_page_attributes, Some(0x1e));
+    assert!(controller.supports_telemetry());
+    // "Firmware Updates (0x14): 2 Slots, no Reset required"
+    assert_eq!(controller.firmware_updates, Some(0x14));
+    assert!(controller.activates_without_reset());
+    assert!(!controller.slot1_read_only());
+    // The NQN only comes from nvme-cli
+    assert_eq!(controller.subsystem_nqn, None);
+    assert_eq!(di.controller_busy_minutes, Some(201));
+    // 201 of 1048 × 60 powered-on minutes
+    assert!((di.busy_fraction().unwrap() - 201.0 / 62_880.0).abs() < 1e-12);
+}
+
+#[test]
+fn nvme_without_total_capacity_uses_namespace_size() {
+    let di = parse_fixture("/dev/test", "nvme_crucial_p1_worn", "NVMe");
+    assert_eq!(di.model.as_deref(), Some("CT1000P1SSD8"));
+    assert_eq!(di.capacity, Some(1_000_204_886_016.0));
+    assert_eq!(di.capacity_str.as_deref(), Some("1.00 TB"));
+    assert_eq!(di.health_percent, Some(13));
+    assert_eq!(di.temp_c, Some(52));
+    assert_tb(di.data_written_tb, 164.858);
+    assert_eq!(di.power_cycles, Some(4102));
+    assert_eq!(di.power_on_hours, Some(31877));
+    assert_eq!(di.unsafe_shutdowns, Some(1377));
+    // No Log Page Attributes line, so no telemetry button
+    assert!(!di.nvme_controller.unwrap().supports_telemetry());
+}
+
+#[test]
+fn nvme_percentage_used_over_100_clamps_health_to_zero() {
+    let di = parse_fixture("/dev/test", "nvme_intel_660p_overworn", "NVMe");
+    assert_eq!(di.model.as_deref(), Some("INTEL SSDPEKNW512G8"));
+    assert_eq!(di.capacity_str.as_deref(), Some("512 GB"));
+    assert_eq!(di.health_percent, Some(0));
+    assert_eq!(di.temp_c, Some(44));
+    assert_eq!(di.power_on_hours, Some(22108));
+}
+
+#[test]
+fn sata_samsung_870_evo() {
+    let di = parse_fixture("/dev/test", "sata_samsung_870_evo", "SATA");
+    assert_eq!(di.model.as_deref(), Some("Samsung SSD 870 EVO 500GB"));
+    assert_eq!(di.serial.as_deref(), Some("S62ANJ0R654321X"));
+    assert_eq!(di.firmware.as_deref(), Some("SVT02B6Q"));
+    assert_eq!(di.wwn.as_deref(), Some("0x5002538f41234567"));
+    assert_eq!(di.nvme_controller, None);
+    assert_eq!(di.capacity, Some(500_107_862_016.0));
+    assert_eq!(di.capacity_str.as_deref(), Some("500 GB"));
+    // SATA drives do not report Percentage Used
+    assert_eq!(di.health_percent, None);
+    // Only the airflow sensor (190) is reported on this model
+    assert_eq!(di.temp_c, Some(33));
+    assert_eq!(di.power_on_hours, Some(12034));
+    assert_eq!(di.power_cycles, Some(812));
+    assert_tb(di.data_written_tb, 18.336);
+    assert_eq!(di.data_read_tb, None);
+    assert_eq!(di.unsafe_shutdowns, None);
+    assert_eq!(di.rotation_rpm, None);
+    assert_eq!(di.protocol.as_deref(), Some("ATA"));
+    assert_eq!(di.device_type.as_deref(), Some("SSD"));
+    // Selective self-test span rows must not be mistaken for SMART attributes
+    assert_eq!(di.smart_attributes.len(), 14);
+    assert!(di.smart_attributes.iter().all(|a| a.status == AttributeStatus::Good));
+    // Samsung does not report NAND writes
+    assert_eq!(di.write_amplification, None);
+    let wear = di.ssd_wear().unwrap();
+    assert_eq!(wear.used_percent, Some(2));
+    assert_eq!(wear.source.as_deref(), Some("Wear_Leveling_Count"));
+}
+
+#[test]
+fn sata_crucial_mx500() {
+    let di = parse_fixture("/dev/test", "sata_crucial_mx500", "SATA");
+    assert_eq!(di.model.as_deref(), Some("CT1000MX500SSD1"));
+    assert_eq!(di.capacity_str.as_deref(), Some("1.00 TB"));
+    // Raw value carries a "(Min/Max 0/52)" suffix
+    assert_eq!(di.temp_c, Some(36));
+    assert_eq!(di.power_on_hours, Some(9876));
+    assert_eq!(di.power_cycles, Some(1021));
+    assert_tb(di.data_written_tb, 21.112);
+    assert_eq!(di.smart_attributes.len(), 23);
+
+    let temp = attr(&di, "194");
+    assert_eq!(temp.name, "Temperature_Celsius");
+    assert_eq!(temp.current, "064");
+    assert_eq!(temp.worst, "048");
+    assert_eq!(temp.threshold, "000");
+    assert_eq!(temp.raw_value, "36 (Min/Max 0/52)");
+    assert_eq!(temp.raw_numeric(), Some(36));
+    // A minimum of 0 was never recorded
+    let range = di.temperature_range().unwrap();
+    assert_eq!((range.min, range.max), (None, 52));
+    assert_eq!(range.describe(), "Lifetime range: up to 52°C");
+
+    // (host pages + FTL pages) / host pages
+    let waf = di.write_amplification.unwrap();
+    assert!((waf - 2.6307).abs() < 1e-4, "waf {}", waf);
+    let wear = di.ssd_wear().unwrap();
+    assert_eq!((wear.used_percent, wear.avg_erase_count), (Some(9), Some(137)));
+    assert_eq!(wear.source.as_deref(), Some("Percent_Lifetime_Remain"));
+}
+
+#[test]
+fn sata_kingston_a400_vendor_attributes() {
+    let di = parse_fixture("/dev/test", "sata_kingston_a400", "SATA");
+    assert_eq!(di.model.as_deref(), Some("KINGSTON SA400S37240G"));
+    assert_eq!(di.capacity_str.as_deref(), Some("240 GB"));
+    assert_eq!(di.temp_c, Some(30));
+    assert_eq!(di.temperature_range().unwrap().describe(), "Lifetime range: 17–38°C");
+    assert_eq!(di.power_on_hours, Some(3120));
+    assert_eq!(di.power_cycles, Some(433));
+    // Writes are reported in GiB rather than LBAs
+    assert_eq!(di.data_written_tb, None);
+    assert_eq!(di.smart_attributes.len(), 25);
+    assert_eq!(attr(&di, "170").raw_value, "0/9");
+    assert_eq!(attr(&di, "170").raw_numeric(), None);
+    // Flash_Writes_GiB / Lifetime_Writes_GiB
+    let waf = di.write_amplification.unwrap();
+    assert!((waf - 4321.0 / 3012.0).abs() < 1e-9);
+    let wear = di.ssd_wear().unwrap();
+    assert_eq!(wear.used_percent, Some(4));
+    assert_eq!((wear.avg_erase_count, wear.max_erase_count), (Some(42), Some(61)));
+}
+
+#[test]
+fn sata_intel_320_matches_advisory() {
+    let di = parse_fixture("/dev/test", "sata_intel_320", "SATA");
+    assert_eq!(di.model.as_deref(), Some("INTEL SSDSA2CW120G3"));
+    assert_eq!(di.capacity_str.as_deref(), Some("120 GB"));
+    assert_eq!(di.temp_c, None);
+    assert_eq!(di.power_on_hours, Some(51234));
+    assert_eq!(di.power_cycles, Some(2345));
+    // 4PC10302 predates the 8MB bug fix; 4PC10362 has it
+    assert_eq!(di.advisories.len(), 1);
+    assert!(lookup("INTEL SSDSA2CW120G3", Some("4PC10362")).is_empty());
+    assert_eq!(di.ssd_wear().unwrap().used_percent, Some(6));
+}
+
+#[test]
+fn hdd_seagate_barracuda_smr() {
+    let di = parse_fixture("/dev/test", "hdd_seagate_barracuda_smr", "HDD");
+    assert_eq!(di.model.as_deref(), Some("ST2000DM008-2FR102"));
+    // Desktop drives ship with error recovery control disabled
+    assert_eq!(di.erc.map(|e| (e.read_ds, e.write_ds)), Some((None, None)));
+    assert_eq!(di.capacity_str.as_deref(), Some("2.00 TB"));
+    assert_eq!(di.rotation_rpm, Some(7200));
+    // Airflow_Temperature_Cel keeps the extremes of the current power cycle only
+    assert_eq!(di.temperature_range().unwrap().describe(), "Range since power-on: 22–42°C");
+    assert_eq!(di.device_type.as_deref(), Some("HDD"));
+    // 194 takes precedence over the airflow sensor; raw value has a "(0 18 0 0 0)" suffix
+    assert_eq!(di.temp_c, Some(34));
+    // Seagate appends "(144 12 0)" to the power-on hours raw value
+    assert_eq!(di.power_on_hours, Some(21345));
+    assert_eq!(di.power_cycles, Some(3398));
+    assert_tb(di.data_written_tb, 9.336);
+    assert_tb(di.data_read_tb, 50.568);
+    assert_eq!(attr(&di, "240").raw_value, "20987h+12m+33.456s");
+    assert_eq!(di.advisories.len(), 1);
+}
+
+#[test]
+fn hdd_wd_red_efax() {
+    let di = parse_fixture("/dev/test", "hdd_wd_red_efax", "HDD");
+    assert_eq!(di.model.as_deref(), Some("WDC WD40EFAX-68JH4N0"));
+    assert_eq!(di.serial.as_deref(), Some("WD-WX12D91A2B3C"));
+    assert_eq!(di.capacity_str.as_deref(), Some("4.00 TB"));
+    assert_eq!(di.rotation_rpm, Some(5400));
+    assert_eq!(di.temp_c, Some(36));
+    assert_eq!(di.power_on_hours, Some(26543));
+    assert_eq!(di.power_cycles, Some(110));
+    assert_eq!(attr(&di, "199").raw_numeric(), Some(7));
+    assert_eq!(di.udma_crc_errors(), Some(7));
+    assert_eq!(di.advisories.len(), 1);
+    let erc = di.erc.unwrap();
+    assert_eq!((erc.read_ds, erc.write_ds), (Some(70), Some(70)));
+    assert_eq!(di.last_self_test.as_deref(), Some("Completed without error"));
+}
+
+#[test]
+fn hdd_failing_attribute_is_critical() {
+    let di = parse_fixture("/dev/test", "hdd_toshiba_failing", "HDD");
+    assert_eq!(di.model.as_deref(), Some("TOSHIBA HDWD130"));
+    assert_eq!(di.capacity_str.as_deref(), Some("3.00 TB"));
+    assert_eq!(di.temp_c, Some(40));
+    assert_eq!(di.power_on_hours, Some(48765));
+    assert_eq!(di.power_cycles, Some(2198));
+    // Error log register dumps must not be mistaken for SMART attributes
+    assert_eq!(di.smart_attributes.len(), 17);
+
+    let realloc = attr(&di, "5");
+    assert_eq!(realloc.raw_value, "1976");
+    assert_eq!(realloc.status, AttributeStatus::Critical);
+    // TYPE column: a FAILING_NOW in WHEN_FAILED must not shift it
+    assert!(realloc.pre_fail);
+    assert!(!attr(&di, "197").pre_fail);
+    assert_eq!(di.smart_attributes.iter().filter(|a| a.pre_fail).count(), 7);
+    assert_eq!(attr(&di, "197").raw_numeric(), Some(312));
+    assert_eq!(attr(&di, "198").raw_numeric(), Some(41));
+    let sectors = di.critical_sectors().unwrap();
+    assert_eq!((sectors.reallocated, sectors.pending, sectors.uncorrectable), (Some(1976), Some(312), Some(41)));
+    assert!(sectors.any_nonzero());
+    assert!(di.advisories.is_empty());
+
+    assert_eq!(di.smart_passed, Some(false));
+    assert_eq!(di.last_self_test.as_deref(), Some("Completed: read failure"));
+    assert_eq!(
+        di.problems(),
+        [
+            "SMART overall-health check FAILED",
+            "1 critical SMART attribute(s)",
+            "Last self-test: Completed: read failure",
+        ]
+    );
+}
+
+#[test]
+fn hdd_marginal_attribute_is_warning() {
+    let di = parse_fixture("/dev/test", "hdd_seagate_warning_threshold", "HDD");
+    assert_eq!(di.model.as_deref(), Some("ST3000DM001-1CH166"));
+    assert_eq!(di.temp_c, Some(39));
+    assert_eq!(di.power_on_hours, Some(39876));
+    assert_eq!(di.power_cycles, Some(7612));
+    assert_eq!(attr(&di, "190").status, AttributeStatus::Warning);
+    assert_eq!(attr(&di, "5").status, AttributeStatus::Good);
+    assert_eq!(attr(&di, "188").raw_value, "0 0 3");
+    assert_eq!(di.advisories.len(), 1);
+}
+
+#[test]
+fn hdd_helium_level_attribute() {
+    let di = parse_fixture("/dev/test", "hdd_hgst_helium", "HDD");
+    assert_eq!(di.model.as_deref(), Some("HGST HUH721010ALE600"));
+    assert_eq!(di.capacity, Some(10_000_831_348_736.0));
+    assert_eq!(di.capacity_str.as_deref(), Some("10.0 TB"));
+    assert_eq!(di.temp_c, Some(37));
+    assert_eq!(attr(&di, "22").name, "Helium_Level");
+    assert_eq!(attr(&di, "22").raw_numeric(), Some(100));
+    assert_eq!(di.helium_level(), Some((100, 25)));
+}
+
+#[test]
+fn usb_unknown_bridge_yields_empty_info() {
+    let di = parse_fixture("/dev/test", "usb_unknown_bridge", "SATA");
+    assert_eq!(di.dev, "/dev/test");
+    assert_eq!(di.model, None);
+    assert_eq!(di.serial, None);
+    assert_eq!(di.capacity, None);
+    assert_eq!(di.temp_c, None);
+    assert_eq!(di.power_on_hours, None);
+    assert!(di.smart_attributes.is_empty());
+}
+
+#[test]
+fn sata_smart_disabled_keeps_identity() {
+    let di = parse_fixture("/dev/test", "sata_smart_disabled", "HDD");
+    assert_eq!(di.model.as_deref(), Some("WDC WD10EZEX-08WN4A0"));
+    assert_eq!(di.firmware.as_deref(), Some("01.01A01"));
+    assert_eq!(di.capacity_str.as_deref(), Some("1.00 TB"));
+    assert_eq!(di.rotation_rpm, Some(7200));
+    assert_eq!(di.temp_c, None);
+    assert_eq!(di.power_on_hours, None);
+    assert!(di.smart_attributes.is_empty());
+    assert_eq!(di.smart_enabled, Some(false));
+}
+
+#[test]
+fn nvme_decimal_comma_locale_grouping() {
+    let di = parse_fixture("/dev/test", "nvme_samsung_980_pro_de", "NVMe");
+    assert_eq!(di.capacity, Some(1_000_204_886_016.0));
+    assert_eq!(di.capacity_str.as_deref(), Some("1.00 TB"));
+    assert_eq!(di.health_percent, Some(97));
+    assert_tb(di.data_written_tb, 6.321);
+    assert_tb(di.data_read_tb, 12.863);
+    assert_eq!(di.power_cycles, Some(1532));
+    assert_eq!(di.power_on_hours, Some(4321));
+    assert_eq!(di.unsafe_shutdowns, Some(87));
+    assert_eq!(di.controller_busy_minutes, Some(1234));
+}
+
+#[test]
+fn sata_decimal_comma_locale_capacity() {
+    let di = parse_fixture("/dev/test", "hdd_seagate_barracuda_smr_de", "HDD");
+    assert_eq!(di.capacity, Some(2_000_398_934_016.0));
+    assert_eq!(di.capacity_str.as_deref(), Some("2.00 TB"));
+    assert_eq!(di.power_on_hours, Some(21345));
+}
+
+#[test]
+fn scheduler_active_is_bracketed() {
+    let (active, available) = parse_scheduler("[mq-deadline] kyber bfq none\n");
+    assert_eq!(active.as_deref(), Some("mq-deadline"));
+    assert_eq!(available, vec!["mq-deadline", "kyber", "bfq", "none"]);
+
+    let (active, available) = parse_scheduler("[none] mq-deadline\n");
+    assert_eq!(active.as_deref(), Some("none"));
+    assert_eq!(available.len(), 2);
+}
+
+#[test]
+fn mountinfo_merges_mount_and_super_options() {
+    let text = "\
+22 1 259:2 / / rw,noatime shared:1 - ext4 /dev/nvme0n1p2 rw,discard,errors=remount-ro
+45 22 8:1 / /mnt/backup\\040disk ro,relatime shared:30 - btrfs /dev/sda1 ro,ssd,space_cache=v2
+";
+    let map = parse_mountinfo(text);
+    assert_eq!(map["/"], vec!["rw", "noatime", "discard", "errors=remount-ro"]);
+    let backup = &map["/mnt/backup disk"];
+    assert_eq!(backup[0], "ro");
+    assert!(backup.iter().any(|o| o == "ssd"));
+    assert_eq!(backup.iter().filter(|o| *o == "ro").count(), 1);
+}
+
+#[test]
+fn lsblk_pairs_and_partition_types() {
+    let text = "\
+NAME=\"nvme0n1\" PARTLABEL=\"\" PARTUUID=\"\" PARTTYPE=\"\"
+NAME=\"nvme0n1p1\" PARTLABEL=\"EFI\\x20system\\x20partition\" PARTUUID=\"9f1c2d3e-0001-4a5b-8c7d-0e1f2a3b4c5d\" PARTTYPE=\"c12a7328-f81f-11d2-ba4b-00a0c93ec93b\"
+";
+    let rows = parse_lsblk_pairs(text);
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["PARTLABEL"], "");
+    assert_eq!(rows[1]["NAME"], "nvme0n1p1");
+    assert_eq!(rows[1]["PARTLABEL"], "EFI system partition");
+    assert_eq!(partition_type_name(&rows[1]["PARTTYPE"]), Some("EFI System"));
+    assert_eq!(partition_type_name("0x83"), Some("Linux filesystem"));
+    assert_eq!(partition_type_name("00000000-1111-2222-3333-444444444444"), None);
+}
+
+#[test]
+fn unallocated_space_after_clone() {
+    // 1 TB drive holding a cloned 500 GB layout
+    let text = "\
+NAME=\"sdb\" TYPE=\"disk\" SIZE=\"1000204886016\" PARTLABEL=\"\" PARTUUID=\"\" PARTTYPE=\"\"
+NAME=\"sdb1\" TYPE=\"part\" SIZE=\"536870912\" PARTLABEL=\"\" PARTUUID=\"\" PARTTYPE=\"\"
+NAME=\"sdb2\" TYPE=\"part\" SIZE=\"499569524736\" PARTLABEL=\"\" PARTUUID=\"\" PARTTYPE=\"\"
+";
+    let free = unallocated_bytes(&parse_lsblk_pairs(text)).unwrap();
+    assert_eq!(free, 1_000_204_886_016 - 536_870_912 - 499_569_524_736);
+
+    // Fully partitioned drive only loses alignment padding
+    let text = "\
+NAME=\"sda\" TYPE=\"disk\" SIZE=\"500107862016\"
+NAME=\"sda1\" TYPE=\"part\" SIZE=\"500106788864\"
+";
+    assert_eq!(unallocated_bytes(&parse_lsblk_pairs(text)), None);
+}
+
+#[test]
+fn system_drive_roles() {
+    // Root on LVM inside LUKS, ESP mounted at /boot/efi
+    let text = "\
+NAME=\"nvme0n1\" MOUNTPOINT=\"\" PARTTYPE=\"\"
+NAME=\"nvme0n1p1\" MOUNTPOINT=\"/boot/efi\" PARTTYPE=\"c12a7328-f81f-11d2-ba4b-00a0c93ec93b\"
+NAME=\"nvme0n1p2\" MOUNTPOINT=\"\" PARTTYPE=\"0fc63daf-8483-4772-8e79-3d69d8477de4\"
+NAME=\"luks-root\" MOUNTPOINT=\"\" PARTTYPE=\"\"
+NAME=\"vg0-root\" MOUNTPOINT=\"/\" PARTTYPE=\"\"
+";
+    assert_eq!(system_roles(&parse_lsblk_pairs(text)), (true, true));
+
+    // A second drive with an unmounted ESP left over from another install
+    let text = "\
+NAME=\"sda\" MOUNTPOINT=\"\" PARTTYPE=\"\"
+NAME=\"sda1\" MOUNTPOINT=\"\" PARTTYPE=\"c12a7328-f81f-11d2-ba4b-00a0c93ec93b\"
+NAME=\"sda2\" MOUNTPOINT=\"/home\" PARTTYPE=\"0fc63daf-8483-4772-8e79-3d69d8477de4\"
+";
+    assert_eq!(system_roles(&parse_lsblk_pairs(text)), (false, false));
+}
+
+#[test]
+fn sct_temperature_history() {
+    let text = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/smartctl/sct_temp_history.txt"))
+        .unwrap();
+    let now = 1_700_000_000;
+    let samples = parse_sct_temp_history(&text, now);
+
+    // 8 slots in total, the first one unfilled; skipped rows repeat 31 °C
+    assert_eq!(samples.len(), 7);
+    assert_eq!(samples[0], (now - 6 * 600, 31.0));
+    assert_eq!(samples[3], (now - 3 * 600, 31.0));
+    assert_eq!(samples[6], (now, 36.0));
+}
+
+#[test]
+fn hdparm_apm_levels() {
+    assert_eq!(parse_hdparm_apm("\n/dev/sda:\n APM_level\t= 128\n"), Some(128));
+    // APM disabled reads as 255, the level that disables it
+    assert_eq!(parse_hdparm_apm("\n/dev/sda:\n APM_level\t= off\n"), Some(255));
+    assert_eq!(parse_hdparm_apm("\n/dev/sda:\n APM_level\t= not supported\n"), None);
+}
+
+#[test]
+fn hdparm_security_frozen() {
+    let text = "\
+Commands/features:
+\tEnabled\tSupported:
+\t   *\tSMART feature set
+Security: 
+\tMaster password revision code = 65534
+\t\tsupported
+\tnot\tenabled
+\tnot\tlocked
+\t\tfrozen
+\tnot\texpired: security count
+\t\tsupported: enhanced erase
+\t2min for SECURITY ERASE UNIT. 2min for ENHANCED SECURITY ERASE UNIT.
+Logical Unit WWN Device Identifier: 5002538f41234567
+";
+    let sec = parse_hdparm_security(text).unwrap();
+    assert!(sec.supported && sec.frozen && sec.enhanced_erase);
+    assert!(!sec.enabled && !sec.locked);
+    assert!(sec.erase_time.unwrap().starts_with("2min"));
+
+    assert!(parse_hdparm_security("Commands/features:\n").is_none());
+}
+
+#[test]
+fn sedutil_query_opal() {
+    let text = "\
+/dev/nvme0 NVMe Samsung SSD 970 EVO Plus 1TB             2B2QEXM7 S4EWNX0R123456
+TPer function (0x0001)
+    ACKNAK = N, ASYNC = N. BufferManagement = N, comIDManagement  = N, Streaming = Y, SYNC = Y
+Locking function (0x0002)
+    Locked = N, LockingEnabled = Y, LockingSupported = Y, MBRDone = N, MBREnabled = N, MediaEncrypt = Y
+Geometry function (0x0003)
+    Align = Y, Alignment Granularity = 8 (4096), Logical Block size = 512, Lowest Aligned LBA = 0
+Opal SSC 2.00 function (0x0203)
+    Base comID = 0x0001, Initial PIN = 0x00, Reverted PIN = 0x00, comIDs = 1
+";
+    let sed = parse_sedutil_query(text).unwrap();
+    assert_eq!(sed.ssc.as_deref(), Some("Opal SSC 2.00"));
+    assert!(sed.media_encrypt && sed.locking_supported && sed.locking_enabled);
+    assert!(!sed.locked);
+
+    assert!(parse_sedutil_query("/dev/sda is Not a TCG Enterprise, Opal, OpalLite or Pyrite drive\n").is_none());
+}
+
+#[test]
+fn privacy_mask_hides_serial_and_wwn() {
+    let mut di = parse_fixture("/dev/test", "hdd_wd_red_efax", "HDD");
+    di.by_id = Some("/dev/disk/by-id/ata-WDC_WD40EFAX-68JH4N0_WD-WX12D91A2B3C".into());
+    let masked = di.masked();
+    assert_eq!(masked.serial.as_deref(), Some("•••••••••••••3C"));
+    assert_eq!(masked.by_id.as_deref(), Some("/dev/disk/by-id/ata-WDC_WD40EFAX-68JH4N0_•••••••••••••3C"));
+    let wwn = masked.wwn.unwrap();
+    assert_eq!(wwn.chars().count(), di.wwn.as_ref().unwrap().len());
+    assert!(wwn.starts_with("••••") && !wwn.contains("0x"));
+    // Everything else is left alone
+    assert_eq!(masked.model, di.model);
+}
+
+#[test]
+fn nvme_id_ctrl_fields() {
+    let text = "\
+NVME Identify Controller:
+vid       : 0x144d
+ssvid     : 0x144d
+sn        : S5GXNF0R123456A
+mn        : Samsung SSD 980 PRO 1TB
+apsta     : 0x1
+hmpre     : 0
+hmmin     : 0
+vwc       : 0x7
+subnqn    : nqn.2014.08.org.nvmexpress:144d144dS5GXNF0R123456A     Samsung SSD 980 PRO 1TB
+ioccsz    : 0
+";
+    let mut di = parse_fixture("/dev/test", "nvme_samsung_980_pro", "NVMe");
+    let controller = di.nvme_controller.as_mut().unwrap();
+    parse_id_ctrl(text, controller);
+    assert_eq!(controller.subsystem_nqn.as_deref(), Some("nqn.2014.08.org.nvmexpress:144d144dS5GXNF0R123456A"));
+    assert_eq!(controller.volatile_write_cache, Some(true));
+    assert_eq!(controller.hmb_preferred_bytes, Some(0));
+    assert_eq!(controller.apst_supported, Some(true));
+    // smartctl fields are kept
+    assert_eq!(controller.controller_id, Some(6));
+
+    // Privacy mode masks the serial embedded in the NQN
+    let masked = di.masked().nvme_controller.unwrap().subsystem_nqn.unwrap();
+    assert_eq!(masked, "nqn.2014.08.org.nvmexpress:144d144d•••••••••••••6A");
+
+    // A blank NQN is not taken from the next line
+    let mut blank = di.nvme_controller.clone().unwrap();
+    blank.subsystem_nqn = None;
+    parse_id_ctrl("subnqn    : \nioccsz    : 0\nhmpre     : 16384\nvwc       : 0x6\n", &mut blank);
+    assert_eq!(blank.subsystem_nqn, None);
+    assert_eq!(blank.hmb_preferred_bytes, Some(64 * 1024 * 1024));
+    assert_eq!(blank.volatile_write_cache, Some(false));
+}
+
+#[test]
+fn nvme_host_memory_buffer_feature() {
+    let enabled = "\
+get-feature:0x0d (Host Memory Buffer), Current value:0x00000001
+\tEnable Host Memory (EHM): Enabled
+\tHost Memory Descriptor List Entry Count (HMDLEC): 4
+\tHost Memory Buffer Size (HSIZE): 16384
+";
+    assert_eq!(parse_hmb_feature(enabled), Some(64 * 1024 * 1024));
+    let disabled = "get-feature:0x0d (Host Memory Buffer), Current value:0x00000000\n\tEnable Host Memory (EHM): Disabled\n";
+    assert_eq!(parse_hmb_feature(disabled), Some(0));
+    assert_eq!(parse_hmb_feature("NVMe status: Invalid Field in Command(0x2)\n"), None);
+}
+
+#[test]
+fn nvme_apst_feature() {
+    let text = "\
+get-feature:0x0c (Autonomous Power State Transition), Current value:0x00000001
+\tAutonomous Power State Transition Enable (APSTE): Enabled
+\tAuto PST Entries\t.................
+\tEntry[ 0]
+\t.................
+\tIdle Time Prior to Transition (ITPT): 86 ms
+\tIdle Transition Power State   (ITPS): 3
+\t.................
+\tEntry[ 1]
+\t.................
+\tIdle Time Prior to Transition (ITPT): 86 ms
+\tIdle Transition Power State   (ITPS): 3
+\t.................
+\tEntry[ 3]
+\t.................
+\tIdle Time Prior to Transition (ITPT): 750 ms
+\tIdle Transition Power State   (ITPS): 4
+\t.................
+\tEntry[ 4]
+\t.................
+\tIdle Time Prior to Transition (ITPT): 0 ms
+\tIdle Transition Power State   (ITPS): 0
+";
+    let mut apst = parse_apst_feature(text).unwrap();
+    assert!(apst.enabled);
+    assert_eq!(apst.transitions, [(86, 3), (750, 4)]);
+    apst.max_latency_us = Some(100_000);
+    assert_eq!(apst.summary(), "enabled: PS3 after 86 ms idle, PS4 after 750 ms idle; latency limit 100000 µs");
+
+    let mut off = parse_apst_feature("get-feature:0x0c (Autonomous Power State Transition), Current value:0x00000000\n").unwrap();
+    off.max_latency_us = Some(0);
+    assert_eq!(off.summary(), "disabled; turned off by the kernel (latency limit 0)");
+    assert_eq!(parse_apst_feature("NVMe status: Invalid Field in Command(0x2)\n"), None);
+}
+
+#[test]
+fn sata_port_scsi_host() {
+    let device = std::path::Path::new("/sys/devices/pci0000:00/0000:00:17.0/ata3/host2/target2:0:0/2:0:0:0");
+    assert_eq!(scsi_host_name(device).as_deref(), Some("host2"));
+    // NVMe namespaces have no SCSI host
+    assert_eq!(scsi_host_name(std::path::Path::new("/sys/devices/pci0000:00/0000:00:1d.0/0000:3d:00.0/nvme/nvme0")), None);
+}
+
+#[test]
+fn network_and_usb_transports() {
+    assert_eq!(nvme_transport_name("pcie"), None);
+    assert_eq!(nvme_transport_name("tcp").as_deref(), Some("NVMe/TCP"));
+    assert_eq!(nvme_transport_name("rdma").as_deref(), Some("NVMe/RDMA"));
+    assert_eq!(nvme_transport_name("loop").as_deref(), Some("NVMe loop"));
+
+    assert!(is_iscsi_device(std::path::Path::new("/sys/devices/platform/host3/session1/target3:0:0/3:0:0:0")));
+    // A local SATA disk and a USB stick have no iSCSI session
+    assert!(!is_iscsi_device(std::path::Path::new("/sys/devices/pci0000:00/0000:00:17.0/ata3/host2/target2:0:0/2:0:0:0")));
+    assert!(!is_iscsi_device(std::path::Path::new("/sys/devices/pci0000:00/0000:00:14.0/usb2/2-1/2-1:1.0/host4/target4:0:0/4:0:0:0")));
+
+    // Only the USB stick goes through a USB controller
+    assert!(is_usb_device(std::path::Path::new("/sys/devices/pci0000:00/0000:00:14.0/usb2/2-1/2-1:1.0/host4/target4:0:0/4:0:0:0")));
+    assert!(!is_usb_device(std::path::Path::new("/sys/devices/platform/host3/session1/target3:0:0/3:0:0:0")));
+
+    assert_eq!(format_capacity(500_107_862_016), "500 GB");
+    assert_eq!(format_capacity(2_000_398_934_016), "2.00 TB");
+    assert_eq!(format_capacity(32_212_254_720), "32.2 GB");
+}
+
+#[test]
+fn nvme_firmware_slot_log() {
+    let text = "\
+Firmware Log for device:nvme0
+afi  : 0x21
+frs1 : 0x2020445733363032 (620361WD)
+frs2 : 0x2020445734363032 (620461WD)
+frs3 : 0x0000000000000000 ()
+";
+    let fw = parse_fw_log(text).unwrap();
+    assert_eq!(fw.active, 1);
+    assert_eq!(fw.next_reset, Some(2));
+    assert_eq!(fw.slots, [(1, "620361WD".to_string()), (2, "620461WD".to_string())]);
+
+    // Nothing pending when the next-reset field repeats the active slot or is empty
+    assert_eq!(parse_fw_log("afi  : 0x11\nfrs1 : 0x3031 (10)\n").unwrap().next_reset, None);
+    assert_eq!(parse_fw_log("NVMe status: Invalid Log Page(0x9)\n"), None);
+}
+
+#[test]
+fn nvme_persistent_event_log() {
+    let text = "\
+Persistent Event Log for device: nvme0n1
+Action for Persistent Event Log: 1
+Event Type: Power-on or Reset Event(0x6)
+Event Timestamp: 12345
+Event Type: Firmware Commit Event(0x4)
+Event Timestamp: 1696312345000
+Event Type: SMART / Health Log Snapshot Event(0x1)
+Event Timestamp: 1696312400000
+Event Type: Thermal Excursion Event(0xd)
+Event Timestamp: 1698001000000
+Event Type: Sanitize Start Event(0x9)
+Event Timestamp: 1699000000000
+Event Type: Sanitize Completion Event(0xa)
+Event Timestamp: 1699000600000
+";
+    let events = parse_persistent_event_log(text);
+    // The SMART snapshot and the sanitize completion are not counted
+    assert_eq!(events.len(), 4);
+    // A timestamp counted from power-on (clock never set) has no date
+    assert_eq!(events[0].category, EventCategory::PowerOnReset);
+    assert_eq!(events[0].timestamp, None);
+    assert_eq!(events[1].category, EventCategory::FirmwareCommit);
+    assert_eq!(events[1].timestamp, Some(1_696_312_345));
+    assert_eq!(
+        event_counts(&events),
+        [
+            (EventCategory::ThermalExcursion, 1),
+            (EventCategory::FirmwareCommit, 1),
+            (EventCategory::Sanitize, 1),
+            (EventCategory::PowerOnReset, 1),
+        ]
+    );
+
+    let di = parse_fixture("/dev/test", "nvme_wd_sn850x", "nvme");
+    assert!(di.nvme_controller.unwrap().supports_persistent_event_log());
+}
+
+#[test]
+fn nvme_endurance_groups_and_namespaces() {
+    let mut controller = parse_fixture("/dev/test", "nvme_samsung_980_pro", "NVMe").nvme_controller.unwrap();
+    parse_id_ctrl("ctratt    : 0x10\nendgidmax : 1\n", &mut controller);
+    assert!(controller.supports_endurance_groups());
+    assert_eq!(controller.endurance_group_max, Some(1));
+
+    let log = "\
+Endurance Group Log for NVME device:nvme0 Group ID:1
+critical_warning        : 0
+endurance_group_features: 0
+avl_spare               : 98
+avl_spare_threshold     : 10
+percent_used            : 7%
+domain_identifier       : 0
+endurance_estimate      : 0
+data_units_read         : 1200000
+data_units_written      : 2000000
+media_units_written     : 2600000
+host_read_cmds          : 0
+host_write_cmds         : 0
+media_data_integrity_err: 0
+num_err_info_log_entries: 0
+total_end_grp_cap       : 3840755982336
+unalloc_end_grp_cap     : 840755982336
+";
+    let group = parse_endurance_log(log, 1).unwrap();
+    assert_eq!(group.percent_used, Some(7));
+    assert_eq!(group.available_spare, Some(98));
+    assert_eq!(group.host_bytes_written, Some(1_024_000_000_000));
+    assert_eq!(group.media_bytes_written, Some(1_331_200_000_000));
+    assert_eq!(group.total_bytes, Some(3_840_755_982_336));
+    assert_eq!(group.unallocated_bytes, Some(840_755_982_336));
+    assert!((group.write_amplification().unwrap() - 1.3).abs() < 1e-9);
+    assert_eq!(parse_endurance_log("NVMe status: Invalid Log Page(0x2109)\n", 2), None);
+
+    let id_ns = "\
+NVME Identify Namespace 1:
+nsze    : 0x1749a956
+ncap    : 0x1749a956
+nuse    : 0xba4d4ab
+nsfeat  : 0
+nlbaf   : 1
+flbas   : 0x1
+endgid  : 1
+lbaf  0 : ms:0   lbads:9  rp:0x1
+lbaf  1 : ms:0   lbads:12 rp:0 (in use)
+";
+    let ns = parse_id_ns(id_ns, 1).unwrap();
+    assert_eq!(ns.endurance_group, Some(1));
+    assert_eq!(ns.size_bytes, 0x1749a956 * 4096);
+    assert_eq!(ns.used_bytes, 0xba4d4ab * 4096);
+    assert_eq!(parse_id_ns("nsze    : 0x100\n", 2), None);
+
+    // With a single group, a namespace that does not report its group belongs to it
+    controller.endurance_groups = vec![group];
+    controller.namespace_usage = vec![ns, NamespaceUsage { nsid: 2, endurance_group: None, size_bytes: 4096, used_bytes: 0 }];
+    assert_eq!(controller.group_namespaces(1).len(), 2);
+}
@@ -0,0 +1,117 @@
+// Tests for the JSON module and the report export/import round trip.
+
+mod common;
+
+use common::parse_fixture;
+use ssd_info_cli::gui::report::Report;
+use ssd_info_cli::json::Json;
+use ssd_info_cli::models::{ApstStatus, AttributeStatus, EnduranceGroup, FirmwareSlots, NamespaceUsage, PartitionInfo};
+
+#[test]
+fn json_parse_and_print() {
+    let text = r#" {"a": [1, -2.5e1, true, null], "b\n": "q\"é😀", "c": {}} "#;
+    let json = Json::parse(text).unwrap();
+    assert_eq!(json.get("a").unwrap().as_array().unwrap()[1].as_f64(), Some(-25.0));
+    assert_eq!(json.get("b\n").and_then(Json::as_str), Some("q\"é😀"));
+    assert_eq!(Json::parse(&json.to_string()).unwrap(), json);
+    assert_eq!(Json::parse(&json.to_pretty()).unwrap(), json);
+    assert_eq!(json.to_string(), r#"{"a":[1,-25,true,null],"b\n":"q\"é😀","c":{}}"#);
+
+    assert!(Json::parse("{\"a\": 1,}").is_err());
+    assert!(Json::parse("[1] x").is_err());
+    assert!(Json::parse("\"open").is_err());
+}
+
+#[test]
+fn json_rejects_deep_nesting() {
+    let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+    assert!(Json::parse(&nested(128)).is_ok());
+    assert!(Json::parse(&nested(129)).unwrap_err().contains("nesting too deep"));
+    // Deep enough to overflow the stack without the limit
+    assert!(Json::parse(&"[{\"a\":".repeat(300_000)).unwrap_err().contains("nesting too deep"));
+}
+
+#[test]
+fn report_round_trip() {
+    let mut failing = parse_fixture("/dev/sda", "hdd_toshiba_failing", "HDD");
+    failing.partitions.push(PartitionInfo {
+        mount_point: "/data".into(),
+        fs_type: "ext4".into(),
+        total_gb: 3000.0,
+        used_gb: 1000.0,
+        free_gb: 2000.0,
+        used_percent: 33.3,
+        mount_options: vec!["rw".into(), "noatime".into()],
+        device: "sda1".into(),
+        start_bytes: Some(1_048_576),
+        alignment_bytes: Some(4096),
+        fs_label: Some("Data".into()),
+        part_label: None,
+        part_uuid: Some("1234-abcd".into()),
+        part_type: Some("0x83".into()),
+        inodes_total: None,
+        inodes_used: None,
+    });
+    let report = Report {
+        host: "workstation".into(),
+        generated_at: 1_700_000_000,
+        drives: vec![failing.clone(), parse_fixture("/dev/sda", "sata_intel_320", "SATA")],
+    };
+
+    let loaded = Report::from_json(&Json::parse(&report.to_json().to_pretty()).unwrap()).unwrap();
+    assert_eq!(loaded.host, "workstation");
+    assert_eq!(loaded.generated_at, 1_700_000_000);
+    assert_eq!(loaded.drives.len(), 2);
+
+    let di = &loaded.drives[0];
+    assert_eq!(di.model, failing.model);
+    assert_eq!(di.serial, failing.serial);
+    assert_eq!(di.power_on_hours, failing.power_on_hours);
+    assert_eq!(di.smart_passed, Some(false));
+    assert_eq!(di.last_self_test, failing.last_self_test);
+    assert_eq!(di.smart_attributes.len(), failing.smart_attributes.len());
+    assert_eq!(di.smart_attributes[0].raw_value, failing.smart_attributes[0].raw_value);
+    assert!(di.smart_attributes.iter().any(|a| a.status == AttributeStatus::Critical));
+    assert!(di.smart_attributes.iter().zip(&failing.smart_attributes).all(|(a, b)| a.pre_fail == b.pre_fail));
+    assert_eq!(di.problems(), failing.problems());
+    assert_eq!(di.partitions[0].mount_options, ["rw", "noatime"]);
+    assert_eq!(di.partitions[0].is_aligned(), Some(true));
+    // Advisories are looked up again on import
+    assert_eq!(loaded.drives[1].advisories.len(), 1);
+}
+
+#[test]
+fn report_keeps_nvme_controller() {
+    let mut nvme = parse_fixture("/dev/sda", "nvme_wd_sn850x", "NVMe");
+    let controller = nvme.nvme_controller.as_mut().unwrap();
+    controller.subsystem_nqn = Some("nqn.2018-01.com.wdc:nguid:E8238FA6BF53-0001-001B448B4A1B2C3D".into());
+    controller.volatile_write_cache = Some(true);
+    controller.hmb_preferred_bytes = Some(200 << 20);
+    controller.hmb_allocated_bytes = Some(0);
+    controller.firmware_slots =
+        Some(FirmwareSlots { active: 1, next_reset: Some(2), slots: vec![(1, "620361WD".into()), (2, "620461WD".into())] });
+    controller.apst = Some(ApstStatus { enabled: true, transitions: vec![(86, 3), (750, 4)], max_latency_us: Some(100_000) });
+    controller.endurance_groups = vec![EnduranceGroup {
+        id: 1,
+        percent_used: Some(3),
+        available_spare: Some(100),
+        host_bytes_written: Some(512_000_000),
+        media_bytes_written: Some(640_000_000),
+        total_bytes: None,
+        unallocated_bytes: None,
+    }];
+    controller.namespace_usage =
+        vec![NamespaceUsage { nsid: 1, endurance_group: Some(1), size_bytes: 2_000_398_934_016, used_bytes: 1_000_000_000_000 }];
+    let report = Report { host: "workstation".into(), generated_at: 0, drives: vec![nvme.clone(), parse_fixture("/dev/sda", "sata_intel_320", "SATA")] };
+
+    let loaded = Report::from_json(&Json::parse(&report.to_json().to_pretty()).unwrap()).unwrap();
+    assert_eq!(loaded.drives[0].nvme_controller, nvme.nvme_controller);
+    assert_eq!(loaded.drives[1].nvme_controller, None);
+}
+
+#[test]
+fn report_rejects_other_documents() {
+    assert!(Report::from_json(&Json::parse(r#"{"drives": []}"#).unwrap()).is_err());
+    let newer = r#"{"format": "ssd_info_cli report", "version": 99, "drives": []}"#;
+    assert!(Report::from_json(&Json::parse(newer).unwrap()).is_err());
+}
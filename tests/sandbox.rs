@@ -0,0 +1,25 @@
+// Tests for running host tools from inside a Flatpak sandbox.
+
+use ssd_info_cli::sandbox::{flatpak_host_command, host_command, in_flatpak};
+
+#[test]
+fn flatpak_spawn_passes_the_environment_as_options() {
+    let mut cmd = flatpak_host_command("smartctl", &[("LC_ALL", "C")]);
+    cmd.args(["-a", "/dev/sda"]);
+    assert_eq!(cmd.get_program(), "flatpak-spawn");
+    let args: Vec<_> = cmd.get_args().map(|a| a.to_str().unwrap()).collect();
+    assert_eq!(args, ["--host", "--env=LC_ALL=C", "smartctl", "-a", "/dev/sda"]);
+    assert_eq!(cmd.get_envs().count(), 0);
+}
+
+#[test]
+fn host_command_runs_the_tool_directly_outside_flatpak() {
+    if in_flatpak() {
+        return;
+    }
+    let cmd = host_command("smartctl", &[("LC_ALL", "C")]);
+    assert_eq!(cmd.get_program(), "smartctl");
+    assert_eq!(cmd.get_args().count(), 0);
+    let envs: Vec<_> = cmd.get_envs().map(|(k, v)| (k.to_str().unwrap(), v.and_then(|v| v.to_str()))).collect();
+    assert_eq!(envs, [("LC_ALL", Some("C"))]);
+}
@@ -0,0 +1,259 @@
+// End-to-end scans of a fake device tree: /dev and /sys entries in a temporary directory and stub
+// smartctl, hdparm and nvme scripts that print the captured outputs in tests/fixtures/smartctl and tests/fixtures/hdparm.
+
+use ssd_info_cli::gui::disk_scanner::{detected_devices_from, forget_static_probes, is_sleep_state, scan_disks_from, SystemRoot};
+use std::os::unix::fs::{symlink, PermissionsExt};
+use std::path::Path;
+
+/// Writes a file, creating its parent directories.
+fn write(path: &Path, contents: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, contents).unwrap();
+}
+
+/// Installs a stub tool that prints a fixed output for each argument list it knows and nothing otherwise.
+fn stub(root: &SystemRoot, program: &str, cases: &[(&str, &str)]) {
+    let mut script = String::from("#!/bin/sh\ncase \"$*\" in\n");
+    for (args, output) in cases {
+        script.push_str(&format!("  \"{}\") cat <<'EOF'\n{}\nEOF\n  ;;\n", args, output));
+    }
+    script.push_str("esac\n");
+    let path = root.bin.as_ref().unwrap().join(program);
+    write(&path, &script);
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+/// Reads a captured smartctl output.
+fn fixture(name: &str) -> String {
+    std::fs::read_to_string(format!("{}/tests/fixtures/smartctl/{}.txt", env!("CARGO_MANIFEST_DIR"), name)).unwrap()
+}
+
+#[test]
+fn scan_reads_fake_device_tree() {
+    let dir = std::env::temp_dir().join(format!("ssd_info_scanner_{}", std::process::id()));
+    let root = SystemRoot::under(&dir);
+
+    // Three whole drives; the partition, the loop device and the by-id directory are not drives
+    for name in ["nvme0n1", "nvme0n1p1", "sda", "sdb", "sda1", "loop0"] {
+        write(&root.dev.join(name), "");
+    }
+    std::fs::create_dir_all(root.dev.join("disk/by-id")).unwrap();
+    symlink("../../sda", root.dev.join("disk/by-id/ata-CT1000MX500SSD1_1915E1F5A2B7")).unwrap();
+    symlink("../../sda", root.dev.join("disk/by-id/wwn-0x500a0751e1f5a2b7")).unwrap();
+
+    // sda is a flash drive on an AHCI port, sdb spins
+    write(&root.sys.join("block/sda/queue/rotational"), "0\n");
+    write(&root.sys.join("block/sda/queue/scheduler"), "[mq-deadline] kyber none\n");
+    write(&root.sys.join("block/sda/queue/nr_requests"), "64\n");
+    let port = root.sys.join("devices/pci0000:00/0000:00:17.0/ata3/host2/target2:0:0/2:0:0:0");
+    std::fs::create_dir_all(&port).unwrap();
+    symlink(&port, root.sys.join("block/sda/device")).unwrap();
+    write(&root.sys.join("class/scsi_host/host2/link_power_management_policy"), "med_power_with_dipm\n");
+    write(&root.sys.join("block/sdb/queue/rotational"), "1\n");
+
+    stub(&root, "smartctl", &[
+        ("-a /dev/nvme0n1", &fixture("nvme_wd_sn850x")),
+        ("-n standby -a -l scterc /dev/sda", &fixture("sata_crucial_mx500")),
+    ]);
+    stub(&root, "hdparm", &[("-C /dev/sdb", " drive state is:  standby"), ("-W /dev/sda", " write-caching =  1 (on)")]);
+    stub(&root, "nvme", &[("get-feature /dev/nvme0n1 -f 0x02", "get-feature:0x02 (Power Management), Current value:0x00000003")]);
+
+    assert_eq!(detected_devices_from(&root).unwrap(), ["/dev/nvme0n1", "/dev/sda", "/dev/sdb"]);
+
+    let mut probed = Vec::new();
+    let drives = scan_disks_from(&root, |dev| probed.push(dev.to_string())).unwrap();
+    assert_eq!(probed, ["/dev/nvme0n1", "/dev/sda", "/dev/sdb"]);
+    let kinds: Vec<(&str, &str)> = drives.iter().map(|d| (d.dev.as_str(), d.kind.as_str())).collect();
+    assert_eq!(kinds, [("/dev/nvme0n1", "NVMe"), ("/dev/sda", "SATA"), ("/dev/sdb", "HDD")]);
+
+    let nvme = &drives[0];
+    assert!(nvme.model.as_deref().unwrap().contains("WD_BLACK SN850X"));
+    assert_eq!(nvme.power_state.as_deref(), Some("PS3"));
+    // Tools missing from the stub directory count as not installed
+    assert_eq!(nvme.write_cache, None);
+
+    let sata = &drives[1];
+    assert_eq!(sata.model.as_deref(), Some("CT1000MX500SSD1"));
+    assert_eq!(sata.network_transport, None);
+    assert_eq!(sata.write_cache, Some(true));
+    assert_eq!(sata.by_id.as_deref(), Some("/dev/disk/by-id/ata-CT1000MX500SSD1_1915E1F5A2B7"));
+    let queue = sata.queue.as_ref().unwrap();
+    assert_eq!((queue.scheduler.as_deref(), queue.nr_requests), (Some("mq-deadline"), Some(64)));
+    let link = sata.link_power.as_ref().unwrap();
+    assert_eq!((link.host.as_str(), link.policy.as_str()), ("host2", "med_power_with_dipm"));
+    // The host's mounted filesystems do not leak into the fake tree
+    assert!(sata.partitions.is_empty());
+
+    // A sleeping drive is not woken for smartctl
+    let hdd = &drives[2];
+    assert_eq!(hdd.power_state.as_deref(), Some("standby"));
+    assert_eq!(hdd.model, None);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn drives_in_sleep_mode_are_not_woken() {
+    let dir = std::env::temp_dir().join(format!("ssd_info_scanner_sleep_{}", std::process::id()));
+    let root = SystemRoot::under(&dir);
+    write(&root.dev.join("sdb"), "");
+    write(&root.sys.join("block/sdb/queue/rotational"), "1\n");
+    // smartctl would report the drive if it were asked
+    stub(&root, "smartctl", &[("-n standby -a -l scterc /dev/sdb", &fixture("hdd_wd_red_efax"))]);
+    let sleeping = std::fs::read_to_string(format!("{}/tests/fixtures/hdparm/sleeping.txt", env!("CARGO_MANIFEST_DIR"))).unwrap();
+    stub(&root, "hdparm", &[("-C /dev/sdb", &sleeping)]);
+
+    let drives = scan_disks_from(&root, |_| {}).unwrap();
+    assert_eq!(drives[0].power_state.as_deref(), Some("sleeping"));
+    assert!(drives[0].power_state.as_deref().is_some_and(is_sleep_state));
+    assert!(drives[0].cached);
+    assert_eq!(drives[0].model, None);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn network_drives_are_described_from_sysfs() {
+    let dir = std::env::temp_dir().join(format!("ssd_info_scanner_net_{}", std::process::id()));
+    let root = SystemRoot::under(&dir);
+    for name in ["nvme1n1", "sdc"] {
+        write(&root.dev.join(name), "");
+    }
+    // No tools at all: a network drive must not need smartctl
+    std::fs::create_dir_all(root.bin.as_ref().unwrap()).unwrap();
+
+    // NVMe/TCP namespace behind a native multipath subsystem
+    let subsystem = root.sys.join("devices/virtual/nvme-subsystem/nvme-subsys1");
+    write(&subsystem.join("nvme1/transport"), "tcp\n");
+    write(&subsystem.join("model"), "Linux\n");
+    write(&subsystem.join("serial"), "f1d2a4c3b5e6\n");
+    write(&subsystem.join("firmware_rev"), "6.8.0\n");
+    std::fs::create_dir_all(root.sys.join("block/nvme1n1")).unwrap();
+    symlink(&subsystem, root.sys.join("block/nvme1n1/device")).unwrap();
+    write(&root.sys.join("block/nvme1n1/size"), "209715200\n");
+
+    // iSCSI LUN
+    let lun = root.sys.join("devices/platform/host3/session1/target3:0:0/3:0:0:0");
+    write(&lun.join("vendor"), "LIO-ORG \n");
+    write(&lun.join("model"), "backstore1\n");
+    write(&root.sys.join("block/sdc/queue/rotational"), "1\n");
+    symlink(&lun, root.sys.join("block/sdc/device")).unwrap();
+    write(&root.sys.join("block/sdc/size"), "3906250000\n");
+
+    let drives = scan_disks_from(&root, |_| {}).unwrap();
+    assert_eq!(drives.len(), 2);
+
+    let nvme = &drives[0];
+    assert_eq!(nvme.network_transport.as_deref(), Some("NVMe/TCP"));
+    assert_eq!((nvme.model.as_deref(), nvme.serial.as_deref(), nvme.firmware.as_deref()), (Some("Linux"), Some("f1d2a4c3b5e6"), Some("6.8.0")));
+    assert_eq!(nvme.capacity_str.as_deref(), Some("107 GB"));
+    assert_eq!((nvme.health_percent, nvme.smart_passed), (None, None));
+
+    let iscsi = &drives[1];
+    assert!(iscsi.is_network_attached());
+    assert_eq!(iscsi.network_transport.as_deref(), Some("iSCSI"));
+    assert_eq!(iscsi.model.as_deref(), Some("LIO-ORG backstore1"));
+    assert_eq!(iscsi.capacity, Some(2_000_000_000_000.0));
+    assert!(iscsi.problems().is_empty());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn card_readers_and_flash_sticks_are_flagged_removable() {
+    let dir = std::env::temp_dir().join(format!("ssd_info_scanner_usb_{}", std::process::id()));
+    let root = SystemRoot::under(&dir);
+    for name in ["sdb", "sdc", "sdd"] {
+        write(&root.dev.join(name), "");
+    }
+    let usb = |port: &str, lun: &str| root.sys.join(format!("devices/pci0000:00/0000:00:14.0/usb2/{}/{}:1.0/host4/target4:0:0/{}", port, port, lun));
+
+    // sdb: card reader with the removable flag
+    write(&root.sys.join("block/sdb/removable"), "1\n");
+    // sdc: flash stick that clears the flag but passes no SMART data through
+    write(&root.sys.join("block/sdc/removable"), "0\n");
+    std::fs::create_dir_all(usb("2-1", "4:0:0:0")).unwrap();
+    symlink(usb("2-1", "4:0:0:0"), root.sys.join("block/sdc/device")).unwrap();
+    // sdd: USB SSD whose bridge passes SMART through
+    write(&root.sys.join("block/sdd/removable"), "0\n");
+    write(&root.sys.join("block/sdd/queue/rotational"), "0\n");
+    std::fs::create_dir_all(usb("2-2", "5:0:0:0")).unwrap();
+    symlink(usb("2-2", "5:0:0:0"), root.sys.join("block/sdd/device")).unwrap();
+    stub(&root, "smartctl", &[("-n standby -a -l scterc /dev/sdd", &fixture("sata_samsung_870_evo"))]);
+
+    let drives = scan_disks_from(&root, |_| {}).unwrap();
+    let removable: Vec<(&str, bool)> = drives.iter().map(|d| (d.dev.as_str(), d.removable)).collect();
+    assert_eq!(removable, [("/dev/sdb", true), ("/dev/sdc", true), ("/dev/sdd", false)]);
+    assert!(!drives[0].has_smart_data());
+    assert!(drives[2].has_smart_data());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn battery_saver_leaves_suspended_drives_asleep() {
+    let dir = std::env::temp_dir().join(format!("ssd_info_scanner_pm_{}", std::process::id()));
+    let mut root = SystemRoot::under(&dir);
+    write(&root.dev.join("nvme0n1"), "");
+    // The controller's PCI function is runtime-suspended
+    write(&root.sys.join("block/nvme0n1/device/device/power/runtime_status"), "suspended\n");
+    stub(&root, "smartctl", &[("-a /dev/nvme0n1", &fixture("nvme_wd_sn850x"))]);
+
+    let awake = scan_disks_from(&root, |_| {}).unwrap();
+    assert!(!awake[0].cached);
+    assert!(awake[0].model.is_some());
+
+    root.skip_suspended = true;
+    let asleep = scan_disks_from(&root, |_| {}).unwrap();
+    assert!(asleep[0].cached);
+    assert_eq!((asleep[0].model.as_deref(), asleep[0].power_state.as_deref()), (None, Some("suspended")));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn nvme_static_probes_are_read_once_until_a_manual_rescan() {
+    let dir = std::env::temp_dir().join(format!("ssd_info_scanner_idctrl_{}", std::process::id()));
+    let root = SystemRoot::under(&dir);
+    write(&root.dev.join("nvme0n1"), "");
+    stub(&root, "smartctl", &[("-a /dev/nvme0n1", &fixture("nvme_wd_sn850x"))]);
+    // Logs the subcommand of every run in a file next to it
+    let calls = dir.join("nvme-calls");
+    let script = format!(
+        "#!/bin/sh\necho \"$1\" >> '{}'\nif [ \"$1\" = id-ctrl ]; then\n  printf 'oacs      : 0x17\\nsubnqn    : nqn.2018-01.com.wdc:nguid:E8238FA6BF53\\n'\nfi\n",
+        calls.display()
+    );
+    let nvme = root.bin.as_ref().unwrap().join("nvme");
+    write(&nvme, &script);
+    std::fs::set_permissions(&nvme, std::fs::Permissions::from_mode(0o755)).unwrap();
+    // sedutil-cli that starts but fails, as flatpak-spawn does when the host lacks it
+    let sedutil = root.bin.as_ref().unwrap().join("sedutil-cli");
+    write(&sedutil, "#!/bin/sh\necho 'flatpak-spawn: sedutil-cli: not found' >&2\nexit 1\n");
+    std::fs::set_permissions(&sedutil, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let count = |subcommand: &str| std::fs::read_to_string(&calls).unwrap().lines().filter(|l| *l == subcommand).count();
+
+    for _ in 0..3 {
+        let drives = scan_disks_from(&root, |_| {}).unwrap();
+        let controller = drives[0].nvme_controller.as_ref().unwrap();
+        assert_eq!(controller.subsystem_nqn.as_deref(), Some("nqn.2018-01.com.wdc:nguid:E8238FA6BF53"));
+        // OACS bit 0 (Security Send/Receive) marks the drive as SED-capable when sedutil-cli fails
+        assert!(drives[0].sed.is_some());
+    }
+    assert_eq!((count("id-ctrl"), count("fw-log"), count("sanitize-log")), (1, 1, 1));
+    // The power state and write cache features are read on every refresh, APST only once
+    assert_eq!(count("get-feature"), 3 * 2 + 1);
+
+    // A manual rescan reads everything again
+    forget_static_probes(None);
+    scan_disks_from(&root, |_| {}).unwrap();
+    assert_eq!((count("id-ctrl"), count("fw-log"), count("sanitize-log")), (2, 2, 2));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn missing_device_directory_is_an_error() {
+    let root = SystemRoot::under("/nonexistent/ssd_info_root");
+    let err = detected_devices_from(&root).unwrap_err();
+    assert!(err.contains("/nonexistent/ssd_info_root/dev"));
+    assert!(scan_disks_from(&root, |_| {}).is_err());
+}
@@ -0,0 +1,94 @@
+// Tests for scheduled report exports: their settings, when they are due, and rotation of old files.
+
+mod common;
+
+use common::parse_fixture;
+use ssd_info_cli::gui::report::Report;
+use ssd_info_cli::gui::scheduled_export::{rotate_exports, ExportFormat, ExportInterval, ExportSchedule, DEFAULT_KEEP};
+use ssd_info_cli::gui::settings::Settings;
+use std::path::PathBuf;
+
+fn report(generated_at: u64) -> Report {
+    let drive = parse_fixture("/dev/nvme0n1", "nvme_wd_sn850x", "NVMe");
+    Report { host: "box".into(), generated_at, drives: vec![drive] }
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("ssd_info_export_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn export_settings_round_trip() {
+    assert_eq!(Settings::parse("").export, None);
+
+    let settings = Settings::parse("[export]\ninterval = weekly\nformat = both\ndir = /srv/ssd reports\nkeep = 12\n");
+    let export = settings.export.clone().unwrap();
+    assert_eq!(export.interval, ExportInterval::Weekly);
+    assert_eq!(export.format, ExportFormat::Both);
+    assert_eq!(export.dir, PathBuf::from("/srv/ssd reports"));
+    assert_eq!(export.keep, 12);
+    assert_eq!(Settings::parse(&settings.to_text()).export, Some(export));
+
+    // Malformed values keep the defaults
+    let export = Settings::parse("[export]\ninterval = hourly\nformat = xml\nkeep = 0\n").export.unwrap();
+    assert_eq!(export, ExportSchedule { dir: export.dir.clone(), ..ExportSchedule::default() });
+    assert_eq!(export.keep, DEFAULT_KEEP);
+}
+
+#[test]
+fn export_is_due_after_one_interval() {
+    let schedule = ExportSchedule { interval: ExportInterval::Weekly, ..ExportSchedule::default() };
+    assert!(schedule.is_due(None, 1_000));
+    assert!(!schedule.is_due(Some(1_000), 1_000 + 6 * 86_400));
+    assert!(schedule.is_due(Some(1_000), 1_000 + 7 * 86_400));
+}
+
+#[test]
+fn exports_are_written_and_rotated() {
+    let dir = temp_dir("rotate");
+    let schedule = ExportSchedule { format: ExportFormat::Both, dir: dir.join("nested"), keep: 2, ..ExportSchedule::default() };
+    assert_eq!(schedule.last_export(), None);
+
+    for time in [100, 200, 300] {
+        let files = schedule.export(&report(time)).unwrap();
+        assert_eq!(files.len(), 2);
+    }
+    assert_eq!(schedule.last_export(), Some(300));
+
+    let mut names: Vec<String> = std::fs::read_dir(&schedule.dir)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().into_string().unwrap())
+        .collect();
+    names.sort();
+    assert_eq!(
+        names,
+        ["ssd-report-auto-200.csv", "ssd-report-auto-200.json", "ssd-report-auto-300.csv", "ssd-report-auto-300.json"]
+    );
+
+    let loaded = Report::load(&schedule.dir.join("ssd-report-auto-300.json")).unwrap();
+    assert_eq!(loaded.generated_at, 300);
+    assert_eq!(loaded.drives.len(), 1);
+    let csv = std::fs::read_to_string(schedule.dir.join("ssd-report-auto-300.csv")).unwrap();
+    assert!(csv.starts_with("time,unix_time,host,device,"));
+    assert_eq!(csv.lines().count(), 2);
+    assert!(csv.lines().nth(1).unwrap().contains(",box,/dev/nvme0n1,"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn rotation_leaves_other_files_alone() {
+    let dir = temp_dir("other");
+    std::fs::create_dir_all(&dir).unwrap();
+    for name in ["ssd-report-auto-1.json", "ssd-report-auto-2.json", "ssd-report-box-1.json", "notes.txt", "ssd-report-auto-x.json"] {
+        std::fs::write(dir.join(name), "").unwrap();
+    }
+    assert_eq!(rotate_exports(&dir, 1).unwrap(), 1);
+    assert!(!dir.join("ssd-report-auto-1.json").exists());
+    assert!(dir.join("ssd-report-auto-2.json").exists());
+    assert!(dir.join("ssd-report-box-1.json").exists());
+    assert!(dir.join("notes.txt").exists());
+    assert_eq!(rotate_exports(&dir.join("missing"), 1).unwrap(), 0);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,85 @@
+// Tests for the local REST API server.
+
+mod common;
+
+use common::parse_fixture;
+use ssd_info_cli::gui::report::Report;
+use ssd_info_cli::json::Json;
+use ssd_info_cli::server::{host_allowed, route, serve};
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+
+fn report() -> Report {
+    let drive = parse_fixture("/dev/nvme0n1", "nvme_wd_sn850x", "NVMe");
+    Report { host: "box".into(), generated_at: 1_700_000_000, drives: vec![drive] }
+}
+
+#[test]
+fn routes() {
+    let report = report();
+    let (status, body) = route("GET", "/drives", Ok(&report));
+    assert_eq!(status, 200);
+    assert_eq!(Report::from_json(&body).unwrap().drives.len(), 1);
+
+    let (status, body) = route("GET", "/drives/23124S801234?pretty", Ok(&report));
+    assert_eq!(status, 200);
+    assert_eq!(body.get("dev").and_then(Json::as_str), Some("/dev/nvme0n1"));
+
+    assert_eq!(route("GET", "/drives/NOPE", Ok(&report)).0, 404);
+    assert_eq!(route("GET", "/", Ok(&report)).0, 404);
+    assert_eq!(route("POST", "/drives", Ok(&report)).0, 405);
+    let pending = "First scan still running".to_string();
+    let (status, body) = route("GET", "/drives", Err(&pending));
+    assert_eq!(status, 503);
+    assert_eq!(body.get("error").and_then(Json::as_str), Some("First scan still running"));
+}
+
+#[test]
+fn serves_http() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let latest = Arc::new(Mutex::new(Ok(report())));
+    std::thread::spawn(move || serve(listener, latest));
+
+    let mut conn = std::net::TcpStream::connect(addr).unwrap();
+    conn.write_all(b"GET /drives HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+    let mut response = String::new();
+    conn.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    assert!(head.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(head.contains("Content-Type: application/json"));
+    assert_eq!(Report::from_json(&Json::parse(body).unwrap()).unwrap().host, "box");
+}
+
+#[test]
+fn only_direct_host_names_are_served() {
+    let local: std::net::IpAddr = "192.168.1.20".parse().unwrap();
+    assert!(host_allowed(Some("localhost:8190"), local));
+    assert!(host_allowed(Some("192.168.1.20:8190"), local));
+    assert!(host_allowed(Some("192.168.1.20"), local));
+    assert!(!host_allowed(Some("evil.example:8190"), local));
+    assert!(!host_allowed(Some("192.168.1.21:8190"), local));
+    assert!(!host_allowed(None, local));
+    assert!(host_allowed(Some("[::1]:8190"), "::1".parse().unwrap()));
+    assert!(host_allowed(Some("127.0.0.1:8190"), "::ffff:127.0.0.1".parse().unwrap()));
+}
+
+#[test]
+fn slow_clients_do_not_block_others() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let latest = Arc::new(Mutex::new(Ok(report())));
+    std::thread::spawn(move || serve(listener, latest));
+
+    // Drips part of a request and never finishes it
+    let mut slow = std::net::TcpStream::connect(addr).unwrap();
+    slow.write_all(b"GET /dri").unwrap();
+
+    let started = std::time::Instant::now();
+    let mut conn = std::net::TcpStream::connect(addr).unwrap();
+    conn.write_all(b"GET /drives HTTP/1.1\r\nHost: evil.example\r\n\r\n").unwrap();
+    let mut response = String::new();
+    conn.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 403 Forbidden\r\n"));
+    assert!(started.elapsed() < std::time::Duration::from_secs(4));
+}
@@ -0,0 +1,85 @@
+// Tests for the session event log and the SMART changes it records between scans.
+
+mod common;
+
+use common::parse_fixture;
+use ssd_info_cli::gui::session_log::{default_log_path, drive_changes, EventKind, SessionLog};
+use ssd_info_cli::models::DiskInfo;
+
+/// Sets an attribute's raw value, and its normalized value if given.
+fn set_attribute(di: &mut DiskInfo, id: &str, raw: &str, current: Option<&str>) {
+    let attr = di.smart_attributes.iter_mut().find(|a| a.id == id).unwrap();
+    attr.raw_value = raw.to_string();
+    if let Some(current) = current {
+        attr.current = current.to_string();
+    }
+}
+
+#[test]
+fn only_meaningful_smart_changes_are_reported() {
+    let before = parse_fixture("/dev/sda", "sata_crucial_mx500", "SATA");
+    assert!(drive_changes(&before, &before).is_empty());
+
+    // Hours, LBAs written and temperature move on every scan and are not worth a line
+    let mut busy = before.clone();
+    set_attribute(&mut busy, "9", "99999", None);
+    set_attribute(&mut busy, "246", "123456789", None);
+    set_attribute(&mut busy, "194", "61 (Min/Max 0/70)", Some("039"));
+    assert!(drive_changes(&before, &busy).is_empty());
+
+    // Newly reallocated sectors, a normalized value drop and a failed self-assessment are
+    let mut worse = busy.clone();
+    set_attribute(&mut worse, "5", "8", Some("099"));
+    worse.smart_passed = Some(false);
+    let changes = drive_changes(&before, &worse);
+    assert_eq!(changes[0], "/dev/sda: SMART self-assessment PASSED → FAILED");
+    assert!(changes.iter().any(|c| c.starts_with("/dev/sda: Reallocate_NAND_Blk_Cnt (5) value 100 → 099")));
+    assert!(changes.iter().any(|c| c == "/dev/sda: Reallocate_NAND_Blk_Cnt (5) raw 0 → 8"));
+    assert_eq!(changes.len(), 3);
+}
+
+#[test]
+fn nvme_health_and_media_errors_are_reported() {
+    let before = parse_fixture("/dev/sda", "nvme_wd_sn850x", "NVMe");
+    let mut after = before.clone();
+    after.health_percent = before.health_percent.map(|h| h - 1);
+    after.media_errors = Some(before.media_errors.unwrap() + 2);
+    let changes = drive_changes(&before, &after);
+    assert_eq!(changes.len(), 2);
+    assert!(changes[0].contains(": health "));
+    assert!(changes[1].ends_with(&format!("media errors {} → {}", before.media_errors.unwrap(), after.media_errors.unwrap())));
+}
+
+#[test]
+fn log_exports_one_line_per_event() {
+    let mut log = SessionLog::default();
+    log.record(1_700_000_000, EventKind::Scan, "Scanned 3 drive(s)");
+    log.record(1_700_000_005, EventKind::Change, "/dev/sda: health 98% → 97%");
+    log.record(1_700_000_009, EventKind::Alert, "warning: line\nbreak");
+
+    let text = log.to_text();
+    let lines: Vec<&str> = text.lines().collect();
+    assert!(lines[0].starts_with('#'));
+    assert_eq!(lines[1], "2023-11-14 22:13:20  SCAN    Scanned 3 drive(s)");
+    assert_eq!(lines[2], "2023-11-14 22:13:25  CHANGE  /dev/sda: health 98% → 97%");
+    assert_eq!(lines[3], "2023-11-14 22:13:29  ALERT   warning: line break");
+
+    let dir = std::env::temp_dir().join(format!("ssd_info_session_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("session.log");
+    log.save(&path).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), text);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(default_log_path(42).ends_with("ssd-session-42.log"));
+}
+
+#[test]
+fn log_keeps_the_newest_events() {
+    let mut log = SessionLog::default();
+    for i in 0..50_010 {
+        log.record(i, EventKind::Scan, "");
+    }
+    assert_eq!(log.entries.len(), 50_000);
+    assert_eq!(log.entries[0].timestamp, 10);
+}
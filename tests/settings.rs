@@ -0,0 +1,117 @@
+// Tests for the per-drive settings file format.
+
+use ssd_info_cli::gui::settings::{format_date, parse_date, EnduranceRating, PowerSaver, Settings};
+use ssd_info_cli::models::{DiskInfo, HealthWeights};
+
+#[test]
+fn settings_round_trip() {
+    let text = "# comment\n[Drive-A]\ntbw = 600\nunknown = 1\n\n[Drive-B]\ndwpd = 0.3\n[Drive-C]\ntbw = abc\n";
+    let settings = Settings::parse(text);
+    assert_eq!(settings.drive("Drive-A").endurance, Some(EnduranceRating::Tbw(600.0)));
+    assert_eq!(settings.drive("Drive-B").endurance, Some(EnduranceRating::Dwpd(0.3)));
+    assert_eq!(settings.drive("Drive-C").endurance, None);
+    assert_eq!(settings.drive("missing").endurance, None);
+
+    // Drives without settings are dropped when saving
+    let reparsed = Settings::parse(&settings.to_text());
+    assert_eq!(reparsed.drives.len(), 2);
+    assert_eq!(reparsed.drive("Drive-B").endurance, Some(EnduranceRating::Dwpd(0.3)));
+}
+
+#[test]
+fn dwpd_converts_over_warranty_period() {
+    // 0.3 DWPD on a 1 TB drive over 5 years
+    let tbw = EnduranceRating::Dwpd(0.3).tbw(Some(1e12), 5.0).unwrap();
+    assert!((tbw - 547.5).abs() < 1e-9);
+    assert_eq!(EnduranceRating::Dwpd(0.3).tbw(None, 5.0), None);
+    assert_eq!(EnduranceRating::Tbw(600.0).tbw(None, 5.0), Some(600.0));
+}
+
+#[test]
+fn warranty_end_and_dates() {
+    assert_eq!(parse_date("1970-01-02"), Some(1));
+    assert_eq!(parse_date("2023-02-30"), None);
+    assert_eq!(parse_date("2024-13-01"), None);
+    assert_eq!(format_date(parse_date("2024-02-29").unwrap()), "2024-02-29");
+
+    let settings = Settings::parse("[D]\npurchased = 2024-02-29\nwarranty_years = 3\n");
+    let drive = settings.drive("D");
+    assert_eq!(drive.warranty_end().map(format_date).as_deref(), Some("2027-02-28"));
+    assert_eq!(Settings::parse(&settings.to_text()).drive("D"), drive);
+    assert_eq!(Settings::parse("[D]\npurchased = 2024-01-01\n").drive("D").warranty_end(), None);
+}
+
+#[test]
+fn visible_columns_round_trip() {
+    let all = [("ID", true), ("Type", false), ("Raw", true)];
+    let mut settings = Settings::parse("[columns]\npartitions = Mount point, Inodes\n");
+    assert!(settings.column_visible("partitions", "Inodes", false));
+    assert!(!settings.column_visible("partitions", "Label", true));
+    // Tables without an entry use the defaults
+    assert!(settings.column_visible("smart", "ID", true) && !settings.column_visible("smart", "Type", false));
+
+    settings.set_column_visible("smart", &all, "Type", true);
+    settings.set_column_visible("smart", &all, "Raw", false);
+    assert_eq!(settings.columns["smart"], ["ID", "Type"]);
+
+    let reparsed = Settings::parse(&settings.to_text());
+    assert_eq!(reparsed.columns, settings.columns);
+}
+
+#[test]
+fn minimize_on_close_round_trip() {
+    assert!(!Settings::parse("").minimize_on_close);
+    let settings = Settings::parse("[window]\nminimize_on_close = true\n");
+    assert!(settings.minimize_on_close);
+    assert!(Settings::parse(&settings.to_text()).minimize_on_close);
+    // The section name is not mistaken for a drive key
+    assert!(settings.drives.is_empty());
+}
+
+#[test]
+fn power_saver_round_trip() {
+    let settings = Settings::parse("");
+    assert_eq!(settings.power_saver, PowerSaver::Auto);
+    assert!(settings.power_saver.is_active(true) && !settings.power_saver.is_active(false));
+    assert!(!settings.to_text().contains("[power]"));
+
+    let settings = Settings::parse("[power]\nsaver = never\n");
+    assert_eq!(settings.power_saver, PowerSaver::Never);
+    assert!(!settings.power_saver.is_active(true));
+    assert_eq!(Settings::parse(&settings.to_text()).power_saver, PowerSaver::Never);
+    assert_eq!(Settings::parse("[power]\nsaver = sometimes\n").power_saver, PowerSaver::Auto);
+    assert!(settings.drives.is_empty());
+}
+
+#[test]
+fn hidden_drives_round_trip() {
+    let mut settings = Settings::parse("[hidden]\nserials = S1, S2\n");
+    assert_eq!(settings.hidden, ["S1", "S2"]);
+    let mut di = DiskInfo::empty("/dev/sdb");
+    assert!(!settings.is_hidden(&di));
+    di.serial = Some("S2".into());
+    assert!(settings.is_hidden(&di));
+
+    settings.set_hidden("S2", false);
+    settings.set_hidden("S3", true);
+    settings.set_hidden("S3", true);
+    assert_eq!(settings.hidden, ["S1", "S3"]);
+    assert!(!settings.is_hidden(&di));
+    assert_eq!(Settings::parse(&settings.to_text()).hidden, settings.hidden);
+}
+
+#[test]
+fn health_weights_round_trip() {
+    let defaults = Settings::parse("");
+    assert_eq!(defaults.health, HealthWeights::default());
+    assert!(!defaults.to_text().contains("[health]"));
+
+    // Negative and unknown weights are ignored
+    let settings = Settings::parse("[health]\npending_sector = 25\ncrc_error = 0\nwear = -1\nfan_noise = 3\n");
+    assert_eq!(settings.health.pending_sector, 25.0);
+    assert_eq!(settings.health.crc_error, 0.0);
+    assert_eq!(settings.health.wear, 1.0);
+    let text = settings.to_text();
+    assert!(text.contains("[health]\npending_sector = 25\ncrc_error = 0\n"));
+    assert_eq!(Settings::parse(&text).health, settings.health);
+}
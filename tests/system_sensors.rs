@@ -0,0 +1,140 @@
+// Tests for the system sensor readers (hwmon and power_supply fixtures in tests/fixtures, nvidia-smi text).
+
+use ssd_info_cli::gui::system_sensors::{
+    batteries_from, cpu_sensors_from, cpu_temperature_from, fan_speeds_from, on_battery_from, parse_nvidia_smi_gpus,
+    parse_proc_swaps, swap_drives_from,
+};
+use std::path::Path;
+
+#[test]
+fn cpu_temperature_averages_cpu_sensors_only() {
+    let root = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/hwmon"));
+    // Tctl (52) and Package id 0 (48); Tccd1 and the NVMe composite sensor are ignored
+    assert_eq!(cpu_temperature_from(root), Some(50.0));
+}
+
+#[test]
+fn cpu_temperature_missing_root() {
+    assert_eq!(cpu_temperature_from(Path::new("/nonexistent/hwmon")), None);
+    assert!(cpu_sensors_from(Path::new("/nonexistent/hwmon")).is_empty());
+}
+
+#[test]
+fn cpu_sensors_list_every_cpu_input() {
+    let root = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/hwmon"));
+    // Tccd1 is listed even though the average leaves it out; the NVMe sensor is not a CPU sensor
+    assert_eq!(
+        cpu_sensors_from(root),
+        vec![("Tctl".to_string(), 52.0), ("Tccd1".to_string(), 60.0), ("Package id 0".to_string(), 48.0)]
+    );
+}
+
+#[test]
+fn nvidia_smi_lists_every_gpu() {
+    let text = "NVIDIA GeForce RTX 3080, 47\nNVIDIA RTX A4000, 61\n";
+    let gpus = parse_nvidia_smi_gpus(text);
+    assert_eq!(gpus.len(), 2);
+    assert_eq!(gpus[0], ("NVIDIA GeForce RTX 3080".to_string(), 47.0));
+    assert_eq!(gpus[1].1, 61.0);
+
+    // "[N/A]" temperatures (e.g., passthrough GPUs) are skipped
+    assert!(parse_nvidia_smi_gpus("Tesla T4, [N/A]\n").is_empty());
+}
+
+#[test]
+fn fan_speeds_use_label_or_chip_name() {
+    let root = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/hwmon"));
+    let fans = fan_speeds_from(root);
+    assert_eq!(fans, vec![("CPU Fan".to_string(), 1180), ("nct6798 fan2".to_string(), 0)]);
+}
+
+#[test]
+fn batteries_skip_mains_and_peripherals() {
+    let root = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/power_supply"));
+    let batteries = batteries_from(root);
+    // AC is a mains adapter and hidpp_battery_0 a wireless mouse
+    assert_eq!(batteries.iter().map(|b| b.name.as_str()).collect::<Vec<_>>(), ["BAT0", "BAT1"]);
+
+    // Energy in µWh
+    let bat0 = &batteries[0];
+    assert_eq!(bat0.model.as_deref(), Some("SMP 5B10W13975"));
+    assert_eq!((bat0.status.as_deref(), bat0.charge_percent, bat0.cycle_count), (Some("Discharging"), Some(87), Some(312)));
+    assert_eq!((bat0.full_capacity, bat0.design_capacity, bat0.capacity_unit), (Some(45.6), Some(57.0), "Wh"));
+    assert!((bat0.health_percent().unwrap() - 80.0).abs() < 1e-9);
+
+    // Charge in µAh; a cycle count of 0 means the firmware does not count
+    let bat1 = &batteries[1];
+    assert_eq!((bat1.full_capacity, bat1.design_capacity, bat1.capacity_unit), (Some(4100.0), Some(4000.0), "mAh"));
+    assert_eq!(bat1.cycle_count, None);
+    assert_eq!(bat1.model, None);
+
+    assert!(batteries_from(Path::new("/nonexistent/power_supply")).is_empty());
+}
+
+#[test]
+fn proc_swaps_lists_paths() {
+    let text = "\
+Filename\t\t\t\tType\t\tSize\t\tUsed\t\tPriority
+/dev/nvme0n1p3                          partition\t8388604\t\t1024\t\t-2
+/swap\\040file                           file\t\t2097148\t\t0\t\t-3
+";
+    assert_eq!(parse_proc_swaps(text), vec!["/dev/nvme0n1p3", "/swap file"]);
+    assert!(parse_proc_swaps("Filename Type Size Used Priority\n").is_empty());
+}
+
+#[test]
+fn on_battery_only_without_a_charger_online() {
+    // The fixture laptop is plugged in, although BAT0 still reports Discharging
+    let fixtures = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/power_supply"));
+    assert!(!on_battery_from(fixtures));
+
+    let dir = std::env::temp_dir().join(format!("ssd_info_power_supply_{}", std::process::id()));
+    let write = |path: &str, contents: &str| {
+        std::fs::create_dir_all(dir.join(path).parent().unwrap()).unwrap();
+        std::fs::write(dir.join(path), contents).unwrap();
+    };
+    // A desktop whose only battery is a wireless mouse's
+    write("hidpp_battery_0/type", "Battery\n");
+    write("hidpp_battery_0/scope", "Device\n");
+    write("hidpp_battery_0/status", "Discharging\n");
+    assert!(!on_battery_from(&dir));
+
+    // Unplugged laptop
+    write("AC/type", "Mains\n");
+    write("AC/online", "0\n");
+    write("BAT0/type", "Battery\n");
+    write("BAT0/status", "Discharging\n");
+    assert!(on_battery_from(&dir));
+
+    // Charging over USB-C
+    write("ucsi-source-psy-USBC000:001/type", "USB\n");
+    write("ucsi-source-psy-USBC000:001/online", "1\n");
+    assert!(!on_battery_from(&dir));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(!on_battery_from(&dir));
+}
+
+#[test]
+fn swap_partitions_resolve_to_their_drive() {
+    // /sys/class/block entries link into the parent drive's directory, as in sysfs
+    let dir = std::env::temp_dir().join(format!("ssd_info_swap_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let class_block = dir.join("class/block");
+    std::fs::create_dir_all(&class_block).unwrap();
+    for (drive, part) in [("sda", None), ("sdaa", Some("sdaa1")), ("nvme0n1", Some("nvme0n1p3")), ("sdb", None)] {
+        let drive_dir = dir.join("block").join(drive);
+        std::fs::create_dir_all(&drive_dir).unwrap();
+        std::os::unix::fs::symlink(&drive_dir, class_block.join(drive)).unwrap();
+        if let Some(part) = part {
+            std::fs::create_dir_all(drive_dir.join(part)).unwrap();
+            std::fs::write(drive_dir.join(part).join("partition"), "1\n").unwrap();
+            std::os::unix::fs::symlink(drive_dir.join(part), class_block.join(part)).unwrap();
+        }
+    }
+
+    let swaps = ["/dev/sdaa1", "/dev/nvme0n1p3", "/dev/sdb", "/swapfile", "/dev/mapper/vg-swap"].map(String::from);
+    // sdaa1 belongs to sdaa, not sda
+    assert_eq!(swap_drives_from(&class_block, &swaps), ["sdaa", "nvme0n1", "sdb"]);
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,33 @@
+// Tests for the terminal interface layout and key decoding, using the smartctl fixtures.
+
+mod common;
+
+use common::parse_fixture;
+use ssd_info_cli::tui::{parse_keys, render, Key};
+
+#[test]
+fn parse_keys_decodes_arrows_pages_and_letters() {
+    assert_eq!(
+        parse_keys(b"\x1b[A\x1b[Bj k\x1b[5~\x1b[6~rxq"),
+        [Key::Up, Key::Down, Key::Down, Key::PageDown, Key::Up, Key::PageUp, Key::PageDown, Key::Refresh, Key::Quit]
+    );
+    assert_eq!(parse_keys(&[0x03]), [Key::Quit]);
+}
+
+#[test]
+fn render_lays_out_sidebar_and_report() {
+    let drives = [parse_fixture("/dev/nvme0n1", "nvme_wd_sn850x", "NVMe"), parse_fixture("/dev/sda", "hdd_toshiba_failing", "HDD")];
+    let screen = render(&drives, 1, 0, 100, 20, "Updated 3s ago");
+
+    assert_eq!(screen.len(), 20);
+    assert!(screen[0].contains("2 drive(s)"));
+    // The first drive is plain, the selected failing drive is highlighted
+    assert!(screen[1].starts_with(" /dev/nvme0n1") && screen[1].contains("OK"));
+    assert!(screen[3].starts_with("\x1b[7m /dev/sda") && screen[3].contains("FAILING"));
+    // The report of the selected drive fills the right column
+    assert!(screen[1].contains("│ == /dev/sda =="));
+    assert!(screen[19].starts_with(" ↑↓ select") && screen[19].ends_with("Updated 3s ago "));
+
+    let scrolled = render(&drives, 1, 1, 100, 20, "");
+    assert!(!scrolled[1].contains("== /dev/sda =="));
+}
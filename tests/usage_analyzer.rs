@@ -0,0 +1,28 @@
+// Tests for the disk usage analyzer, run against a small tree in the temp directory.
+
+use ssd_info_cli::gui::usage_analyzer::analyze;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+#[test]
+fn analyze_sums_children_and_ranks_largest_files() {
+    let root = std::env::temp_dir().join(format!("ssd_info_usage_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(root.join("big/nested")).unwrap();
+    std::fs::create_dir_all(root.join("small")).unwrap();
+    std::fs::write(root.join("big/nested/blob.bin"), vec![1u8; 256 * 1024]).unwrap();
+    std::fs::write(root.join("small/note.txt"), vec![1u8; 8 * 1024]).unwrap();
+    std::fs::write(root.join("top.txt"), vec![1u8; 64 * 1024]).unwrap();
+
+    let counter = AtomicU64::new(0);
+    let report = analyze(&root, &counter, &AtomicBool::new(false));
+    std::fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(counter.load(Ordering::Relaxed), 3);
+    assert_eq!(report.errors, 0);
+    let names: Vec<_> = report.entries.iter().map(|e| e.path.file_name().unwrap().to_string_lossy().into_owned()).collect();
+    assert_eq!(names, ["big", "top.txt", "small"]);
+    assert!(report.entries[0].is_dir && !report.entries[1].is_dir);
+    assert_eq!(report.total_bytes, report.entries.iter().map(|e| e.bytes).sum::<u64>());
+    assert!(report.largest_files[0].0.ends_with("blob.bin"));
+    assert_eq!(report.largest_files.len(), 3);
+}